@@ -0,0 +1,22 @@
+-- One row per bound profile per refresh. `refresh::do_refresh` already builds
+-- every one of these numbers for the board; this keeps them instead of
+-- discarding them, so `/history` has a trend to draw.
+--
+-- Keyed by profile, not Discord user: a rebind moves `accounts.user_id`, but a
+-- profile's history is the profile's. `aoe4_name` is snapshotted for the chart's
+-- legend, since aoe4world names change. `taken_at` is bound by the caller, so
+-- every row from one refresh shares one timestamp.
+create table if not exists rating_snapshots (
+  id integer primary key autoincrement,
+  aoe4_id bigint not null,
+  aoe4_name text not null,
+  taken_at timestamp not null,
+  rating integer not null,
+  elo integer not null,
+  rank_level text not null,
+  global_rank integer not null,
+  games_count integer not null,
+  win_rate real not null
+);
+
+create index if not exists rating_snapshots_by_profile on rating_snapshots (aoe4_id, taken_at);
@@ -0,0 +1,8 @@
+-- The ranked board's messages in `RANK_CHANNEL_ID`, one row per chunk of five
+-- players, the same shape `tournament_bracket_messages` gives a split bracket.
+-- With the ids stored, a refresh edits the board in place instead of deleting
+-- and reposting the whole channel every cron tick.
+create table if not exists ranked_board_messages (
+  ordinal integer primary key,              -- which chunk, top of the board first
+  message_id bigint not null
+);
@@ -0,0 +1,20 @@
+-- The ranked board can be more than one board now — the main one, plus an
+-- optional ranked-team board in a channel of its own — so each chunk is keyed
+-- by which board it belongs to as well as by its ordinal. Every row already
+-- stored is the main board's, which is the only one that existed.
+--
+-- SQLite cannot change a primary key in place, so the table is rebuilt. Nothing
+-- references it, so this stays an ordinary transacted migration.
+create table ranked_board_messages_new (
+  board text not null,                      -- 'main' | 'team'; see `refresh::boards`
+  ordinal integer not null,                 -- which chunk, top of the board first
+  message_id bigint not null,
+  primary key (board, ordinal)
+);
+
+insert into ranked_board_messages_new (board, ordinal, message_id)
+select 'main', ordinal, message_id
+from ranked_board_messages;
+
+drop table ranked_board_messages;
+alter table ranked_board_messages_new rename to ranked_board_messages;
@@ -0,0 +1,17 @@
+-- Inactivity reminders, one row per Discord member who has either touched
+-- `/reminder` or been nagged. A member with no row gets the defaults: reminded,
+-- never snoozed, never nagged.
+--
+-- Not called `reminders`: schema.sql still drops that name on every boot, for
+-- the feature this replaces, so a table by that name would not survive a restart.
+--
+-- `nagged_for` is the member's last game at the time they were nagged. A nag is
+-- only due again once they have played since — one ping per idle stretch, not
+-- one per cron tick.
+create table if not exists inactivity_reminders (
+  user_id bigint primary key,
+  opted_out boolean not null default false,
+  snoozed_until timestamp,
+  nagged_for timestamp,
+  nagged_at timestamp
+);
@@ -0,0 +1,14 @@
+-- Where the game result feed left off for each bound profile, plus what it needs
+-- to judge the next game against: the tier it last saw, the best rating it has
+-- seen, and the current ranked solo win streak.
+--
+-- A profile's first row is written without posting anything — its existing
+-- history is the baseline, not news.
+create table if not exists game_feed_state (
+  aoe4_id bigint primary key,
+  last_game_at timestamp not null,
+  last_game_id bigint,
+  rank_level text not null,
+  best_rating integer not null,
+  win_streak integer not null default 0
+);
@@ -0,0 +1,19 @@
+-- Rank-tier roles the bot has handed out in the home guild, one per member, so
+-- a refresh knows what to take back — without listing the guild's members, and
+-- without ever touching a role an admin gave by hand.
+create table if not exists tier_role_grants (
+  user_id bigint primary key,
+  role_id bigint not null,
+  rank_level text not null
+);
+
+-- Every role the sync added or removed, and why, so a bad mapping can be traced
+-- back to the members it touched.
+create table if not exists tier_role_changes (
+  id integer primary key autoincrement,
+  user_id bigint not null,
+  role_id bigint not null,
+  action text not null check (action in ('add', 'remove')),
+  rank_level text,                            -- null when the member unbound or went unranked
+  changed_at timestamp not null default current_timestamp
+);
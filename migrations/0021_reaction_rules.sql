@@ -0,0 +1,46 @@
+-- The home guild's message reactions, one row per trigger, managed with
+-- `/reaction add|remove|list` rather than hardcoded in emperor.rs.
+--
+-- `pattern` depends on `kind`: a user id for `author` and `mention`, the text to
+-- look for for `substring`, and a regular expression for `regex`. `emoji` is
+-- either a unicode emoji or a custom one written `<:name:id>`. `probability` is
+-- the chance of reacting when the rule matches; `follow_up_chance` the chance of
+-- posting the emoji as a message instead when the reaction is blocked.
+create table if not exists reaction_rules (
+  id integer primary key autoincrement,
+  kind text not null check (kind in ('author', 'mention', 'substring', 'regex')),
+  pattern text not null,
+  emoji text not null,
+  probability real not null default 1.0 check (probability between 0 and 1),
+  follow_up_chance real not null default 0.0 check (follow_up_chance between 0 and 1),
+  created_by bigint,                          -- null for the rules seeded below
+  created_at timestamp not null default current_timestamp
+);
+
+-- What emperor.rs used to hardcode. Rules are independent of each other, so the
+-- old "one of 🍄, 🐷 or the usual emoji" draw becomes the usual emoji every time
+-- plus a rare extra 🐷 or 🍄 — for the emperor's own messages only.
+insert into reaction_rules (kind, pattern, emoji, probability, follow_up_chance) values
+  ('author', '453010726311821322', '<:emoji_93:1299285258457448522>', 1.0, 0.1),
+  ('author', '1511740443132428328', '<:emoji_93:1299285258457448522>', 1.0, 0.1),
+  ('mention', '453010726311821322', '<:emoji_93:1299285258457448522>', 1.0, 0.1),
+  ('mention', '1511740443132428328', '<:emoji_93:1299285258457448522>', 1.0, 0.1),
+  ('substring', '天子', '<:emoji_93:1299285258457448522>', 1.0, 0.1),
+  ('substring', '唱歌', '<:emoji_93:1299285258457448522>', 1.0, 0.1),
+  ('author', '453010726311821322', '🐷', 0.1, 0.0),
+  ('author', '1511740443132428328', '🐷', 0.1, 0.0),
+  ('author', '453010726311821322', '🍄', 0.01, 0.0),
+  ('author', '1511740443132428328', '🍄', 0.01, 0.0),
+  ('substring', '那可', '<:knock:1264746593366839431>', 1.0, 0.1),
+  ('substring', '納可', '<:knock:1264746593366839431>', 1.0, 0.1),
+  ('substring', 'knock', '<:knock:1264746593366839431>', 1.0, 0.1),
+  ('mention', '364796522396647424', '<:knock:1264746593366839431>', 1.0, 0.1),
+  ('substring', '平等院', '<:castle:1338936646615306250>', 1.0, 0.1),
+  ('substring', '海門城堡', '<:castle:1338936646615306250>', 1.0, 0.1),
+  ('regex', '[bB]alt', '<:balt:1264326708962525225>', 1.0, 0.1),
+  ('substring', '包吞', '<:balt:1264326708962525225>', 1.0, 0.1),
+  ('mention', '202510973519527937', '<:balt:1264326708962525225>', 1.0, 0.1),
+  ('regex', '城主|成主|快攻|試煉|喝水|諸葛弩|議會廳|競技場|勝利塔|衝車|搓車', '🦧', 1.0, 0.1),
+  ('regex', '(?s)all.*in|in.*all', '🦧', 1.0, 0.1),
+  ('substring', '象', '🐘', 1.0, 0.1),
+  ('author', '302663000463114242', '🦝', 1.0, 0.1);
@@ -0,0 +1,6 @@
+-- Members who asked with `/reactions off` not to be reacted to: neither their
+-- own messages nor, by a mention rule, messages about them. No row means opted in.
+create table if not exists reaction_opt_outs (
+  user_id bigint primary key,
+  opted_out_at timestamp not null default current_timestamp
+);
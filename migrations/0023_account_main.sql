@@ -0,0 +1,17 @@
+-- A member's main account, chosen with `/bind main`. The board folds a member's
+-- other bound profiles in under it; with none chosen it falls back to whichever
+-- profile ranks highest, as it always did.
+--
+-- `accounts` itself is still created by schema.sql (see 0001), which runs first
+-- in the bot. Created here too, in schema.sql's exact shape, so the migrator
+-- still runs on a database that has never seen schema.sql.
+create table if not exists accounts (
+  id integer primary key autoincrement,
+  user_id bigint not null,
+  aoe4_id bigint not null unique
+);
+
+alter table accounts add column main boolean not null default false;
+
+-- At most one main per member.
+create unique index if not exists accounts_one_main_per_user on accounts (user_id) where main;
@@ -0,0 +1,13 @@
+-- Profile ownership. A binding made with `/bind verify` is marked verified: its
+-- owner proved it by putting a code in their in-game name. A profile someone
+-- else holds can only be taken over that way, or by an admin.
+alter table accounts add column verified boolean not null default false;
+
+-- The one code each member has outstanding, replaced by asking again and gone
+-- once confirmed. Expiry is checked against `issued_at` in code (`verify.rs`).
+create table if not exists bind_verifications (
+  user_id bigint primary key,
+  aoe4_id bigint not null,
+  code text not null,
+  issued_at timestamp not null
+);
@@ -0,0 +1,4 @@
+-- When each profile was bound, for `/accounts export`. Null for bindings made
+-- before this column existed: SQLite will not add a column defaulting to the
+-- current time, and there is no honest value to backfill.
+alter table accounts add column bound_at timestamp;
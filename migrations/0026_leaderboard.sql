@@ -0,0 +1,31 @@
+-- The board as the latest refresh left it, one row per Discord account on the
+-- main board's ladder, so `/leaderboard` can page, sort and filter it without
+-- asking aoe4world for anything. Every refresh replaces it whole.
+create table if not exists leaderboard_entries (
+  user_id bigint primary key,
+  aoe4_id bigint not null,
+  aoe4_name text not null,
+  discord_display text not null,
+  rank_level text not null,
+  rating integer not null,
+  elo integer not null,
+  recent_max_rating integer not null,
+  global_rank integer not null,
+  games_played integer not null,
+  win_rate real not null,
+  favorite_civ text not null,                 -- aoe4world's id, e.g. holy_roman_empire
+  refreshed_at timestamp not null
+);
+
+-- One open `/leaderboard` reply and where it stands. A button names only the
+-- view's id, the way a tournament button names only its tournament, so the
+-- sort and filters live here rather than in the custom_id.
+create table if not exists leaderboard_views (
+  id integer primary key autoincrement,
+  user_id bigint not null,
+  sort text not null,
+  tier text,                                  -- null for every tier
+  civ text,                                   -- null for every civilization
+  page integer not null default 0,
+  created_at timestamp not null default current_timestamp
+);
@@ -0,0 +1,24 @@
+-- `/season close` freezes the stored board (leaderboard_entries) here before
+-- aoe4world's season reset wipes the ratings it was built from. A season is
+-- never reopened or edited; its standings are the board as it stood.
+create table if not exists seasons (
+  id integer primary key autoincrement,
+  name text not null unique collate nocase,
+  closed_at timestamp not null,
+  closed_by bigint not null,
+  board_refreshed_at timestamp not null        -- when the frozen board was fetched
+);
+
+create table if not exists season_standings (
+  season_id integer not null references seasons(id) on delete cascade,
+  user_id bigint not null,
+  aoe4_id bigint not null,
+  aoe4_name text not null,
+  discord_display text not null,
+  place integer not null,                      -- 1-based, by rating
+  rank_level text not null,
+  rating integer not null,
+  primary key (season_id, user_id)
+);
+
+create index if not exists season_standings_aoe4_id on season_standings (aoe4_id);
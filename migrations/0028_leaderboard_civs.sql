@@ -0,0 +1,10 @@
+-- Every civilization each member on the stored board plays, as the same
+-- refresh saw it, so `/civs` and `/civ` can add the server up without asking
+-- aoe4world. Replaced whole alongside leaderboard_entries.
+create table if not exists leaderboard_civs (
+  user_id bigint not null,
+  civilization text not null,                 -- aoe4world's id, e.g. holy_roman_empire
+  pick_rate real not null,                     -- percent of the member's games
+  games integer not null,                      -- estimated from pick_rate and games played
+  primary key (user_id, civilization)
+);
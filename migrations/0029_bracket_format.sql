@@ -0,0 +1,10 @@
+-- The format `start` builds, chosen before there is a stage to hold it: stages
+-- are only written at start, so the choice lives on the tournament and is copied
+-- into `tournament_stages.format` then. Same vocabulary as that column, so the
+-- copy never needs translating.
+alter table tournaments add column format text not null default 'single_elim'
+  check (format in ('single_elim','double_elim','swiss','group','round_robin'));
+
+-- Double elimination only: whether a losers-bracket champion who wins the grand
+-- final forces a second one.
+alter table tournaments add column grand_final_reset boolean not null default 0;
@@ -0,0 +1,4 @@
+-- A Swiss stage's round count, chosen before there is a stage to hold it — the
+-- same arrangement as `format`. `start` copies it into the stage's `config`;
+-- null means "pick from the field size", which only `start` knows.
+alter table tournaments add column swiss_rounds integer check (swiss_rounds is null or swiss_rounds >= 1);
@@ -0,0 +1,11 @@
+-- A group stage's shape, chosen before there is a stage to hold it — the same
+-- arrangement as `swiss_rounds`. Null means "pick from the field size" for the
+-- group count and two for the places each group sends on, both only `start` can
+-- settle; it copies them into the stage's `config`.
+alter table tournaments add column group_count integer check (group_count is null or group_count >= 1);
+alter table tournaments add column group_advance integer check (group_advance is null or group_advance >= 1);
+
+-- The bracket the group stage feeds, with `format = 'group'`. `grand_final_reset`
+-- applies to it when it is double elimination.
+alter table tournaments add column playoff_format text not null default 'single_elim'
+  check (playoff_format in ('single_elim','double_elim'));
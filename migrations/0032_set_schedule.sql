@@ -0,0 +1,27 @@
+-- `/set schedule`: one player proposes a time and the other confirms it. The
+-- proposal is held apart from `scheduled_at`, which only ever holds a time both
+-- sides agreed to, so a pending counter-proposal never hides the agreed one.
+alter table tournament_sets add column proposed_at timestamp;
+alter table tournament_sets add column proposed_by bigint;
+
+-- Minutes before a scheduled set at which its thread is reminded, comma
+-- separated and largest first; empty for no reminders. Copied into
+-- `set_schedule_reminders` when a time is confirmed, so changing it only
+-- affects times confirmed afterwards.
+alter table tournaments add column schedule_reminders text not null default '60,10';
+
+-- One row per reminder a confirmed time is owed, written in the same
+-- transaction that confirms it and replaced whenever it changes. The scheduler
+-- posts whatever is due and stamps `posted_at`, so a reminder that came due
+-- while the bot was down is still found on the first tick after it comes back.
+--
+-- Not called `reminders`: schema.sql still drops that name on every boot.
+create table if not exists set_schedule_reminders (
+  set_id integer not null references tournament_sets(id) on delete cascade,
+  lead_minutes integer not null check (lead_minutes > 0),
+  remind_at timestamp not null,
+  posted_at timestamp,
+  primary key (set_id, lead_minutes)
+);
+
+create index if not exists set_schedule_reminders_due on set_schedule_reminders (remind_at) where posted_at is null;
@@ -0,0 +1,10 @@
+-- Opt-in: the scheduler runs open-checkin, close-checkin and start on the
+-- tournament's timeline instead of waiting for an organizer to. Off by default,
+-- since a start time nobody has corrected is a placeholder, not a plan.
+alter table tournaments add column autopilot boolean not null default false;
+
+-- When the "check-in is closing" ping went out for the current close time.
+-- Cleared whenever `checkin_closes_at` is written, so each check-in round is
+-- owed exactly one, and stamped by a conditional update so it is never posted
+-- twice.
+alter table tournaments add column checkin_reminded_at timestamp;
@@ -0,0 +1,24 @@
+-- Rules sharing a `draw_group` are one draw between them rather than
+-- independent rolls: of the group's rules a message matches, at most one
+-- reacts, picked with `probability` as its share. Null is a rule of its own.
+alter table reaction_rules add column draw_group text;
+
+-- 0021 split the emperor's "one of 🍄, 🐷 or the usual emoji" into independent
+-- rules, so a message could get the usual emoji and a 🐷 both, and only the
+-- emperor's own messages ever drew the rare ones. Put the old draw back, for
+-- every seeded trigger of the usual emoji an admin has not removed: 1% 🍄, 10%
+-- 🐷, the usual emoji otherwise.
+delete from reaction_rules where created_by is null and emoji in ('🐷', '🍄');
+
+insert into reaction_rules (kind, pattern, emoji, probability, follow_up_chance, draw_group)
+  select kind, pattern, '🍄', 0.01, 0.0, 'emperor'
+  from reaction_rules
+  where created_by is null and emoji = '<:emoji_93:1299285258457448522>';
+
+insert into reaction_rules (kind, pattern, emoji, probability, follow_up_chance, draw_group)
+  select kind, pattern, '🐷', 0.1, 0.0, 'emperor'
+  from reaction_rules
+  where created_by is null and emoji = '<:emoji_93:1299285258457448522>';
+
+update reaction_rules set probability = 0.89, draw_group = 'emperor'
+  where created_by is null and emoji = '<:emoji_93:1299285258457448522>';
@@ -1,4 +1,5 @@
--- The reminder feature was removed; drop its table where it still exists.
+-- The original reminder feature's table; drop it where it still exists. Its
+-- replacement is `inactivity_reminders` (migrations/0018), under another name.
 drop table if exists reminders;
 
 create table if not exists accounts (
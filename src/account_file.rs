@@ -0,0 +1,397 @@
+//! `/accounts export` and `/accounts import`: every binding as a JSON or CSV
+//! file, and back. What replaced `/rebuild`, which could only recover the
+//! bindings the bot had ever announced, by scraping the channel for them.
+//!
+//! Reading a file and checking it are pure, so every way a file can be wrong
+//! is unit-tested; the command does the one database read and the aoe4world
+//! lookups around them.
+
+use crate::db::Account;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Csv => "csv",
+        }
+    }
+
+    /// By extension, so an exported file goes back in as it came out.
+    pub(crate) fn of_file(filename: &str) -> Option<Self> {
+        let (_, extension) = filename.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+const CSV_HEADER: &str = "user_id,aoe4_id,main,verified,bound_at";
+
+/// One binding as the file has it. The Discord id goes out as a string: it is
+/// past what a spreadsheet or a JavaScript number holds exactly, and a
+/// silently rounded id binds a profile to nobody.
+#[derive(Serialize, Deserialize)]
+struct Row {
+    #[serde(serialize_with = "as_string", deserialize_with = "from_string_or_number")]
+    user_id: i64,
+    aoe4_id: i64,
+    #[serde(default)]
+    main: bool,
+    #[serde(default)]
+    verified: bool,
+    #[serde(default)]
+    bound_at: Option<DateTime<Utc>>,
+}
+
+fn as_string<S: serde::Serializer>(id: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&id.to_string())
+}
+
+fn from_string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Id {
+        Number(i64),
+        Text(String),
+    }
+    match Id::deserialize(deserializer)? {
+        Id::Number(id) => Ok(id),
+        Id::Text(text) => text.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+pub(crate) fn export(accounts: &[Account], format: Format) -> String {
+    match format {
+        Format::Json => {
+            let rows = accounts
+                .iter()
+                .map(|account| Row {
+                    user_id: account.user_id,
+                    aoe4_id: account.aoe4_id,
+                    main: account.main,
+                    verified: account.verified,
+                    bound_at: account.bound_at,
+                })
+                .collect::<Vec<_>>();
+            serde_json::to_string_pretty(&rows).expect("rows always serialize")
+        },
+        Format::Csv => {
+            let mut csv = String::from(CSV_HEADER);
+            for account in accounts {
+                csv.push_str(&format!(
+                    "\n{},{},{},{},{}",
+                    account.user_id,
+                    account.aoe4_id,
+                    account.main,
+                    account.verified,
+                    account.bound_at.map(|at| at.to_rfc3339()).unwrap_or_default()
+                ));
+            }
+            csv.push('\n');
+            csv
+        },
+    }
+}
+
+/// A row's number in the file, counted from 1, and the binding it holds or
+/// why it could not be read.
+pub(crate) type ParsedRow = (usize, Result<Account, String>);
+
+/// Every row of the file, numbered from 1 for the report. A row that cannot
+/// be read is a problem in the report rather than a failed import, so one
+/// typo is reported alongside everything else wrong with the file.
+pub(crate) fn parse(text: &str, format: Format) -> Result<Vec<ParsedRow>, String> {
+    match format {
+        Format::Json => {
+            let values: Vec<serde_json::Value> =
+                serde_json::from_str(text).map_err(|err| format!("不是 JSON 陣列: {err}"))?;
+            Ok(values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    let row = serde_json::from_value::<Row>(value)
+                        .map(to_account)
+                        .map_err(|err| err.to_string());
+                    (index + 1, row)
+                })
+                .collect())
+        },
+        Format::Csv => {
+            let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+            let header = lines.next().ok_or("檔案是空的")?;
+            let columns = header.split(',').map(str::trim).collect::<Vec<_>>();
+            for required in ["user_id", "aoe4_id"] {
+                if !columns.contains(&required) {
+                    return Err(format!("缺少 `{required}` 欄位"));
+                }
+            }
+            Ok(lines
+                .enumerate()
+                .map(|(index, line)| (index + 1, csv_row(&columns, line)))
+                .collect())
+        },
+    }
+}
+
+fn csv_row(columns: &[&str], line: &str) -> Result<Account, String> {
+    let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+    if fields.len() != columns.len() {
+        return Err(format!("應有 {} 欄，實際 {} 欄", columns.len(), fields.len()));
+    }
+    let field = |name: &str| {
+        columns
+            .iter()
+            .position(|column| *column == name)
+            .map(|index| fields[index])
+            .filter(|value| !value.is_empty())
+    };
+    let id = |name: &str| {
+        field(name)
+            .ok_or_else(|| format!("缺少 `{name}`"))?
+            .parse::<i64>()
+            .map_err(|_| format!("`{name}` 不是數字"))
+    };
+    let flag = |name: &str| match field(name) {
+        None | Some("false") | Some("0") => Ok(false),
+        Some("true") | Some("1") => Ok(true),
+        Some(_) => Err(format!("`{name}` 應為 true 或 false")),
+    };
+    let bound_at = field("bound_at")
+        .map(|value| DateTime::parse_from_rfc3339(value).map(|at| at.with_timezone(&Utc)))
+        .transpose()
+        .map_err(|_| "`bound_at` 不是 RFC 3339 時間".to_string())?;
+    Ok(to_account(Row {
+        user_id: id("user_id")?,
+        aoe4_id: id("aoe4_id")?,
+        main: flag("main")?,
+        verified: flag("verified")?,
+        bound_at,
+    }))
+}
+
+fn to_account(row: Row) -> Account {
+    Account {
+        user_id: row.user_id,
+        aoe4_id: row.aoe4_id,
+        main: row.main,
+        verified: row.verified,
+        bound_at: row.bound_at,
+    }
+}
+
+/// What an import would do, and why it cannot, if it cannot.
+#[derive(Debug, Default)]
+pub(crate) struct Report {
+    /// Rows to write: new bindings, and existing ones whose flags change.
+    pub writes: Vec<Account>,
+    pub new: usize,
+    pub changed: usize,
+    pub unchanged: usize,
+    /// Anything that stops the import, one line each.
+    pub problems: Vec<String>,
+}
+
+impl Report {
+    /// Checks `rows` against the bindings already in the database and the
+    /// profiles aoe4world says do not exist. A profile already bound to someone
+    /// else is a conflict, never a takeover: that is what `/bind verify` and an
+    /// admin's `/bind` are for, one profile at a time.
+    pub(crate) fn check(rows: Vec<ParsedRow>, existing: &[Account], missing: &HashSet<i64>) -> Self {
+        let existing = existing
+            .iter()
+            .map(|account| (account.aoe4_id, account))
+            .collect::<HashMap<_, _>>();
+        let mut report = Report::default();
+        let mut seen_profiles = HashMap::new();
+        let mut mains = HashMap::new();
+
+        for (line, row) in rows {
+            let row = match row {
+                Ok(row) => row,
+                Err(err) => {
+                    report.problems.push(format!("第 {line} 列: {err}"));
+                    continue;
+                },
+            };
+            if row.user_id <= 0 || row.aoe4_id <= 0 {
+                report.problems.push(format!("第 {line} 列: id 必須是正數"));
+                continue;
+            }
+            if let Some(first) = seen_profiles.insert(row.aoe4_id, line) {
+                report
+                    .problems
+                    .push(format!("第 {line} 列: 帳號 `{}` 與第 {first} 列重複", row.aoe4_id));
+                continue;
+            }
+            if row.main
+                && let Some(first) = mains.insert(row.user_id, line)
+            {
+                report.problems.push(format!(
+                    "第 {line} 列: 成員 `{}` 的主帳號已在第 {first} 列指定",
+                    row.user_id
+                ));
+                continue;
+            }
+            if missing.contains(&row.aoe4_id) {
+                report
+                    .problems
+                    .push(format!("第 {line} 列: aoe4world 上沒有帳號 `{}`", row.aoe4_id));
+                continue;
+            }
+            match existing.get(&row.aoe4_id) {
+                Some(held) if held.user_id != row.user_id => report.problems.push(format!(
+                    "第 {line} 列: 帳號 `{}` 已綁定給成員 `{}`，檔案中為 `{}`",
+                    row.aoe4_id, held.user_id, row.user_id
+                )),
+                Some(held) if held.main == row.main && held.verified == row.verified => report.unchanged += 1,
+                Some(_) => {
+                    report.changed += 1;
+                    report.writes.push(row);
+                },
+                None => {
+                    report.new += 1;
+                    report.writes.push(row);
+                },
+            }
+        }
+        report
+    }
+
+    pub(crate) fn render(&self, applied: bool) -> String {
+        let mut text = format!(
+            "新增 {} 筆，更新 {} 筆，不變 {} 筆",
+            self.new, self.changed, self.unchanged
+        );
+        if self.problems.is_empty() {
+            text.push_str(if applied {
+                "\n已匯入。"
+            } else {
+                "\n沒有問題。確認無誤後加上 `apply: True` 再執行一次即可匯入。"
+            });
+            return text;
+        }
+        text.push_str(&format!("\n有 {} 個問題，沒有寫入任何資料:", self.problems.len()));
+        // Room for the rest of the message within Discord's 2000 characters.
+        let mut shown = 0;
+        for problem in &self.problems {
+            if text.len() + problem.len() > 1800 {
+                break;
+            }
+            text.push('\n');
+            text.push_str(problem);
+            shown += 1;
+        }
+        if shown < self.problems.len() {
+            text.push_str(&format!("\n…還有 {} 個", self.problems.len() - shown));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(user_id: i64, aoe4_id: i64, main: bool) -> Account {
+        Account {
+            user_id,
+            aoe4_id,
+            main,
+            verified: false,
+            bound_at: None,
+        }
+    }
+
+    fn rows(accounts: Vec<Account>) -> Vec<ParsedRow> {
+        accounts.into_iter().enumerate().map(|(i, a)| (i + 1, Ok(a))).collect()
+    }
+
+    #[test]
+    fn an_export_reads_back_in_either_format() {
+        let mut accounts = vec![account(720955323183267840, 100, true), account(2, 200, false)];
+        accounts[1].verified = true;
+        accounts[1].bound_at = Some(DateTime::UNIX_EPOCH);
+        for format in [Format::Json, Format::Csv] {
+            let back = parse(&export(&accounts, format), format).unwrap();
+            let back = back.into_iter().map(|(_, row)| row.unwrap()).collect::<Vec<_>>();
+            assert_eq!(back.len(), 2);
+            assert_eq!(back[0].user_id, 720955323183267840, "{format:?}");
+            assert!(back[0].main);
+            assert!(back[1].verified);
+            assert_eq!(back[1].bound_at, Some(DateTime::UNIX_EPOCH));
+        }
+    }
+
+    #[test]
+    fn discord_ids_go_out_as_strings_and_come_back_either_way() {
+        assert!(export(&[account(1, 100, false)], Format::Json).contains(r#""user_id": "1""#));
+        let rows = parse(
+            r#"[{"user_id": 1, "aoe4_id": 100}, {"user_id": "2", "aoe4_id": 200}]"#,
+            Format::Json,
+        )
+        .unwrap();
+        assert!(rows.iter().all(|(_, row)| row.is_ok()));
+    }
+
+    #[test]
+    fn a_bad_row_is_reported_with_its_line_not_fatal() {
+        let rows = parse("user_id,aoe4_id\n1,100\nx,200\n3\n", Format::Csv).unwrap();
+        assert!(rows[0].1.is_ok());
+        assert!(rows[1].1.as_ref().unwrap_err().contains("user_id"));
+        assert!(rows[2].1.is_err());
+        assert!(parse("aoe4_id\n100\n", Format::Csv).is_err());
+    }
+
+    #[test]
+    fn the_format_comes_from_the_extension() {
+        assert_eq!(Format::of_file("accounts.JSON"), Some(Format::Json));
+        assert_eq!(Format::of_file("accounts.csv"), Some(Format::Csv));
+        assert_eq!(Format::of_file("accounts.txt"), None);
+    }
+
+    #[test]
+    fn new_changed_and_unchanged_rows_are_counted() {
+        let existing = [account(1, 100, false), account(1, 101, false)];
+        let report = Report::check(
+            rows(vec![
+                account(1, 100, false),
+                account(1, 101, true),
+                account(2, 200, false),
+            ]),
+            &existing,
+            &HashSet::new(),
+        );
+        assert!(report.problems.is_empty());
+        assert_eq!((report.new, report.changed, report.unchanged), (1, 1, 1));
+        assert_eq!(report.writes.len(), 2);
+    }
+
+    #[test]
+    fn conflicts_are_all_reported() {
+        let existing = [account(9, 300, false)];
+        let report = Report::check(
+            rows(vec![
+                account(1, 100, true),
+                account(1, 100, false), // the same profile twice
+                account(1, 101, true),  // a second main
+                account(2, 200, false), // not on aoe4world
+                account(2, 300, false), // someone else's
+            ]),
+            &existing,
+            &HashSet::from([200]),
+        );
+        assert_eq!(report.problems.len(), 4, "{:?}", report.problems);
+        assert!(report.render(false).contains("沒有寫入任何資料"));
+    }
+}
@@ -0,0 +1,348 @@
+//! The one way out to aoe4world: every request goes through `get_json`, which
+//! answers from a per-endpoint TTL cache when it can, waits its turn on a token
+//! bucket when it can't, and retries a 429, a 5xx or a timeout with backoff.
+//!
+//! What comes back is a typed `ApiError` rather than a bare `None`, so a caller
+//! can tell "no such player" from "aoe4world is down" and say which.
+//!
+//! The cache holds response bodies, not decoded values, so one cache serves every
+//! response type; decoding a cached body is cheap next to a round trip.
+
+use reqwest::{Client, StatusCode, Url, header};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{debug, error, warn};
+
+// aoe4world requires a UA identifying the app with contact info: https://aoe4world.com/api
+const USER_AGENT: &str = concat!(
+    "aoe4-bot/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/NCTU-deepshine/aoe4-bot; discord: deepshine)"
+);
+
+/// Long enough for a slow profile; a request that hangs past it is treated as
+/// aoe4world being unavailable and retried.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// aoe4world asks API users to be considerate rather than publishing a hard
+/// number, and every request carries our contact details in `USER_AGENT`. A few
+/// requests a second keeps a full refresh to well under a minute while staying
+/// far from anything that would get the bot noticed.
+const REQUESTS_PER_SECOND: f64 = 4.0;
+const BURST: f64 = 8.0;
+
+/// One try plus two retries. Past that the request is failing for a reason
+/// waiting will not fix this minute, and the caller has a user waiting.
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// A `Retry-After` longer than this is not honoured: the request gives up and
+/// the caller reports aoe4world as rate limiting instead of hanging.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// Past this many entries, expired ones are swept on insert.
+const CACHE_SWEEP_AT: usize = 1024;
+
+/// Why a request to aoe4world produced nothing usable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ApiError {
+    /// A 404: the profile (or whatever was asked for) does not exist.
+    NotFound,
+    /// Still a 429 after every retry.
+    RateLimited,
+    /// A 5xx, a timeout or a failed connection, after every retry.
+    Unavailable(String),
+    /// Any other status, or a body that does not decode — retrying will not help.
+    Unexpected(String),
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::NotFound => write!(f, "not found on aoe4world"),
+            ApiError::RateLimited => write!(f, "aoe4world is rate limiting requests"),
+            ApiError::Unavailable(detail) => write!(f, "aoe4world is unavailable: {detail}"),
+            ApiError::Unexpected(detail) => write!(f, "unexpected response from aoe4world: {detail}"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// The endpoints the bot calls, each cached for as long as its data stays useful.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Endpoint {
+    Profile,
+    /// A profile read to check a `/bind verify` code, which has to see the name
+    /// as it is now rather than as it was a few minutes ago.
+    Ownership,
+    Autocomplete,
+    Games,
+    Esports,
+}
+
+impl Endpoint {
+    /// A profile changes with every game, so it is only cached long enough to
+    /// absorb a burst — a refresh and `/查分` a minute apart. An autocomplete
+    /// prefix is retyped keystroke by keystroke and names rarely change. A games
+    /// page is polled for what is new, so it is barely cached at all. The esports
+    /// leaderboard is a community sheet updated by hand. An ownership check is
+    /// never cached, nor served from the cache.
+    fn ttl(self) -> Duration {
+        match self {
+            Endpoint::Profile => Duration::from_secs(5 * 60),
+            Endpoint::Ownership => Duration::ZERO,
+            Endpoint::Autocomplete => Duration::from_secs(30 * 60),
+            Endpoint::Games => Duration::from_secs(60),
+            Endpoint::Esports => Duration::from_secs(6 * 60 * 60),
+        }
+    }
+
+    /// For log lines.
+    fn what(self) -> &'static str {
+        match self {
+            Endpoint::Profile => "profile",
+            Endpoint::Ownership => "profile (uncached)",
+            Endpoint::Autocomplete => "player autocomplete",
+            Endpoint::Games => "games",
+            Endpoint::Esports => "esports leaderboard",
+        }
+    }
+}
+
+pub(crate) async fn get_json<T: DeserializeOwned>(url: Url, endpoint: Endpoint) -> Result<T, ApiError> {
+    let cacheable = !endpoint.ttl().is_zero();
+    let cached = cacheable.then(|| cache().get(url.as_str(), Instant::now())).flatten();
+    let body = match cached {
+        Some(body) => {
+            debug!("aoe4world {} served from cache", endpoint.what());
+            body
+        },
+        None => {
+            let body: Arc<str> = get_text(url.clone(), endpoint).await?.into();
+            if cacheable {
+                cache().insert(url.as_str(), body.clone(), Instant::now() + endpoint.ttl());
+            }
+            body
+        },
+    };
+    serde_json::from_str(&body).map_err(|err| {
+        error!("aoe4world {} decode failed: {}", endpoint.what(), err);
+        ApiError::Unexpected(err.to_string())
+    })
+}
+
+async fn get_text(url: Url, endpoint: Endpoint) -> Result<String, ApiError> {
+    let mut attempt = 0;
+    loop {
+        bucket().acquire().await;
+        let (error, retry_after) = match client().get(url.clone()).send().await {
+            Ok(response) => {
+                let status = response.status();
+                if status.is_success() {
+                    return response.text().await.map_err(|err| {
+                        error!("aoe4world {} body failed: {}", endpoint.what(), err);
+                        ApiError::Unavailable(err.to_string())
+                    });
+                }
+                let retry_after = response
+                    .headers()
+                    .get(header::RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.trim().parse::<u64>().ok())
+                    .map(Duration::from_secs);
+                (status_error(status), retry_after)
+            },
+            Err(err) if err.is_timeout() || err.is_connect() => (ApiError::Unavailable(err.to_string()), None),
+            Err(err) => (ApiError::Unexpected(err.to_string()), None),
+        };
+
+        attempt += 1;
+        let delay = retry_delay(attempt, retry_after);
+        if !is_retryable(&error) || attempt >= MAX_ATTEMPTS || delay > MAX_RETRY_DELAY {
+            if error != ApiError::NotFound {
+                error!("aoe4world {} request failed: {}", endpoint.what(), error);
+            }
+            return Err(error);
+        }
+        warn!(
+            "aoe4world {} request failed ({}), retrying in {:?}",
+            endpoint.what(),
+            error,
+            delay
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// What an unsuccessful status means.
+fn status_error(status: StatusCode) -> ApiError {
+    match status {
+        StatusCode::NOT_FOUND => ApiError::NotFound,
+        StatusCode::TOO_MANY_REQUESTS => ApiError::RateLimited,
+        status if status.is_server_error() => ApiError::Unavailable(status.to_string()),
+        status => ApiError::Unexpected(status.to_string()),
+    }
+}
+
+fn is_retryable(error: &ApiError) -> bool {
+    matches!(error, ApiError::RateLimited | ApiError::Unavailable(_))
+}
+
+/// How long to wait before retry number `attempt` (1-based): whatever the
+/// server asked for, or else doubling from `BASE_RETRY_DELAY`.
+fn retry_delay(attempt: u32, retry_after: Option<Duration>) -> Duration {
+    retry_after.unwrap_or_else(|| BASE_RETRY_DELAY * 2u32.saturating_pow(attempt.saturating_sub(1)))
+}
+
+fn client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("failed to build aoe4world http client")
+    })
+}
+
+fn cache() -> &'static TtlCache {
+    static CACHE: OnceLock<TtlCache> = OnceLock::new();
+    CACHE.get_or_init(TtlCache::default)
+}
+
+fn bucket() -> &'static TokenBucket {
+    static BUCKET: OnceLock<TokenBucket> = OnceLock::new();
+    BUCKET.get_or_init(|| TokenBucket::new(BURST, REQUESTS_PER_SECOND))
+}
+
+/// Response bodies by URL, each with its own expiry. Times are passed in, so
+/// expiry is testable without sleeping.
+#[derive(Default)]
+struct TtlCache {
+    entries: Mutex<HashMap<String, (Instant, Arc<str>)>>,
+}
+
+impl TtlCache {
+    fn get(&self, key: &str, now: Instant) -> Option<Arc<str>> {
+        let entries = self.entries.lock().unwrap();
+        let (expires_at, body) = entries.get(key)?;
+        (*expires_at > now).then(|| body.clone())
+    }
+
+    fn insert(&self, key: &str, body: Arc<str>, expires_at: Instant) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= CACHE_SWEEP_AT {
+            entries.retain(|_, (expires, _)| *expires > Instant::now());
+        }
+        entries.insert(key.to_string(), (expires_at, body));
+    }
+}
+
+/// A token bucket that hands out waits rather than refusals: a caller over the
+/// limit is told how long to sleep, and the token is theirs once they have. The
+/// balance may go negative, which is what queues concurrent callers fairly
+/// instead of letting them all wake at once and race for one token.
+struct TokenBucket {
+    capacity: f64,
+    per_second: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    fn new(capacity: f64, per_second: f64) -> Self {
+        Self {
+            capacity,
+            per_second,
+            state: Mutex::new((capacity, Instant::now())),
+        }
+    }
+
+    async fn acquire(&self) {
+        let wait = self.take(Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Takes one token as of `now` and returns how long to wait before using it.
+    fn take(&self, now: Instant) -> Duration {
+        let mut state = self.state.lock().unwrap();
+        let (tokens, last) = *state;
+        let refilled =
+            (tokens + now.saturating_duration_since(last).as_secs_f64() * self.per_second).min(self.capacity);
+        let left = refilled - 1.0;
+        *state = (left, now);
+        if left >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-left / self.per_second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_map_to_what_they_mean() {
+        assert_eq!(status_error(StatusCode::NOT_FOUND), ApiError::NotFound);
+        assert_eq!(status_error(StatusCode::TOO_MANY_REQUESTS), ApiError::RateLimited);
+        assert!(matches!(
+            status_error(StatusCode::BAD_GATEWAY),
+            ApiError::Unavailable(_)
+        ));
+        assert!(matches!(status_error(StatusCode::BAD_REQUEST), ApiError::Unexpected(_)));
+    }
+
+    #[test]
+    fn only_throttling_and_outages_are_retried() {
+        assert!(is_retryable(&ApiError::RateLimited));
+        assert!(is_retryable(&ApiError::Unavailable("503".into())));
+        assert!(!is_retryable(&ApiError::NotFound));
+        assert!(!is_retryable(&ApiError::Unexpected("400".into())));
+    }
+
+    #[test]
+    fn backoff_doubles_unless_the_server_names_a_delay() {
+        assert_eq!(retry_delay(1, None), BASE_RETRY_DELAY);
+        assert_eq!(retry_delay(2, None), BASE_RETRY_DELAY * 2);
+        assert_eq!(retry_delay(3, None), BASE_RETRY_DELAY * 4);
+        assert_eq!(retry_delay(1, Some(Duration::from_secs(3))), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn a_cached_body_is_served_until_it_expires() {
+        let cache = TtlCache::default();
+        let now = Instant::now();
+        cache.insert("players/1", "{}".into(), now + Duration::from_secs(60));
+        assert_eq!(cache.get("players/1", now).as_deref(), Some("{}"));
+        assert_eq!(cache.get("players/1", now + Duration::from_secs(61)), None);
+        assert_eq!(cache.get("players/2", now), None);
+    }
+
+    #[test]
+    fn the_bucket_allows_a_burst_then_spaces_requests_out() {
+        let bucket = TokenBucket::new(2.0, 4.0);
+        let now = Instant::now();
+        assert_eq!(bucket.take(now), Duration::ZERO);
+        assert_eq!(bucket.take(now), Duration::ZERO);
+        assert_eq!(bucket.take(now), Duration::from_millis(250));
+        // A second caller in the same instant queues behind the first.
+        assert_eq!(bucket.take(now), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn the_bucket_refills_but_never_past_its_capacity() {
+        let bucket = TokenBucket::new(2.0, 4.0);
+        let now = Instant::now();
+        let later = now + Duration::from_secs(60);
+        assert_eq!(bucket.take(later), Duration::ZERO);
+        assert_eq!(bucket.take(later), Duration::ZERO);
+        assert_eq!(bucket.take(later), Duration::from_millis(250));
+    }
+}
@@ -0,0 +1,299 @@
+//! `/查分`'s player card: the numbers `RankedPlayer::info` lists, laid out as
+//! an image.
+//!
+//! Drawn the way `history` draws its chart — an SVG document here, rasterized
+//! by `tournament::bracket_raster` on the bundled Noto faces — so a CJK name or
+//! civilization looks the same in every client, and the PNG still reads right
+//! once it is shared somewhere that is not Discord.
+
+use crate::Error;
+use crate::locale::Locale;
+use crate::tournament::bracket_raster;
+use crate::tournament::bracket_svg::{BACKGROUND, FONT_FAMILY, FOREGROUND, STROKE, escape_xml};
+use unicode_width::UnicodeWidthChar;
+
+/// Everything printed on a card, already worded. `RankedPlayer::card` fills it
+/// in, so this module only lays text out and never formats a number itself.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Card {
+    /// The labels' language — the invoker's, like the rest of `/查分`'s reply.
+    pub locale: Locale,
+    pub name: String,
+    /// The raw tier, e.g. `diamond_2`, for the accent colour.
+    pub rank_level: String,
+    /// The tier as shown, with the ladder when it is not ranked solo.
+    pub tier: String,
+    pub rating: i32,
+    pub recent_max_rating: i32,
+    pub elo: i32,
+    pub global_rank: i32,
+    pub games_played: i32,
+    pub win_rate: f64,
+    /// Up to three, most played first, with their pick rates.
+    pub civs: Vec<(String, f64)>,
+    /// Every ladder the profile is on, one line each — empty when the headline
+    /// ladder is the only one, as in `RankedPlayer::info`.
+    pub ladders: Vec<String>,
+    pub last_played: String,
+}
+
+const WIDTH_PX: f64 = 640.0;
+/// Without any ladder lines; each one adds `LADDER_ROW_PX`.
+const BASE_HEIGHT_PX: f64 = 300.0;
+const LADDER_ROW_PX: f64 = 22.0;
+const MARGIN_PX: f64 = 28.0;
+const NAME_SIZE_PX: f64 = 30.0;
+const LABEL_SIZE_PX: f64 = 14.0;
+const VALUE_SIZE_PX: f64 = 24.0;
+const TEXT_SIZE_PX: f64 = 16.0;
+/// Where the civilization column starts; the stats sit left of it.
+const CIVS_LEFT: f64 = 360.0;
+const CIV_BAR_PX: f64 = 200.0;
+/// In display cells, where a CJK character is two. Past this a name is cut
+/// rather than run off the card.
+const NAME_MAX_CELLS: usize = 28;
+const CIV_MAX_CELLS: usize = 22;
+const MUTED: &str = "#949ba4";
+
+/// Each tier's colour, for the stripe down the card's edge and the tier text.
+/// Unranked, or a tier added later than this, is drawn in `STROKE`.
+fn tier_colour(rank_level: &str) -> &'static str {
+    match rank_level.rsplit_once('_').map_or(rank_level, |(tier, _)| tier) {
+        "conqueror" => "#f0b232",
+        "diamond" => "#00a8fc",
+        "platinum" => "#4fdfc4",
+        "gold" => "#e6b800",
+        "silver" => "#b5bac1",
+        "bronze" => "#c47a3d",
+        _ => STROKE,
+    }
+}
+
+/// `text` cut to `max_cells` display cells, with an ellipsis when anything was
+/// cut.
+fn fit(text: &str, max_cells: usize) -> String {
+    let mut cells = 0;
+    let mut out = String::new();
+    for ch in text.chars() {
+        cells += ch.width().unwrap_or(0);
+        if cells > max_cells {
+            out.push('…');
+            return out;
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// `civilization_zh` names a civilization twice, the second time in its own
+/// language and script — Mongolian, Arabic, Persian — none of which the
+/// bundled faces cover. The card keeps the first name only.
+fn civ_name(name: &str) -> &str {
+    name.split_once('(').map_or(name, |(short, _)| short).trim()
+}
+
+pub(crate) fn svg(card: &Card) -> String {
+    let accent = tier_colour(&card.rank_level);
+    let ladders_height = match card.ladders.len() {
+        0 => 0.0,
+        lines => (lines + 1) as f64 * LADDER_ROW_PX + 8.0,
+    };
+    let height = BASE_HEIGHT_PX + ladders_height;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH_PX}\" height=\"{height}\" \
+         viewBox=\"0 0 {WIDTH_PX} {height}\"><rect width=\"{WIDTH_PX}\" height=\"{height}\" fill=\"{BACKGROUND}\"/>\
+         <rect width=\"8\" height=\"{height}\" fill=\"{accent}\"/>"
+    );
+    out.push_str(&text(
+        MARGIN_PX,
+        MARGIN_PX + 24.0,
+        NAME_SIZE_PX,
+        FOREGROUND,
+        &fit(&card.name, NAME_MAX_CELLS),
+    ));
+    out.push_str(&text(
+        MARGIN_PX,
+        MARGIN_PX + 54.0,
+        TEXT_SIZE_PX + 2.0,
+        accent,
+        &card.tier,
+    ));
+
+    let locale = card.locale;
+    let stats = [
+        (locale.pick("排名積分", "Rating"), card.rating.to_string()),
+        (
+            locale.pick("近期最高", "Recent max"),
+            card.recent_max_rating.to_string(),
+        ),
+        ("Elo", card.elo.to_string()),
+        (locale.pick("全球排名", "Global rank"), format!("#{}", card.global_rank)),
+        (locale.pick("勝率", "Win rate"), format!("{}%", card.win_rate.round())),
+        (locale.pick("場次", "Games"), card.games_played.to_string()),
+    ];
+    for (index, (label, value)) in stats.iter().enumerate() {
+        let x = MARGIN_PX + (index % 3) as f64 * 108.0;
+        let y = MARGIN_PX + 110.0 + (index / 3) as f64 * 64.0;
+        out.push_str(&text(x, y, LABEL_SIZE_PX, MUTED, label));
+        out.push_str(&text(x, y + 28.0, VALUE_SIZE_PX, FOREGROUND, value));
+    }
+
+    out.push_str(&text(
+        CIVS_LEFT,
+        MARGIN_PX + 110.0,
+        LABEL_SIZE_PX,
+        MUTED,
+        locale.pick("愛用文明", "Favourite civs"),
+    ));
+    for (index, (civ, pick_rate)) in card.civs.iter().enumerate() {
+        let y = MARGIN_PX + 138.0 + index as f64 * 44.0;
+        let label = format!("{} {}%", fit(civ_name(civ), CIV_MAX_CELLS), pick_rate.round());
+        out.push_str(&text(CIVS_LEFT, y, TEXT_SIZE_PX, FOREGROUND, &label));
+        let filled = CIV_BAR_PX * (pick_rate / 100.0).clamp(0.0, 1.0);
+        out.push_str(&format!(
+            "<rect x=\"{CIVS_LEFT}\" y=\"{}\" width=\"{CIV_BAR_PX}\" height=\"6\" rx=\"3\" fill=\"{STROKE}\" \
+             fill-opacity=\"0.3\"/><rect x=\"{CIVS_LEFT}\" y=\"{}\" width=\"{filled}\" height=\"6\" rx=\"3\" \
+             fill=\"{accent}\"/>",
+            y + 10.0,
+            y + 10.0
+        ));
+    }
+
+    if !card.ladders.is_empty() {
+        let top = BASE_HEIGHT_PX - MARGIN_PX - 8.0;
+        out.push_str(&text(
+            MARGIN_PX,
+            top,
+            LABEL_SIZE_PX,
+            MUTED,
+            locale.pick("各模式", "Every ladder"),
+        ));
+        for (index, line) in card.ladders.iter().enumerate() {
+            let y = top + (index + 1) as f64 * LADDER_ROW_PX;
+            out.push_str(&text(MARGIN_PX, y, LABEL_SIZE_PX, FOREGROUND, line));
+        }
+    }
+    out.push_str(&text(
+        MARGIN_PX,
+        height - MARGIN_PX + 8.0,
+        LABEL_SIZE_PX,
+        MUTED,
+        &format!("{}: {}", locale.pick("上次遊玩", "Last played"), card.last_played),
+    ));
+    out.push_str("</svg>");
+    out
+}
+
+fn text(x: f64, y: f64, size: f64, fill: &str, content: &str) -> String {
+    format!(
+        "<text x=\"{x}\" y=\"{y}\" font-family=\"{FONT_FAMILY}\" font-size=\"{size}\" fill=\"{fill}\">{}</text>",
+        escape_xml(content)
+    )
+}
+
+/// The card as a PNG, rasterized off the async executor for the same reason
+/// `bracket_view` does.
+pub(crate) async fn png(card: &Card) -> Result<Vec<u8>, Error> {
+    let svg = svg(card);
+    Ok(tokio::task::spawn_blocking(move || bracket_raster::rasterize(&svg)).await??)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card() -> Card {
+        Card {
+            locale: Locale::ZhTw,
+            name: "測試選手".to_string(),
+            rank_level: "diamond_2".to_string(),
+            tier: "鑽石2".to_string(),
+            rating: 1523,
+            recent_max_rating: 1580,
+            elo: 1490,
+            global_rank: 1234,
+            games_played: 321,
+            win_rate: 54.6,
+            civs: vec![
+                ("蒙古(ᠶᠡᠬᠡ ᠮᠣᠩᠭᠣᠯ ᠤᠯᠤᠰ)".to_string(), 41.2),
+                ("英格蘭(English)".to_string(), 20.0),
+                ("法蘭西(Français)".to_string(), 9.5),
+            ],
+            ladders: Vec::new(),
+            last_played: "最近".to_string(),
+        }
+    }
+
+    #[test]
+    fn a_long_name_is_cut_by_display_width_not_by_characters() {
+        assert_eq!(fit("MarineLorD", NAME_MAX_CELLS), "MarineLorD");
+        // Four CJK characters are eight cells.
+        assert_eq!(fit("測試選手測試", 8), "測試選手…");
+        assert_eq!(fit("abcdefghij", 8), "abcdefgh…");
+    }
+
+    #[test]
+    fn only_the_first_name_of_a_civilization_is_drawn() {
+        assert_eq!(civ_name("蒙古(ᠶᠡᠬᠡ ᠮᠣᠩᠭᠣᠯ ᠤᠯᠤᠰ)"), "蒙古");
+        assert_eq!(civ_name("some new civ"), "some new civ");
+        assert!(!svg(&card()).contains('ᠶ'));
+    }
+
+    #[test]
+    fn the_stripe_takes_the_tier_colour() {
+        assert_eq!(tier_colour("diamond_2"), "#00a8fc");
+        assert_eq!(tier_colour("conqueror_3"), tier_colour("conqueror_1"));
+        assert_eq!(tier_colour("unranked"), STROKE);
+    }
+
+    #[test]
+    fn a_name_with_markup_characters_cannot_break_the_document() {
+        let doc = svg(&Card {
+            name: "<b>&".to_string(),
+            ..card()
+        });
+        assert!(doc.contains("&lt;b&gt;&amp;"));
+        assert!(!doc.contains("<b>"));
+    }
+
+    #[test]
+    fn a_card_rasterizes_to_a_png() {
+        let png = bracket_raster::rasterize(&svg(&card())).expect("the card should rasterize");
+        assert!(png.starts_with(&[0x89, 0x50, 0x4E, 0x47]));
+    }
+
+    #[test]
+    fn every_ladder_line_grows_the_card() {
+        let short = svg(&card());
+        let tall = svg(&Card {
+            ladders: vec![
+                "單挑排名: 鑽石2, 積分 1523".to_string(),
+                "團隊排名: 白金3, 積分 1210".to_string(),
+            ],
+            ..card()
+        });
+        assert!(short.contains("height=\"300\""));
+        assert!(tall.contains("height=\"374\""));
+        assert!(tall.contains("團隊排名"));
+    }
+
+    #[test]
+    fn the_labels_follow_the_locale() {
+        let english = svg(&Card {
+            locale: Locale::En,
+            ..card()
+        });
+        assert!(english.contains("Favourite civs"));
+        assert!(!english.contains("愛用文明"));
+        assert!(svg(&card()).contains("愛用文明"));
+    }
+
+    #[test]
+    fn a_card_with_no_civilizations_still_rasterizes() {
+        let png = bracket_raster::rasterize(&svg(&Card {
+            civs: Vec::new(),
+            ..card()
+        }));
+        assert!(png.is_ok());
+    }
+}
@@ -0,0 +1,248 @@
+//! `/civs` and `/civ`: the server's civilizations, added up from what the last
+//! refresh stored in `leaderboard_civs` and `leaderboard_entries` — like
+//! `/leaderboard`, a lookup costs one query and no aoe4world calls.
+//!
+//! Popularity is weighted by games, not by members: a member's pick rates are
+//! turned into games when stored, so someone with 800 games counts for more
+//! than someone with 20.
+
+use crate::aoe4world::{civilization_name, rank_level_name};
+use crate::db::{LeaderboardCiv, LeaderboardEntry};
+use crate::locale::Locale;
+use crate::ranked::escape;
+use crate::reply::chunk_lines;
+use std::collections::HashMap;
+
+/// Games with a civilization before a member can be its top player, so a
+/// high-rated member's one-off pick does not crown them.
+const MIN_TOP_PLAYER_GAMES: i64 = 10;
+
+/// One civilization across the server.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Popularity<'a> {
+    pub civilization: String,
+    pub games: i64,
+    /// The highest-rated member with at least `MIN_TOP_PLAYER_GAMES` with it.
+    pub top_player: Option<&'a LeaderboardEntry>,
+}
+
+/// Every civilization played, most games first.
+pub(crate) fn popularity<'a>(entries: &'a [LeaderboardEntry], civs: &[LeaderboardCiv]) -> Vec<Popularity<'a>> {
+    let members = entries
+        .iter()
+        .map(|entry| (entry.user_id, entry))
+        .collect::<HashMap<_, _>>();
+    let mut by_civ = HashMap::<&str, Popularity<'a>>::new();
+    for civ in civs {
+        let Some(&member) = members.get(&civ.user_id) else {
+            continue;
+        };
+        let total = by_civ.entry(&civ.civilization).or_insert_with(|| Popularity {
+            civilization: civ.civilization.clone(),
+            games: 0,
+            top_player: None,
+        });
+        total.games += civ.games;
+        if civ.games >= MIN_TOP_PLAYER_GAMES
+            && total
+                .top_player
+                .is_none_or(|top| (member.rating, -member.user_id) > (top.rating, -top.user_id))
+        {
+            total.top_player = Some(member);
+        }
+    }
+    let mut popularity = by_civ.into_values().filter(|civ| civ.games > 0).collect::<Vec<_>>();
+    popularity.sort_by(|a, b| b.games.cmp(&a.games).then_with(|| a.civilization.cmp(&b.civilization)));
+    popularity
+}
+
+/// The members whose most played civilization is `civilization`, highest
+/// rated first, each with how often they pick it.
+pub(crate) fn mains<'a>(
+    entries: &'a [LeaderboardEntry],
+    civs: &[LeaderboardCiv],
+    civilization: &str,
+) -> Vec<(&'a LeaderboardEntry, f64)> {
+    let pick_rates = civs
+        .iter()
+        .filter(|civ| civ.civilization == civilization)
+        .map(|civ| (civ.user_id, civ.pick_rate))
+        .collect::<HashMap<_, _>>();
+    let mut mains = entries
+        .iter()
+        .filter(|entry| entry.favorite_civ == civilization)
+        .map(|entry| (entry, pick_rates.get(&entry.user_id).copied().unwrap_or(0.0)))
+        .collect::<Vec<_>>();
+    mains.sort_by(|(a, _), (b, _)| b.rating.cmp(&a.rating).then_with(|| a.user_id.cmp(&b.user_id)));
+    mains
+}
+
+/// `/civs` as messages, each under Discord's limit.
+pub(crate) fn render_popularity(
+    popularity: &[Popularity],
+    members: usize,
+    refreshed_at: i64,
+    locale: Locale,
+) -> Vec<String> {
+    let games = popularity.iter().map(|civ| civ.games).sum::<i64>();
+    let heading = locale.pick(
+        format!("**伺服器文明統計** · {members} 位成員、約 {games} 場 · 資料更新於 <t:{refreshed_at}:R>"),
+        format!("**Server civilizations** · {members} members, about {games} games · as of <t:{refreshed_at}:R>"),
+    );
+    let lines = popularity.iter().enumerate().map(|(index, civ)| {
+        let share = civ.games as f64 * 100.0 / games.max(1) as f64;
+        let mut line = format!(
+            "`{:>2}` **{}** {:.1}% · {} {}",
+            index + 1,
+            civilization_name(&civ.civilization, locale),
+            share,
+            civ.games,
+            locale.pick("場", "games"),
+        );
+        if let Some(top) = civ.top_player {
+            line.push_str(&format!(" · 👑 {}（{}）", escape(&top.discord_display), top.rating));
+        }
+        line
+    });
+    chunk_lines(heading, lines)
+}
+
+/// `/civ` as messages, each under Discord's limit.
+pub(crate) fn render_mains(civilization: &str, mains: &[(&LeaderboardEntry, f64)], locale: Locale) -> Vec<String> {
+    let (civ, count) = (civilization_name(civilization, locale), mains.len());
+    if mains.is_empty() {
+        return vec![locale.pick(
+            format!("目前沒有成員主玩 **{civ}**"),
+            format!("Nobody on the server mains **{civ}** right now"),
+        )];
+    }
+    let heading = locale.pick(
+        format!("**{civ}** 主玩成員 · 共 {count} 人"),
+        format!("**{civ}** mains · {count} members"),
+    );
+    let lines = mains.iter().enumerate().map(|(index, (entry, pick_rate))| {
+        format!(
+            "`{:>3}` **{}** · {} · {}（{}）· {}%",
+            index + 1,
+            entry.rating,
+            rank_level_name(&entry.rank_level, locale),
+            escape(&entry.discord_display),
+            escape(&entry.aoe4_name),
+            pick_rate.round(),
+        )
+    });
+    chunk_lines(heading, lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(user_id: i64, rating: i64, favorite_civ: &str) -> LeaderboardEntry {
+        LeaderboardEntry {
+            user_id,
+            aoe4_id: user_id * 10,
+            aoe4_name: format!("p{user_id}"),
+            discord_display: format!("d{user_id}"),
+            rank_level: "gold_2".to_string(),
+            rating,
+            elo: rating,
+            recent_max_rating: rating,
+            global_rank: 1000,
+            games_played: 100,
+            win_rate: 50.0,
+            favorite_civ: favorite_civ.to_string(),
+            refreshed_at: chrono::DateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn civ(user_id: i64, civilization: &str, pick_rate: f64, games: i64) -> LeaderboardCiv {
+        LeaderboardCiv {
+            user_id,
+            civilization: civilization.to_string(),
+            pick_rate,
+            games,
+        }
+    }
+
+    #[test]
+    fn popularity_is_weighted_by_games_not_members() {
+        let entries = vec![
+            entry(1, 1500, "english"),
+            entry(2, 1200, "mongols"),
+            entry(3, 900, "mongols"),
+        ];
+        let civs = vec![
+            civ(1, "english", 80.0, 400),
+            civ(1, "mongols", 20.0, 100),
+            civ(2, "mongols", 100.0, 30),
+            civ(3, "mongols", 100.0, 20),
+        ];
+        let popularity = popularity(&entries, &civs);
+        let games = popularity
+            .iter()
+            .map(|civ| (civ.civilization.as_str(), civ.games))
+            .collect::<Vec<_>>();
+        assert_eq!(games, vec![("english", 400), ("mongols", 150)]);
+    }
+
+    #[test]
+    fn the_top_player_needs_enough_games_with_the_civ() {
+        let entries = vec![entry(1, 1500, "english"), entry(2, 1200, "mongols")];
+        let civs = vec![
+            civ(1, "mongols", 2.0, 2),
+            civ(2, "mongols", 100.0, 30),
+            civ(1, "english", 98.0, 98),
+        ];
+        let popularity = popularity(&entries, &civs);
+        let mongols = popularity.iter().find(|civ| civ.civilization == "mongols").unwrap();
+        assert_eq!(mongols.top_player.map(|top| top.user_id), Some(2));
+    }
+
+    #[test]
+    fn civs_of_members_no_longer_on_the_board_are_ignored() {
+        let civs = vec![civ(9, "english", 100.0, 50)];
+        assert!(popularity(&[], &civs).is_empty());
+    }
+
+    #[test]
+    fn mains_are_the_members_whose_favourite_it_is_highest_rated_first() {
+        let entries = vec![
+            entry(1, 1200, "english"),
+            entry(2, 1500, "english"),
+            entry(3, 1800, "mongols"),
+        ];
+        let civs = vec![
+            civ(1, "english", 60.0, 60),
+            civ(2, "english", 45.0, 45),
+            civ(3, "english", 30.0, 30),
+        ];
+        let mains = mains(&entries, &civs, "english");
+        let found = mains
+            .iter()
+            .map(|(entry, rate)| (entry.user_id, *rate))
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec![(2, 45.0), (1, 60.0)]);
+    }
+
+    #[test]
+    fn the_lists_read_in_the_invokers_language() {
+        let entries = vec![entry(1, 1500, "english")];
+        let civs = vec![civ(1, "english", 100.0, 100)];
+        let zh = render_popularity(&popularity(&entries, &civs), 1, 0, Locale::ZhTw);
+        assert!(
+            zh[0].contains("\n` 1` **英格蘭") && zh[0].contains("100.0% · 100 場 · 👑 d1（1500）"),
+            "{}",
+            zh[0]
+        );
+        let en = render_mains("english", &mains(&entries, &civs, "english"), Locale::En);
+        assert_eq!(
+            en[0],
+            "**English** mains · 1 members\n`  1` **1500** · Gold 2 · d1（p1）· 100%"
+        );
+        assert_eq!(
+            render_mains("mongols", &[], Locale::En),
+            vec!["Nobody on the server mains **Mongols** right now".to_string()]
+        );
+    }
+}
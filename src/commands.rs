@@ -1,39 +1,76 @@
-use crate::aoe4world::search_players;
-use crate::db::{bind_account, to_channel_id, to_db_id, to_message_id};
-use crate::guilds::{home_only, tournament_only};
+use crate::account_file;
+use crate::aoe4world::{ApiError, autocomplete_players, civilization_name, fetch_profile, fetch_profile_uncached};
+use crate::card;
+use crate::civ_stats;
+use crate::db::{
+    Account, BindOutcome, Claim, claim_account, clear_verification, close_season, create_leaderboard_view,
+    delete_reaction_rule, get_season, get_verification, import_accounts, insert_reaction_rule, list_accounts_for_user,
+    list_all, list_leaderboard, list_leaderboard_civs, list_reaction_rules, list_season_standings, list_seasons,
+    list_snapshots_since, season_history, set_main_account, set_reaction_opt_out, set_reminder_opt_out,
+    snooze_reminders, start_verification, to_channel_id, to_db_id, to_message_id, to_user_id, unbind_account,
+};
+use crate::debounce;
+use crate::emperor::{self, Trigger, parse_emoji};
+use crate::guilds::{home_admin_only, home_only, is_home_admin, tournament_only};
+use crate::h2h;
+use crate::history;
+use crate::leaderboard;
 use crate::locale::Locale;
-use crate::ranked::try_create_ranked_without_account;
-use crate::refresh::do_refresh;
+use crate::ranked::{escape, try_create_ranked_without_account};
+use crate::refresh::{CONCURRENT_FETCHES, do_refresh};
 use crate::reply::ephemeral;
+use crate::season;
 use crate::tournament::access::{
     self, Access, create_tournament_only, may_manage, tournament_admin_only, tournament_manage_only,
     wrong_channel_message,
 };
+use crate::tournament::audit::Actor;
 use crate::tournament::db as tournament_db;
 use crate::tournament::panel_check::PanelOutcome;
 use crate::tournament::slug::{slugify, validate_slug};
 use crate::tournament::{
-    audit, bracket, bracket_view, checkin, checkin_panel, completion, import, invite as tournament_invite, panel,
-    redraft, registration, report, seed_panel, seeding, set_thread, setup as tournament_setup,
-    start as tournament_start, teardown,
+    audit, bracket, bracket_view, checkin, checkin_panel, completion, groups as tournament_groups, import,
+    invite as tournament_invite, lifecycle, panel, redraft, registration, report, schedule, seed_panel, seeding,
+    set_thread, setup as tournament_setup, swiss, teardown,
 };
+use crate::verify;
 use crate::{Context, Data, Error};
-use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
-use regex::Regex;
+use chrono::{DateTime, Utc};
+use poise::futures_util::future::join_all;
+use poise::futures_util::{StreamExt, stream};
 use serenity::all::{
-    AutocompleteChoice, ChannelId, CreateChannel, GetMessages, GuildChannel, PermissionOverwrite,
-    PermissionOverwriteType, Permissions, ResolvedValue, RoleId, Unresolved, User, UserId,
+    Attachment, AutocompleteChoice, ChannelId, CreateAttachment, CreateChannel, CreateMessage, GuildChannel,
+    Mentionable, PermissionOverwrite, PermissionOverwriteType, Permissions, ResolvedValue, RoleId, Unresolved, User,
+    UserId,
 };
 use serenity::json::json;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 use tracing::{error, info};
 
-static INTERACTION_CHANNEL_ID: ChannelId = ChannelId::new(1263524546582020254);
+pub(crate) static INTERACTION_CHANNEL_ID: ChannelId = ChannelId::new(1263524546582020254);
 
 pub(crate) type Command = poise::Command<Data, Error>;
 
 /// The home guild's commands
 pub(crate) fn home() -> Vec<Command> {
-    vec![rebuild(), bind(), id(), name(), refresh(), check()]
+    vec![
+        bind(),
+        id(),
+        name(),
+        refresh(),
+        check(),
+        rating_history(),
+        head_to_head(),
+        reminder(),
+        reaction(),
+        reactions(),
+        accounts(),
+        browse_leaderboard(),
+        season(),
+        civs(),
+        civ(),
+    ]
 }
 
 /// The tournament guild's commands.
@@ -45,7 +82,7 @@ pub(crate) fn tournament() -> Vec<Command> {
     slash_command,
     guild_only,
     check = "home_only",
-    subcommands("id", "name"),
+    subcommands("id", "name", "bind_verify", "bind_confirm", "bind_list", "bind_remove", "bind_main"),
     subcommand_required
 )]
 pub async fn bind(_: Context<'_>) -> Result<(), Error> {
@@ -53,61 +90,266 @@ pub async fn bind(_: Context<'_>) -> Result<(), Error> {
 }
 
 #[poise::command(slash_command, guild_only, check = "home_only")]
-pub async fn id(ctx: Context<'_>, aoe4_id: i32) -> Result<(), Error> {
+pub async fn id(
+    ctx: Context<'_>,
+    aoe4_id: i32,
+    #[description = "Admins only: take the profile over from whoever holds it"]
+    #[description_localized("zh-TW", "僅限管理員：從目前綁定者手上接管這個帳號")]
+    take_over: Option<bool>,
+) -> Result<(), Error> {
     info!("attempting to bind id {}", aoe4_id);
-    let user_id = ctx.author().id;
-    info!("binding discord user {} with aoe4 player {}", user_id, aoe4_id);
-    let message = bind_account(&ctx.data().database, to_db_id(user_id), i64::from(aoe4_id))
+    bind_profile(ctx, i64::from(aoe4_id), take_over.unwrap_or(false)).await
+}
+
+/// What a bind announces in the channel, in the binder's language.
+fn bound_message(user_id: i64, aoe4_id: i64, locale: Locale) -> String {
+    locale.pick(
+        format!("綁定discord帳號 `{user_id}` 與世紀帝國四帳號 `{aoe4_id}` "),
+        format!("Bound Discord account `{user_id}` to Age of Empires IV profile `{aoe4_id}` "),
+    )
+}
+
+/// `/bind id` and `/bind name` once they have a profile id. A profile someone
+/// else holds is refused: its owner can prove it is theirs with `/bind verify`
+/// instead. An admin can take it over, but only by asking with `take_over` — an
+/// admin binding themselves by mistake must not quietly strip a member of a
+/// verified profile.
+async fn bind_profile(ctx: Context<'_>, aoe4_id: i64, take_over: bool) -> Result<(), Error> {
+    let locale = Locale::from_context(ctx);
+    let user_id = to_db_id(ctx.author().id);
+    let claim = if !take_over {
+        Claim::Plain
+    } else if is_home_admin(ctx).await? {
+        Claim::Admin
+    } else {
+        return ephemeral(
+            ctx,
+            locale.pick(
+                "只有管理員可以接管別人綁定的帳號",
+                "Only an admin can take over a profile someone else has bound",
+            ),
+        )
+        .await;
+    };
+    info!(
+        "binding discord user {} with aoe4 player {} ({:?})",
+        user_id, aoe4_id, claim
+    );
+    let outcome = claim_account(&ctx.data().database, user_id, aoe4_id, claim)
         .await
         .inspect_err(|_error| {
             error!("database insert failed");
         })?;
-    ctx.say(message).await?;
+    match outcome {
+        BindOutcome::Bound => {
+            ctx.say(bound_message(user_id, aoe4_id, locale)).await?;
+        },
+        BindOutcome::TakenOver { from } => {
+            info!("aoe4 player {aoe4_id} taken over from discord user {from}");
+            let from = to_user_id(from).mention();
+            let note = locale.pick(
+                format!("（從 {from} 接管，原本的驗證已取消）"),
+                format!("(taken over from {from}; their verification was dropped)"),
+            );
+            ctx.say(format!("{}{note}", bound_message(user_id, aoe4_id, locale)))
+                .await?;
+        },
+        BindOutcome::AlreadyYours => {
+            ephemeral(
+                ctx,
+                locale.pick(
+                    format!("你已經綁定帳號 `{aoe4_id}` 了"),
+                    format!("Profile `{aoe4_id}` is already bound to you"),
+                ),
+            )
+            .await?
+        },
+        BindOutcome::HeldBy {
+            user_id: holder,
+            verified,
+        } => {
+            let holder = to_user_id(holder).mention();
+            let message = match (locale, verified) {
+                (Locale::ZhTw, verified) => format!(
+                    "帳號 `{aoe4_id}` {}給 {holder}。如果這是你的帳號，請用 `/bind verify` 驗證後取回。",
+                    if verified { "已被驗證綁定" } else { "已被綁定" }
+                ),
+                (Locale::En, verified) => format!(
+                    "Profile `{aoe4_id}` is {}bound to {holder}. If it is yours, prove it with `/bind verify` to take it back.",
+                    if verified { "verified and " } else { "" }
+                ),
+            };
+            ephemeral(ctx, message).await?;
+        },
+    }
     Ok(())
 }
 
-#[poise::command(slash_command, guild_only, check = "home_only")]
-pub async fn rebuild(ctx: Context<'_>) -> Result<(), Error> {
-    ctx.defer().await?;
-    let channel = ctx.guild_channel().await.unwrap();
+/// Your bound profiles, main first.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "list",
+    description_localized("zh-TW", "列出你綁定的世紀帝國四帳號")
+)]
+pub async fn bind_list(ctx: Context<'_>) -> Result<(), Error> {
+    let locale = Locale::from_context(ctx);
+    let accounts = list_accounts_for_user(&ctx.data().database, to_db_id(ctx.author().id)).await?;
+    if accounts.is_empty() {
+        return ephemeral(
+            ctx,
+            locale.pick(
+                "你還沒有綁定世紀帝國四帳號，用 `/bind name` 綁定",
+                "You have no Age of Empires IV profile bound yet; bind one with `/bind name`",
+            ),
+        )
+        .await;
+    }
+    let names = profile_names(accounts.iter().map(|account| account.aoe4_id)).await;
+    let mut lines = accounts
+        .iter()
+        .map(|account| account_label(account, names.get(&account.aoe4_id), locale))
+        .collect::<Vec<_>>();
+    if !accounts.iter().any(|account| account.main) {
+        lines.push(
+            locale
+                .pick(
+                    "還沒有指定主帳號，排行榜會用排名最高的帳號；可用 `/bind main` 指定",
+                    "No main profile chosen, so the board uses your highest ranked one; choose one with `/bind main`",
+                )
+                .to_string(),
+        );
+    }
+    ephemeral(ctx, lines.join("\n")).await
+}
+
+/// Unbind one of your profiles.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "remove",
+    description_localized("zh-TW", "解除綁定一個世紀帝國四帳號")
+)]
+pub async fn bind_remove(
+    ctx: Context<'_>,
+    #[description = "The profile to unbind"]
+    #[description_localized("zh-TW", "要解除綁定的帳號")]
+    #[autocomplete = "auto_complete_own_account"]
+    aoe4_id: i64,
+) -> Result<(), Error> {
+    let locale = Locale::from_context(ctx);
+    let user_id = to_db_id(ctx.author().id);
+    if !unbind_account(&ctx.data().database, user_id, aoe4_id).await? {
+        return ephemeral(ctx, not_yours(aoe4_id, locale)).await;
+    }
+    info!("discord user {user_id} unbound aoe4 player {aoe4_id}");
+    ephemeral(
+        ctx,
+        locale.pick(
+            format!("已解除綁定帳號 `{aoe4_id}`，排行榜會在下次刷新時更新"),
+            format!("Unbound profile `{aoe4_id}`; the board catches up on the next refresh"),
+        ),
+    )
+    .await
+}
 
-    let regex1 = Regex::new(r"綁定discord帳號 `(?<user_id>[0-9]+)` 與世紀帝國四帳號 `(?<aoe4_id>[0-9]+)`").unwrap();
-    let regex2 =
-        Regex::new(r"Bound discord user `(?<user_id>[0-9]+)` to aoe4 world profile `(?<aoe4_id>[0-9]+)`").unwrap();
+/// Choose which of your profiles the board shows you by.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "main",
+    description_localized("zh-TW", "指定排行榜顯示的主帳號")
+)]
+pub async fn bind_main(
+    ctx: Context<'_>,
+    #[description = "The profile to show you by"]
+    #[description_localized("zh-TW", "要作為主帳號的帳號")]
+    #[autocomplete = "auto_complete_own_account"]
+    aoe4_id: i64,
+) -> Result<(), Error> {
+    let locale = Locale::from_context(ctx);
+    let user_id = to_db_id(ctx.author().id);
+    if !set_main_account(&ctx.data().database, user_id, aoe4_id).await? {
+        return ephemeral(ctx, not_yours(aoe4_id, locale)).await;
+    }
+    info!("discord user {user_id} made aoe4 player {aoe4_id} their main");
+    ephemeral(
+        ctx,
+        locale.pick(
+            format!("已將帳號 `{aoe4_id}` 設為主帳號，排行榜會在下次刷新時更新"),
+            format!("Profile `{aoe4_id}` is now your main; the board catches up on the next refresh"),
+        ),
+    )
+    .await
+}
 
-    let mut latest_message = channel.last_message_id.unwrap();
-    let limit = 50;
-    let mut messages = channel
-        .messages(ctx.http(), GetMessages::new().before(latest_message).limit(limit))
-        .await?;
-    loop {
-        info!("loading first batch, size {}", messages.len());
-        for message in messages.iter() {
-            let content = &message.content;
-            latest_message = message.id;
-            if let Some(cap) = regex1.captures(content) {
-                let user_id = cap["user_id"].parse::<i64>().unwrap();
-                let aoe4_id = cap["aoe4_id"].parse::<i64>().unwrap();
-                let msg = bind_account(&ctx.data().database, user_id, aoe4_id).await?;
-                info!(msg);
-            }
-            if let Some(cap) = regex2.captures(content) {
-                let user_id = cap["user_id"].parse::<i64>().unwrap();
-                let aoe4_id = cap["aoe4_id"].parse::<i64>().unwrap();
-                let msg = bind_account(&ctx.data().database, user_id, aoe4_id).await?;
-                info!(msg);
-            }
-        }
-        if messages.len() < limit as usize {
-            break;
-        }
-        messages = channel
-            .messages(ctx.http(), GetMessages::new().before(latest_message).limit(limit))
-            .await?;
+fn not_yours(aoe4_id: i64, locale: Locale) -> String {
+    locale.pick(
+        format!("你沒有綁定帳號 `{aoe4_id}`"),
+        format!("Profile `{aoe4_id}` is not bound to you"),
+    )
+}
+
+/// One line of `/bind list`: the profile's name, when aoe4world can be reached
+/// for it, a star on the main, and whether it is verified.
+fn account_label(account: &Account, name: Option<&String>, locale: Locale) -> String {
+    let star = if account.main { "⭐ " } else { "" };
+    let verified = if account.verified {
+        locale.pick(" ✅ 已驗證", " ✅ verified")
+    } else {
+        ""
+    };
+    match name {
+        Some(name) => format!("{star}{} (`{}`){verified}", escape(name), account.aoe4_id),
+        None => format!("{star}`{}`{verified}", account.aoe4_id),
     }
+}
 
-    ctx.say("重建完成").await?;
-    Ok(())
+/// How long a profile's name is waited for before its label falls back to the
+/// bare id. Autocomplete must answer within Discord's three seconds, and the
+/// name is only a nicety next to the id.
+const PROFILE_NAME_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Each profile's current name, fetched all at once rather than one after
+/// another. A profile missing from the map failed or ran out of time.
+async fn profile_names(aoe4_ids: impl IntoIterator<Item = i64>) -> HashMap<i64, String> {
+    join_all(aoe4_ids.into_iter().map(|aoe4_id| async move {
+        let profile = tokio::time::timeout(PROFILE_NAME_TIMEOUT, fetch_profile(aoe4_id)).await;
+        Some((aoe4_id, profile.ok()?.ok()?.name))
+    }))
+    .await
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// The caller's own bound profiles, for `/bind remove` and `/bind main`,
+/// matched on id or name.
+async fn auto_complete_own_account(ctx: Context<'_>, partial: &str) -> impl Iterator<Item = AutocompleteChoice> {
+    let accounts = list_accounts_for_user(&ctx.data().database, to_db_id(ctx.author().id))
+        .await
+        .unwrap_or_default();
+    let names = profile_names(accounts.iter().map(|account| account.aoe4_id)).await;
+    let partial = partial.trim().to_lowercase();
+    let mut choices = Vec::new();
+    for account in accounts {
+        // Plain text, unlike `account_label`: a choice label is not markdown.
+        let name = names.get(&account.aoe4_id);
+        let id = account.aoe4_id.to_string();
+        if !id.starts_with(&partial) && !name.is_some_and(|name| name.to_lowercase().contains(&partial)) {
+            continue;
+        }
+        let star = if account.main { "⭐ " } else { "" };
+        let label = match name {
+            Some(name) => format!("{star}{name} ({id})"),
+            None => format!("{star}{id}"),
+        };
+        choices.push(AutocompleteChoice::new(label, json!(account.aoe4_id)));
+    }
+    choices.into_iter()
 }
 
 /// The value a hint choice carries. Discord requires a choice's value to match
@@ -144,29 +386,29 @@ async fn auto_complete_id(ctx: Context<'_>, username: &str) -> impl Iterator<Ite
         let hint = Locale::from_context(ctx).pick("請輸入你的遊戲名稱…", "Type your in-game name…");
         return vec![AutocompleteChoice::new(hint, json!(NO_PROFILE_PICKED))].into_iter();
     }
-    aoe4_search_choices(username).await.into_iter()
+    aoe4_search_choices(ctx, username).await.into_iter()
 }
 
-/// Searches aoe4world by name and formats the results as choices, keyed by
+/// How long a keystroke has to stand before it is looked up — long enough that
+/// typing a name at speed costs one request, not one per letter.
+const AUTOCOMPLETE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Looks up profiles by name prefix and formats them as choices, keyed by
 /// profile id — the part `auto_complete_id` and `auto_complete_invite_profile`
 /// share once past their differing empty-field behavior.
-async fn aoe4_search_choices(username: &str) -> Vec<AutocompleteChoice> {
-    info!("search aoe4 world profiles with username {}", username);
-    let mut players = match search_players(username).await {
-        None => vec![],
-        Some(profiles) => profiles.players,
-    };
-    players.sort();
-    players
+async fn aoe4_search_choices(ctx: Context<'_>, prefix: &str) -> Vec<AutocompleteChoice> {
+    if !debounce::settled(ctx.author().id.get(), AUTOCOMPLETE_DEBOUNCE).await {
+        return Vec::new();
+    }
+    info!("autocomplete aoe4 world profiles with prefix {}", prefix);
+    let Ok(result) = autocomplete_players(prefix).await else {
+        return Vec::new();
+    };
+    let locale = Locale::from_context(ctx);
+    result
+        .players
         .into_iter()
-        .filter_map(|player| {
-            let data = player.leaderboards.rm_solo?;
-            Some(AutocompleteChoice::new(
-                format!("{} - 階級: {}, 積分: {}", player.name, data.rank_level(), data.rating()),
-                json!(player.profile_id),
-            ))
-        })
-        .take(10)
+        .map(|player| AutocompleteChoice::new(player.label(locale), json!(player.profile_id)))
         .collect()
 }
 
@@ -196,7 +438,7 @@ async fn auto_complete_invite_profile(ctx: Context<'_>, partial: &str) -> impl I
         }
         return Vec::new().into_iter();
     }
-    aoe4_search_choices(partial).await.into_iter()
+    aoe4_search_choices(ctx, partial).await.into_iter()
 }
 
 /// The `user` option's value, if the admin has already picked one — read out
@@ -219,59 +461,1116 @@ fn already_selected_user(ctx: Context<'_>) -> Option<UserId> {
     })
 }
 
-#[poise::command(slash_command, guild_only, check = "home_only")]
-pub async fn name(
+#[poise::command(slash_command, guild_only, check = "home_only")]
+pub async fn name(
+    ctx: Context<'_>,
+    #[description = "Type your in-game name and pick yourself from the list"]
+    #[description_localized("zh-TW", "輸入你的遊戲內名稱，然後從清單中選擇自己")]
+    #[autocomplete = "auto_complete_id"]
+    in_game_name: i32,
+    #[description = "Admins only: take the profile over from whoever holds it"]
+    #[description_localized("zh-TW", "僅限管理員：從目前綁定者手上接管這個帳號")]
+    take_over: Option<bool>,
+) -> Result<(), Error> {
+    let Some(aoe4_id) = picked_profile(in_game_name) else {
+        ask_for_in_game_name(ctx).await?;
+        return Ok(());
+    };
+    info!("attempting to bind id {}", aoe4_id);
+    bind_profile(ctx, i64::from(aoe4_id), take_over.unwrap_or(false)).await
+}
+
+/// Prove a profile is yours with a code in its in-game name.
+///
+/// Also how a profile someone else bound is taken back.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "verify",
+    description_localized("zh-TW", "驗證帳號所有權：把驗證碼暫時放進遊戲名稱")
+)]
+pub async fn bind_verify(
+    ctx: Context<'_>,
+    #[description = "Type your in-game name and pick yourself from the list"]
+    #[description_localized("zh-TW", "輸入你的遊戲內名稱，然後從清單中選擇自己")]
+    #[autocomplete = "auto_complete_id"]
+    in_game_name: i32,
+) -> Result<(), Error> {
+    let Some(aoe4_id) = picked_profile(in_game_name) else {
+        ask_for_in_game_name(ctx).await?;
+        return Ok(());
+    };
+    let locale = Locale::from_context(ctx);
+    let code = verify::new_code();
+    let issued_at = Utc::now();
+    start_verification(
+        &ctx.data().database,
+        to_db_id(ctx.author().id),
+        aoe4_id.into(),
+        &code,
+        issued_at,
+    )
+    .await?;
+    let expires = (issued_at + verify::CODE_LIFETIME).timestamp();
+    ephemeral(
+        ctx,
+        locale.pick(
+            format!(
+                "請在遊戲中把名稱暫時改成包含 `{code}`，打完一場讓 aoe4world 更新後，用 `/bind confirm` 完成驗證。\n\
+                驗證碼 <t:{expires}:R> 失效，驗證完成後就可以改回原本的名稱。"
+            ),
+            format!(
+                "Put `{code}` somewhere in your in-game name for now, play a game so aoe4world picks it up, then \
+                run `/bind confirm`.\nThe code expires <t:{expires}:R>; once verified you can change your name back."
+            ),
+        ),
+    )
+    .await
+}
+
+/// For a profile id aoe4world does not know.
+fn no_such_player(locale: Locale) -> &'static str {
+    locale.pick("aoe4world 上沒有這位玩家", "aoe4world has no such player")
+}
+
+fn aoe4world_unreachable(locale: Locale) -> &'static str {
+    locale.pick(
+        "aoe4world 暫時無法連線，請稍後再試",
+        "aoe4world can't be reached right now, please try again later",
+    )
+}
+
+/// Check the code from `/bind verify` and bind the profile as verified.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "confirm",
+    description_localized("zh-TW", "檢查遊戲名稱中的驗證碼，完成驗證綁定")
+)]
+pub async fn bind_confirm(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+    let locale = Locale::from_context(ctx);
+    let pool = &ctx.data().database;
+    let user_id = to_db_id(ctx.author().id);
+    let Some(pending) = get_verification(pool, user_id).await? else {
+        return ephemeral(
+            ctx,
+            locale.pick(
+                "你沒有進行中的驗證，請先用 `/bind verify`",
+                "You have no verification in progress; start one with `/bind verify`",
+            ),
+        )
+        .await;
+    };
+    if verify::is_expired(pending.issued_at, Utc::now()) {
+        clear_verification(pool, user_id).await?;
+        return ephemeral(
+            ctx,
+            locale.pick(
+                "驗證碼已過期，請重新用 `/bind verify`",
+                "The code has expired; get a new one with `/bind verify`",
+            ),
+        )
+        .await;
+    }
+    let profile = match fetch_profile_uncached(pending.aoe4_id).await {
+        Ok(profile) => profile,
+        Err(ApiError::NotFound) => return ephemeral(ctx, no_such_player(locale)).await,
+        Err(_) => return ephemeral(ctx, aoe4world_unreachable(locale)).await,
+    };
+    if !verify::name_carries(&profile.name, &pending.code) {
+        let (name, code) = (profile.name.replace('`', ""), &pending.code);
+        return ephemeral(
+            ctx,
+            locale.pick(
+                format!(
+                    "aoe4world 上的名稱是 `{name}`，還找不到 `{code}`。aoe4world 可能要等你打完下一場才會更新名稱。"
+                ),
+                format!(
+                    "Your name on aoe4world is `{name}`, which does not have `{code}` in it yet. aoe4world may not \
+                    update it until you finish another game."
+                ),
+            ),
+        )
+        .await;
+    }
+
+    let outcome = claim_account(pool, user_id, pending.aoe4_id, Claim::Verified).await?;
+    clear_verification(pool, user_id).await?;
+    if let BindOutcome::TakenOver { from } = outcome {
+        info!(
+            "aoe4 player {} taken over from discord user {from} by verification",
+            pending.aoe4_id
+        );
+    }
+    info!("discord user {user_id} verified aoe4 player {}", pending.aoe4_id);
+    ephemeral(
+        ctx,
+        locale.pick(
+            "驗證成功，現在可以把遊戲名稱改回來了",
+            "Verified — you can change your in-game name back now",
+        ),
+    )
+    .await?;
+    // Said in the channel, like any bind.
+    ctx.channel_id()
+        .say(
+            ctx.http(),
+            format!(
+                "{}{}",
+                bound_message(user_id, pending.aoe4_id, locale),
+                locale.pick("（已驗證）", "(verified)")
+            ),
+        )
+        .await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only, check = "home_only", rename = "查分")]
+pub async fn check(
+    ctx: Context<'_>,
+    #[description = "Type an in-game name and pick the player from the list"]
+    #[description_localized("zh-TW", "輸入遊戲內名稱，然後從清單中選擇玩家")]
+    #[autocomplete = "auto_complete_id"]
+    in_game_name: i32,
+) -> Result<(), Error> {
+    let Some(aoe4_id) = picked_profile(in_game_name) else {
+        ask_for_in_game_name(ctx).await?;
+        return Ok(());
+    };
+    info!("attempting to check id {}", aoe4_id);
+    ctx.defer().await?;
+    let locale = Locale::from_context(ctx);
+    let player = match try_create_ranked_without_account(aoe4_id).await {
+        Ok(Some(player)) => player,
+        Ok(None) => {
+            info!("no ranked data for aoe4 id {}", aoe4_id);
+            ctx.say(locale.pick("查不到這位玩家的排名積分資料", "This player has no ranked data"))
+                .await?;
+            return Ok(());
+        },
+        Err(ApiError::NotFound) => {
+            ctx.say(no_such_player(locale)).await?;
+            return Ok(());
+        },
+        Err(_) => {
+            ctx.say(aoe4world_unreachable(locale)).await?;
+            return Ok(());
+        },
+    };
+    let png = card::png(&player.card(locale)).await?;
+    // Best-effort: the card is the answer, and a season list is a footnote.
+    let seasons = season_history(&ctx.data().database, aoe4_id.into())
+        .await
+        .ok()
+        .and_then(|results| season::history(&results, locale));
+    let mut content = format!("<https://aoe4world.com/players/{aoe4_id}>");
+    if let Some(seasons) = seasons {
+        content.push_str(&format!("\n{seasons}"));
+    }
+    // The text the card replaced rides along as its alt text, for screen
+    // readers and for search.
+    ctx.http()
+        .get_channel(INTERACTION_CHANNEL_ID)
+        .await?
+        .guild()
+        .unwrap()
+        .send_message(
+            ctx.http(),
+            CreateMessage::new()
+                .content(content)
+                .add_file(CreateAttachment::bytes(png, "card.png").description(player.info(locale))),
+        )
+        .await?;
+    ctx.say(locale.pick("查分成功", "Posted the player card")).await?;
+    Ok(())
+}
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum LeaderboardSort {
+    #[name = "Rating"]
+    #[name_localized("zh-TW", "排名積分")]
+    Rating,
+    #[name = "Elo"]
+    Elo,
+    #[name = "Recent max rating"]
+    #[name_localized("zh-TW", "近期最高積分")]
+    RecentMax,
+    #[name = "Games played"]
+    #[name_localized("zh-TW", "場次")]
+    Games,
+    #[name = "Win rate"]
+    #[name_localized("zh-TW", "勝率")]
+    WinRate,
+}
+
+/// The tiers `/leaderboard` filters by, as aoe4world names them in a
+/// `rank_level` before the division.
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum LeaderboardTier {
+    #[name = "Conqueror"]
+    #[name_localized("zh-TW", "征服者")]
+    Conqueror,
+    #[name = "Diamond"]
+    #[name_localized("zh-TW", "鑽石")]
+    Diamond,
+    #[name = "Platinum"]
+    #[name_localized("zh-TW", "白金")]
+    Platinum,
+    #[name = "Gold"]
+    #[name_localized("zh-TW", "黃金")]
+    Gold,
+    #[name = "Silver"]
+    #[name_localized("zh-TW", "白銀")]
+    Silver,
+    #[name = "Bronze"]
+    #[name_localized("zh-TW", "青銅")]
+    Bronze,
+}
+
+/// The favourite civilizations on the stored board, matched on either name.
+async fn auto_complete_board_civ(ctx: Context<'_>, partial: &str) -> impl Iterator<Item = AutocompleteChoice> {
+    let locale = Locale::from_context(ctx);
+    let entries = list_leaderboard(&ctx.data().database).await.unwrap_or_default();
+    let partial = partial.trim().to_lowercase();
+    let mut civs = entries.into_iter().map(|entry| entry.favorite_civ).collect::<Vec<_>>();
+    civs.sort();
+    civs.dedup();
+    civs.into_iter()
+        .filter(move |civ| civ.contains(&partial) || civilization_name(civ, locale).to_lowercase().contains(&partial))
+        .take(25)
+        .map(move |civ| AutocompleteChoice::new(civilization_name(&civ, locale), civ))
+}
+
+// `browse_leaderboard` rather than `leaderboard`, the `crate::leaderboard`
+// module's name, the same way `/history` is `rating_history`.
+/// The ranked board, privately, a page at a time — sorted and filtered as asked.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "leaderboard",
+    description_localized("zh-TW", "私下瀏覽排行榜，可排序與篩選")
+)]
+pub async fn browse_leaderboard(
+    ctx: Context<'_>,
+    #[description = "What to rank by (defaults to rating)"]
+    #[description_localized("zh-TW", "排序依據（預設排名積分）")]
+    sort: Option<LeaderboardSort>,
+    #[description = "Only members in this tier"]
+    #[description_localized("zh-TW", "只看這個階級的成員")]
+    tier: Option<LeaderboardTier>,
+    #[description = "Only members whose favourite civilization this is"]
+    #[description_localized("zh-TW", "只看愛用這個文明的成員")]
+    #[autocomplete = "auto_complete_board_civ"]
+    civ: Option<String>,
+) -> Result<(), Error> {
+    let sort = match sort.unwrap_or(LeaderboardSort::Rating) {
+        LeaderboardSort::Rating => leaderboard::Sort::Rating,
+        LeaderboardSort::Elo => leaderboard::Sort::Elo,
+        LeaderboardSort::RecentMax => leaderboard::Sort::RecentMax,
+        LeaderboardSort::Games => leaderboard::Sort::Games,
+        LeaderboardSort::WinRate => leaderboard::Sort::WinRate,
+    };
+    let tier = tier.map(|tier| match tier {
+        LeaderboardTier::Conqueror => "conqueror",
+        LeaderboardTier::Diamond => "diamond",
+        LeaderboardTier::Platinum => "platinum",
+        LeaderboardTier::Gold => "gold",
+        LeaderboardTier::Silver => "silver",
+        LeaderboardTier::Bronze => "bronze",
+    });
+    let civ = civ.map(|civ| civ.trim().to_string()).filter(|civ| !civ.is_empty());
+
+    let locale = Locale::from_context(ctx);
+    let pool = &ctx.data().database;
+    let view = create_leaderboard_view(pool, to_db_id(ctx.author().id), sort.tag(), tier, civ.as_deref()).await?;
+    let Some((content, components)) = leaderboard::view_message(pool, &view, locale).await? else {
+        return ephemeral(
+            ctx,
+            locale.pick(
+                "排行榜還沒有資料，請等下一次刷新",
+                "The leaderboard is empty until the next refresh",
+            ),
+        )
+        .await;
+    };
+    ctx.send(
+        poise::CreateReply::default()
+            .content(content)
+            .components(components)
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Archived seasons of the ranked board — see `crate::season`.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    subcommands("season_close", "season_show"),
+    subcommand_required,
+    description_localized("zh-TW", "排行榜的賽季封存")
+)]
+pub async fn season(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Freeze the ranked board, as of the last refresh, into a named season.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_admin_only",
+    rename = "close",
+    description_localized("zh-TW", "將目前的排行榜（最後一次刷新的資料）封存為一個賽季")
+)]
+pub async fn season_close(
+    ctx: Context<'_>,
+    #[description = "The season's name, e.g. S9"]
+    #[description_localized("zh-TW", "賽季名稱，例如 S9")]
+    name: String,
+) -> Result<(), Error> {
+    let locale = Locale::from_context(ctx);
+    let Some(name) = season::normalize_name(&name) else {
+        return ephemeral(
+            ctx,
+            locale.pick(
+                format!("賽季名稱須為 1 到 {} 個字的單行文字", season::MAX_NAME_CHARS),
+                format!(
+                    "A season name is one line of 1 to {} characters",
+                    season::MAX_NAME_CHARS
+                ),
+            ),
+        )
+        .await;
+    };
+    let pool = &ctx.data().database;
+    let entries = list_leaderboard(pool).await?;
+    if entries.is_empty() {
+        return ephemeral(
+            ctx,
+            locale.pick(
+                "排行榜還沒有資料，請等下一次刷新再封存",
+                "The leaderboard is empty; close the season after the next refresh",
+            ),
+        )
+        .await;
+    }
+    let standings = season::final_order(&entries);
+    let Some(closed) = close_season(pool, name, to_db_id(ctx.author().id), &standings).await? else {
+        return ephemeral(
+            ctx,
+            locale.pick(
+                format!("已經有名為「{}」的賽季", escape(name)),
+                format!("There is already a season called “{}”", escape(name)),
+            ),
+        )
+        .await;
+    };
+    info!(
+        "{} closed season {} with {} members",
+        ctx.author().name,
+        closed.name,
+        standings.len()
+    );
+    let (count, at) = (standings.len(), closed.board_refreshed_at.timestamp());
+    ctx.say(locale.pick(
+        format!(
+            "已封存賽季 **{}**：{count} 位成員，排行榜資料截至 <t:{at}:f>。使用 `/season show` 查看",
+            escape(&closed.name)
+        ),
+        format!(
+            "Closed season **{}**: {count} members, from the board as of <t:{at}:f>. See it with `/season show`",
+            escape(&closed.name)
+        ),
+    ))
+    .await?;
+    Ok(())
+}
+
+/// The archived seasons' names, newest first.
+async fn auto_complete_season(ctx: Context<'_>, partial: &str) -> impl Iterator<Item = AutocompleteChoice> {
+    let seasons = list_seasons(&ctx.data().database).await.unwrap_or_default();
+    let partial = partial.trim().to_lowercase();
+    seasons
+        .into_iter()
+        .filter(move |season| season.name.to_lowercase().contains(&partial))
+        .take(25)
+        .map(|season| AutocompleteChoice::new(season.name.clone(), season.name))
+}
+
+/// An archived season's final standings.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "show",
+    description_localized("zh-TW", "查看已封存賽季的最終排名")
+)]
+pub async fn season_show(
+    ctx: Context<'_>,
+    #[description = "The season's name"]
+    #[description_localized("zh-TW", "賽季名稱")]
+    #[autocomplete = "auto_complete_season"]
+    name: String,
+) -> Result<(), Error> {
+    let locale = Locale::from_context(ctx);
+    let pool = &ctx.data().database;
+    let Some(found) = get_season(pool, name.trim()).await? else {
+        return ephemeral(
+            ctx,
+            locale.pick(
+                format!("沒有名為「{}」的賽季", escape(name.trim())),
+                format!("There is no season called “{}”", escape(name.trim())),
+            ),
+        )
+        .await;
+    };
+    let standings = list_season_standings(pool, found.id).await?;
+    for chunk in season::render(&found, &standings, locale) {
+        ephemeral(ctx, chunk).await?;
+    }
+    Ok(())
+}
+
+/// The server's civilizations by games played, each with its top player.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    description_localized("zh-TW", "伺服器成員最常玩的文明，以及各文明的頂尖玩家")
+)]
+pub async fn civs(ctx: Context<'_>) -> Result<(), Error> {
+    let locale = Locale::from_context(ctx);
+    let pool = &ctx.data().database;
+    let entries = list_leaderboard(pool).await?;
+    let Some(refreshed_at) = entries.iter().map(|entry| entry.refreshed_at).max() else {
+        return ephemeral(
+            ctx,
+            locale.pick(
+                "排行榜還沒有資料，請等下一次刷新",
+                "The leaderboard is empty until the next refresh",
+            ),
+        )
+        .await;
+    };
+    let civs = list_leaderboard_civs(pool).await?;
+    let popularity = civ_stats::popularity(&entries, &civs);
+    for chunk in civ_stats::render_popularity(&popularity, entries.len(), refreshed_at.timestamp(), locale) {
+        ephemeral(ctx, chunk).await?;
+    }
+    Ok(())
+}
+
+/// Every civilization anyone on the stored board plays, matched on either name.
+async fn auto_complete_played_civ(ctx: Context<'_>, partial: &str) -> impl Iterator<Item = AutocompleteChoice> {
+    let locale = Locale::from_context(ctx);
+    let rows = list_leaderboard_civs(&ctx.data().database).await.unwrap_or_default();
+    let partial = partial.trim().to_lowercase();
+    let mut civs = rows.into_iter().map(|row| row.civilization).collect::<Vec<_>>();
+    civs.sort();
+    civs.dedup();
+    civs.into_iter()
+        .filter(move |civ| civ.contains(&partial) || civilization_name(civ, locale).to_lowercase().contains(&partial))
+        .take(25)
+        .map(move |civ| AutocompleteChoice::new(civilization_name(&civ, locale), civ))
+}
+
+/// The members who main a civilization.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    description_localized("zh-TW", "列出主玩某個文明的成員")
+)]
+pub async fn civ(
+    ctx: Context<'_>,
+    #[description = "The civilization"]
+    #[description_localized("zh-TW", "文明")]
+    #[autocomplete = "auto_complete_played_civ"]
+    name: String,
+) -> Result<(), Error> {
+    let locale = Locale::from_context(ctx);
+    let pool = &ctx.data().database;
+    let entries = list_leaderboard(pool).await?;
+    let civs = list_leaderboard_civs(pool).await?;
+    let name = name.trim();
+    // Typed rather than picked: match it against either language's name.
+    let civilization = civs
+        .iter()
+        .map(|civ| civ.civilization.as_str())
+        .chain(entries.iter().map(|entry| entry.favorite_civ.as_str()))
+        .find(|civ| {
+            *civ == name
+                || [Locale::ZhTw, Locale::En]
+                    .into_iter()
+                    .any(|locale| civilization_name(civ, locale).eq_ignore_ascii_case(name))
+        })
+        .unwrap_or(name)
+        .to_string();
+    let mains = civ_stats::mains(&entries, &civs, &civilization);
+    for chunk in civ_stats::render_mains(&civilization, &mains, locale) {
+        ephemeral(ctx, chunk).await?;
+    }
+    Ok(())
+}
+
+/// How far back `/history` looks when not told otherwise.
+const DEFAULT_HISTORY_DAYS: i64 = 90;
+
+/// Ten years: further back than any snapshot, and well inside what `chrono`
+/// can subtract from now without panicking.
+const MAX_HISTORY_DAYS: i64 = 3650;
+
+// `rating_history` rather than `history`, which is already the name of the
+// `crate::history` module; `rename` keeps the Discord-visible command `/history`.
+/// Draws a member's rating over time, from the snapshots each refresh keeps.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "history",
+    description_localized("zh-TW", "畫出成員的積分走勢圖，資料來自每次刷新的紀錄。")
+)]
+pub async fn rating_history(
+    ctx: Context<'_>,
+    #[description = "The member to chart (defaults to you)"]
+    #[description_localized("zh-TW", "要查看的成員（預設為自己）")]
+    member: Option<User>,
+    #[description = "How many days back to chart (defaults to 90, at most 3650)"]
+    #[description_localized("zh-TW", "要往回看幾天（預設 90 天，最多 3650 天）")]
+    days: Option<i64>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let member = member.as_ref().unwrap_or(ctx.author());
+    let days = days.unwrap_or(DEFAULT_HISTORY_DAYS).clamp(1, MAX_HISTORY_DAYS);
+    let since = Utc::now() - chrono::Duration::days(days);
+
+    let pool = &ctx.data().database;
+    let accounts = list_accounts_for_user(pool, to_db_id(member.id)).await?;
+    if accounts.is_empty() {
+        ctx.say(format!("{} 還沒有綁定世紀帝國四帳號", member.name)).await?;
+        return Ok(());
+    }
+
+    let mut series = Vec::new();
+    for account in &accounts {
+        let snapshots = list_snapshots_since(pool, account.aoe4_id, since).await?;
+        series.extend(history::Series::from_snapshots(&snapshots));
+    }
+
+    let title = format!(
+        "{} — {}天積分走勢",
+        member.global_name.as_ref().unwrap_or(&member.name),
+        days
+    );
+    let Some(png) = history::chart_png(&title, &series).await? else {
+        ctx.say(format!("最近{}天內還沒有 {} 的積分紀錄", days, member.name))
+            .await?;
+        return Ok(());
+    };
+    ctx.send(poise::CreateReply::default().attachment(CreateAttachment::bytes(png, "history.png")))
+        .await?;
+    Ok(())
+}
+
+/// The lifetime record between two players, from every game aoe4world has of
+/// them facing each other.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "h2h",
+    description_localized("zh-TW", "查看兩位玩家之間的對戰紀錄")
+)]
+pub async fn head_to_head(
+    ctx: Context<'_>,
+    #[description = "The first member"]
+    #[description_localized("zh-TW", "第一位成員")]
+    first: Option<User>,
+    #[description = "The second member"]
+    #[description_localized("zh-TW", "第二位成員")]
+    second: Option<User>,
+    #[description = "Or the first player by in-game name"]
+    #[description_localized("zh-TW", "或以遊戲內名稱指定第一位玩家")]
+    #[autocomplete = "auto_complete_id"]
+    first_player: Option<i32>,
+    #[description = "Or the second player by in-game name"]
+    #[description_localized("zh-TW", "或以遊戲內名稱指定第二位玩家")]
+    #[autocomplete = "auto_complete_id"]
+    second_player: Option<i32>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let first = match h2h_side(ctx, first.as_ref(), first_player).await? {
+        Ok(side) => side,
+        Err(message) => {
+            ctx.say(message).await?;
+            return Ok(());
+        },
+    };
+    let second = match h2h_side(ctx, second.as_ref(), second_player).await? {
+        Ok(side) => side,
+        Err(message) => {
+            ctx.say(message).await?;
+            return Ok(());
+        },
+    };
+    if first.profile_ids.iter().any(|id| second.profile_ids.contains(id)) {
+        ctx.say("請選兩位不同的玩家").await?;
+        return Ok(());
+    }
+
+    let games = match h2h::fetch(&first, &second).await {
+        Ok(games) => games,
+        Err(_) => {
+            ctx.say("aoe4world 暫時無法連線，請稍後再試").await?;
+            return Ok(());
+        },
+    };
+    let summary = h2h::Summary::of(&games, &first, &second);
+    if summary.games() == 0 {
+        ctx.say(format!(
+            "{} 和 {} 還沒有對戰紀錄",
+            escape(&first.name),
+            escape(&second.name)
+        ))
+        .await?;
+        return Ok(());
+    }
+    ctx.say(summary.render(&first, &second)).await?;
+    Ok(())
+}
+
+/// One side of `/h2h`: a member, with every profile they have bound, or else a
+/// single profile picked by name. The `Err` is what to tell the user instead.
+async fn h2h_side(
+    ctx: Context<'_>,
+    member: Option<&User>,
+    player: Option<i32>,
+) -> Result<Result<h2h::Side, String>, Error> {
+    if let Some(member) = member {
+        let accounts = list_accounts_for_user(&ctx.data().database, to_db_id(member.id)).await?;
+        if accounts.is_empty() {
+            return Ok(Err(format!("{} 還沒有綁定世紀帝國四帳號", member.name)));
+        }
+        return Ok(Ok(h2h::Side {
+            name: member.global_name.clone().unwrap_or_else(|| member.name.clone()),
+            profile_ids: accounts.iter().map(|account| account.aoe4_id).collect(),
+        }));
+    }
+    let Some(aoe4_id) = player.and_then(picked_profile) else {
+        return Ok(Err("請為兩邊各指定一位成員，或以遊戲內名稱指定玩家".to_string()));
+    };
+    let name = match fetch_profile(aoe4_id.into()).await {
+        Ok(profile) => profile.name,
+        Err(ApiError::NotFound) => return Ok(Err("aoe4world 上沒有這位玩家".to_string())),
+        Err(_) => aoe4_id.to_string(),
+    };
+    Ok(Ok(h2h::Side {
+        name,
+        profile_ids: vec![aoe4_id.into()],
+    }))
+}
+
+/// How long `/reminder snooze` mutes reminders when not told otherwise, and the
+/// most it will.
+const DEFAULT_SNOOZE_DAYS: i64 = 7;
+const MAX_SNOOZE_DAYS: i64 = 90;
+
+/// Inactivity reminders — see `crate::reminder`.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    subcommands("reminder_off", "reminder_on", "reminder_snooze"),
+    subcommand_required,
+    description_localized("zh-TW", "太久沒打積分時的提醒")
+)]
+pub async fn reminder(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Stop the bot pinging you when you haven't played in a while.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "off",
+    description_localized("zh-TW", "不再收到太久沒打積分的提醒")
+)]
+pub async fn reminder_off(ctx: Context<'_>) -> Result<(), Error> {
+    set_reminder_opt_out(&ctx.data().database, to_db_id(ctx.author().id), true).await?;
+    ephemeral(ctx, "已關閉提醒，想再收到時用 `/reminder on`").await
+}
+
+/// Turn inactivity reminders back on, ending any snooze.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "on",
+    description_localized("zh-TW", "重新開啟提醒，並取消暫停")
+)]
+pub async fn reminder_on(ctx: Context<'_>) -> Result<(), Error> {
+    set_reminder_opt_out(&ctx.data().database, to_db_id(ctx.author().id), false).await?;
+    ephemeral(ctx, "已開啟提醒").await
+}
+
+/// Mute inactivity reminders for a few days.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "snooze",
+    description_localized("zh-TW", "暫停提醒幾天")
+)]
+pub async fn reminder_snooze(
+    ctx: Context<'_>,
+    #[description = "How many days to mute reminders for (defaults to 7, at most 90)"]
+    #[description_localized("zh-TW", "要暫停幾天（預設 7 天，最多 90 天）")]
+    days: Option<i64>,
+) -> Result<(), Error> {
+    let days = days.unwrap_or(DEFAULT_SNOOZE_DAYS).clamp(1, MAX_SNOOZE_DAYS);
+    let until = Utc::now() + chrono::Duration::days(days);
+    snooze_reminders(&ctx.data().database, to_db_id(ctx.author().id), until).await?;
+    ephemeral(ctx, format!("提醒已暫停到 <t:{}:D>", until.timestamp())).await
+}
+
+/// What a reaction rule matches on — `reaction_rules.kind`.
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum ReactionKind {
+    #[name = "Messages from a member"]
+    #[name_localized("zh-TW", "某成員的發言")]
+    Author,
+    #[name = "Messages mentioning a member"]
+    #[name_localized("zh-TW", "提到某成員的訊息")]
+    Mention,
+    #[name = "Messages containing text"]
+    #[name_localized("zh-TW", "包含某段文字的訊息")]
+    Substring,
+    #[name = "Messages matching a regex"]
+    #[name_localized("zh-TW", "符合正規表示式的訊息")]
+    Regex,
+}
+
+impl ReactionKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ReactionKind::Author => "author",
+            ReactionKind::Mention => "mention",
+            ReactionKind::Substring => "substring",
+            ReactionKind::Regex => "regex",
+        }
+    }
+}
+
+/// The bot's message reactions — see `crate::emperor`.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_admin_only",
+    subcommands("reaction_add", "reaction_remove", "reaction_list"),
+    subcommand_required,
+    default_member_permissions = "MANAGE_GUILD",
+    description_localized("zh-TW", "管理機器人對訊息的表情反應")
+)]
+pub async fn reaction(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// React to some messages with an emoji.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_admin_only",
+    rename = "add",
+    description_localized("zh-TW", "新增一條表情反應規則")
+)]
+pub async fn reaction_add(
+    ctx: Context<'_>,
+    #[description = "What to react to"]
+    #[description_localized("zh-TW", "要對什麼訊息反應")]
+    kind: ReactionKind,
+    #[description = "The emoji to react with"]
+    #[description_localized("zh-TW", "要用的表情符號")]
+    emoji: String,
+    #[description = "The member, for the member kinds"]
+    #[description_localized("zh-TW", "成員（用於成員類的規則）")]
+    member: Option<User>,
+    #[description = "The text or regex, for the other kinds"]
+    #[description_localized("zh-TW", "文字或正規表示式（用於其他規則）")]
+    text: Option<String>,
+    #[description = "Chance of reacting when it matches, 0 to 1 (defaults to 1)"]
+    #[description_localized("zh-TW", "符合時反應的機率，0 到 1（預設 1）")]
+    #[min = 0]
+    #[max = 1]
+    probability: Option<f64>,
+    #[description = "Chance of posting the emoji instead when the reaction is blocked, 0 to 1 (defaults to 0)"]
+    #[description_localized("zh-TW", "反應被封鎖時改發表情訊息的機率，0 到 1（預設 0）")]
+    #[min = 0]
+    #[max = 1]
+    follow_up_chance: Option<f64>,
+    #[description = "Rules sharing a group react at most one of them per message, drawn by their chances"]
+    #[description_localized("zh-TW", "同組的規則每則訊息最多一條反應，依各自機率抽選")]
+    group: Option<String>,
+) -> Result<(), Error> {
+    let pattern = match (&kind, member, text) {
+        (ReactionKind::Author | ReactionKind::Mention, Some(member), _) => member.id.to_string(),
+        (ReactionKind::Author | ReactionKind::Mention, None, Some(text)) => text,
+        (ReactionKind::Substring | ReactionKind::Regex, _, Some(text)) => text,
+        (ReactionKind::Author | ReactionKind::Mention, None, None) => {
+            return ephemeral(ctx, "請指定成員").await;
+        },
+        (ReactionKind::Substring | ReactionKind::Regex, _, None) => {
+            return ephemeral(ctx, "請輸入文字或正規表示式").await;
+        },
+    };
+    let trigger = match Trigger::parse(kind.as_str(), &pattern) {
+        Ok(trigger) => trigger,
+        Err(message) => return ephemeral(ctx, message).await,
+    };
+    if let Err(message) = parse_emoji(&emoji) {
+        return ephemeral(ctx, message).await;
+    }
+
+    let id = insert_reaction_rule(
+        &ctx.data().database,
+        kind.as_str(),
+        &trigger.pattern(),
+        emoji.trim(),
+        probability.unwrap_or(1.0).clamp(0.0, 1.0),
+        follow_up_chance.unwrap_or(0.0).clamp(0.0, 1.0),
+        group.as_deref().map(str::trim).filter(|group| !group.is_empty()),
+        to_db_id(ctx.author().id),
+    )
+    .await?;
+    ctx.data().reaction_rules.invalidate();
+    info!("{} added reaction rule #{id}", ctx.author().name);
+    ephemeral(ctx, format!("已新增規則 `#{id}`")).await
+}
+
+/// Stop reacting by one rule.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_admin_only",
+    rename = "remove",
+    description_localized("zh-TW", "刪除一條表情反應規則")
+)]
+pub async fn reaction_remove(
+    ctx: Context<'_>,
+    #[description = "The rule's number, from /reaction list"]
+    #[description_localized("zh-TW", "規則編號（見 /reaction list）")]
+    id: i64,
+) -> Result<(), Error> {
+    if !delete_reaction_rule(&ctx.data().database, id).await? {
+        return ephemeral(ctx, format!("沒有規則 `#{id}`")).await;
+    }
+    ctx.data().reaction_rules.invalidate();
+    info!("{} removed reaction rule #{id}", ctx.author().name);
+    ephemeral(ctx, format!("已刪除規則 `#{id}`")).await
+}
+
+/// Every reaction rule, with its number.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_admin_only",
+    rename = "list",
+    description_localized("zh-TW", "列出所有表情反應規則")
+)]
+pub async fn reaction_list(ctx: Context<'_>) -> Result<(), Error> {
+    let rules = list_reaction_rules(&ctx.data().database).await?;
+    if rules.is_empty() {
+        return ephemeral(ctx, "目前沒有任何規則").await;
+    }
+    // One message holds 2000 characters; a long list goes out in several.
+    let mut chunks = vec![String::new()];
+    for line in rules.iter().map(emperor::describe) {
+        let chunk = chunks.last_mut().unwrap();
+        if !chunk.is_empty() && chunk.len() + line.len() + 1 > 2000 {
+            chunks.push(String::new());
+        }
+        let chunk = chunks.last_mut().unwrap();
+        chunk.push_str(&line);
+        chunk.push('\n');
+    }
+    for chunk in chunks {
+        ephemeral(ctx, chunk).await?;
+    }
+    Ok(())
+}
+
+/// Whether the bot's reactions may target you — see `crate::emperor`.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    subcommands("reactions_off", "reactions_on"),
+    subcommand_required,
+    description_localized("zh-TW", "機器人是否對你的訊息加上表情反應")
+)]
+pub async fn reactions(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Stop the bot reacting to your messages, or to messages mentioning you.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "off",
+    description_localized("zh-TW", "不再對你的訊息或提到你的訊息加上表情反應")
+)]
+pub async fn reactions_off(ctx: Context<'_>) -> Result<(), Error> {
+    set_reaction_opt_out(&ctx.data().database, to_db_id(ctx.author().id), true).await?;
+    ctx.data().reaction_rules.invalidate();
+    ephemeral(ctx, "已關閉表情反應，想恢復時用 `/reactions on`").await
+}
+
+/// Let the bot react to your messages again.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_only",
+    rename = "on",
+    description_localized("zh-TW", "恢復對你的訊息加上表情反應")
+)]
+pub async fn reactions_on(ctx: Context<'_>) -> Result<(), Error> {
+    set_reaction_opt_out(&ctx.data().database, to_db_id(ctx.author().id), false).await?;
+    ctx.data().reaction_rules.invalidate();
+    ephemeral(ctx, "已恢復表情反應").await
+}
+
+/// The largest file `/accounts import` reads. An export of a few thousand
+/// bindings is well under this.
+const MAX_IMPORT_BYTES: u32 = 1024 * 1024;
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum AccountFileFormat {
+    #[name = "JSON"]
+    Json,
+    #[name = "CSV"]
+    Csv,
+}
+
+/// Every binding, out to a file and back in — see `crate::account_file`.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_admin_only",
+    subcommands("accounts_export", "accounts_import"),
+    subcommand_required,
+    default_member_permissions = "MANAGE_GUILD",
+    description_localized("zh-TW", "匯出或匯入所有帳號綁定")
+)]
+pub async fn accounts(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Attach every binding as a file, with its main and verified flags.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_admin_only",
+    rename = "export",
+    description_localized("zh-TW", "把所有帳號綁定匯出成檔案")
+)]
+pub async fn accounts_export(
     ctx: Context<'_>,
-    #[description = "Type your in-game name and pick yourself from the list"]
-    #[description_localized("zh-TW", "輸入你的遊戲內名稱，然後從清單中選擇自己")]
-    #[autocomplete = "auto_complete_id"]
-    in_game_name: i32,
+    #[description = "File format (defaults to JSON)"]
+    #[description_localized("zh-TW", "檔案格式（預設 JSON）")]
+    format: Option<AccountFileFormat>,
 ) -> Result<(), Error> {
-    let Some(aoe4_id) = picked_profile(in_game_name) else {
-        ask_for_in_game_name(ctx).await?;
-        return Ok(());
+    let format = match format {
+        Some(AccountFileFormat::Csv) => account_file::Format::Csv,
+        _ => account_file::Format::Json,
     };
-    info!("attempting to bind id {}", aoe4_id);
-    let user_id = ctx.author().id;
-    info!("binding discord user {} with aoe4 player {}", user_id, aoe4_id);
-    let message = bind_account(&ctx.data().database, to_db_id(user_id), i64::from(aoe4_id))
-        .await
-        .inspect_err(|_error| {
-            error!("database insert failed");
-        })?;
-    ctx.say(message).await?;
+    let accounts = list_all(&ctx.data().database).await?;
+    let file = account_file::export(&accounts, format);
+    let filename = format!("accounts-{}.{}", Utc::now().format("%Y%m%d"), format.extension());
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!("共 {} 筆綁定", accounts.len()))
+            .attachment(CreateAttachment::bytes(file.into_bytes(), filename))
+            .ephemeral(true),
+    )
+    .await?;
     Ok(())
 }
 
-#[poise::command(slash_command, guild_only, check = "home_only", rename = "查分")]
-pub async fn check(
+/// Check a file from /accounts export against aoe4world, then import it.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "home_admin_only",
+    rename = "import",
+    description_localized("zh-TW", "檢查並匯入帳號綁定檔案")
+)]
+pub async fn accounts_import(
     ctx: Context<'_>,
-    #[description = "Type an in-game name and pick the player from the list"]
-    #[description_localized("zh-TW", "輸入遊戲內名稱，然後從清單中選擇玩家")]
-    #[autocomplete = "auto_complete_id"]
-    in_game_name: i32,
+    #[description = "A .json or .csv file, as /accounts export makes them"]
+    #[description_localized("zh-TW", "由 /accounts export 產生的 .json 或 .csv 檔案")]
+    file: Attachment,
+    #[description = "Write it, if nothing is wrong (defaults to only checking)"]
+    #[description_localized("zh-TW", "沒有問題時寫入（預設只檢查）")]
+    apply: Option<bool>,
 ) -> Result<(), Error> {
-    let Some(aoe4_id) = picked_profile(in_game_name) else {
-        ask_for_in_game_name(ctx).await?;
-        return Ok(());
+    ctx.defer_ephemeral().await?;
+    let Some(format) = account_file::Format::of_file(&file.filename) else {
+        return ephemeral(ctx, "請上傳 .json 或 .csv 檔案").await;
     };
-    info!("attempting to check id {}", aoe4_id);
-    ctx.defer().await?;
-    let Some(player) = try_create_ranked_without_account(aoe4_id).await else {
-        info!("no ranked data for aoe4 id {}", aoe4_id);
-        ctx.say("查不到這位玩家的單挑積分資料").await?;
-        return Ok(());
+    if file.size > MAX_IMPORT_BYTES {
+        return ephemeral(ctx, "檔案太大").await;
+    }
+    let text = match String::from_utf8(file.download().await?) {
+        Ok(text) => text,
+        Err(_) => return ephemeral(ctx, "檔案不是 UTF-8 文字").await,
     };
-    let info = player.info();
-    ctx.http()
-        .get_channel(INTERACTION_CHANNEL_ID)
-        .await?
-        .guild()
-        .unwrap()
-        .say(ctx.http(), info)
-        .await?;
-    ctx.say("查分成功").await?;
-    Ok(())
+    let rows = match account_file::parse(&text, format) {
+        Ok(rows) => rows,
+        Err(message) => return ephemeral(ctx, message).await,
+    };
+
+    // Each profile once, however many rows name it.
+    let profiles = rows
+        .iter()
+        .filter_map(|(_, row)| row.as_ref().ok().map(|row| row.aoe4_id))
+        .collect::<HashSet<i64>>();
+    let lookups = stream::iter(profiles)
+        .map(|aoe4_id| async move { (aoe4_id, fetch_profile(aoe4_id).await) })
+        .buffer_unordered(CONCURRENT_FETCHES)
+        .collect::<Vec<_>>()
+        .await;
+    let mut missing = HashSet::new();
+    for (aoe4_id, lookup) in lookups {
+        match lookup {
+            Ok(_) => {},
+            Err(ApiError::NotFound) => {
+                missing.insert(aoe4_id);
+            },
+            Err(_) => return ephemeral(ctx, "aoe4world 暫時無法連線，無法檢查檔案，請稍後再試").await,
+        }
+    }
+
+    let pool = &ctx.data().database;
+    let report = account_file::Report::check(rows, &list_all(pool).await?, &missing);
+    let apply = apply.unwrap_or(false) && report.problems.is_empty();
+    if apply {
+        import_accounts(pool, &report.writes).await?;
+        info!(
+            "{} imported {} account bindings from {}",
+            ctx.author().name,
+            report.writes.len(),
+            file.filename
+        );
+    }
+    ephemeral(ctx, report.render(apply)).await
 }
 
 #[poise::command(slash_command, guild_only, check = "home_only")]
@@ -336,8 +1635,7 @@ pub async fn create(
     let locale = Locale::from_context(ctx);
     ctx.defer().await?;
 
-    // guild_only + tournament_only already guarantee a real guild text channel,
-    // the same shortcut rebuild() takes on the same guarantee.
+    // guild_only + tournament_only already guarantee a real guild text channel.
     let announce_channel = ctx.guild_channel().await.unwrap();
     let category_id = announce_channel.parent_id;
 
@@ -892,9 +2190,9 @@ pub async fn uninvite(
 
 // Opens check-in for the tournament resolved from the invoking channel
 // and posts the check-in panel to the register
-// channel `/tournament create` made. `minutes` is purely informational —
-// there is no cron closing check-in automatically; `/tournament close-checkin`
-// stays a separate, explicit action.
+// channel `/tournament create` made. `minutes` is only shown unless the
+// autopilot is on — then it is when the autopilot closes check-in; otherwise
+// `/tournament close-checkin` stays a separate, explicit action.
 /// Opens check-in and posts its panel. `minutes`, if given, is shown as when it closes.
 #[poise::command(
     slash_command,
@@ -905,7 +2203,7 @@ pub async fn uninvite(
 )]
 pub async fn open_checkin(
     ctx: Context<'_>,
-    #[description = "Minutes until check-in closes — informational only; closing is still a separate command"]
+    #[description = "Minutes until check-in closes — only shown, unless the autopilot is on to close it then"]
     minutes: Option<i64>,
 ) -> Result<(), Error> {
     ctx.defer_ephemeral().await?;
@@ -914,39 +2212,15 @@ pub async fn open_checkin(
         return Ok(());
     };
 
-    let pool = &ctx.data().database;
-    let outcome = checkin::open(pool, &tournament, minutes).await?;
-    audit::log_action("open-checkin", tournament.id, &tournament.slug, ctx.author(), &outcome);
-
-    if let checkin::OpenCheckinOutcome::Opened { closes_at } = outcome {
-        // Always set by `create()` when the tournament was made.
-        let register_channel_id = to_channel_id(tournament.register_channel_id.unwrap());
-        let message_id = checkin_panel::post_initial(
-            ctx.http(),
-            pool,
-            register_channel_id,
-            tournament.id,
-            &tournament.name,
-            closes_at,
-            true,
-        )
-        .await?;
-        tournament_db::set_checkin_message_id(pool, tournament.id, Some(to_db_id(message_id))).await?;
-
-        // Registration closes here, so the panel must stop inviting
-        // sign-ups the gate would now refuse. Re-read: the status has moved.
-        let tournament = tournament_db::get_tournament(pool, tournament.id).await?.unwrap();
-        panel::refresh_now(ctx.http(), pool, &tournament).await?;
-
-        // A one-time nudge: the panel alone only reaches whoever happens to be
-        // looking. Invitees are exempt — check-in was never asked of them.
-        let entries = tournament_db::list_entries_for_tournament(pool, tournament.id).await?;
-        let user_ids: Vec<i64> = checkin::checkin_pool(&entries).iter().map(|e| e.user_id).collect();
-        for message in checkin::checkin_ping_messages(locale, &user_ids) {
-            register_channel_id.say(ctx.http(), message).await?;
-        }
-    }
-
+    let outcome = lifecycle::open_checkin(
+        ctx.http(),
+        &ctx.data().database,
+        &tournament,
+        minutes,
+        Actor::Member(ctx.author()),
+        Some(locale),
+    )
+    .await?;
     ephemeral(ctx, outcome.message(&tournament.name, locale)).await?;
     Ok(())
 }
@@ -994,75 +2268,21 @@ pub async fn close_checkin(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    let pool = &ctx.data().database;
-    let outcome = checkin::close(pool, &tournament).await?;
-    audit::log_action("close-checkin", tournament.id, &tournament.slug, ctx.author(), &outcome);
-
-    if !matches!(outcome, checkin::CloseCheckinOutcome::Closed { .. }) {
-        ephemeral(ctx, outcome.message(&tournament.name, locale)).await?;
-        return Ok(());
+    let (outcome, seeded) = lifecycle::close_checkin(
+        ctx.http(),
+        &ctx.data().database,
+        &tournament,
+        Actor::Member(ctx.author()),
+    )
+    .await?;
+    let mut message = outcome.message(&tournament.name, locale);
+    if let Some(seeded) = seeded {
+        message = format!("{message}\n{}", seeded.message(&tournament.name, locale));
     }
-
-    checkin_panel::close(ctx.http(), pool, &tournament).await?;
-    // The order is whatever the organizers left it as — closing check-in is the
-    // edge that used to overwrite a hand-made one.
-    let policy = seeding::SeedPolicy::from_source(&tournament.seed_source);
-    let seeded = seed_and_post_panel(ctx, &tournament, policy, locale).await?;
-    ephemeral(ctx, format!("{}\n{seeded}", outcome.message(&tournament.name, locale))).await?;
+    ephemeral(ctx, message).await?;
     Ok(())
 }
 
-/// Fetches ratings, writes the seed order under `policy` and puts the seeding
-/// panel in front of the organizers, returning the line to append to the
-/// caller's reply.
-///
-/// **Best-effort by design.** By the time this runs the tournament has already
-/// advanced to `seeding`, so an aoe4world outage must not fail the command and
-/// strand the lifecycle — it seeds from whatever ratings are stored, says so, and
-/// points at `/tournament seed refresh`. The panel is best-effort for the
-/// same reason, which is what `ensure_seed_panel` buys.
-async fn seed_and_post_panel(
-    ctx: Context<'_>,
-    tournament: &tournament_db::Tournament,
-    policy: seeding::SeedPolicy,
-    locale: Locale,
-) -> Result<String, Error> {
-    let pool = &ctx.data().database;
-    let outcome = seeding::refresh_ratings(pool, tournament, policy).await?;
-    audit::log_action("seed", tournament.id, &tournament.slug, ctx.author(), &outcome);
-
-    let message = outcome.message(&tournament.name, locale);
-    match ensure_seed_panel(ctx, tournament).await {
-        PanelOutcome::Present | PanelOutcome::Reposted | PanelOutcome::NotConfigured => Ok(message),
-        // Said out loud rather than swallowed: the seeding itself succeeded, and
-        // an organizer who cannot see the panel needs to know it is the panel
-        // that is missing, not the seeding.
-        PanelOutcome::Failed => Ok(format!(
-            "{message}\n{}",
-            locale.pick(
-                "種子名單：無法確認或張貼，請確認機器人可在賽程頻道發言。",
-                "Seeding panel: could not confirm or post — check the bot can send messages in the bracket channel.",
-            )
-        )),
-        // The seeding panel has no phase gate — `ensure` never returns this for it.
-        PanelOutcome::NotExpected => unreachable!("the seeding panel has no phase gate"),
-    }
-}
-
-/// Leaves `#{slug}-bracket` showing a current seeding panel, whatever state it
-/// was in — a thin wrapper over `seed_panel::ensure` kept here because
-/// `seed_and_post_panel` needs the same "never propagates" fallback that
-/// `refresh_seed_panel` does, and this is where both already look for it.
-///
-/// **Never propagates.** A panel that has gone missing — a stale
-/// `seed_message_id`, or one an organizer deleted — used to turn `close-checkin`
-/// into an error that posted nothing, *after* the status had already moved to
-/// `seeding` and the no-shows had been marked. Recovering from that is the whole
-/// reason the fallback exists rather than a bare edit.
-async fn ensure_seed_panel(ctx: Context<'_>, tournament: &tournament_db::Tournament) -> PanelOutcome {
-    seed_panel::ensure(ctx.http(), &ctx.data().database, tournament).await
-}
-
 // The one backward lifecycle edge, for a check-in
 // opened too early or closed too soon. A full reset rather than a partial undo:
 // the check-in panel is deleted outright, so the next `/tournament open-checkin`
@@ -1168,22 +2388,81 @@ impl FromRound {
     }
 }
 
-/// Taiwan is UTC+8 year round with no daylight saving, so a fixed offset is exact
-/// and saves a `chrono-tz` dependency. Organizers type a local wall time; it is
-/// stored UTC and rendered back as a Discord timestamp, which every reader sees
-/// in their own zone.
-const LOCAL_OFFSET_HOURS: i32 = 8;
+/// The bracket `start` builds. One choice per shape rather than a format and a
+/// separate reset flag: a reset means nothing to a single-elimination bracket, and
+/// one option cannot be set to a combination that does not exist.
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum BracketFormat {
+    #[name = "Single elimination"]
+    #[name_localized("zh-TW", "單淘汰")]
+    Single,
+    #[name = "Double elimination"]
+    #[name_localized("zh-TW", "雙敗淘汰")]
+    Double,
+    #[name = "Double elimination with a grand final reset"]
+    #[name_localized("zh-TW", "雙敗淘汰（總決賽可重置）")]
+    DoubleWithReset,
+    #[name = "Swiss"]
+    #[name_localized("zh-TW", "瑞士制")]
+    Swiss,
+    #[name = "Groups, then single elimination"]
+    #[name_localized("zh-TW", "分組循環賽，再單淘汰")]
+    GroupsSingle,
+    #[name = "Groups, then double elimination"]
+    #[name_localized("zh-TW", "分組循環賽，再雙敗淘汰")]
+    GroupsDouble,
+    #[name = "Groups, then double elimination with a grand final reset"]
+    #[name_localized("zh-TW", "分組循環賽，再雙敗淘汰（總決賽可重置）")]
+    GroupsDoubleWithReset,
+}
+
+impl BracketFormat {
+    fn format(&self) -> bracket::Format {
+        match self {
+            BracketFormat::Single => bracket::Format::SingleElimination,
+            BracketFormat::Double => bracket::Format::DoubleElimination {
+                grand_final_reset: false,
+            },
+            BracketFormat::DoubleWithReset => bracket::Format::DoubleElimination {
+                grand_final_reset: true,
+            },
+            BracketFormat::Swiss => bracket::Format::Swiss,
+            BracketFormat::GroupsSingle | BracketFormat::GroupsDouble | BracketFormat::GroupsDoubleWithReset => {
+                bracket::Format::Groups
+            },
+        }
+    }
+
+    /// The bracket a group stage feeds, and what the grand final reset applies to.
+    /// Every other format is its own playoff.
+    fn playoff(&self) -> bracket::Format {
+        match self {
+            BracketFormat::GroupsSingle => bracket::Format::SingleElimination,
+            BracketFormat::GroupsDouble => bracket::Format::DoubleElimination {
+                grand_final_reset: false,
+            },
+            BracketFormat::GroupsDoubleWithReset => bracket::Format::DoubleElimination {
+                grand_final_reset: true,
+            },
+            _ => self.format(),
+        }
+    }
+}
 
+/// Organizers type a local wall time, read as `schedule::LOCAL_OFFSET_HOURS` —
+/// the same reading `/set schedule` gives a time with no offset. It is stored
+/// UTC and rendered back as a Discord timestamp, which every reader sees in
+/// their own zone.
+///
 /// Pure, so the parsing rules are testable without a Discord context.
 fn parse_start_time(input: &str) -> Option<DateTime<Utc>> {
-    let naive = NaiveDateTime::parse_from_str(input.trim(), "%Y-%m-%d %H:%M").ok()?;
-    let offset = FixedOffset::east_opt(LOCAL_OFFSET_HOURS * 3600)?;
-    Some(naive.and_local_timezone(offset).single()?.to_utc())
+    schedule::parse_local(input)
 }
 
 // Configuration a tournament needs before `/tournament start` will run. Always
 // reports the full state, so it doubles as "what am I still missing?".
 /// Configures the tournament. Run with no options to see what's still needed.
+#[allow(clippy::too_many_arguments)]
 #[poise::command(
     slash_command,
     guild_only,
@@ -1202,6 +2481,28 @@ pub async fn setup(
     #[description = "Invite-only: nobody can sign themselves up, only /tournament invite adds them"]
     #[description_localized("zh-TW", "邀請制：無法自行報名，只能由主辦方用 /tournament invite 加入")]
     invite_only: Option<bool>,
+    #[description = "Single elimination, double elimination, Swiss or groups; fixed once the tournament starts"]
+    #[description_localized("zh-TW", "單淘汰、雙敗淘汰、瑞士制或分組循環賽；開賽後無法更改")]
+    format: Option<BracketFormat>,
+    #[description = "Swiss rounds to play; defaults to enough to leave one unbeaten player"]
+    #[description_localized("zh-TW", "瑞士制的輪數；預設為足以只剩一位全勝者的輪數")]
+    #[min = 1]
+    swiss_rounds: Option<i64>,
+    #[description = "How many groups a group stage splits the field into; defaults to groups of about four"]
+    #[description_localized("zh-TW", "分組循環賽的組數；預設為每組約四人")]
+    #[min = 1]
+    #[max = 26]
+    groups: Option<i64>,
+    #[description = "How many from each group go through to the playoff; defaults to 2"]
+    #[description_localized("zh-TW", "每組晉級季後賽的人數；預設為 2")]
+    #[min = 1]
+    advance: Option<i64>,
+    #[description = "Minutes before a scheduled match to remind its thread, e.g. 60,10; \"off\" for none"]
+    #[description_localized("zh-TW", "在排定的比賽前幾分鐘於討論串提醒，例如 60,10；輸入 off 關閉")]
+    reminders: Option<String>,
+    #[description = "Open check-in, close it and start on the scheduled timeline, without anyone running them"]
+    #[description_localized("zh-TW", "依排定時間自動開放簽到、結束簽到並開賽，無須手動執行")]
+    autopilot: Option<bool>,
 ) -> Result<(), Error> {
     ctx.defer_ephemeral().await?;
     let locale = Locale::from_context(ctx);
@@ -1210,6 +2511,22 @@ pub async fn setup(
     };
     let pool = &ctx.data().database;
 
+    // Checked before anything is written, so a refused format does not leave the
+    // other options half-applied.
+    if (format.is_some() || swiss_rounds.is_some() || groups.is_some() || advance.is_some())
+        && matches!(tournament.status.as_str(), "running" | "completed" | "canceled")
+    {
+        ephemeral(
+            ctx,
+            locale.pick(
+                "賽程表已經產生，無法再更改賽制。",
+                "The bracket already exists, so the format can't change any more.",
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
     if let Some(cap) = cap {
         if cap < 2 {
             ephemeral(
@@ -1237,6 +2554,28 @@ pub async fn setup(
         tournament_db::set_scheduled_start_at(pool, tournament.id, parsed).await?;
     }
 
+    if let Some(reminders) = &reminders {
+        let Some(leads) = schedule::parse_leads(reminders) else {
+            ephemeral(
+                ctx,
+                locale.pick(
+                    "看不懂這些提醒時間 — 請列出賽前幾分鐘，例如 `60,10`（最多 5 個，每個不超過一週），或輸入 `off`。",
+                    "Couldn't read those reminders — list minutes before the match, e.g. `60,10` (at most 5, \
+                     each within a week), or `off`.",
+                ),
+            )
+            .await?;
+            return Ok(());
+        };
+        // Only times confirmed from here on pick this up: a reminder already
+        // owed was planned against the leads in force when it was agreed.
+        tournament_db::set_schedule_reminders(pool, tournament.id, &schedule::format_leads(&leads)).await?;
+    }
+
+    if let Some(autopilot) = autopilot {
+        tournament_db::set_autopilot(pool, tournament.id, autopilot).await?;
+    }
+
     if let Some(invite_only) = invite_only {
         // Only the door changes. Anyone who already signed up stays in the field
         // — the summary reports both, so a mode flip over an existing roster is
@@ -1245,6 +2584,38 @@ pub async fn setup(
         tournament_db::set_registration_mode(pool, tournament.id, mode).await?;
     }
 
+    if let Some(format) = &format {
+        // A group stage's reset belongs to its playoff, so it is read off that.
+        let playoff = format.playoff();
+        let grand_final_reset = matches!(
+            playoff,
+            bracket::Format::DoubleElimination {
+                grand_final_reset: true
+            }
+        );
+        tournament_db::set_format(
+            pool,
+            tournament.id,
+            format.format().as_db(),
+            playoff.as_db(),
+            grand_final_reset,
+        )
+        .await?;
+    }
+
+    if let Some(rounds) = swiss_rounds {
+        // Stored whatever the format, so it can be set before or after choosing
+        // Swiss. Whether it fits the field is `start`'s question: the field can
+        // still grow or shrink until then.
+        tournament_db::set_swiss_rounds(pool, tournament.id, Some(rounds)).await?;
+    }
+
+    if groups.is_some() || advance.is_some() {
+        // Stored whatever the format, like the Swiss round count, and checked
+        // against the field by `start`.
+        tournament_db::set_group_stage(pool, tournament.id, groups, advance).await?;
+    }
+
     // Re-read so the summary reflects what was just written.
     let tournament = tournament_db::get_tournament(pool, tournament.id).await?.unwrap();
     let presets = tournament_db::list_round_presets(pool, tournament.id).await?;
@@ -1253,16 +2624,27 @@ pub async fn setup(
         tournament.id,
         &tournament.slug,
         ctx.author(),
-        &(cap, start_time, invite_only),
+        &(
+            cap,
+            start_time,
+            invite_only,
+            format,
+            swiss_rounds,
+            groups,
+            advance,
+            reminders,
+            autopilot,
+        ),
     );
 
     // The panel displays the cap, the start time and which door is open, so it
     // goes stale the moment any of them is written.
     panel::refresh_now(ctx.http(), pool, &tournament).await?;
     // The invite-only preview's open-seat padding depends on both the cap and
-    // whether padding happens at all, so a change to either leaves the
-    // bracket stale otherwise. Unconditional, like the panel refresh above —
-    // `reconcile` only touches messages that actually changed.
+    // whether padding happens at all, and the preview is drawn in the chosen
+    // format, so a change to any of them leaves the bracket stale otherwise.
+    // Unconditional, like the panel refresh above — `reconcile` only touches
+    // messages that actually changed.
     bracket_view::reconcile_now(ctx.http(), pool, &tournament).await?;
 
     let entries = tournament_db::list_entries_for_tournament(pool, tournament.id).await?;
@@ -1290,6 +2672,24 @@ fn preset_scope(from_depth: i64, locale: Locale) -> String {
     locale.pick(format!("{name}{space}之後"), format!("{name} onwards"))
 }
 
+/// The format as the summary names it.
+fn format_name(format: bracket::Format, locale: Locale) -> &'static str {
+    match format {
+        bracket::Format::SingleElimination => locale.pick("單淘汰", "single elimination"),
+        bracket::Format::DoubleElimination {
+            grand_final_reset: false,
+        } => locale.pick("雙敗淘汰", "double elimination"),
+        bracket::Format::DoubleElimination {
+            grand_final_reset: true,
+        } => locale.pick(
+            "雙敗淘汰（總決賽可重置）",
+            "double elimination with a grand final reset",
+        ),
+        bracket::Format::Swiss => locale.pick("瑞士制", "Swiss"),
+        bracket::Format::Groups => locale.pick("分組循環賽", "groups"),
+    }
+}
+
 fn setup_summary(
     tournament: &tournament_db::Tournament,
     presets: &[tournament_db::RoundPreset],
@@ -1365,14 +2765,82 @@ fn setup_summary(
         registration::RegistrationState::Closed => locale.pick("已結束", "closed"),
     };
 
+    let parsed = bracket::Format::from_db(&tournament.format, tournament.grand_final_reset);
+    let mut format = format_name(parsed, locale).to_string();
+    if parsed == bracket::Format::Swiss {
+        format.push_str(&match tournament.swiss_rounds {
+            Some(rounds) => locale.pick(format!("，{rounds} 輪"), format!(", {rounds} rounds")),
+            None => locale
+                .pick("，輪數依人數決定", ", rounds set by field size")
+                .to_string(),
+        });
+    }
+    if parsed == bracket::Format::Groups {
+        let playoff = bracket::Format::from_db(&tournament.playoff_format, tournament.grand_final_reset);
+        let playoff = format_name(playoff, locale);
+        let groups = tournament.group_count.map_or_else(
+            || {
+                locale
+                    .pick("組數依人數決定", "group count set by field size")
+                    .to_string()
+            },
+            |count| locale.pick(format!("{count} 組"), format!("{count} groups")),
+        );
+        let advance = tournament
+            .group_advance
+            .map_or(tournament_groups::DEFAULT_ADVANCE, |advance| {
+                usize::try_from(advance).unwrap_or(0)
+            });
+        format.push_str(&locale.pick(
+            format!("，{groups}，每組前 {advance} 名晉級{playoff}"),
+            format!(" into {playoff} — {groups}, top {advance} of each advance"),
+        ));
+    }
+
+    let leads = schedule::leads(tournament);
+    let reminders = if leads.is_empty() {
+        locale.pick("關閉", "off").to_string()
+    } else {
+        let minutes = leads.iter().map(i64::to_string).collect::<Vec<_>>();
+        locale.pick(
+            format!("賽前 {} 分鐘", minutes.join("、")),
+            format!("{} minutes before", minutes.join(", ")),
+        )
+    };
+
+    // Said alongside the placeholder warning rather than folded into it: an
+    // autopilot that is on but waiting is the case someone needs to notice.
+    let autopilot = match (
+        tournament.autopilot,
+        tournament_setup::start_time_is_default(tournament),
+    ) {
+        (false, _) => locale.pick("關閉", "off").to_string(),
+        (true, false) => locale
+            .pick(
+                "開啟 — 開賽前一小時開放簽到，開賽時結束簽到並開賽",
+                "on — opens check-in an hour before the start, then closes it and starts on time",
+            )
+            .to_string(),
+        (true, true) => locale
+            .pick(
+                "開啟，但要等設定正確的開賽時間",
+                "on, but waiting for a real start time",
+            )
+            .to_string(),
+    };
+
     format!(
-        "**{} — {}**\n{}: {registered}/{}\n{}: {door}\n{}: {start}{placeholder}\n{}:\n{preset_lines}{still_needed}",
+        "**{} — {}**\n{}: {registered}/{}\n{}: {door}\n{}: {format}\n{}: {start}{placeholder}\n\
+         {}: {autopilot}\n{}: {reminders}\n{}:\n{preset_lines}{still_needed}",
         tournament.name,
         locale.pick("賽事設定", "setup"),
         locale.pick("已報名 / 上限", "Registered / cap"),
         tournament.entrant_cap,
         locale.pick("報名方式", "Registration"),
+        locale.pick("賽制", "Format"),
         locale.pick("開賽時間", "Start time"),
+        locale.pick("自動排程", "Autopilot"),
+        locale.pick("對戰提醒", "Match reminders"),
         locale.pick("抽選預設", "Draft presets"),
     )
 }
@@ -1644,7 +3112,15 @@ pub async fn seed_refresh(ctx: Context<'_>) -> Result<(), Error> {
     // after, so the field and the column cannot disagree if the pass fails.
     let policy = seeding::SeedPolicy::Suggest;
     tournament_db::set_seed_source(&ctx.data().database, tournament.id, policy.as_source()).await?;
-    let message = seed_and_post_panel(ctx, &tournament, policy, locale).await?;
+    let seeded = lifecycle::seed(
+        ctx.http(),
+        &ctx.data().database,
+        &tournament,
+        policy,
+        Actor::Member(ctx.author()),
+    )
+    .await?;
+    let message = seeded.message(&tournament.name, locale);
     bracket_view::reconcile_now(ctx.http(), &ctx.data().database, &tournament).await?;
     ephemeral(ctx, message).await?;
     Ok(())
@@ -1710,6 +3186,12 @@ pub async fn refresh_panels(ctx: Context<'_>) -> Result<(), Error> {
     lines.push(refresh_register_panel(ctx, &tournament, locale).await?);
     lines.push(refresh_checkin_panel(ctx, &tournament, locale).await?);
     lines.push(refresh_seed_panel(ctx, &tournament, locale).await?);
+    if let Some(line) = refresh_swiss_stage(ctx, &tournament, locale).await? {
+        lines.push(line);
+    }
+    if let Some(line) = refresh_group_stage(ctx, &tournament, locale).await? {
+        lines.push(line);
+    }
 
     lines.push(match bracket_view::reconcile_now(ctx.http(), pool, &tournament).await {
         Ok(bracket_view::ReconcileOutcome::NoChannel) => locale
@@ -1741,6 +3223,57 @@ pub async fn refresh_panels(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// A Swiss round whose last set settled while pairing the next one failed: the
+/// settlement already stands, so this is the only thing that catches it up.
+/// `None` for a tournament without a Swiss stage.
+async fn refresh_swiss_stage(
+    ctx: Context<'_>,
+    tournament: &tournament_db::Tournament,
+    locale: Locale,
+) -> Result<Option<String>, Error> {
+    if tournament.status != "running" {
+        return Ok(None);
+    }
+    let pool = &ctx.data().database;
+    let line = match swiss::advance(pool, tournament.id).await? {
+        None => return Ok(None),
+        Some(swiss::Progress::Waiting { .. }) => locale.pick("瑞士制：本輪進行中。", "Swiss: round in progress."),
+        Some(swiss::Progress::Paired { .. }) => {
+            set_thread::open_ready(ctx.http(), pool, tournament).await;
+            locale.pick("瑞士制：已排出下一輪。", "Swiss: next round paired.")
+        },
+        Some(swiss::Progress::Finished) => locale.pick("瑞士制：最後一輪已結束。", "Swiss: final round over."),
+    };
+    Ok(Some(line.to_string()))
+}
+
+/// The group-stage counterpart of `refresh_swiss_stage`, which also catches up a
+/// playoff the presets could not cover when the last group round ended. `None`
+/// for a tournament without a group stage, or once its playoff is drawn.
+async fn refresh_group_stage(
+    ctx: Context<'_>,
+    tournament: &tournament_db::Tournament,
+    locale: Locale,
+) -> Result<Option<String>, Error> {
+    if tournament.status != "running" {
+        return Ok(None);
+    }
+    let pool = &ctx.data().database;
+    let line = match tournament_groups::advance(pool, tournament.id).await? {
+        None => return Ok(None),
+        Some(tournament_groups::Progress::Waiting) => locale.pick("小組賽：本輪進行中。", "Groups: round in progress."),
+        Some(tournament_groups::Progress::Paired { .. }) => {
+            set_thread::open_ready(ctx.http(), pool, tournament).await;
+            locale.pick("小組賽：已開放下一輪。", "Groups: next round opened.")
+        },
+        Some(tournament_groups::Progress::PlayoffDrawn) => {
+            set_thread::open_ready(ctx.http(), pool, tournament).await;
+            locale.pick("小組賽：已結束，季後賽已抽出。", "Groups: over, playoff drawn.")
+        },
+    };
+    Ok(Some(line.to_string()))
+}
+
 /// The registration panel: `panel::ensure`'s outcome, worded for this reply.
 async fn refresh_register_panel(
     ctx: Context<'_>,
@@ -1797,20 +3330,22 @@ async fn refresh_seed_panel(
     tournament: &tournament_db::Tournament,
     locale: Locale,
 ) -> Result<String, Error> {
-    Ok(match ensure_seed_panel(ctx, tournament).await {
-        PanelOutcome::Present => locale.pick("種子名單：正常。", "Seeding panel: fine."),
-        PanelOutcome::Reposted => locale.pick("種子名單：已重新張貼。", "Seeding panel: reposted."),
-        PanelOutcome::NotConfigured => {
-            locale.pick("種子名單：這場賽事沒有賽程頻道。", "Seeding panel: no bracket channel.")
-        },
-        PanelOutcome::Failed => locale.pick(
-            "種子名單：無法確認或張貼。",
-            "Seeding panel: could not confirm or post.",
-        ),
-        // The seeding panel has no phase gate — `ensure` never returns this for it.
-        PanelOutcome::NotExpected => unreachable!("the seeding panel has no phase gate"),
-    }
-    .to_string())
+    Ok(
+        match seed_panel::ensure(ctx.http(), &ctx.data().database, tournament).await {
+            PanelOutcome::Present => locale.pick("種子名單：正常。", "Seeding panel: fine."),
+            PanelOutcome::Reposted => locale.pick("種子名單：已重新張貼。", "Seeding panel: reposted."),
+            PanelOutcome::NotConfigured => {
+                locale.pick("種子名單：這場賽事沒有賽程頻道。", "Seeding panel: no bracket channel.")
+            },
+            PanelOutcome::Failed => locale.pick(
+                "種子名單：無法確認或張貼。",
+                "Seeding panel: could not confirm or post.",
+            ),
+            // The seeding panel has no phase gate — `ensure` never returns this for it.
+            PanelOutcome::NotExpected => unreachable!("the seeding panel has no phase gate"),
+        }
+        .to_string(),
+    )
 }
 
 /// Re-applies the output channels' overwrites, so a tournament created before
@@ -1868,21 +3403,13 @@ pub async fn start(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    let pool = &ctx.data().database;
-    let outcome = tournament_start::start(pool, &tournament).await?;
-    audit::log_action("start", tournament.id, &tournament.slug, ctx.author(), &outcome);
-
-    if matches!(outcome, tournament_start::StartOutcome::Started { .. }) {
-        // The preview messages become the real bracket in place — re-read so the
-        // status is `running` and the provisional label comes off.
-        let tournament = tournament_db::get_tournament(pool, tournament.id).await?.unwrap();
-        bracket_view::reconcile_now(ctx.http(), pool, &tournament).await?;
-        panel::refresh_now(ctx.http(), pool, &tournament).await?;
-        // The same opener a completed set runs, so round one and every round
-        // after it are opened by one piece of code.
-        set_thread::open_ready(ctx.http(), pool, &tournament).await;
-    }
-
+    let outcome = lifecycle::start(
+        ctx.http(),
+        &ctx.data().database,
+        &tournament,
+        Actor::Member(ctx.author()),
+    )
+    .await?;
     ephemeral(ctx, outcome.message(&tournament.name, locale)).await?;
     Ok(())
 }
@@ -1984,7 +3511,7 @@ async fn delete_tournament_channels(ctx: Context<'_>, tournament: &tournament_db
     guild_only,
     check = "tournament_only",
     rename = "set",
-    subcommands("set_report", "set_award", "set_redraft", "set_done"),
+    subcommands("set_report", "set_award", "set_redraft", "set_done", "set_schedule"),
     subcommand_required
 )]
 pub async fn set_root(_: Context<'_>) -> Result<(), Error> {
@@ -2268,6 +3795,56 @@ pub async fn set_done(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// 🗓️ Proposes when this match is played; the other player confirms it on the panel.
+///
+/// Resolved like `/set redraft`. A player's time waits on the other player's
+/// confirm button; an admin who is neither player sets it outright, since there
+/// is nobody on whose behalf they would be waiting.
+#[poise::command(
+    slash_command,
+    guild_only,
+    check = "tournament_only",
+    rename = "schedule",
+    description_localized("zh-TW", "🗓️ 提議這場對戰的比賽時間，由對手在面板上確認。")
+)]
+pub async fn set_schedule(
+    ctx: Context<'_>,
+    #[description = "YYYY-MM-DD HH:MM in UTC+8, or add your offset (e.g. +09:00), or paste a <t:…> timestamp"]
+    #[description_localized(
+        "zh-TW",
+        "格式 YYYY-MM-DD HH:MM（UTC+8），可在後面加上時區如 +09:00，或貼上 <t:…> 時間戳"
+    )]
+    time: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+    let locale = Locale::from_context(ctx);
+    let Some((tournament, set, caller_access)) = resolve_set_for_participant(ctx).await? else {
+        return Ok(());
+    };
+
+    let Some(at) = schedule::parse_time(&time) else {
+        ephemeral(ctx, schedule::ScheduleOutcome::Unreadable.message(locale)).await?;
+        return Ok(());
+    };
+    let outcome = schedule::propose(
+        ctx.http(),
+        &ctx.data().database,
+        &tournament,
+        &set,
+        to_db_id(ctx.author().id),
+        caller_access.may_manage_tournament(),
+        at,
+        Utc::now(),
+    )
+    .await?;
+    audit::log_action("set schedule", tournament.id, &tournament.slug, ctx.author(), &outcome);
+
+    // Ephemeral like its siblings: the notice in the thread is the record, and
+    // it is the one that pings whoever has to answer.
+    ephemeral(ctx, outcome.message(locale)).await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{FromRound, parse_start_time, preset_scope, read_only_overwrites};
@@ -2349,7 +3926,6 @@ mod tests {
     }
 
     use chrono::{Datelike, Timelike};
-    use regex::Regex;
 
     #[test]
     fn a_local_wall_time_is_stored_as_the_right_utc_instant() {
@@ -2384,27 +3960,4 @@ mod tests {
             assert!(parse_start_time(input).is_none(), "{input:?} should not parse");
         }
     }
-
-    #[test]
-    fn test_regex() {
-        let regex = Regex::new(r"綁定discord帳號 `(?<user_id>[0-9]+)` 與世紀帝國四帳號 `(?<aoe4_id>[0-9]+)`").unwrap();
-        let hay = "綁定discord帳號 `182108123174010880` 與世紀帝國四帳號 `199837`";
-        let result = regex.captures(hay);
-        assert!(result.is_some());
-        let cap = result.unwrap();
-        assert_eq!("182108123174010880", &cap["user_id"]);
-        assert_eq!("199837", &cap["aoe4_id"]);
-    }
-
-    #[test]
-    fn test_regex2() {
-        let regex =
-            Regex::new(r"Bound discord user `(?<user_id>[0-9]+)` to aoe4 world profile `(?<aoe4_id>[0-9]+)`").unwrap();
-        let hay = "Bound discord user `380688858729414658` to aoe4 world profile `3763401`";
-        let result = regex.captures(hay);
-        assert!(result.is_some());
-        let cap = result.unwrap();
-        assert_eq!("380688858729414658", &cap["user_id"]);
-        assert_eq!("3763401", &cap["aoe4_id"]);
-    }
 }
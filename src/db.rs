@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serenity::all::{ChannelId, MessageId, UserId};
 use sqlx::{FromRow, SqlitePool};
 use tracing::error;
@@ -23,31 +24,1020 @@ pub(crate) fn to_message_id(id: i64) -> MessageId {
     MessageId::new(id as u64)
 }
 
-#[derive(FromRow)]
+#[derive(Debug, FromRow)]
 pub(crate) struct Account {
     pub user_id: i64,
     pub aoe4_id: i64,
+    /// Chosen with `/bind main` (migrations/0023).
+    pub main: bool,
+    /// Proven with `/bind verify` (migrations/0024).
+    pub verified: bool,
+    /// `None` for bindings older than migrations/0025.
+    pub bound_at: Option<DateTime<Utc>>,
 }
 
-pub(crate) async fn bind_account(pool: &SqlitePool, user_id: i64, aoe4_id: i64) -> Result<String, sqlx::Error> {
-    sqlx::query("insert into accounts (user_id, aoe4_id) values (?1, ?2) on conflict (aoe4_id) do update set user_id = excluded.user_id")
+/// Binds unconditionally, taking the profile from whoever held it. Setup for
+/// tests only: the bot itself binds through `claim_account`'s ownership rules,
+/// or `/accounts import`'s.
+#[cfg(test)]
+pub(crate) async fn bind_account(pool: &SqlitePool, user_id: i64, aoe4_id: i64) -> Result<(), sqlx::Error> {
+    // A profile taken over by someone else stops being the old owner's main.
+    sqlx::query(
+        r"
+        insert into accounts (user_id, aoe4_id, bound_at) values (?1, ?2, ?3)
+        on conflict (aoe4_id) do update set
+            main = case when user_id = excluded.user_id then main else false end,
+            user_id = excluded.user_id
+        ",
+    )
+    .bind(user_id)
+    .bind(aoe4_id)
+    .bind(Utc::now())
+    .execute(pool)
+    .await
+    .inspect_err(|err| {
+        error!("database operation failed with error {}", err.to_string());
+    })?;
+    Ok(())
+}
+
+/// On whose word a profile is being bound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Claim {
+    /// `/bind id` or `/bind name`: anyone, for a profile nobody holds.
+    Plain,
+    /// `/bind confirm`: the code was found in the profile's name.
+    Verified,
+    /// A home-guild admin who asked with `take_over`, who may take a profile
+    /// over from whoever holds it.
+    Admin,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum BindOutcome {
+    Bound,
+    /// Already the caller's; a verified claim still marks it verified.
+    AlreadyYours,
+    /// Taken over from the member who held it.
+    TakenOver {
+        from: i64,
+    },
+    /// Someone else holds it and the claim is not enough to take it.
+    HeldBy {
+        user_id: i64,
+        verified: bool,
+    },
+}
+
+/// `bind_account` with ownership rules: a profile someone else holds is only
+/// taken over on a verified or admin claim.
+pub(crate) async fn claim_account(
+    pool: &SqlitePool,
+    user_id: i64,
+    aoe4_id: i64,
+    claim: Claim,
+) -> Result<BindOutcome, sqlx::Error> {
+    let verified = claim == Claim::Verified;
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+    let holder: Option<(i64, bool)> = sqlx::query_as("select user_id, verified from accounts where aoe4_id = ?1")
+        .bind(aoe4_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+
+    let outcome = match holder {
+        None => {
+            sqlx::query("insert into accounts (user_id, aoe4_id, verified, bound_at) values (?1, ?2, ?3, ?4)")
+                .bind(user_id)
+                .bind(aoe4_id)
+                .bind(verified)
+                .bind(Utc::now())
+                .execute(&mut *tx)
+                .await
+                .inspect_err(log_db_error)?;
+            BindOutcome::Bound
+        },
+        Some((holder, _)) if holder == user_id => {
+            sqlx::query("update accounts set verified = verified or ?2 where aoe4_id = ?1")
+                .bind(aoe4_id)
+                .bind(verified)
+                .execute(&mut *tx)
+                .await
+                .inspect_err(log_db_error)?;
+            BindOutcome::AlreadyYours
+        },
+        Some((holder, holder_verified)) if claim == Claim::Plain => BindOutcome::HeldBy {
+            user_id: holder,
+            verified: holder_verified,
+        },
+        Some((holder, _)) => {
+            sqlx::query(
+                "update accounts set user_id = ?2, main = false, verified = ?3, bound_at = ?4 where aoe4_id = ?1",
+            )
+            .bind(aoe4_id)
+            .bind(user_id)
+            .bind(verified)
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await
+            .inspect_err(log_db_error)?;
+            BindOutcome::TakenOver { from: holder }
+        },
+    };
+    tx.commit().await.inspect_err(log_db_error)?;
+    Ok(outcome)
+}
+
+pub(crate) async fn list_all(pool: &SqlitePool) -> Result<Vec<Account>, sqlx::Error> {
+    let accounts: Vec<Account> =
+        sqlx::query_as("select user_id, aoe4_id, main, verified, bound_at from accounts order by id")
+            .fetch_all(pool)
+            .await
+            .inspect_err(|err| {
+                error!("database operation failed with error {}", err.to_string());
+            })?;
+    Ok(accounts)
+}
+
+/// Main first, then in the order they were bound.
+pub(crate) async fn list_accounts_for_user(pool: &SqlitePool, user_id: i64) -> Result<Vec<Account>, sqlx::Error> {
+    sqlx::query_as(
+        "select user_id, aoe4_id, main, verified, bound_at from accounts where user_id = ?1 order by main desc, id",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .inspect_err(log_db_error)
+}
+
+/// Writes `/accounts import`'s rows in one go: each is bound as given, taking
+/// over any earlier binding of the profile, and a member given a main in the
+/// file loses whatever main they had. The caller has already refused a file
+/// with conflicts, so nothing here decides ownership.
+pub(crate) async fn import_accounts(pool: &SqlitePool, rows: &[Account]) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+    for row in rows.iter().filter(|row| row.main) {
+        sqlx::query("update accounts set main = false where user_id = ?1 and aoe4_id != ?2")
+            .bind(row.user_id)
+            .bind(row.aoe4_id)
+            .execute(&mut *tx)
+            .await
+            .inspect_err(log_db_error)?;
+    }
+    for row in rows {
+        sqlx::query(
+            r"
+            insert into accounts (user_id, aoe4_id, main, verified, bound_at) values (?1, ?2, ?3, ?4, ?5)
+            on conflict (aoe4_id) do update set
+                user_id = excluded.user_id,
+                main = excluded.main,
+                verified = excluded.verified,
+                bound_at = coalesce(excluded.bound_at, bound_at)
+            ",
+        )
+        .bind(row.user_id)
+        .bind(row.aoe4_id)
+        .bind(row.main)
+        .bind(row.verified)
+        .bind(row.bound_at)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    }
+    tx.commit().await.inspect_err(log_db_error)
+}
+
+/// Whether `user_id` had `aoe4_id` bound to unbind — someone else's binding is
+/// left alone.
+pub(crate) async fn unbind_account(pool: &SqlitePool, user_id: i64, aoe4_id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("delete from accounts where user_id = ?1 and aoe4_id = ?2")
         .bind(user_id)
         .bind(aoe4_id)
         .execute(pool)
         .await
-        .inspect_err(|err| {
-            error!("database operation failed with error {}", err.to_string());
-        })?;
+        .inspect_err(log_db_error)?;
+    Ok(result.rows_affected() > 0)
+}
 
-    Ok(format!("綁定discord帳號 `{}` 與世紀帝國四帳號 `{}` ", user_id, aoe4_id))
+/// Makes `aoe4_id` `user_id`'s main and any other of theirs not. `false`, with
+/// nothing changed, when it is not bound to them.
+pub(crate) async fn set_main_account(pool: &SqlitePool, user_id: i64, aoe4_id: i64) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+    let owned = sqlx::query_scalar::<_, i64>("select count(*) from accounts where user_id = ?1 and aoe4_id = ?2")
+        .bind(user_id)
+        .bind(aoe4_id)
+        .fetch_one(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    if owned == 0 {
+        return Ok(false);
+    }
+    // Cleared first, so the one-main-per-member index never sees two.
+    sqlx::query("update accounts set main = false where user_id = ?1 and main")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    sqlx::query("update accounts set main = true where user_id = ?1 and aoe4_id = ?2")
+        .bind(user_id)
+        .bind(aoe4_id)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    tx.commit().await.inspect_err(log_db_error)?;
+    Ok(true)
 }
 
-pub(crate) async fn list_all(pool: &SqlitePool) -> Result<Vec<Account>, sqlx::Error> {
-    let accounts: Vec<Account> = sqlx::query_as("select user_id, aoe4_id from accounts")
+fn log_db_error(err: &sqlx::Error) {
+    // Debug, not Display, for the same reason as `tournament::db`'s copy: Display
+    // drops the constraint name and the SQLite extended code.
+    error!("database operation failed with error {err:?}");
+}
+
+// rating_snapshots — one row per bound profile per refresh (migrations/0015).
+
+#[derive(FromRow, Debug, Clone)]
+pub(crate) struct RatingSnapshot {
+    pub aoe4_id: i64,
+    pub aoe4_name: String,
+    pub taken_at: DateTime<Utc>,
+    pub rating: i64,
+    pub elo: i64,
+    pub rank_level: String,
+    pub global_rank: i64,
+    pub games_count: i64,
+    pub win_rate: f64,
+}
+
+pub(crate) async fn insert_snapshot(pool: &SqlitePool, snapshot: &RatingSnapshot) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r"
+        insert into rating_snapshots
+            (aoe4_id, aoe4_name, taken_at, rating, elo, rank_level, global_rank, games_count, win_rate)
+        values (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+        ",
+    )
+    .bind(snapshot.aoe4_id)
+    .bind(&snapshot.aoe4_name)
+    .bind(snapshot.taken_at)
+    .bind(snapshot.rating)
+    .bind(snapshot.elo)
+    .bind(&snapshot.rank_level)
+    .bind(snapshot.global_rank)
+    .bind(snapshot.games_count)
+    .bind(snapshot.win_rate)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+/// Oldest first, which is the order a chart draws them in.
+pub(crate) async fn list_snapshots_since(
+    pool: &SqlitePool,
+    aoe4_id: i64,
+    since: DateTime<Utc>,
+) -> Result<Vec<RatingSnapshot>, sqlx::Error> {
+    sqlx::query_as(
+        r"
+        select aoe4_id, aoe4_name, taken_at, rating, elo, rank_level, global_rank, games_count, win_rate
+        from rating_snapshots
+        where aoe4_id = ?1
+          and taken_at >= ?2
+        order by taken_at
+        ",
+    )
+    .bind(aoe4_id)
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .inspect_err(log_db_error)
+}
+
+/// Every profile's snapshots since `since`, each profile's oldest first — the
+/// week the digest sums up.
+pub(crate) async fn list_all_snapshots_since(
+    pool: &SqlitePool,
+    since: DateTime<Utc>,
+) -> Result<Vec<RatingSnapshot>, sqlx::Error> {
+    sqlx::query_as(
+        r"
+        select aoe4_id, aoe4_name, taken_at, rating, elo, rank_level, global_rank, games_count, win_rate
+        from rating_snapshots
+        where taken_at >= ?1
+        order by aoe4_id, taken_at
+        ",
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .inspect_err(log_db_error)
+}
+
+// ranked_board_messages — each board's chunks in its channel (migrations/0016, 0017).
+
+#[derive(FromRow, Debug)]
+pub(crate) struct BoardMessage {
+    pub ordinal: i64,
+    pub message_id: i64,
+}
+
+pub(crate) async fn upsert_board_message(
+    pool: &SqlitePool,
+    board: &str,
+    ordinal: i64,
+    message_id: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r"
+        insert into ranked_board_messages (board, ordinal, message_id)
+        values (?1, ?2, ?3)
+        on conflict (board, ordinal) do update set message_id = excluded.message_id
+        ",
+    )
+    .bind(board)
+    .bind(ordinal)
+    .bind(message_id)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+/// Drops every chunk from `ordinal` onwards — the tail left behind when the
+/// board shrinks by a chunk, as `tournament::db::delete_bracket_messages_from`
+/// does for a bracket.
+pub(crate) async fn delete_board_messages_from(
+    pool: &SqlitePool,
+    board: &str,
+    ordinal: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(r"delete from ranked_board_messages where board = ?1 and ordinal >= ?2")
+        .bind(board)
+        .bind(ordinal)
+        .execute(pool)
+        .await
+        .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+pub(crate) async fn list_board_messages(pool: &SqlitePool, board: &str) -> Result<Vec<BoardMessage>, sqlx::Error> {
+    sqlx::query_as(r"select ordinal, message_id from ranked_board_messages where board = ?1 order by ordinal")
+        .bind(board)
         .fetch_all(pool)
         .await
-        .inspect_err(|err| {
-            error!("database operation failed with error {}", err.to_string());
-        })?;
-    Ok(accounts)
+        .inspect_err(log_db_error)
+}
+
+// inactivity_reminders — who opted out, who snoozed, who was already nagged (migrations/0018).
+
+#[derive(FromRow, Debug, Clone, Default)]
+pub(crate) struct ReminderState {
+    pub user_id: i64,
+    pub opted_out: bool,
+    pub snoozed_until: Option<DateTime<Utc>>,
+    pub nagged_for: Option<DateTime<Utc>>,
+}
+
+pub(crate) async fn list_reminder_states(pool: &SqlitePool) -> Result<Vec<ReminderState>, sqlx::Error> {
+    sqlx::query_as(r"select user_id, opted_out, snoozed_until, nagged_for from inactivity_reminders")
+        .fetch_all(pool)
+        .await
+        .inspect_err(log_db_error)
+}
+
+/// `/reminder off` and `/reminder on`. Turning reminders back on also lifts a
+/// snooze, so "on" always means on.
+pub(crate) async fn set_reminder_opt_out(pool: &SqlitePool, user_id: i64, opted_out: bool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r"
+        insert into inactivity_reminders (user_id, opted_out)
+        values (?1, ?2)
+        on conflict (user_id) do update set
+            opted_out = excluded.opted_out,
+            snoozed_until = case when excluded.opted_out then snoozed_until else null end
+        ",
+    )
+    .bind(user_id)
+    .bind(opted_out)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+pub(crate) async fn snooze_reminders(pool: &SqlitePool, user_id: i64, until: DateTime<Utc>) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r"
+        insert into inactivity_reminders (user_id, snoozed_until)
+        values (?1, ?2)
+        on conflict (user_id) do update set snoozed_until = excluded.snoozed_until
+        ",
+    )
+    .bind(user_id)
+    .bind(until)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+/// Records a nag for the idle stretch that ends at `last_game`, so the next run
+/// leaves this member alone until they have played again.
+pub(crate) async fn mark_nagged(
+    pool: &SqlitePool,
+    user_id: i64,
+    last_game: DateTime<Utc>,
+    nagged_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r"
+        insert into inactivity_reminders (user_id, nagged_for, nagged_at)
+        values (?1, ?2, ?3)
+        on conflict (user_id) do update set
+            nagged_for = excluded.nagged_for,
+            nagged_at = excluded.nagged_at
+        ",
+    )
+    .bind(user_id)
+    .bind(last_game)
+    .bind(nagged_at)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+// game_feed_state — how far the result feed has read each profile (migrations/0019).
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub(crate) struct FeedState {
+    pub aoe4_id: i64,
+    pub last_game_at: DateTime<Utc>,
+    pub last_game_id: Option<i64>,
+    pub rank_level: String,
+    pub best_rating: i64,
+    pub win_streak: i64,
+}
+
+pub(crate) async fn get_feed_state(pool: &SqlitePool, aoe4_id: i64) -> Result<Option<FeedState>, sqlx::Error> {
+    sqlx::query_as(
+        r"
+        select aoe4_id, last_game_at, last_game_id, rank_level, best_rating, win_streak
+        from game_feed_state
+        where aoe4_id = ?1
+        ",
+    )
+    .bind(aoe4_id)
+    .fetch_optional(pool)
+    .await
+    .inspect_err(log_db_error)
+}
+
+pub(crate) async fn save_feed_state(pool: &SqlitePool, state: &FeedState) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r"
+        insert into game_feed_state (aoe4_id, last_game_at, last_game_id, rank_level, best_rating, win_streak)
+        values (?1, ?2, ?3, ?4, ?5, ?6)
+        on conflict (aoe4_id) do update set
+            last_game_at = excluded.last_game_at,
+            last_game_id = excluded.last_game_id,
+            rank_level = excluded.rank_level,
+            best_rating = excluded.best_rating,
+            win_streak = excluded.win_streak
+        ",
+    )
+    .bind(state.aoe4_id)
+    .bind(state.last_game_at)
+    .bind(state.last_game_id)
+    .bind(&state.rank_level)
+    .bind(state.best_rating)
+    .bind(state.win_streak)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+// tier_role_grants / tier_role_changes — rank-tier roles handed out by refresh (migrations/0020).
+
+#[derive(FromRow, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TierRoleGrant {
+    pub user_id: i64,
+    pub role_id: i64,
+    pub rank_level: String,
+}
+
+pub(crate) async fn list_tier_role_grants(pool: &SqlitePool) -> Result<Vec<TierRoleGrant>, sqlx::Error> {
+    sqlx::query_as(r"select user_id, role_id, rank_level from tier_role_grants")
+        .fetch_all(pool)
+        .await
+        .inspect_err(log_db_error)
+}
+
+/// Records `role_id` as granted to `user_id`, replacing any earlier grant, and
+/// logs the add.
+pub(crate) async fn grant_tier_role(
+    pool: &SqlitePool,
+    user_id: i64,
+    role_id: i64,
+    rank_level: &str,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+    sqlx::query(
+        r"
+        insert into tier_role_grants (user_id, role_id, rank_level)
+        values (?1, ?2, ?3)
+        on conflict (user_id) do update set role_id = excluded.role_id, rank_level = excluded.rank_level
+        ",
+    )
+    .bind(user_id)
+    .bind(role_id)
+    .bind(rank_level)
+    .execute(&mut *tx)
+    .await
+    .inspect_err(log_db_error)?;
+    sqlx::query(r"insert into tier_role_changes (user_id, role_id, action, rank_level) values (?1, ?2, 'add', ?3)")
+        .bind(user_id)
+        .bind(role_id)
+        .bind(rank_level)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    tx.commit().await.inspect_err(log_db_error)
+}
+
+/// Forgets `user_id`'s grant of `role_id`, if that is still the one on record,
+/// and logs the removal. `rank_level` is what they are now, if anything.
+pub(crate) async fn revoke_tier_role(
+    pool: &SqlitePool,
+    user_id: i64,
+    role_id: i64,
+    rank_level: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+    sqlx::query(r"delete from tier_role_grants where user_id = ?1 and role_id = ?2")
+        .bind(user_id)
+        .bind(role_id)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    sqlx::query(r"insert into tier_role_changes (user_id, role_id, action, rank_level) values (?1, ?2, 'remove', ?3)")
+        .bind(user_id)
+        .bind(role_id)
+        .bind(rank_level)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    tx.commit().await.inspect_err(log_db_error)
+}
+
+// reaction_rules — what the home guild's messages get reacted with (migrations/0021).
+
+#[derive(FromRow, Debug, Clone)]
+pub(crate) struct ReactionRule {
+    pub id: i64,
+    pub kind: String,
+    pub pattern: String,
+    pub emoji: String,
+    pub probability: f64,
+    pub follow_up_chance: f64,
+    /// Rules sharing one are a single draw — see migrations/0034.
+    pub draw_group: Option<String>,
+}
+
+pub(crate) async fn list_reaction_rules(pool: &SqlitePool) -> Result<Vec<ReactionRule>, sqlx::Error> {
+    sqlx::query_as(
+        r"select id, kind, pattern, emoji, probability, follow_up_chance, draw_group from reaction_rules order by id",
+    )
+    .fetch_all(pool)
+    .await
+    .inspect_err(log_db_error)
+}
+
+/// Returns the new rule's id. `kind` and `pattern` are checked by the caller —
+/// see `emperor::Trigger::parse`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn insert_reaction_rule(
+    pool: &SqlitePool,
+    kind: &str,
+    pattern: &str,
+    emoji: &str,
+    probability: f64,
+    follow_up_chance: f64,
+    draw_group: Option<&str>,
+    created_by: i64,
+) -> Result<i64, sqlx::Error> {
+    let id = sqlx::query_scalar(
+        r"
+        insert into reaction_rules (kind, pattern, emoji, probability, follow_up_chance, draw_group, created_by)
+        values (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        returning id
+        ",
+    )
+    .bind(kind)
+    .bind(pattern)
+    .bind(emoji)
+    .bind(probability)
+    .bind(follow_up_chance)
+    .bind(draw_group)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(id)
+}
+
+/// Whether there was such a rule to delete.
+pub(crate) async fn delete_reaction_rule(pool: &SqlitePool, id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(r"delete from reaction_rules where id = ?1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .inspect_err(log_db_error)?;
+    Ok(result.rows_affected() > 0)
+}
+
+// reaction_opt_outs — members the reaction rules leave alone (migrations/0022).
+
+pub(crate) async fn list_reaction_opt_outs(pool: &SqlitePool) -> Result<Vec<i64>, sqlx::Error> {
+    sqlx::query_scalar(r"select user_id from reaction_opt_outs")
+        .fetch_all(pool)
+        .await
+        .inspect_err(log_db_error)
+}
+
+pub(crate) async fn set_reaction_opt_out(pool: &SqlitePool, user_id: i64, opted_out: bool) -> Result<(), sqlx::Error> {
+    let query = if opted_out {
+        r"insert into reaction_opt_outs (user_id) values (?1) on conflict (user_id) do nothing"
+    } else {
+        r"delete from reaction_opt_outs where user_id = ?1"
+    };
+    sqlx::query(query)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+// bind_verifications — `/bind verify` codes waiting to be confirmed (migrations/0024).
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub(crate) struct PendingVerification {
+    pub aoe4_id: i64,
+    pub code: String,
+    pub issued_at: DateTime<Utc>,
+}
+
+/// Replaces whatever code `user_id` had outstanding.
+pub(crate) async fn start_verification(
+    pool: &SqlitePool,
+    user_id: i64,
+    aoe4_id: i64,
+    code: &str,
+    issued_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r"
+        insert into bind_verifications (user_id, aoe4_id, code, issued_at)
+        values (?1, ?2, ?3, ?4)
+        on conflict (user_id) do update set
+            aoe4_id = excluded.aoe4_id,
+            code = excluded.code,
+            issued_at = excluded.issued_at
+        ",
+    )
+    .bind(user_id)
+    .bind(aoe4_id)
+    .bind(code)
+    .bind(issued_at)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+pub(crate) async fn get_verification(
+    pool: &SqlitePool,
+    user_id: i64,
+) -> Result<Option<PendingVerification>, sqlx::Error> {
+    sqlx::query_as(r"select aoe4_id, code, issued_at from bind_verifications where user_id = ?1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .inspect_err(log_db_error)
+}
+
+pub(crate) async fn clear_verification(pool: &SqlitePool, user_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query(r"delete from bind_verifications where user_id = ?1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+// leaderboard_entries / leaderboard_civs / leaderboard_views — what `/leaderboard`, `/civs` and `/civ` read
+// (migrations/0026, 0028).
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub(crate) struct LeaderboardEntry {
+    pub user_id: i64,
+    pub aoe4_id: i64,
+    pub aoe4_name: String,
+    pub discord_display: String,
+    pub rank_level: String,
+    pub rating: i64,
+    pub elo: i64,
+    pub recent_max_rating: i64,
+    pub global_rank: i64,
+    pub games_played: i64,
+    pub win_rate: f64,
+    pub favorite_civ: String,
+    pub refreshed_at: DateTime<Utc>,
+}
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub(crate) struct LeaderboardCiv {
+    pub user_id: i64,
+    pub civilization: String,
+    pub pick_rate: f64,
+    pub games: i64,
+}
+
+/// Swaps in a refresh's board in one transaction, so a `/leaderboard` opened
+/// mid-refresh sees the old board or the new one, never half of each.
+pub(crate) async fn replace_leaderboard(
+    pool: &SqlitePool,
+    entries: &[LeaderboardEntry],
+    civs: &[LeaderboardCiv],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+    for delete in ["delete from leaderboard_entries", "delete from leaderboard_civs"] {
+        sqlx::query(delete).execute(&mut *tx).await.inspect_err(log_db_error)?;
+    }
+    for civ in civs {
+        sqlx::query(
+            r"
+            insert into leaderboard_civs (user_id, civilization, pick_rate, games) values (?1, ?2, ?3, ?4)
+            ",
+        )
+        .bind(civ.user_id)
+        .bind(&civ.civilization)
+        .bind(civ.pick_rate)
+        .bind(civ.games)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    }
+    for entry in entries {
+        sqlx::query(
+            r"
+            insert into leaderboard_entries
+                (user_id, aoe4_id, aoe4_name, discord_display, rank_level, rating, elo, recent_max_rating,
+                 global_rank, games_played, win_rate, favorite_civ, refreshed_at)
+            values (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+            ",
+        )
+        .bind(entry.user_id)
+        .bind(entry.aoe4_id)
+        .bind(&entry.aoe4_name)
+        .bind(&entry.discord_display)
+        .bind(&entry.rank_level)
+        .bind(entry.rating)
+        .bind(entry.elo)
+        .bind(entry.recent_max_rating)
+        .bind(entry.global_rank)
+        .bind(entry.games_played)
+        .bind(entry.win_rate)
+        .bind(&entry.favorite_civ)
+        .bind(entry.refreshed_at)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    }
+    tx.commit().await.inspect_err(log_db_error)
+}
+
+/// In no particular order: every view sorts for itself.
+pub(crate) async fn list_leaderboard(pool: &SqlitePool) -> Result<Vec<LeaderboardEntry>, sqlx::Error> {
+    sqlx::query_as::<_, LeaderboardEntry>("select * from leaderboard_entries")
+        .fetch_all(pool)
+        .await
+        .inspect_err(log_db_error)
+}
+
+/// In no particular order, like `list_leaderboard`.
+pub(crate) async fn list_leaderboard_civs(pool: &SqlitePool) -> Result<Vec<LeaderboardCiv>, sqlx::Error> {
+    sqlx::query_as::<_, LeaderboardCiv>("select * from leaderboard_civs")
+        .fetch_all(pool)
+        .await
+        .inspect_err(log_db_error)
+}
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub(crate) struct LeaderboardView {
+    pub id: i64,
+    pub user_id: i64,
+    pub sort: String,
+    pub tier: Option<String>,
+    pub civ: Option<String>,
+    pub page: i64,
+}
+
+/// How long a view's buttons keep working. An ephemeral reply is gone from the
+/// client long before this; the rows only need to outlive the longest browse.
+const LEADERBOARD_VIEW_LIFETIME: chrono::Duration = chrono::Duration::days(1);
+
+/// Also clears out views past `LEADERBOARD_VIEW_LIFETIME`, so the table holds
+/// only the handful anyone could still be clicking through.
+pub(crate) async fn create_leaderboard_view(
+    pool: &SqlitePool,
+    user_id: i64,
+    sort: &str,
+    tier: Option<&str>,
+    civ: Option<&str>,
+) -> Result<LeaderboardView, sqlx::Error> {
+    sqlx::query("delete from leaderboard_views where created_at < ?1")
+        .bind(Utc::now() - LEADERBOARD_VIEW_LIFETIME)
+        .execute(pool)
+        .await
+        .inspect_err(log_db_error)?;
+    sqlx::query_as::<_, LeaderboardView>(
+        r"
+        insert into leaderboard_views (user_id, sort, tier, civ, created_at) values (?1, ?2, ?3, ?4, ?5)
+        returning id, user_id, sort, tier, civ, page
+        ",
+    )
+    .bind(user_id)
+    .bind(sort)
+    .bind(tier)
+    .bind(civ)
+    .bind(Utc::now())
+    .fetch_one(pool)
+    .await
+    .inspect_err(log_db_error)
+}
+
+pub(crate) async fn get_leaderboard_view(pool: &SqlitePool, id: i64) -> Result<Option<LeaderboardView>, sqlx::Error> {
+    sqlx::query_as::<_, LeaderboardView>(
+        "select id, user_id, sort, tier, civ, page from leaderboard_views where id = ?1",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .inspect_err(log_db_error)
+}
+
+pub(crate) async fn set_leaderboard_view_page(pool: &SqlitePool, id: i64, page: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("update leaderboard_views set page = ?2 where id = ?1")
+        .bind(id)
+        .bind(page)
+        .execute(pool)
+        .await
+        .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+// seasons / season_standings — boards frozen by `/season close` (migrations/0027).
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub(crate) struct Season {
+    pub id: i64,
+    pub name: String,
+    pub closed_at: DateTime<Utc>,
+    pub closed_by: i64,
+    pub board_refreshed_at: DateTime<Utc>,
+}
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub(crate) struct SeasonStanding {
+    pub user_id: i64,
+    pub aoe4_id: i64,
+    pub aoe4_name: String,
+    pub discord_display: String,
+    pub place: i64,
+    pub rank_level: String,
+    pub rating: i64,
+}
+
+/// A season a profile finished, for `/查分`.
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub(crate) struct SeasonResult {
+    pub name: String,
+    pub place: i64,
+    /// How many finished the season, for "3rd of 40".
+    pub of: i64,
+    pub rank_level: String,
+    pub rating: i64,
+}
+
+/// Archives `entries` as season `name`, first place first. `None` when a
+/// season already has that name (case-insensitively): a closed season is never
+/// overwritten.
+pub(crate) async fn close_season(
+    pool: &SqlitePool,
+    name: &str,
+    closed_by: i64,
+    entries: &[&LeaderboardEntry],
+) -> Result<Option<Season>, sqlx::Error> {
+    let board_refreshed_at = entries
+        .iter()
+        .map(|entry| entry.refreshed_at)
+        .max()
+        .unwrap_or_else(Utc::now);
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+    let Some(season) = sqlx::query_as::<_, Season>(
+        r"
+        insert into seasons (name, closed_at, closed_by, board_refreshed_at) values (?1, ?2, ?3, ?4)
+        on conflict (name) do nothing
+        returning *
+        ",
+    )
+    .bind(name)
+    .bind(Utc::now())
+    .bind(closed_by)
+    .bind(board_refreshed_at)
+    .fetch_optional(&mut *tx)
+    .await
+    .inspect_err(log_db_error)?
+    else {
+        return Ok(None);
+    };
+    for (place, entry) in (1..).zip(entries) {
+        sqlx::query(
+            r"
+            insert into season_standings
+                (season_id, user_id, aoe4_id, aoe4_name, discord_display, place, rank_level, rating)
+            values (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ",
+        )
+        .bind(season.id)
+        .bind(entry.user_id)
+        .bind(entry.aoe4_id)
+        .bind(&entry.aoe4_name)
+        .bind(&entry.discord_display)
+        .bind(place)
+        .bind(&entry.rank_level)
+        .bind(entry.rating)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    }
+    tx.commit().await.inspect_err(log_db_error)?;
+    Ok(Some(season))
+}
+
+pub(crate) async fn get_season(pool: &SqlitePool, name: &str) -> Result<Option<Season>, sqlx::Error> {
+    sqlx::query_as::<_, Season>("select * from seasons where name = ?1")
+        .bind(name)
+        .fetch_optional(pool)
+        .await
+        .inspect_err(log_db_error)
+}
+
+/// Newest first.
+pub(crate) async fn list_seasons(pool: &SqlitePool) -> Result<Vec<Season>, sqlx::Error> {
+    sqlx::query_as::<_, Season>("select * from seasons order by closed_at desc, id desc")
+        .fetch_all(pool)
+        .await
+        .inspect_err(log_db_error)
+}
+
+pub(crate) async fn list_season_standings(
+    pool: &SqlitePool,
+    season_id: i64,
+) -> Result<Vec<SeasonStanding>, sqlx::Error> {
+    sqlx::query_as::<_, SeasonStanding>(
+        r"
+        select user_id, aoe4_id, aoe4_name, discord_display, place, rank_level, rating
+        from season_standings where season_id = ?1 order by place
+        ",
+    )
+    .bind(season_id)
+    .fetch_all(pool)
+    .await
+    .inspect_err(log_db_error)
+}
+
+/// Every season the profile finished, newest first. A season archives each
+/// member under their main profile, so this also finds the seasons of whoever
+/// owns `aoe4_id` now, when one of their other profiles was the main back then.
+pub(crate) async fn season_history(pool: &SqlitePool, aoe4_id: i64) -> Result<Vec<SeasonResult>, sqlx::Error> {
+    sqlx::query_as::<_, SeasonResult>(
+        r"
+        select seasons.name, standings.place, standings.rank_level, standings.rating,
+               (select count(*) from season_standings where season_id = seasons.id) as of
+        from season_standings standings
+        join seasons on seasons.id = standings.season_id
+        where standings.aoe4_id = ?1
+           or standings.user_id in (select user_id from accounts where aoe4_id = ?1)
+        order by seasons.closed_at desc, seasons.id desc
+        ",
+    )
+    .bind(aoe4_id)
+    .fetch_all(pool)
+    .await
+    .inspect_err(log_db_error)
 }
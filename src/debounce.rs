@@ -0,0 +1,61 @@
+//! Per-user debouncing for autocomplete. Discord sends one autocomplete request
+//! per keystroke; each one waits a moment, and only the one nobody has typed
+//! past goes on to do the lookup. The rest answer empty, which Discord drops in
+//! favour of the newer response anyway.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+#[derive(Default)]
+struct Debouncer {
+    latest: Mutex<HashMap<u64, u64>>,
+}
+
+impl Debouncer {
+    /// Marks a new request from `key`, superseding any earlier one.
+    fn begin(&self, key: u64) -> u64 {
+        let mut latest = self.latest.lock().unwrap();
+        let ticket = latest.get(&key).map_or(0, |ticket| ticket + 1);
+        latest.insert(key, ticket);
+        ticket
+    }
+
+    fn is_latest(&self, key: u64, ticket: u64) -> bool {
+        self.latest.lock().unwrap().get(&key) == Some(&ticket)
+    }
+}
+
+fn debouncer() -> &'static Debouncer {
+    static DEBOUNCER: OnceLock<Debouncer> = OnceLock::new();
+    DEBOUNCER.get_or_init(Debouncer::default)
+}
+
+/// Waits `quiet`, then says whether this is still `key`'s newest request.
+pub(crate) async fn settled(key: u64, quiet: Duration) -> bool {
+    let ticket = debouncer().begin(key);
+    tokio::time::sleep(quiet).await;
+    debouncer().is_latest(key, ticket)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_newer_request_supersedes_an_older_one() {
+        let debouncer = Debouncer::default();
+        let first = debouncer.begin(1);
+        let second = debouncer.begin(1);
+        assert!(!debouncer.is_latest(1, first));
+        assert!(debouncer.is_latest(1, second));
+    }
+
+    #[test]
+    fn users_do_not_supersede_each_other() {
+        let debouncer = Debouncer::default();
+        let mine = debouncer.begin(1);
+        debouncer.begin(2);
+        assert!(debouncer.is_latest(1, mine));
+    }
+}
@@ -0,0 +1,520 @@
+//! The weekly digest: once a week, one post summing up the server's past seven
+//! days — the biggest climbers and fallers, who played the most, who reached a
+//! new tier, the most played civilizations, and who joined.
+//!
+//! Computed entirely from what refreshes already stored: `rating_snapshots`
+//! for the week's movement, `leaderboard_civs` for the civilizations, and
+//! `accounts.bound_at` for the newcomers. The post asks aoe4world for nothing.
+//! Like the board, it is read by everyone, so it is written in both languages.
+
+use crate::aoe4world::{civilization_bilingual, rank_level_name, rank_level_order};
+use crate::civ_stats;
+use crate::commands::INTERACTION_CHANNEL_ID;
+use crate::db::{
+    Account, LeaderboardCiv, LeaderboardEntry, RatingSnapshot, list_all, list_all_snapshots_since, list_leaderboard,
+    list_leaderboard_civs, to_user_id,
+};
+use crate::locale::Locale;
+use crate::ranked::escape;
+use crate::{Data, Error};
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serenity::all::{ChannelId, CreateAllowedMentions, CreateMessage, Http, Mentionable};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use tracing::{error, info};
+
+/// How far back the digest looks, and how often it is posted.
+const WEEK: Duration = Duration::days(7);
+
+/// Where the week starts, for a digest posted at `now`: a week back, floored to
+/// midnight UTC. The post goes out half an hour after the midnight refresh, so
+/// a plain week back would start just past last Monday's snapshot and lose
+/// every profile's first half-day of movement.
+fn week_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    (now - WEEK).date_naive().and_time(NaiveTime::MIN).and_utc()
+}
+
+/// Members per ranked section.
+const TOP: usize = 3;
+
+/// Newcomers named before the rest are only counted, so a busy week's post
+/// stays one message.
+const MAX_NEWCOMERS: usize = 15;
+
+/// The same for new tiers, which take a line each — the week after a season
+/// reset can promote most of the server.
+const MAX_PROMOTIONS: usize = 10;
+
+fn channel() -> ChannelId {
+    resolve_channel(std::env::var("DIGEST_CHANNEL_ID").ok().as_deref())
+}
+
+/// Pure, like `refresh::resolve_boards`. Unset means the channel `/查分` and
+/// the reminders post in; a value that is not a channel id is logged and that
+/// default used, since a typo should not silence the digest.
+fn resolve_channel(value: Option<&str>) -> ChannelId {
+    let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+        return INTERACTION_CHANNEL_ID;
+    };
+    match value.parse::<u64>().ok().filter(|id| *id != 0) {
+        Some(id) => ChannelId::new(id),
+        None => {
+            error!("DIGEST_CHANNEL_ID {value:?} is not a channel id; posting the digest in the default channel");
+            INTERACTION_CHANNEL_ID
+        },
+    }
+}
+
+/// One member's week: a profile's first and last snapshot in the window.
+#[derive(Debug, Clone, PartialEq)]
+struct Movement {
+    user_id: i64,
+    name: String,
+    rating_change: i64,
+    from_rating: i64,
+    to_rating: i64,
+    games: i64,
+    from_level: String,
+    to_level: String,
+}
+
+impl Movement {
+    /// Whether the profile ended the week in a higher tier than it started
+    /// in — a new tier, not just a division.
+    fn promoted(&self) -> bool {
+        match (rank_level_order(&self.from_level), rank_level_order(&self.to_level)) {
+            (Some((from, _)), Some((to, _))) => to > from,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Digest {
+    climbers: Vec<Movement>,
+    fallers: Vec<Movement>,
+    most_games: Vec<Movement>,
+    promotions: Vec<Movement>,
+    /// Civilization and the games the server has played with it, most first —
+    /// `civ_stats::popularity`, the same count `/civs` shows.
+    civs: Vec<(String, i64)>,
+    newcomers: Vec<i64>,
+}
+
+/// `snapshots` in `list_all_snapshots_since`'s order. Only profiles still
+/// bound count, and a profile needs two snapshots to have moved at all.
+///
+/// One entry per member, as on the board (`refresh::rank`), so someone with an
+/// alt cannot fill a section on their own: their main profile's week if it
+/// moved, otherwise their highest rated one's, with every profile's games added
+/// up.
+fn movements(snapshots: &[RatingSnapshot], accounts: &[Account]) -> Vec<Movement> {
+    let owners = accounts
+        .iter()
+        .map(|account| (account.aoe4_id, account))
+        .collect::<HashMap<_, _>>();
+    let mut by_member = BTreeMap::<i64, Vec<(bool, Movement)>>::new();
+    for week in snapshots.chunk_by(|a, b| a.aoe4_id == b.aoe4_id) {
+        let (Some(first), Some(last)) = (week.first(), week.last()) else {
+            continue;
+        };
+        let Some(account) = owners.get(&first.aoe4_id) else {
+            continue;
+        };
+        if week.len() < 2 {
+            continue;
+        }
+        by_member.entry(account.user_id).or_default().push((
+            account.main,
+            Movement {
+                user_id: account.user_id,
+                name: last.aoe4_name.clone(),
+                rating_change: last.rating - first.rating,
+                from_rating: first.rating,
+                to_rating: last.rating,
+                games: last.games_count - first.games_count,
+                from_level: first.rank_level.clone(),
+                to_level: last.rank_level.clone(),
+            },
+        ));
+    }
+    by_member
+        .into_values()
+        .filter_map(|mut profiles| {
+            let games = profiles.iter().map(|(_, movement)| movement.games).sum();
+            profiles.sort_by_key(|(main, movement)| (std::cmp::Reverse(*main), std::cmp::Reverse(movement.to_rating)));
+            let (_, mut movement) = profiles.into_iter().next()?;
+            movement.games = games;
+            Some(movement)
+        })
+        .collect()
+}
+
+fn compute(
+    snapshots: &[RatingSnapshot],
+    accounts: &[Account],
+    board: &[LeaderboardEntry],
+    board_civs: &[LeaderboardCiv],
+    since: DateTime<Utc>,
+) -> Digest {
+    let week = movements(snapshots, accounts);
+    let top = |keep: &dyn Fn(&Movement) -> bool, key: &dyn Fn(&Movement) -> i64| {
+        let mut picked = week.iter().filter(|m| keep(m)).cloned().collect::<Vec<_>>();
+        picked.sort_by_key(|m| (std::cmp::Reverse(key(m)), m.user_id));
+        picked.truncate(TOP);
+        picked
+    };
+
+    let civs = civ_stats::popularity(board, board_civs)
+        .into_iter()
+        .take(TOP)
+        .map(|civ| (civ.civilization, civ.games))
+        .collect();
+
+    let newcomers = accounts
+        .iter()
+        .filter(|account| account.bound_at.is_some_and(|at| at >= since))
+        .map(|account| account.user_id)
+        .collect::<BTreeSet<_>>();
+    // Someone who only added another profile this week is not new.
+    let veterans = accounts
+        .iter()
+        .filter(|account| account.bound_at.is_none_or(|at| at < since))
+        .map(|account| account.user_id)
+        .collect::<BTreeSet<_>>();
+
+    let mut promotions = week.iter().filter(|m| m.promoted()).cloned().collect::<Vec<_>>();
+    promotions.sort_by_key(|m| (std::cmp::Reverse(rank_level_order(&m.to_level)), m.user_id));
+
+    Digest {
+        climbers: top(&|m| m.rating_change > 0, &|m| m.rating_change),
+        fallers: top(&|m| m.rating_change < 0, &|m| -m.rating_change),
+        most_games: top(&|m| m.games > 0, &|m| m.games),
+        promotions,
+        civs,
+        newcomers: newcomers.difference(&veterans).copied().collect(),
+    }
+}
+
+fn who(movement: &Movement) -> String {
+    format!(
+        "{} ({})",
+        to_user_id(movement.user_id).mention(),
+        escape(&movement.name)
+    )
+}
+
+fn tier(level: &str) -> String {
+    let (zh, en) = (rank_level_name(level, Locale::ZhTw), rank_level_name(level, Locale::En));
+    if zh == en { zh } else { format!("{zh} / {en}") }
+}
+
+/// The post, or `None` for a week with nothing to say.
+fn render(digest: &Digest) -> Option<String> {
+    let mut sections = Vec::new();
+    let rating_line = |m: &Movement| format!("{} {} → {} ({:+})", who(m), m.from_rating, m.to_rating, m.rating_change);
+    if !digest.climbers.is_empty() {
+        let lines = digest.climbers.iter().map(rating_line).collect::<Vec<_>>();
+        sections.push(format!("📈 **進步最多 / Biggest climbers**\n{}", lines.join("\n")));
+    }
+    if !digest.fallers.is_empty() {
+        let lines = digest.fallers.iter().map(rating_line).collect::<Vec<_>>();
+        sections.push(format!("📉 **退步最多 / Biggest fallers**\n{}", lines.join("\n")));
+    }
+    if !digest.most_games.is_empty() {
+        let lines = digest
+            .most_games
+            .iter()
+            .map(|m| format!("{} {} 場 / games", who(m), m.games))
+            .collect::<Vec<_>>();
+        sections.push(format!("🎮 **最多場次 / Most games**\n{}", lines.join("\n")));
+    }
+    if !digest.promotions.is_empty() {
+        let mut lines = digest
+            .promotions
+            .iter()
+            .take(MAX_PROMOTIONS)
+            .map(|m| format!("{} {} → {}", who(m), tier(&m.from_level), tier(&m.to_level)))
+            .collect::<Vec<_>>();
+        if digest.promotions.len() > MAX_PROMOTIONS {
+            let more = digest.promotions.len() - MAX_PROMOTIONS;
+            lines.push(format!("…還有 {more} 人 / +{more} more"));
+        }
+        sections.push(format!("🎉 **升上新階級 / New tiers**\n{}", lines.join("\n")));
+    }
+    if !digest.civs.is_empty() {
+        let lines = digest
+            .civs
+            .iter()
+            .map(|(civ, games)| format!("{} · {games} 場 / games", civilization_bilingual(civ)))
+            .collect::<Vec<_>>();
+        sections.push(format!(
+            "🏰 **最多人愛用的文明 / Most played civs**\n{}",
+            lines.join("\n")
+        ));
+    }
+    if !digest.newcomers.is_empty() {
+        let mut names = digest
+            .newcomers
+            .iter()
+            .take(MAX_NEWCOMERS)
+            .map(|user_id| to_user_id(*user_id).mention().to_string())
+            .collect::<Vec<_>>();
+        if digest.newcomers.len() > MAX_NEWCOMERS {
+            let more = digest.newcomers.len() - MAX_NEWCOMERS;
+            names.push(format!("+{more}"));
+        }
+        sections.push(format!("👋 **新綁定的成員 / Newly bound**\n{}", names.join(" ")));
+    }
+    if sections.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "**本週伺服器摘要 / This week on the server**\n\n{}",
+        sections.join("\n\n")
+    ))
+}
+
+/// Posts the week's digest. Best-effort, like `reminder::run`: a failure is
+/// logged and the next week's post goes ahead regardless.
+pub(crate) async fn run(http: &Http, data: &Data) {
+    if let Err(err) = post(http, data).await {
+        error!("weekly digest failed: {err:?}");
+    }
+}
+
+async fn post(http: &Http, data: &Data) -> Result<(), Error> {
+    let pool = &data.database;
+    let since = week_start(Utc::now());
+    let snapshots = list_all_snapshots_since(pool, since).await?;
+    let accounts = list_all(pool).await?;
+    let board = list_leaderboard(pool).await?;
+    let board_civs = list_leaderboard_civs(pool).await?;
+    let Some(content) = render(&compute(&snapshots, &accounts, &board, &board_civs, since)) else {
+        info!("nothing for the weekly digest this week");
+        return Ok(());
+    };
+    // Names, not pings, as in the game feed: nobody asked to be summoned.
+    let message = CreateMessage::new()
+        .content(content)
+        .allowed_mentions(CreateAllowedMentions::new());
+    channel().send_message(http, message).await?;
+    info!("posted the weekly digest");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 5, day, 0, 0, 0).unwrap()
+    }
+
+    fn snapshot(aoe4_id: i64, day: u32, rating: i64, games_count: i64, rank_level: &str) -> RatingSnapshot {
+        RatingSnapshot {
+            aoe4_id,
+            aoe4_name: format!("p{aoe4_id}"),
+            taken_at: at(day),
+            rating,
+            elo: rating,
+            rank_level: rank_level.to_string(),
+            global_rank: 1000,
+            games_count,
+            win_rate: 50.0,
+        }
+    }
+
+    fn account(user_id: i64, aoe4_id: i64, bound_day: Option<u32>) -> Account {
+        Account {
+            user_id,
+            aoe4_id,
+            main: false,
+            verified: false,
+            bound_at: bound_day.map(at),
+        }
+    }
+
+    fn board_entry(user_id: i64, civ: &str) -> LeaderboardEntry {
+        LeaderboardEntry {
+            user_id,
+            aoe4_id: user_id * 10,
+            aoe4_name: "P".to_string(),
+            discord_display: "D".to_string(),
+            rank_level: "gold_1".to_string(),
+            rating: 1000,
+            elo: 1000,
+            recent_max_rating: 1000,
+            global_rank: 1,
+            games_played: 1,
+            win_rate: 50.0,
+            favorite_civ: civ.to_string(),
+            refreshed_at: at(7),
+        }
+    }
+
+    fn board_civ(user_id: i64, civ: &str, games: i64) -> LeaderboardCiv {
+        LeaderboardCiv {
+            user_id,
+            civilization: civ.to_string(),
+            pick_rate: 50.0,
+            games,
+        }
+    }
+
+    #[test]
+    fn a_bad_channel_falls_back_to_the_default() {
+        assert_eq!(resolve_channel(None), INTERACTION_CHANNEL_ID);
+        assert_eq!(resolve_channel(Some("  ")), INTERACTION_CHANNEL_ID);
+        assert_eq!(resolve_channel(Some("general")), INTERACTION_CHANNEL_ID);
+        assert_eq!(resolve_channel(Some("123")), ChannelId::new(123));
+    }
+
+    #[test]
+    fn the_week_starts_at_last_weeks_midnight_snapshot() {
+        let posted = Utc.with_ymd_and_hms(2026, 5, 11, 0, 30, 0).unwrap();
+        let snapshot_taken = Utc.with_ymd_and_hms(2026, 5, 4, 0, 0, 0).unwrap();
+        assert_eq!(week_start(posted), snapshot_taken);
+    }
+
+    #[test]
+    fn the_week_is_measured_from_each_profiles_first_snapshot_to_its_last() {
+        let snapshots = vec![
+            snapshot(10, 1, 1000, 100, "gold_3"),
+            snapshot(10, 4, 1050, 110, "platinum_1"),
+            snapshot(10, 7, 1120, 130, "platinum_1"),
+            snapshot(20, 1, 1300, 50, "platinum_2"),
+            snapshot(20, 7, 1250, 58, "platinum_1"),
+            // Only one snapshot: no movement to speak of.
+            snapshot(30, 7, 900, 10, "silver_1"),
+            // No longer bound.
+            snapshot(40, 1, 500, 10, "bronze_1"),
+            snapshot(40, 7, 900, 60, "silver_1"),
+        ];
+        let accounts = vec![account(1, 10, Some(1)), account(2, 20, None), account(3, 30, None)];
+        let week = movements(&snapshots, &accounts);
+        assert_eq!(week.len(), 2);
+        assert_eq!((week[0].user_id, week[0].rating_change, week[0].games), (1, 120, 30));
+        assert!(week[0].promoted());
+        assert_eq!((week[1].user_id, week[1].rating_change, week[1].games), (2, -50, 8));
+        assert!(!week[1].promoted(), "a division down is not a new tier");
+    }
+
+    #[test]
+    fn a_member_with_two_profiles_is_one_entry_led_by_their_main() {
+        let snapshots = vec![
+            snapshot(10, 1, 1000, 100, "gold_3"),
+            snapshot(10, 7, 1050, 110, "gold_3"),
+            // The alt climbed further and is rated higher, but 10 is the main.
+            snapshot(11, 1, 1100, 40, "platinum_1"),
+            snapshot(11, 7, 1300, 60, "platinum_2"),
+            snapshot(20, 1, 900, 10, "silver_1"),
+            snapshot(20, 7, 920, 15, "silver_1"),
+        ];
+        let mut main = account(1, 10, None);
+        main.main = true;
+        let accounts = vec![main, account(1, 11, None), account(2, 20, None)];
+        let week = movements(&snapshots, &accounts);
+        assert_eq!(week.len(), 2);
+        assert_eq!(
+            (
+                week[0].user_id,
+                week[0].name.as_str(),
+                week[0].rating_change,
+                week[0].games
+            ),
+            (1, "p10", 50, 30)
+        );
+
+        let digest = compute(&snapshots, &accounts, &[], &[], at(1));
+        assert_eq!(
+            digest.climbers.iter().map(|m| m.user_id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            digest.most_games.iter().map(|m| m.user_id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn the_digest_ranks_each_section_and_skips_profiles_that_did_not_move_that_way() {
+        let snapshots = vec![
+            snapshot(10, 1, 1000, 100, "gold_3"),
+            snapshot(10, 7, 1120, 130, "platinum_1"),
+            snapshot(20, 1, 1300, 50, "platinum_2"),
+            snapshot(20, 7, 1250, 58, "platinum_2"),
+        ];
+        let accounts = vec![
+            account(1, 10, None),
+            account(2, 20, None),
+            // Member 2's second profile, bound this week: not a newcomer.
+            account(2, 21, Some(5)),
+            account(3, 30, Some(5)),
+        ];
+        let board = vec![
+            board_entry(1, "english"),
+            board_entry(2, "mongols"),
+            board_entry(3, "english"),
+        ];
+        // Counted in games, not in whose favourite it is: member 2's mongols
+        // outnumber everyone's english.
+        let board_civs = vec![
+            board_civ(1, "english", 30),
+            board_civ(1, "mongols", 10),
+            board_civ(2, "mongols", 80),
+            board_civ(3, "english", 20),
+        ];
+        let digest = compute(&snapshots, &accounts, &board, &board_civs, at(1));
+        assert_eq!(digest.climbers.iter().map(|m| m.user_id).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(digest.fallers.iter().map(|m| m.user_id).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(
+            digest.most_games.iter().map(|m| m.user_id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(digest.promotions.iter().map(|m| m.user_id).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(
+            digest.civs,
+            vec![("mongols".to_string(), 90), ("english".to_string(), 50)]
+        );
+        assert_eq!(digest.newcomers, vec![3]);
+
+        let post = render(&digest).unwrap();
+        assert!(post.contains("<@1> (p10) 1000 → 1120 (+120)"), "{post}");
+        assert!(post.contains("<@2> (p20) 1300 → 1250 (-50)"), "{post}");
+        assert!(
+            post.contains("<@1> (p10) 黃金3 / Gold 3 → 白金1 / Platinum 1"),
+            "{post}"
+        );
+        assert!(post.ends_with("**新綁定的成員 / Newly bound**\n<@3>"), "{post}");
+    }
+
+    #[test]
+    fn a_quiet_week_posts_nothing() {
+        assert_eq!(render(&compute(&[], &[], &[], &[], at(1))), None);
+    }
+
+    #[test]
+    fn a_crowd_of_newcomers_is_counted_past_the_cap() {
+        let accounts = (1..=20).map(|id| account(id, id * 10, Some(3))).collect::<Vec<_>>();
+        let post = render(&compute(&[], &accounts, &[], &[], at(1))).unwrap();
+        assert!(post.ends_with(" +5"), "{post}");
+        assert_eq!(post.matches("<@").count(), MAX_NEWCOMERS);
+    }
+
+    #[test]
+    fn a_crowd_of_promotions_is_counted_past_the_cap() {
+        let snapshots = (1..=12)
+            .flat_map(|id| {
+                [
+                    snapshot(id * 10, 1, 1000, 10, "gold_3"),
+                    snapshot(id * 10, 7, 1100, 20, "platinum_1"),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let accounts = (1..=12).map(|id| account(id, id * 10, None)).collect::<Vec<_>>();
+        let post = render(&compute(&snapshots, &accounts, &[], &[], at(1))).unwrap();
+        assert!(post.ends_with("…還有 2 人 / +2 more"), "{post}");
+        assert_eq!(post.matches("白金1 / Platinum 1").count(), MAX_PROMOTIONS);
+    }
+}
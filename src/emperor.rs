@@ -1,122 +1,407 @@
+//! The home guild's message reactions. What reacts to what lives in the
+//! `reaction_rules` table, managed with `/reaction add|remove|list`, so a new
+//! meme no longer needs a deploy.
+//!
+//! Reactions and the follow-up posts are throttled per channel and per member —
+//! the member a rule is aimed at, so a meme about someone is not repeated every
+//! time a different person brings them up — so a busy channel is not a wall of
+//! the same emoji, and members who asked with `/reactions off` are left out
+//! entirely.
+//!
+//! The rules and opt-outs are read on every message, so they are loaded and
+//! parsed once and kept in `ReactionRules` until `/reaction add|remove` or
+//! `/reactions off|on` changes them.
+
+use crate::db::{ReactionRule, list_reaction_opt_outs, list_reaction_rules, to_db_id};
 use crate::guilds::{Feature, Guilds};
 use rand::RngExt;
-use serenity::all::{EmojiId, Message, Reaction, ReactionType, Ready, UserId};
+use regex::Regex;
+use serenity::all::{ChannelId, Message, Reaction, ReactionType, Ready, UserId};
 use serenity::async_trait;
 use serenity::prelude::*;
-use tracing::info;
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+
+/// The longest substring or regex a rule may hold. Rules run on every message
+/// in the guild, so a pathological pattern costs on every one of them.
+const MAX_PATTERN_LEN: usize = 200;
+
+/// How soon the same channel, or the same member, can be reacted to again.
+const REACTION_CHANNEL_COOLDOWN: Duration = Duration::from_secs(10);
+const REACTION_USER_COOLDOWN: Duration = Duration::from_secs(60);
+/// The same for the follow-up posts, which are far noisier than a reaction.
+const FOLLOW_UP_CHANNEL_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+const FOLLOW_UP_USER_COOLDOWN: Duration = Duration::from_secs(30 * 60);
 
 pub(crate) struct Emperor {
     guilds: Guilds,
+    pool: SqlitePool,
+    rules: Arc<ReactionRules>,
+    reactions: Mutex<Cooldown>,
+    follow_ups: Mutex<Cooldown>,
 }
 
 impl Emperor {
-    pub(crate) fn new(guilds: Guilds) -> Self {
-        Self { guilds }
+    pub(crate) fn new(guilds: Guilds, pool: SqlitePool, rules: Arc<ReactionRules>) -> Self {
+        Self {
+            guilds,
+            pool,
+            rules,
+            reactions: Mutex::new(Cooldown::new(REACTION_CHANNEL_COOLDOWN, REACTION_USER_COOLDOWN)),
+            follow_ups: Mutex::new(Cooldown::new(FOLLOW_UP_CHANNEL_COOLDOWN, FOLLOW_UP_USER_COOLDOWN)),
+        }
+    }
+
+    /// Whether a follow-up may be posted now, starting its cooldown if so.
+    fn take_follow_up(&self, channel: ChannelId, target: UserId) -> bool {
+        let now = Instant::now();
+        let mut follow_ups = self.follow_ups.lock().unwrap();
+        if !follow_ups.ready(channel, target, now) {
+            return false;
+        }
+        follow_ups.mark(channel, target, now);
+        true
+    }
+}
+
+/// The reaction rules with their triggers parsed, and who opted out of them,
+/// loaded on the first message that needs them and kept until `invalidate`.
+/// One instance is shared between `Emperor` and the commands that change
+/// either table.
+#[derive(Default)]
+pub(crate) struct ReactionRules {
+    cached: Mutex<CachedRules>,
+}
+
+#[derive(Default)]
+struct CachedRules {
+    /// Bumped by every `invalidate`, so a load that raced one is not kept.
+    generation: u64,
+    loaded: Option<Arc<LoadedRules>>,
+}
+
+struct LoadedRules {
+    rules: Vec<(ReactionRule, Trigger)>,
+    opted_out: HashSet<i64>,
+}
+
+impl ReactionRules {
+    /// Drops what is cached; the next message reads both tables again.
+    pub(crate) fn invalidate(&self) {
+        let mut cached = self.cached.lock().unwrap();
+        cached.generation += 1;
+        cached.loaded = None;
+    }
+
+    async fn get(&self, pool: &SqlitePool) -> Result<Arc<LoadedRules>, sqlx::Error> {
+        let generation = {
+            let cached = self.cached.lock().unwrap();
+            if let Some(loaded) = &cached.loaded {
+                return Ok(loaded.clone());
+            }
+            cached.generation
+        };
+
+        let opted_out = list_reaction_opt_outs(pool).await?.into_iter().collect();
+
+        // A broken rule is logged once per load rather than once per message.
+        let rules = list_reaction_rules(pool)
+            .await?
+            .into_iter()
+            .filter_map(|rule| match Trigger::parse(&rule.kind, &rule.pattern) {
+                Ok(trigger) => Some((rule, trigger)),
+                Err(err) => {
+                    error!("reaction rule #{} is broken: {err}", rule.id);
+                    None
+                },
+            })
+            .collect();
+        let loaded = Arc::new(LoadedRules { rules, opted_out });
+
+        let mut cached = self.cached.lock().unwrap();
+        if cached.generation == generation {
+            cached.loaded = Some(loaded.clone());
+        }
+        Ok(loaded)
+    }
+}
+
+/// When a channel and a member were last reacted to. In memory only: a restart
+/// forgetting them costs at most one early reaction.
+struct Cooldown {
+    per_channel: Duration,
+    per_user: Duration,
+    channels: HashMap<ChannelId, Instant>,
+    users: HashMap<UserId, Instant>,
+}
+
+impl Cooldown {
+    fn new(per_channel: Duration, per_user: Duration) -> Self {
+        Self {
+            per_channel,
+            per_user,
+            channels: HashMap::new(),
+            users: HashMap::new(),
+        }
+    }
+
+    /// Whether neither `channel` nor `user` is cooling down at `now`.
+    fn ready(&self, channel: ChannelId, user: UserId, now: Instant) -> bool {
+        self.channel_ready(channel, now) && cooled(self.users.get(&user), self.per_user, now)
+    }
+
+    fn channel_ready(&self, channel: ChannelId, now: Instant) -> bool {
+        cooled(self.channels.get(&channel), self.per_channel, now)
+    }
+
+    /// Starts both cooldowns, and forgets any that have run out so the maps
+    /// stay as small as the set of recently active channels and members.
+    fn mark(&mut self, channel: ChannelId, user: UserId, now: Instant) {
+        let (per_channel, per_user) = (self.per_channel, self.per_user);
+        self.channels.retain(|_, last| now - *last < per_channel);
+        self.users.retain(|_, last| now - *last < per_user);
+        self.channels.insert(channel, now);
+        self.users.insert(user, now);
+    }
+}
+
+fn cooled(last: Option<&Instant>, cooldown: Duration, now: Instant) -> bool {
+    last.is_none_or(|last| now - *last >= cooldown)
+}
+
+/// When a rule fires, parsed from its `kind` and `pattern` columns.
+#[derive(Debug)]
+pub(crate) enum Trigger {
+    Author(UserId),
+    Mention(UserId),
+    Substring(String),
+    Regex(Regex),
+}
+
+impl Trigger {
+    /// The `Err` is what to tell whoever typed the pattern.
+    pub(crate) fn parse(kind: &str, pattern: &str) -> Result<Self, String> {
+        match kind {
+            "author" | "mention" => {
+                let id = pattern
+                    .trim()
+                    .trim_start_matches("<@")
+                    .trim_start_matches('!')
+                    .trim_end_matches('>')
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|id| *id != 0)
+                    .ok_or_else(|| format!("{pattern:?} 不是成員或成員 id"))?;
+                let id = UserId::new(id);
+                Ok(if kind == "author" {
+                    Trigger::Author(id)
+                } else {
+                    Trigger::Mention(id)
+                })
+            },
+            "substring" | "regex" if pattern.is_empty() => Err("文字不能是空的".to_string()),
+            "substring" | "regex" if pattern.chars().count() > MAX_PATTERN_LEN => {
+                Err(format!("文字最多 {MAX_PATTERN_LEN} 個字"))
+            },
+            "substring" => Ok(Trigger::Substring(pattern.to_string())),
+            "regex" => Regex::new(pattern)
+                .map(Trigger::Regex)
+                .map_err(|err| format!("不是有效的正規表示式: {err}")),
+            _ => Err(format!("{kind:?} 不是觸發方式")),
+        }
+    }
+
+    /// The column value `parse` reads back, normalized — a mention is stored
+    /// as the bare id.
+    pub(crate) fn pattern(&self) -> String {
+        match self {
+            Trigger::Author(id) | Trigger::Mention(id) => id.to_string(),
+            Trigger::Substring(text) => text.clone(),
+            Trigger::Regex(regex) => regex.as_str().to_string(),
+        }
+    }
+
+    /// Who a reaction by this rule is about, for the per-member cooldown: the
+    /// member a mention rule names, and otherwise whoever wrote the message.
+    fn target(&self, author: UserId) -> UserId {
+        match self {
+            Trigger::Mention(id) => *id,
+            Trigger::Author(_) | Trigger::Substring(_) | Trigger::Regex(_) => author,
+        }
+    }
+
+    fn matches(&self, author: UserId, content: &str, mentions: &[UserId]) -> bool {
+        match self {
+            Trigger::Author(id) => author == *id,
+            Trigger::Mention(id) => mentions.contains(id),
+            Trigger::Substring(text) => content.contains(text.as_str()),
+            Trigger::Regex(regex) => regex.is_match(content),
+        }
+    }
+}
+
+/// A unicode emoji, or a custom one as Discord writes it: `<:name:id>`.
+pub(crate) fn parse_emoji(emoji: &str) -> Result<ReactionType, String> {
+    let emoji = emoji.trim();
+    // Anything not starting with `<` converts as "unicode", so a word would be
+    // accepted and only fail once Discord is asked to react with it.
+    let looks_unicode = emoji.chars().count() <= 8 && !emoji.chars().any(|c| c.is_alphanumeric() || c.is_whitespace());
+    match ReactionType::try_from(emoji) {
+        Ok(custom @ ReactionType::Custom { .. }) => Ok(custom),
+        Ok(unicode) if looks_unicode => Ok(unicode),
+        _ => Err(format!("{emoji:?} 不是表情符號")),
+    }
+}
+
+/// One line of `/reaction list`.
+pub(crate) fn describe(rule: &ReactionRule) -> String {
+    let trigger = match rule.kind.as_str() {
+        "author" => format!("<@{}> 發言", rule.pattern),
+        "mention" => format!("提到 <@{}>", rule.pattern),
+        "substring" => format!("包含 `{}`", rule.pattern),
+        _ => format!("符合 `{}`", rule.pattern),
+    };
+    let group = match &rule.draw_group {
+        Some(group) => format!("，與 `{group}` 組內規則擇一"),
+        None => String::new(),
+    };
+    format!(
+        "`#{}` {trigger} → {} ({}%{group}，被封鎖時 {}% 改發訊息)",
+        rule.id,
+        rule.emoji,
+        percent(rule.probability),
+        percent(rule.follow_up_chance)
+    )
+}
+
+/// Which rule of each draw group reacts to a message, given the rules it
+/// matched in order. See `draw`.
+fn draw_groups(matched: &[&ReactionRule]) -> HashSet<i64> {
+    let mut groups: HashMap<&str, Vec<&ReactionRule>> = HashMap::new();
+    for rule in matched {
+        if let Some(group) = &rule.draw_group {
+            groups.entry(group).or_default().push(rule);
+        }
     }
+    groups
+        .into_values()
+        .filter_map(|rules| draw(&rules, rand::rng().random::<f64>()))
+        .collect()
+}
 
-    fn select_emoji() -> ReactionType {
-        let num = rand::rng().random_range(0..100);
-        if num == 0 {
-            ReactionType::from('🍄')
-        } else if num <= 10 {
-            ReactionType::from('🐷')
-        } else {
-            ReactionType::from(EmojiId::new(1299285258457448522))
+/// The one rule of a draw group that reacts, for a `roll` in `0..1`: each rule
+/// takes its probability as its share of the range, in order, and a roll past
+/// all of them draws none. An emoji matched by several rules of the group —
+/// the same meme by name and by mention — takes only its first rule's share,
+/// so matching twice does not make it likelier.
+fn draw(rules: &[&ReactionRule], roll: f64) -> Option<i64> {
+    let mut seen = HashSet::new();
+    let mut edge = 0.0;
+    for rule in rules {
+        if !seen.insert(rule.emoji.as_str()) {
+            continue;
+        }
+        edge += rule.probability.clamp(0.0, 1.0);
+        if roll < edge {
+            return Some(rule.id);
         }
     }
+    None
+}
+
+fn percent(chance: f64) -> String {
+    format!("{}", (chance * 1000.0).round() / 10.0)
 }
 
 #[async_trait]
 impl EventHandler for Emperor {
     async fn message(&self, ctx: Context, new_message: Message) {
-        // Home guild only. This handler matches on user ids and keywords with no
-        // notion of where it is, so without the guard it would start reacting in the
-        // tournament guild the moment the bot joins.
+        // Home guild only. The rules match on user ids and keywords with no
+        // notion of where they are, so without the guard the bot would start
+        // reacting in the tournament guild the moment it joins.
         if !self.guilds.allows(Feature::Home, new_message.guild_id) {
             return;
         }
 
-        let emperor = UserId::new(453010726311821322);
-        let emperor2 = UserId::new(1511740443132428328);
-        let knockgod = UserId::new(364796522396647424);
-        let baltune = UserId::new(202510973519527937);
-        let racoon = UserId::new(302663000463114242);
-        let author = new_message.author.id;
-        let content = &new_message.content;
-        let mut blocked = false;
-        if author == emperor
-            || author == emperor2
-            || content.contains("天子")
-            || content.contains("唱歌")
-            || new_message.mentions_user_id(emperor)
-            || new_message.mentions_user_id(emperor2)
-        {
-            blocked = Self::detect_blocked(new_message.react(&ctx.http, Emperor::select_emoji()).await);
-        }
-        if content.contains("那可")
-            || content.contains("納可")
-            || content.contains("knock")
-            || new_message.mentions_user_id(knockgod)
-        {
-            blocked = Self::detect_blocked(
-                new_message
-                    .react(&ctx.http, ReactionType::from(EmojiId::new(1264746593366839431)))
-                    .await,
-            );
-        }
-        if content.contains("平等院") || content.contains("海門城堡") {
-            blocked = Self::detect_blocked(
-                new_message
-                    .react(&ctx.http, ReactionType::from(EmojiId::new(1338936646615306250)))
-                    .await,
-            );
-        }
-        if content.contains("balt")
-            || content.contains("Balt")
-            || content.contains("包吞")
-            || new_message.mentions_user_id(baltune)
-        {
-            blocked = Self::detect_blocked(
-                new_message
-                    .react(&ctx.http, ReactionType::from(EmojiId::new(1264326708962525225)))
-                    .await,
-            );
-        }
-        if content.contains("城主")
-            || content.contains("成主")
-            || (content.contains("all") && content.contains("in"))
-            || content.contains("快攻")
-            || content.contains("試煉")
-            || content.contains("喝水")
-            || content.contains("諸葛弩")
-            || content.contains("議會廳")
-            || content.contains("競技場")
-            || content.contains("勝利塔")
-            || content.contains("衝車")
-            || content.contains("搓車")
-        {
-            blocked = Self::detect_blocked(new_message.react(&ctx.http, ReactionType::from('🦧')).await);
-        }
-        if content.contains("象") {
-            blocked = Self::detect_blocked(new_message.react(&ctx.http, ReactionType::from('🐘')).await);
-        }
-        if author == racoon {
-            blocked = Self::detect_blocked(new_message.react(&ctx.http, ReactionType::from('🦝')).await);
-        }
-
-        if blocked {
-            let num = rand::rng().random_range(0..10);
-            if num == 0 {
-                let channel = ctx
-                    .http
-                    .get_channel(new_message.channel_id)
-                    .await
-                    .unwrap()
-                    .guild()
-                    .unwrap();
-                channel.say(ctx.http, "<:emoji_93:1299285258457448522>").await.unwrap();
+        let (channel, author) = (new_message.channel_id, new_message.author.id);
+        if !self.reactions.lock().unwrap().channel_ready(channel, Instant::now()) {
+            return;
+        }
+
+        let loaded = match self.rules.get(&self.pool).await {
+            Ok(loaded) => loaded,
+            Err(err) => {
+                error!("could not load reaction rules: {err:?}");
+                return;
+            },
+        };
+        let opted_out = &loaded.opted_out;
+        if opted_out.contains(&to_db_id(author)) {
+            return;
+        }
+
+        // A member who opted out is not a target by mention either.
+        let mentions = new_message
+            .mentions
+            .iter()
+            .map(|user| user.id)
+            .filter(|id| !opted_out.contains(&to_db_id(*id)))
+            .collect::<Vec<_>>();
+        // Only rules aimed at someone not cooling down take part, draws included.
+        let matched = {
+            let reactions = self.reactions.lock().unwrap();
+            let now = Instant::now();
+            loaded
+                .rules
+                .iter()
+                .filter(|(_, trigger)| trigger.matches(author, &new_message.content, &mentions))
+                .map(|(rule, trigger)| (rule, trigger.target(author)))
+                .filter(|(_, target)| reactions.ready(channel, *target, now))
+                .collect::<Vec<_>>()
+        };
+        let drawn = draw_groups(&matched.iter().map(|(rule, _)| *rule).collect::<Vec<_>>());
+        // Several rules often share an emoji — the same meme by name and by
+        // mention — and one reaction of it is enough.
+        let mut reacted = HashSet::new();
+        let mut targets = HashSet::new();
+        let mut followed_up = false;
+        for (rule, target) in matched {
+            if reacted.contains(&rule.emoji) {
+                continue;
+            }
+            let fires = match rule.draw_group {
+                Some(_) => drawn.contains(&rule.id),
+                None => roll(rule.probability),
+            };
+            if !fires {
+                continue;
+            }
+            let Ok(emoji) = parse_emoji(&rule.emoji) else {
+                error!("reaction rule #{} has a bad emoji {:?}", rule.id, rule.emoji);
+                continue;
+            };
+            reacted.insert(rule.emoji.clone());
+            targets.insert(target);
+
+            let blocked = Self::detect_blocked(new_message.react(&ctx.http, emoji).await);
+            // At most one follow-up per message, whichever rule got blocked first.
+            if blocked && !followed_up && roll(rule.follow_up_chance) && self.take_follow_up(channel, target) {
+                followed_up = true;
+                if let Err(err) = channel.say(&ctx.http, &rule.emoji).await {
+                    error!("could not post the follow-up for reaction rule #{}: {err:?}", rule.id);
+                }
             }
         }
+        // Every reaction on one message counts as one: the cooldown is between
+        // messages, not between emoji.
+        let mut reactions = self.reactions.lock().unwrap();
+        let now = Instant::now();
+        for target in targets {
+            reactions.mark(channel, target, now);
+        }
     }
 
     async fn ready(&self, _: Context, ready: Ready) {
@@ -124,6 +409,10 @@ impl EventHandler for Emperor {
     }
 }
 
+fn roll(chance: f64) -> bool {
+    rand::rng().random_bool(chance.clamp(0.0, 1.0))
+}
+
 impl Emperor {
     fn detect_blocked(result: serenity::Result<Reaction>) -> bool {
         match result {
@@ -143,8 +432,129 @@ impl Emperor {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    const AUTHOR: UserId = UserId::new(1);
+    const OTHER: UserId = UserId::new(2);
+
     #[test]
     fn test_contains() {
         assert!(String::from("比那明居天子").contains("天子"))
     }
+
+    #[test]
+    fn members_are_taken_as_ids_or_mentions() {
+        assert!(matches!(Trigger::parse("author", "1"), Ok(Trigger::Author(AUTHOR))));
+        assert!(matches!(
+            Trigger::parse("mention", "<@!2>"),
+            Ok(Trigger::Mention(OTHER))
+        ));
+        assert_eq!(Trigger::parse("mention", "<@2>").unwrap().pattern(), "2");
+        assert!(Trigger::parse("author", "emperor").is_err());
+        assert!(Trigger::parse("author", "0").is_err());
+    }
+
+    #[test]
+    fn each_kind_matches_its_own_part_of_the_message() {
+        let author = Trigger::parse("author", "1").unwrap();
+        assert!(author.matches(AUTHOR, "", &[]));
+        assert!(!author.matches(OTHER, "", &[AUTHOR]));
+
+        let mention = Trigger::parse("mention", "1").unwrap();
+        assert!(mention.matches(OTHER, "", &[AUTHOR]));
+        assert!(!mention.matches(AUTHOR, "", &[]));
+
+        let substring = Trigger::parse("substring", "天子").unwrap();
+        assert!(substring.matches(OTHER, "比那明居天子", &[]));
+
+        let regex = Trigger::parse("regex", "(?s)all.*in|in.*all").unwrap();
+        assert!(regex.matches(OTHER, "in, then all", &[]));
+        assert!(!regex.matches(OTHER, "all", &[]));
+    }
+
+    #[test]
+    fn a_mention_rule_is_aimed_at_the_member_it_names() {
+        assert_eq!(Trigger::parse("mention", "2").unwrap().target(AUTHOR), OTHER);
+        assert_eq!(Trigger::parse("author", "1").unwrap().target(AUTHOR), AUTHOR);
+        assert_eq!(Trigger::parse("substring", "天子").unwrap().target(AUTHOR), AUTHOR);
+    }
+
+    #[test]
+    fn bad_patterns_are_refused() {
+        assert!(Trigger::parse("regex", "(").is_err());
+        assert!(Trigger::parse("substring", "").is_err());
+        assert!(Trigger::parse("substring", &"x".repeat(MAX_PATTERN_LEN + 1)).is_err());
+        assert!(Trigger::parse("nickname", "x").is_err());
+    }
+
+    #[test]
+    fn a_cooldown_holds_the_channel_and_the_member_separately() {
+        let mut cooldown = Cooldown::new(Duration::from_secs(10), Duration::from_secs(60));
+        let (here, there) = (ChannelId::new(1), ChannelId::new(2));
+        let start = Instant::now();
+        assert!(cooldown.ready(here, AUTHOR, start));
+
+        cooldown.mark(here, AUTHOR, start);
+        let later = start + Duration::from_secs(20);
+        assert!(!cooldown.ready(here, AUTHOR, later), "the member is still cooling down");
+        assert!(cooldown.ready(here, OTHER, later), "the channel has cooled down");
+        assert!(
+            !cooldown.ready(there, AUTHOR, later),
+            "the member cools down everywhere"
+        );
+        assert!(!cooldown.ready(here, OTHER, start + Duration::from_secs(5)));
+        assert!(cooldown.ready(here, AUTHOR, start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn a_cooldown_forgets_what_has_run_out() {
+        let mut cooldown = Cooldown::new(Duration::from_secs(10), Duration::from_secs(10));
+        let start = Instant::now();
+        cooldown.mark(ChannelId::new(1), AUTHOR, start);
+        cooldown.mark(ChannelId::new(2), OTHER, start + Duration::from_secs(30));
+        assert_eq!(cooldown.channels.len(), 1);
+        assert_eq!(cooldown.users.len(), 1);
+    }
+
+    #[test]
+    fn emoji_are_unicode_or_custom() {
+        assert_eq!(parse_emoji("🦧"), Ok(ReactionType::from('🦧')));
+        assert!(matches!(
+            parse_emoji("<:emoji_93:1299285258457448522>"),
+            Ok(ReactionType::Custom { .. })
+        ));
+        assert!(parse_emoji("pig").is_err());
+        assert!(parse_emoji("<:pig>").is_err());
+        assert!(parse_emoji("").is_err());
+    }
+
+    fn rule(id: i64, emoji: &str, probability: f64) -> ReactionRule {
+        ReactionRule {
+            id,
+            kind: "substring".to_string(),
+            pattern: "天子".to_string(),
+            emoji: emoji.to_string(),
+            probability,
+            follow_up_chance: 0.0,
+            draw_group: Some("emperor".to_string()),
+        }
+    }
+
+    #[test]
+    fn a_draw_group_reacts_with_one_rule_by_share() {
+        let (mushroom, pig, usual) = (rule(1, "🍄", 0.01), rule(2, "🐷", 0.1), rule(3, "<:e:1>", 0.89));
+        let rules = [&mushroom, &pig, &usual];
+        assert_eq!(draw(&rules, 0.005), Some(1));
+        assert_eq!(draw(&rules, 0.05), Some(2));
+        assert_eq!(draw(&rules, 0.5), Some(3));
+
+        let rare = [&mushroom, &pig];
+        assert_eq!(draw(&rare, 0.5), None, "a roll past every share draws nothing");
+    }
+
+    #[test]
+    fn an_emoji_matched_twice_in_a_draw_takes_one_share() {
+        let (by_name, by_mention, pig) = (rule(1, "🍄", 0.1), rule(2, "🍄", 0.1), rule(3, "🐷", 0.1));
+        assert_eq!(draw(&[&by_name, &by_mention, &pig], 0.15), Some(3));
+    }
 }
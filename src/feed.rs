@@ -0,0 +1,381 @@
+//! The game result feed: polls every bound profile's ranked solo games and posts
+//! what is worth a line in `GAME_FEED_CHANNEL_ID` — a tier promotion or
+//! demotion, a new personal best, every fifth win in a row, and a win over
+//! another member of the server.
+//!
+//! Where each profile was read up to lives in `game_feed_state`, so a poll only
+//! asks aoe4world for games `since` then. A profile seen for the first time is
+//! recorded without posting: its past is the baseline, not news.
+
+use crate::aoe4world::{Game, Profile, fetch_games, fetch_profile, rank_level_order, rank_level_zh};
+use crate::db::{FeedState, get_feed_state, list_all, save_feed_state, to_user_id};
+use crate::ranked::escape;
+use crate::{Data, Error};
+use chrono::Utc;
+use serenity::all::{ChannelId, CreateAllowedMentions, CreateMessage, Http, Mentionable};
+use std::collections::HashMap;
+use tracing::{error, info};
+
+/// A streak is announced at every multiple of this many wins.
+const STREAK_STEP: i64 = 5;
+
+fn channel() -> Option<ChannelId> {
+    resolve_channel(std::env::var("GAME_FEED_CHANNEL_ID").ok().as_deref())
+}
+
+/// Pure, like `refresh::resolve_boards`. Unset means the feed is off; so does a
+/// value that is not a channel id, which is logged.
+fn resolve_channel(value: Option<&str>) -> Option<ChannelId> {
+    let value = value.map(str::trim).filter(|value| !value.is_empty())?;
+    match value.parse::<u64>().ok().filter(|id| *id != 0) {
+        Some(id) => Some(ChannelId::new(id)),
+        None => {
+            error!("GAME_FEED_CHANNEL_ID {value:?} is not a channel id; the game feed is off");
+            None
+        },
+    }
+}
+
+/// Something one profile did that the feed posts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Event {
+    Promoted {
+        from: String,
+        to: String,
+    },
+    Demoted {
+        from: String,
+        to: String,
+    },
+    PersonalBest {
+        rating: i64,
+    },
+    WinStreak {
+        wins: i64,
+    },
+    BeatMember {
+        opponent: i64,
+        opponent_name: String,
+        map: String,
+    },
+}
+
+fn line(user_id: i64, name: &str, event: &Event) -> String {
+    let who = format!("{} ({})", to_user_id(user_id).mention(), escape(name));
+    match event {
+        Event::Promoted { from, to } => format!(
+            "🎉 {who} 升上了 {}！({} → {})",
+            rank_level_zh(to),
+            rank_level_zh(from),
+            rank_level_zh(to)
+        ),
+        Event::Demoted { from, to } => format!(
+            "📉 {who} 掉到了 {} ({} → {})",
+            rank_level_zh(to),
+            rank_level_zh(from),
+            rank_level_zh(to)
+        ),
+        Event::PersonalBest { rating } => format!("🏆 {who} 創下個人最高積分 {rating}！"),
+        Event::WinStreak { wins } => format!("🔥 {who} 已經 {wins} 連勝！"),
+        Event::BeatMember {
+            opponent,
+            opponent_name,
+            map,
+        } => format!(
+            "⚔️ {who} 在 {} 擊敗了 {} ({})",
+            escape(map),
+            to_user_id(*opponent).mention(),
+            escape(opponent_name)
+        ),
+    }
+}
+
+/// Folds `games` into `state`, oldest first, and returns what they made worth
+/// posting. Games already read, and a game still being played, are skipped —
+/// the latter without moving `state` past it, so it is read once it ends.
+///
+/// `members` maps every bound profile to its Discord user, which is how a win
+/// over another member is recognised; a win over one's own alt does not count.
+fn advance(state: &mut FeedState, user_id: i64, games: &[Game], members: &HashMap<i64, i64>) -> Vec<Event> {
+    let mut fresh = games
+        .iter()
+        .filter(|game| !game.ongoing)
+        .filter(|game| {
+            game.started_at > state.last_game_at
+                || (game.started_at == state.last_game_at && state.last_game_id != Some(game.game_id))
+        })
+        .collect::<Vec<&Game>>();
+    fresh.sort_by_key(|game| (game.started_at, game.game_id));
+
+    let mut events = Vec::new();
+    let mut new_best = None;
+    for game in fresh {
+        let Some(me) = game.player(state.aoe4_id) else {
+            continue;
+        };
+        if me.won() {
+            state.win_streak += 1;
+            if state.win_streak % STREAK_STEP == 0 {
+                events.push(Event::WinStreak { wins: state.win_streak });
+            }
+            for opponent in game.opponents(state.aoe4_id) {
+                if let Some(&opponent_user) = members.get(&opponent.profile_id)
+                    && opponent_user != user_id
+                {
+                    events.push(Event::BeatMember {
+                        opponent: opponent_user,
+                        opponent_name: opponent.name.clone(),
+                        map: game.map.clone(),
+                    });
+                }
+            }
+        } else if me.lost() {
+            // Only a loss: a game aoe4world finished without a result for us —
+            // an abandoned lobby, a desync — neither extends nor ends the streak.
+            state.win_streak = 0;
+        }
+        if let Some(after) = me.rating_after().map(i64::from)
+            && after > state.best_rating
+        {
+            state.best_rating = after;
+            new_best = Some(after);
+        }
+        state.last_game_at = game.started_at;
+        state.last_game_id = Some(game.game_id);
+    }
+    // Once per poll, at the peak: three wins in a row are one record, not three.
+    if let Some(rating) = new_best {
+        events.push(Event::PersonalBest { rating });
+    }
+    events
+}
+
+/// Compares the tier aoe4world reports now with the one last seen. Moving to or
+/// from `unranked` is not a promotion or a demotion — placement and decay are
+/// not news — but the new level is still remembered.
+fn tier_change(state: &mut FeedState, rank_level: &str) -> Option<Event> {
+    let from = std::mem::replace(&mut state.rank_level, rank_level.to_string());
+    let (Some(before), Some(after)) = (rank_level_order(&from), rank_level_order(rank_level)) else {
+        return None;
+    };
+    let to = rank_level.to_string();
+    match after.cmp(&before) {
+        std::cmp::Ordering::Greater => Some(Event::Promoted { from, to }),
+        std::cmp::Ordering::Less => Some(Event::Demoted { from, to }),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// The first state for a profile the feed has not seen before.
+fn baseline(aoe4_id: i64, profile: &Profile) -> Option<FeedState> {
+    let solo = profile.modes.rm_solo.as_ref()?;
+    Some(FeedState {
+        aoe4_id,
+        last_game_at: solo.last_game_at.unwrap_or_else(Utc::now),
+        last_game_id: None,
+        rank_level: solo.rank_level.clone(),
+        best_rating: solo.max_rating.or(solo.rating).map_or(0, i64::from),
+        win_streak: 0,
+    })
+}
+
+/// One poll over every bound profile. Best-effort, like `reminder::run`: a
+/// profile that can't be read this time is read next time, from where it left
+/// off.
+pub(crate) async fn run(http: &Http, data: &Data) {
+    let Some(channel) = channel() else {
+        return;
+    };
+    if let Err(err) = poll(http, data, channel).await {
+        error!("game feed failed: {err:?}");
+    }
+}
+
+async fn poll(http: &Http, data: &Data, channel: ChannelId) -> Result<(), Error> {
+    let pool = &data.database;
+    let accounts = list_all(pool).await?;
+    let members = accounts
+        .iter()
+        .map(|account| (account.aoe4_id, account.user_id))
+        .collect::<HashMap<i64, i64>>();
+
+    let mut posted = 0;
+    for account in &accounts {
+        let Ok(profile) = fetch_profile(account.aoe4_id).await else {
+            continue;
+        };
+        // A database error ends this profile's turn, not the poll.
+        let stored = match get_feed_state(pool, account.aoe4_id).await {
+            Ok(stored) => stored,
+            Err(err) => {
+                error!("could not read the game feed state of {}: {err:?}", account.aoe4_id);
+                continue;
+            },
+        };
+        let Some(mut state) = stored else {
+            if let Some(state) = baseline(account.aoe4_id, &profile)
+                && let Err(err) = save_feed_state(pool, &state).await
+            {
+                error!("could not save the game feed baseline of {}: {err:?}", account.aoe4_id);
+            }
+            continue;
+        };
+        let Ok(page) = fetch_games(account.aoe4_id, Some(state.last_game_at)).await else {
+            continue;
+        };
+
+        let mut events = advance(&mut state, account.user_id, &page.games, &members);
+        if let Some(solo) = &profile.modes.rm_solo {
+            events.extend(tier_change(&mut state, &solo.rank_level));
+        }
+        for event in &events {
+            // Names, not pings: the feed is a ticker, not a summons.
+            let message = CreateMessage::new()
+                .content(line(account.user_id, &profile.name, event))
+                .allowed_mentions(CreateAllowedMentions::new());
+            match channel.send_message(http, message).await {
+                Ok(_) => posted += 1,
+                Err(err) => error!("could not post a game feed line: {err:?}"),
+            }
+        }
+        if let Err(err) = save_feed_state(pool, &state).await {
+            error!("could not save the game feed state of {}: {err:?}", account.aoe4_id);
+        }
+    }
+    info!("game feed posted {posted} lines");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoe4world::GamesPage;
+    use chrono::{DateTime, Utc};
+
+    const ME: i64 = 13753974;
+    const MY_USER: i64 = 1;
+
+    fn games() -> Vec<Game> {
+        serde_json::from_str::<GamesPage>(include_str!("aoe4world/testdata/player_games.json"))
+            .unwrap()
+            .games
+    }
+
+    fn state(last_game_at: &str) -> FeedState {
+        FeedState {
+            aoe4_id: ME,
+            last_game_at: last_game_at.parse::<DateTime<Utc>>().unwrap(),
+            last_game_id: None,
+            rank_level: "diamond_2".to_string(),
+            best_rating: 1500,
+            win_streak: 0,
+        }
+    }
+
+    #[test]
+    fn only_games_after_the_last_one_read_are_counted() {
+        let mut state = state("2026-10-13T00:00:00Z");
+        advance(&mut state, MY_USER, &games(), &HashMap::new());
+        assert_eq!(state.win_streak, 1);
+        assert_eq!(state.last_game_id, Some(180000003));
+    }
+
+    #[test]
+    fn a_game_without_a_result_leaves_the_streak_alone() {
+        let mut games = games();
+        for game in &mut games {
+            for seat in game.teams.iter_mut().flatten() {
+                if seat.player.profile_id == ME {
+                    seat.player.result = None;
+                }
+            }
+        }
+        let mut state = state("2026-10-13T00:00:00Z");
+        state.win_streak = 2;
+        advance(&mut state, MY_USER, &games, &HashMap::new());
+        assert_eq!(state.win_streak, 2);
+        assert_eq!(state.last_game_id, Some(180000003), "still read past");
+    }
+
+    #[test]
+    fn a_game_still_being_played_is_left_for_the_next_poll() {
+        let mut state = state("2026-10-01T00:00:00Z");
+        advance(&mut state, MY_USER, &games(), &HashMap::new());
+        assert_eq!(state.last_game_id, Some(180000003), "not the ongoing 180000004");
+    }
+
+    #[test]
+    fn reading_the_same_page_twice_changes_nothing() {
+        let mut state = state("2026-10-01T00:00:00Z");
+        advance(&mut state, MY_USER, &games(), &HashMap::new());
+        let once = state.clone();
+        assert!(advance(&mut state, MY_USER, &games(), &HashMap::new()).is_empty());
+        assert_eq!(state, once);
+    }
+
+    #[test]
+    fn every_fifth_win_in_a_row_is_announced() {
+        let mut state = state("2026-10-01T00:00:00Z");
+        state.win_streak = 3;
+        let events = advance(&mut state, MY_USER, &games(), &HashMap::new());
+        assert_eq!(events, vec![Event::WinStreak { wins: 5 }]);
+    }
+
+    #[test]
+    fn a_win_over_another_member_is_announced_but_not_over_an_alt() {
+        let members = HashMap::from([(ME, MY_USER), (7008236, 2)]);
+        let mut state = state("2026-10-13T00:00:00Z");
+        let events = advance(&mut state, MY_USER, &games(), &members);
+        assert_eq!(
+            events,
+            vec![Event::BeatMember {
+                opponent: 2,
+                opponent_name: "jumpman".to_string(),
+                map: "Dry Arabia".to_string(),
+            }]
+        );
+
+        let alt = HashMap::from([(ME, MY_USER), (7008236, MY_USER)]);
+        let mut state = self::state("2026-10-13T00:00:00Z");
+        assert!(advance(&mut state, MY_USER, &games(), &alt).is_empty());
+    }
+
+    #[test]
+    fn a_new_best_is_announced_once_at_its_peak() {
+        let mut state = state("2026-10-01T00:00:00Z");
+        state.best_rating = 1400;
+        let events = advance(&mut state, MY_USER, &games(), &HashMap::new());
+        assert_eq!(events, vec![Event::PersonalBest { rating: 1414 }]);
+        assert_eq!(state.best_rating, 1414);
+    }
+
+    #[test]
+    fn tier_moves_are_promotions_or_demotions_but_unranked_is_neither() {
+        let mut state = state("2026-10-01T00:00:00Z");
+        assert_eq!(
+            tier_change(&mut state, "diamond_3"),
+            Some(Event::Promoted {
+                from: "diamond_2".to_string(),
+                to: "diamond_3".to_string()
+            })
+        );
+        assert_eq!(
+            tier_change(&mut state, "platinum_3"),
+            Some(Event::Demoted {
+                from: "diamond_3".to_string(),
+                to: "platinum_3".to_string()
+            })
+        );
+        assert_eq!(tier_change(&mut state, "platinum_3"), None);
+        assert_eq!(tier_change(&mut state, "unranked"), None);
+        assert_eq!(tier_change(&mut state, "gold_1"), None);
+        assert_eq!(state.rank_level, "gold_1");
+    }
+
+    #[test]
+    fn the_feed_is_off_without_a_channel() {
+        assert_eq!(resolve_channel(None), None);
+        assert_eq!(resolve_channel(Some("feed")), None);
+        assert_eq!(resolve_channel(Some(" 42 ")), Some(ChannelId::new(42)));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::locale::Locale;
 use crate::reply::ephemeral;
+use crate::tournament::access::manage_guild_for;
 use crate::{Context, Error};
 use serenity::model::id::{GuildId, RoleId};
 
@@ -112,6 +113,38 @@ pub(crate) async fn tournament_only(ctx: Context<'_>) -> Result<bool, Error> {
     allowed_here(ctx, Feature::Tournament).await
 }
 
+/// Command check for the home guild's admin commands: `home_only`, and the
+/// caller must hold `MANAGE_GUILD` there. The home guild has no organizer role
+/// or per-command admin list the way tournaments do, so the guild permission
+/// is the whole rule.
+pub(crate) async fn home_admin_only(ctx: Context<'_>) -> Result<bool, Error> {
+    if !allowed_here(ctx, Feature::Home).await? {
+        return Ok(false);
+    }
+    if is_home_admin(ctx).await? {
+        return Ok(true);
+    }
+    ephemeral(
+        ctx,
+        Locale::from_context(ctx).pick(
+            "只有擁有管理伺服器權限的成員才能使用這個指令。",
+            "Only members with Manage Guild can use this command.",
+        ),
+    )
+    .await?;
+    Ok(false)
+}
+
+/// Whether the caller holds `MANAGE_GUILD` where they are — what makes them an
+/// admin in the home guild, for a command open to everyone that does a little
+/// more for admins.
+pub(crate) async fn is_home_admin(ctx: Context<'_>) -> Result<bool, Error> {
+    match (ctx.guild_id(), ctx.author_member().await) {
+        (Some(guild_id), Some(member)) => manage_guild_for(ctx.http(), guild_id, &member).await,
+        _ => Ok(false),
+    }
+}
+
 async fn allowed_here(ctx: Context<'_>, feature: Feature) -> Result<bool, Error> {
     if ctx.data().guilds.allows(feature, ctx.guild_id()) {
         return Ok(true);
@@ -0,0 +1,279 @@
+//! `/h2h`: the record between two players, from every game aoe4world has of
+//! them facing each other.
+//!
+//! Either side may be a Discord member with several bound profiles, so a side
+//! is a set of profile ids and every pairing across the two sets is fetched.
+//! Summarizing is pure and kept apart from the fetching, so it is testable
+//! against hand-built games.
+
+use crate::aoe4world::{ApiError, Game, GamePlayer, civilization_zh, fetch_head_to_head};
+use crate::ranked::escape;
+use std::collections::{HashMap, HashSet};
+
+/// How many of the most recent games are listed one by one.
+const RECENT_GAMES: usize = 5;
+/// How many maps and civilization matchups are listed, most played first.
+const TOP_MAPS: usize = 5;
+const TOP_MATCHUPS: usize = 8;
+
+/// One side of a head-to-head: a name to show and every profile it covers.
+pub(crate) struct Side {
+    pub name: String,
+    pub profile_ids: Vec<i64>,
+}
+
+/// Every game between any profile of `a` and any profile of `b`, newest first,
+/// each game once.
+pub(crate) async fn fetch(a: &Side, b: &Side) -> Result<Vec<Game>, ApiError> {
+    let mut seen = HashSet::new();
+    let mut games = Vec::new();
+    for &mine in &a.profile_ids {
+        for &theirs in &b.profile_ids {
+            for game in fetch_head_to_head(mine, theirs).await? {
+                if seen.insert(game.game_id) {
+                    games.push(game);
+                }
+            }
+        }
+    }
+    games.sort_by_key(|game| std::cmp::Reverse(game.started_at));
+    Ok(games)
+}
+
+/// What `/h2h` reports, seen from side `a`.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Summary {
+    pub a_wins: usize,
+    pub b_wins: usize,
+    /// Newest first.
+    pub recent: Vec<RecentGame>,
+    /// Most played first, then by name.
+    pub maps: Vec<(String, usize)>,
+    /// `(a's civ, b's civ)`, how many games, and how many `a` won.
+    pub matchups: Vec<((String, String), usize, usize)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct RecentGame {
+    pub date: String,
+    pub map: String,
+    pub a_civ: String,
+    pub b_civ: String,
+    pub a_won: bool,
+}
+
+impl Summary {
+    /// `games` newest first. A game without a result for both sides — still
+    /// going, or abandoned — is left out of everything.
+    pub(crate) fn of(games: &[Game], a: &Side, b: &Side) -> Self {
+        let mut summary = Summary::default();
+        let mut maps: HashMap<String, usize> = HashMap::new();
+        let mut matchups: HashMap<(String, String), (usize, usize)> = HashMap::new();
+
+        for game in games {
+            let (Some(mine), Some(theirs)) = (seat(game, a), seat(game, b)) else {
+                continue;
+            };
+            if mine.result.is_none() || theirs.result.is_none() {
+                continue;
+            }
+            let a_won = mine.won();
+            if a_won {
+                summary.a_wins += 1;
+            } else {
+                summary.b_wins += 1;
+            }
+            if summary.recent.len() < RECENT_GAMES {
+                summary.recent.push(RecentGame {
+                    date: game.started_at.format("%Y-%m-%d").to_string(),
+                    map: game.map.clone(),
+                    a_civ: mine.civilization.clone(),
+                    b_civ: theirs.civilization.clone(),
+                    a_won,
+                });
+            }
+            *maps.entry(game.map.clone()).or_default() += 1;
+            let matchup = matchups
+                .entry((mine.civilization.clone(), theirs.civilization.clone()))
+                .or_default();
+            matchup.0 += 1;
+            matchup.1 += usize::from(a_won);
+        }
+
+        summary.maps = maps.into_iter().collect();
+        summary.maps.sort_by(|x, y| y.1.cmp(&x.1).then_with(|| x.0.cmp(&y.0)));
+        summary.maps.truncate(TOP_MAPS);
+        summary.matchups = matchups
+            .into_iter()
+            .map(|(civs, (played, won))| (civs, played, won))
+            .collect();
+        summary
+            .matchups
+            .sort_by(|x, y| y.1.cmp(&x.1).then_with(|| x.0.cmp(&y.0)));
+        summary.matchups.truncate(TOP_MATCHUPS);
+        summary
+    }
+
+    pub(crate) fn games(&self) -> usize {
+        self.a_wins + self.b_wins
+    }
+
+    pub(crate) fn render(&self, a: &Side, b: &Side) -> String {
+        let (a_name, b_name) = (escape(&a.name), escape(&b.name));
+        let mut text = format!("**{a_name}** vs **{b_name}** 對戰紀錄\n");
+        text.push_str(&format!(
+            "總戰績: {a_name} {} 勝 - {} 勝 {b_name} ({a_name} 勝率 {}%)\n",
+            self.a_wins,
+            self.b_wins,
+            percent(self.a_wins, self.games())
+        ));
+
+        text.push_str("\n最近對戰:\n");
+        for game in &self.recent {
+            let winner = if game.a_won { &a_name } else { &b_name };
+            text.push_str(&format!(
+                "  {} {} — {} vs {} — {winner} 勝\n",
+                game.date,
+                escape(&game.map),
+                civilization_zh(&game.a_civ),
+                civilization_zh(&game.b_civ)
+            ));
+        }
+
+        let maps = self
+            .maps
+            .iter()
+            .map(|(map, played)| format!("{} ×{played}", escape(map)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        text.push_str(&format!("\n常見地圖: {maps}\n"));
+
+        text.push_str(&format!("\n文明對位 ({a_name} 勝率):\n"));
+        for ((a_civ, b_civ), played, won) in &self.matchups {
+            text.push_str(&format!(
+                "  {} vs {}: {played} 場, {}%\n",
+                civilization_zh(a_civ),
+                civilization_zh(b_civ),
+                percent(*won, *played)
+            ));
+        }
+        text
+    }
+}
+
+/// `side`'s player in `game`, whichever of its profiles that was.
+fn seat<'a>(game: &'a Game, side: &Side) -> Option<&'a GamePlayer> {
+    game.players()
+        .find(|player| side.profile_ids.contains(&player.profile_id))
+}
+
+fn percent(part: usize, whole: usize) -> usize {
+    if whole == 0 {
+        return 0;
+    }
+    (part * 100 + whole / 2) / whole
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoe4world::GameSeat;
+    use chrono::{DateTime, Duration, Utc};
+
+    fn player(profile_id: i64, civilization: &str, result: Option<&str>) -> GameSeat {
+        GameSeat {
+            player: GamePlayer {
+                profile_id,
+                name: format!("p{profile_id}"),
+                result: result.map(str::to_string),
+                civilization: civilization.to_string(),
+                rating: None,
+                rating_diff: None,
+            },
+        }
+    }
+
+    /// A 1v1 on `map` between profiles `a` and `b`, `days` after the epoch.
+    fn game(id: i64, days: i64, map: &str, a: (i64, &str), b: (i64, &str), a_won: bool) -> Game {
+        let (a_result, b_result) = if a_won { ("win", "loss") } else { ("loss", "win") };
+        Game {
+            game_id: id,
+            started_at: DateTime::<Utc>::UNIX_EPOCH + Duration::days(days),
+            map: map.to_string(),
+            ongoing: false,
+            teams: vec![
+                vec![player(a.0, a.1, Some(a_result))],
+                vec![player(b.0, b.1, Some(b_result))],
+            ],
+        }
+    }
+
+    fn sides() -> (Side, Side) {
+        (
+            Side {
+                name: "A".to_string(),
+                profile_ids: vec![1, 11],
+            },
+            Side {
+                name: "B".to_string(),
+                profile_ids: vec![2],
+            },
+        )
+    }
+
+    #[test]
+    fn the_record_counts_every_profile_on_a_side() {
+        let (a, b) = sides();
+        let games = vec![
+            game(3, 3, "Arabia", (1, "english"), (2, "french"), true),
+            game(2, 2, "Arabia", (11, "mongols"), (2, "french"), true),
+            game(1, 1, "Lipany", (1, "english"), (2, "rus"), false),
+        ];
+        let summary = Summary::of(&games, &a, &b);
+        assert_eq!((summary.a_wins, summary.b_wins), (2, 1));
+        assert_eq!(summary.maps, vec![("Arabia".to_string(), 2), ("Lipany".to_string(), 1)]);
+        assert_eq!(summary.recent[0].date, "1970-01-04");
+        assert!(summary.recent[0].a_won);
+    }
+
+    #[test]
+    fn matchups_are_seen_from_the_first_side() {
+        let (a, b) = sides();
+        let games = vec![
+            game(2, 2, "Arabia", (1, "english"), (2, "french"), true),
+            game(1, 1, "Arabia", (1, "english"), (2, "french"), false),
+        ];
+        let summary = Summary::of(&games, &a, &b);
+        assert_eq!(
+            summary.matchups,
+            vec![(("english".to_string(), "french".to_string()), 2, 1)]
+        );
+        assert!(summary.render(&a, &b).contains("2 場, 50%"));
+    }
+
+    #[test]
+    fn a_game_without_a_result_is_left_out() {
+        let (a, b) = sides();
+        let mut unfinished = game(1, 1, "Arabia", (1, "english"), (2, "french"), true);
+        unfinished.teams[1][0].player.result = None;
+        assert_eq!(Summary::of(&[unfinished], &a, &b).games(), 0);
+    }
+
+    #[test]
+    fn only_the_most_recent_games_are_listed() {
+        let (a, b) = sides();
+        let games = (0..8)
+            .rev()
+            .map(|day| game(day, day, "Arabia", (1, "english"), (2, "french"), true))
+            .collect::<Vec<_>>();
+        let summary = Summary::of(&games, &a, &b);
+        assert_eq!(summary.games(), 8);
+        assert_eq!(summary.recent.len(), RECENT_GAMES);
+    }
+
+    #[test]
+    fn percentages_round_and_survive_no_games() {
+        assert_eq!(percent(2, 3), 67);
+        assert_eq!(percent(0, 0), 0);
+    }
+}
@@ -0,0 +1,267 @@
+//! Rating history: a snapshot of every bound profile on each refresh, and the
+//! trend chart `/history` draws from them.
+//!
+//! The chart goes through the bracket's image pipeline — an SVG document here,
+//! `tournament::bracket_raster` for the PNG — so a CJK profile name in the legend
+//! renders with the same bundled fonts the bracket uses, whatever the host has.
+
+use crate::Error;
+use crate::db::{RatingSnapshot, insert_snapshot};
+use crate::ranked::RankedPlayer;
+use crate::tournament::bracket_raster;
+use crate::tournament::bracket_svg::{BACKGROUND, FONT_FAMILY, FOREGROUND, STROKE, escape_xml};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use tracing::error;
+
+/// Keeps one row per player. Best-effort, like the rest of a refresh's side
+/// effects: a failed insert is logged and skipped rather than holding the board
+/// hostage to the history table.
+pub(crate) async fn record(pool: &SqlitePool, players: &[RankedPlayer], taken_at: DateTime<Utc>) {
+    for player in players {
+        let snapshot = player.snapshot(taken_at);
+        if let Err(err) = insert_snapshot(pool, &snapshot).await {
+            error!(
+                "failed to record a rating snapshot for aoe4 id {}: {err:?}",
+                snapshot.aoe4_id
+            );
+        }
+    }
+}
+
+/// One line on the chart: a profile's ratings, oldest first.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Series {
+    pub name: String,
+    pub points: Vec<(DateTime<Utc>, i64)>,
+}
+
+impl Series {
+    /// A profile's snapshots as a line, named after the most recent name it was
+    /// seen under. `None` for a profile with nothing recorded in the window.
+    pub(crate) fn from_snapshots(snapshots: &[RatingSnapshot]) -> Option<Self> {
+        let name = snapshots.last()?.aoe4_name.clone();
+        Some(Self {
+            name,
+            points: snapshots.iter().map(|s| (s.taken_at, s.rating)).collect(),
+        })
+    }
+}
+
+const WIDTH_PX: f64 = 800.0;
+const HEIGHT_PX: f64 = 420.0;
+/// Room for the title above the plot, the y labels to its left, the dates
+/// below it and the legend under those.
+const PLOT_LEFT: f64 = 64.0;
+const PLOT_RIGHT: f64 = 24.0;
+const PLOT_TOP: f64 = 48.0;
+const PLOT_BOTTOM: f64 = 84.0;
+const FONT_SIZE_PX: f64 = 14.0;
+const TITLE_SIZE_PX: f64 = 18.0;
+const Y_TICKS: usize = 5;
+const X_TICKS: usize = 4;
+/// Ratings move in tens; a flat line still gets this much headroom either side
+/// so it sits inside the plot rather than on its edge.
+const MIN_Y_SPAN: i64 = 20;
+
+/// One colour per profile, in the order they were bound. Chosen to stay
+/// distinguishable on `BACKGROUND`; a seventh alt wraps around.
+const PALETTE: [&str; 6] = ["#5865f2", "#f0b232", "#23a55a", "#f23f43", "#eb459e", "#00a8fc"];
+
+/// Maps `value` from `[lo, hi]` onto `[to_lo, to_hi]`. A zero-width domain maps
+/// to the middle rather than dividing by zero.
+fn scale(value: f64, lo: f64, hi: f64, to_lo: f64, to_hi: f64) -> f64 {
+    if hi <= lo {
+        return (to_lo + to_hi) / 2.0;
+    }
+    to_lo + (value - lo) / (hi - lo) * (to_hi - to_lo)
+}
+
+/// The y axis' range, padded to `MIN_Y_SPAN` and rounded out to tens so the tick
+/// labels are round numbers.
+fn y_range(series: &[Series]) -> (i64, i64) {
+    let ratings = series.iter().flat_map(|s| s.points.iter().map(|(_, rating)| *rating));
+    let (lo, hi) = ratings.fold((i64::MAX, i64::MIN), |(lo, hi), r| (lo.min(r), hi.max(r)));
+    let pad = (MIN_Y_SPAN - (hi - lo)).max(0) / 2;
+    let lo = (lo - pad).div_euclid(10) * 10;
+    let hi = (hi + pad + 9).div_euclid(10) * 10;
+    (lo, hi.max(lo + MIN_Y_SPAN))
+}
+
+/// Renders `series` as a line chart. `None` when there is not a single point to
+/// draw, which the caller words as "nothing recorded yet" rather than posting an
+/// empty frame.
+pub(crate) fn svg(title: &str, series: &[Series]) -> Option<String> {
+    let times = series
+        .iter()
+        .flat_map(|s| s.points.iter().map(|(at, _)| at.timestamp()));
+    let (t_lo, t_hi) = times.fold((i64::MAX, i64::MIN), |(lo, hi), t| (lo.min(t), hi.max(t)));
+    if t_lo > t_hi {
+        return None;
+    }
+    let (y_lo, y_hi) = y_range(series);
+
+    let (left, right) = (PLOT_LEFT, WIDTH_PX - PLOT_RIGHT);
+    let (top, bottom) = (PLOT_TOP, HEIGHT_PX - PLOT_BOTTOM);
+    let x = |t: i64| scale(t as f64, t_lo as f64, t_hi as f64, left, right);
+    let y = |r: i64| scale(r as f64, y_lo as f64, y_hi as f64, bottom, top);
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH_PX}\" height=\"{HEIGHT_PX}\" \
+         viewBox=\"0 0 {WIDTH_PX} {HEIGHT_PX}\"><rect width=\"{WIDTH_PX}\" height=\"{HEIGHT_PX}\" fill=\"{BACKGROUND}\"/>"
+    );
+    out.push_str(&text(left, PLOT_TOP - 18.0, TITLE_SIZE_PX, "start", title));
+
+    for i in 0..=Y_TICKS {
+        let rating = y_lo + (y_hi - y_lo) * i as i64 / Y_TICKS as i64;
+        let py = y(rating);
+        out.push_str(&format!(
+            "<line x1=\"{left}\" y1=\"{py}\" x2=\"{right}\" y2=\"{py}\" stroke=\"{STROKE}\" \
+             stroke-opacity=\"0.3\" stroke-width=\"1\"/>"
+        ));
+        out.push_str(&text(left - 8.0, py + 5.0, FONT_SIZE_PX, "end", &rating.to_string()));
+    }
+
+    // A single refresh's worth of data has no span to spread ticks across.
+    let x_ticks = if t_lo == t_hi { 0 } else { X_TICKS };
+    for i in 0..=x_ticks {
+        let t = t_lo + (t_hi - t_lo) * i as i64 / X_TICKS as i64;
+        let label = DateTime::from_timestamp(t, 0).map_or_else(String::new, |at| at.format("%m-%d").to_string());
+        out.push_str(&text(x(t), bottom + 20.0, FONT_SIZE_PX, "middle", &label));
+    }
+
+    for (index, line) in series.iter().enumerate() {
+        let colour = PALETTE[index % PALETTE.len()];
+        let points = line
+            .points
+            .iter()
+            .map(|(at, rating)| format!("{},{}", x(at.timestamp()), y(*rating)))
+            .collect::<Vec<_>>();
+        if points.len() > 1 {
+            out.push_str(&format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{colour}\" stroke-width=\"2.5\" \
+                 stroke-linejoin=\"round\"/>",
+                points.join(" ")
+            ));
+        }
+        // Every point gets a dot: with a refresh twice a day the line alone hides
+        // how sparse a new member's history is, and a lone point has no line.
+        for (at, rating) in &line.points {
+            out.push_str(&format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"3\" fill=\"{colour}\"/>",
+                x(at.timestamp()),
+                y(*rating)
+            ));
+        }
+
+        let legend_x = left + (index % 3) as f64 * 240.0;
+        let legend_y = HEIGHT_PX - PLOT_BOTTOM + 44.0 + (index / 3) as f64 * 20.0;
+        out.push_str(&format!(
+            "<rect x=\"{legend_x}\" y=\"{}\" width=\"12\" height=\"12\" fill=\"{colour}\"/>",
+            legend_y - 11.0
+        ));
+        out.push_str(&text(legend_x + 18.0, legend_y, FONT_SIZE_PX, "start", &line.name));
+    }
+
+    out.push_str("</svg>");
+    Some(out)
+}
+
+fn text(x: f64, y: f64, size: f64, anchor: &str, content: &str) -> String {
+    format!(
+        "<text x=\"{x}\" y=\"{y}\" font-family=\"{FONT_FAMILY}\" font-size=\"{size}\" \
+         text-anchor=\"{anchor}\" fill=\"{FOREGROUND}\">{}</text>",
+        escape_xml(content)
+    )
+}
+
+/// The chart as a PNG, rasterized off the async executor for the same reason
+/// `bracket_view` does. `None` when there is nothing to draw.
+pub(crate) async fn chart_png(title: &str, series: &[Series]) -> Result<Option<Vec<u8>>, Error> {
+    let Some(svg) = svg(title, series) else {
+        return Ok(None);
+    };
+    Ok(Some(
+        tokio::task::spawn_blocking(move || bracket_raster::rasterize(&svg)).await??,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn line(name: &str, ratings: &[i64]) -> Series {
+        let start = DateTime::from_timestamp(1_760_000_000, 0).unwrap();
+        Series {
+            name: name.to_string(),
+            points: ratings
+                .iter()
+                .enumerate()
+                .map(|(i, r)| (start + Duration::hours(12 * i as i64), *r))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn scale_maps_the_ends_of_the_domain_onto_the_ends_of_the_range() {
+        assert_eq!(scale(0.0, 0.0, 10.0, 100.0, 200.0), 100.0);
+        assert_eq!(scale(10.0, 0.0, 10.0, 100.0, 200.0), 200.0);
+        // Inverted, as the y axis is: a higher rating is nearer the top.
+        assert_eq!(scale(10.0, 0.0, 10.0, 200.0, 100.0), 100.0);
+    }
+
+    #[test]
+    fn a_zero_width_domain_lands_in_the_middle() {
+        assert_eq!(scale(5.0, 5.0, 5.0, 100.0, 200.0), 150.0);
+    }
+
+    #[test]
+    fn the_y_range_covers_every_rating_on_round_numbers() {
+        let (lo, hi) = y_range(&[line("A", &[1234, 1301]), line("B", &[1188])]);
+        assert!(lo <= 1188 && hi >= 1301, "{lo}..{hi}");
+        assert_eq!(lo % 10, 0);
+        assert_eq!(hi % 10, 0);
+    }
+
+    #[test]
+    fn a_flat_line_still_gets_headroom() {
+        let (lo, hi) = y_range(&[line("A", &[1500, 1500, 1500])]);
+        assert!(hi - lo >= MIN_Y_SPAN);
+        assert!(lo < 1500 && hi > 1500, "{lo}..{hi}");
+    }
+
+    #[test]
+    fn nothing_to_draw_is_none_rather_than_an_empty_frame() {
+        assert_eq!(svg("empty", &[]), None);
+        assert_eq!(svg("empty", &[line("A", &[])]), None);
+    }
+
+    #[test]
+    fn a_lone_point_is_drawn_as_a_dot_without_a_line() {
+        let doc = svg("one", &[line("A", &[1500])]).unwrap();
+        assert!(doc.contains("<circle"), "{doc}");
+        assert!(!doc.contains("<polyline"), "{doc}");
+    }
+
+    #[test]
+    fn every_profile_gets_its_own_line_and_legend_entry() {
+        let doc = svg("two", &[line("Main", &[1500, 1520]), line("Alt", &[1100, 1090])]).unwrap();
+        assert_eq!(doc.matches("<polyline").count(), 2, "{doc}");
+        assert!(doc.contains(">Main</text>") && doc.contains(">Alt</text>"), "{doc}");
+    }
+
+    #[test]
+    fn a_name_with_markup_characters_cannot_break_the_document() {
+        let doc = svg("<b>&", &[line("<script>", &[1500, 1510])]).unwrap();
+        assert!(!doc.contains("<script>") && !doc.contains("<b>"), "{doc}");
+        assert!(doc.contains("&lt;script&gt;"), "{doc}");
+    }
+
+    #[test]
+    fn a_chart_rasterizes_to_a_png() {
+        let doc = svg("測試", &[line("MarineLorD", &[1500, 1520, 1490])]).unwrap();
+        let png = bracket_raster::rasterize(&doc).expect("the chart should rasterize");
+        assert!(png.starts_with(&[0x89, 0x50, 0x4E, 0x47]));
+    }
+}
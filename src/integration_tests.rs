@@ -62,6 +62,9 @@ mod tests {
     /// The version that adds `registration_mode`.
     const REGISTRATION_MODE: i64 = 9;
 
+    /// The version that keys `ranked_board_messages` by board.
+    const RANKED_BOARD_KEY: i64 = 17;
+
     /// A pool migrated to just *before* `version`, so a migration can be applied to
     /// a database that already holds rows.
     ///
@@ -89,7 +92,11 @@ mod tests {
         // rebuilt tables. What has to survive is the ranked board, which belongs to
         // another feature and shares no keys with this one.
         let pool = pool_migrated_to_before(OPTIONAL_AOE4_ID).await;
-        crate::db::bind_account(&pool, 7, 700).await.unwrap();
+        // Raw, since `bind_account` writes columns later migrations add.
+        sqlx::query("insert into accounts (user_id, aoe4_id) values (7, 700)")
+            .execute(&pool)
+            .await
+            .unwrap();
         sqlx::query("insert into tournaments (slug, name, created_by) values ('cup', 'Cup', 1)")
             .execute(&pool)
             .await
@@ -300,7 +307,10 @@ mod tests {
             "create table accounts (
             id integer primary key autoincrement,
             user_id bigint not null,
-            aoe4_id bigint not null
+            aoe4_id bigint not null,
+            main boolean not null default false,
+            verified boolean not null default false,
+            bound_at timestamp
         )",
         )
         .await
@@ -340,10 +350,10 @@ mod tests {
         let aoe4_id2 = 222;
 
         // Bind first account
-        let _ = bind_account(&pool, user_id, aoe4_id1).await.unwrap();
+        bind_account(&pool, user_id, aoe4_id1).await.unwrap();
 
         // Bind second account to SAME user
-        let _ = bind_account(&pool, user_id, aoe4_id2).await.unwrap();
+        bind_account(&pool, user_id, aoe4_id2).await.unwrap();
 
         let all = list_all(&pool).await.unwrap();
         assert_eq!(all.len(), 2);
@@ -363,11 +373,11 @@ mod tests {
         let aoe4_id = 789;
 
         // Bind aoe4_id to user1
-        let _ = bind_account(&pool, user1, aoe4_id).await.unwrap();
+        bind_account(&pool, user1, aoe4_id).await.unwrap();
 
         // Try to bind SAME aoe4_id to user2
         // This should UPDATE the owner to user2 because of ON CONFLICT (aoe4_id) DO UPDATE SET user_id = excluded.user_id
-        let _ = bind_account(&pool, user2, aoe4_id).await.unwrap();
+        bind_account(&pool, user2, aoe4_id).await.unwrap();
 
         let all = list_all(&pool).await.unwrap();
         assert_eq!(all.iter().filter(|a| a.user_id == user1).count(), 0);
@@ -377,6 +387,551 @@ mod tests {
         assert_eq!(owned[0].aoe4_id, aoe4_id);
     }
 
+    #[tokio::test]
+    async fn a_member_unbinds_only_their_own_profiles() {
+        let pool = test_pool().await;
+        bind_account(&pool, 1, 100).await.unwrap();
+        bind_account(&pool, 2, 200).await.unwrap();
+
+        assert!(!crate::db::unbind_account(&pool, 1, 200).await.unwrap());
+        assert!(crate::db::unbind_account(&pool, 1, 100).await.unwrap());
+        let left = list_all(&pool).await.unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].aoe4_id, 200);
+    }
+
+    #[tokio::test]
+    async fn a_member_has_at_most_one_main_and_it_lists_first() {
+        let pool = test_pool().await;
+        bind_account(&pool, 1, 100).await.unwrap();
+        bind_account(&pool, 1, 101).await.unwrap();
+        bind_account(&pool, 2, 200).await.unwrap();
+
+        assert!(!crate::db::set_main_account(&pool, 1, 200).await.unwrap());
+        assert!(crate::db::set_main_account(&pool, 1, 101).await.unwrap());
+        assert!(crate::db::set_main_account(&pool, 1, 100).await.unwrap());
+
+        let mine = crate::db::list_accounts_for_user(&pool, 1).await.unwrap();
+        assert_eq!(
+            mine.iter().map(|a| (a.aoe4_id, a.main)).collect::<Vec<_>>(),
+            vec![(100, true), (101, false)]
+        );
+        let err = sqlx::query("update accounts set main = true where aoe4_id = 101")
+            .execute(&pool)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("UNIQUE"), "unexpected error: {err}");
+    }
+
+    #[tokio::test]
+    async fn a_profile_taken_over_stops_being_the_old_owners_main() {
+        let pool = test_pool().await;
+        bind_account(&pool, 1, 100).await.unwrap();
+        crate::db::set_main_account(&pool, 1, 100).await.unwrap();
+
+        bind_account(&pool, 1, 100).await.unwrap();
+        assert!(crate::db::list_accounts_for_user(&pool, 1).await.unwrap()[0].main);
+
+        bind_account(&pool, 2, 100).await.unwrap();
+        assert!(!crate::db::list_accounts_for_user(&pool, 2).await.unwrap()[0].main);
+    }
+
+    #[tokio::test]
+    async fn only_a_verified_or_admin_claim_takes_over_a_held_profile() {
+        use crate::db::{BindOutcome, Claim, claim_account, list_accounts_for_user};
+        let pool = test_pool().await;
+        assert_eq!(
+            claim_account(&pool, 1, 100, Claim::Plain).await.unwrap(),
+            BindOutcome::Bound
+        );
+        assert_eq!(
+            claim_account(&pool, 1, 100, Claim::Plain).await.unwrap(),
+            BindOutcome::AlreadyYours
+        );
+        assert_eq!(
+            claim_account(&pool, 2, 100, Claim::Plain).await.unwrap(),
+            BindOutcome::HeldBy {
+                user_id: 1,
+                verified: false
+            }
+        );
+
+        assert_eq!(
+            claim_account(&pool, 2, 100, Claim::Verified).await.unwrap(),
+            BindOutcome::TakenOver { from: 1 }
+        );
+        assert!(list_accounts_for_user(&pool, 2).await.unwrap()[0].verified);
+        assert_eq!(
+            claim_account(&pool, 1, 100, Claim::Plain).await.unwrap(),
+            BindOutcome::HeldBy {
+                user_id: 2,
+                verified: true
+            }
+        );
+
+        assert_eq!(
+            claim_account(&pool, 3, 100, Claim::Admin).await.unwrap(),
+            BindOutcome::TakenOver { from: 2 }
+        );
+        assert!(!list_accounts_for_user(&pool, 3).await.unwrap()[0].verified);
+    }
+
+    #[tokio::test]
+    async fn verifying_a_profile_you_already_hold_marks_it_verified() {
+        use crate::db::{BindOutcome, Claim, claim_account, list_accounts_for_user};
+        let pool = test_pool().await;
+        claim_account(&pool, 1, 100, Claim::Plain).await.unwrap();
+        assert_eq!(
+            claim_account(&pool, 1, 100, Claim::Verified).await.unwrap(),
+            BindOutcome::AlreadyYours
+        );
+        claim_account(&pool, 1, 100, Claim::Plain).await.unwrap();
+        assert!(list_accounts_for_user(&pool, 1).await.unwrap()[0].verified);
+    }
+
+    #[tokio::test]
+    async fn an_import_moves_the_main_and_keeps_a_known_bind_time() {
+        use crate::db::{Account, import_accounts, list_accounts_for_user};
+        let pool = test_pool().await;
+        bind_account(&pool, 1, 100).await.unwrap();
+        crate::db::set_main_account(&pool, 1, 100).await.unwrap();
+        let bound_at = list_accounts_for_user(&pool, 1).await.unwrap()[0].bound_at;
+        assert!(bound_at.is_some());
+
+        let row = |aoe4_id, main| Account {
+            user_id: 1,
+            aoe4_id,
+            main,
+            verified: true,
+            bound_at: None,
+        };
+        import_accounts(&pool, &[row(100, false), row(101, true)])
+            .await
+            .unwrap();
+
+        let mine = list_accounts_for_user(&pool, 1).await.unwrap();
+        assert_eq!(
+            mine.iter().map(|a| (a.aoe4_id, a.main, a.verified)).collect::<Vec<_>>(),
+            vec![(101, true, true), (100, false, true)]
+        );
+        assert_eq!(
+            mine[1].bound_at, bound_at,
+            "a file without bound_at keeps the stored one"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_refresh_replaces_the_stored_leaderboard_whole() {
+        use crate::db::{
+            LeaderboardCiv, LeaderboardEntry, list_leaderboard, list_leaderboard_civs, replace_leaderboard,
+        };
+        let pool = test_pool().await;
+        let entry = |user_id, rating| LeaderboardEntry {
+            user_id,
+            aoe4_id: user_id * 10,
+            aoe4_name: "P".to_string(),
+            discord_display: "D".to_string(),
+            rank_level: "gold_1".to_string(),
+            rating,
+            elo: rating,
+            recent_max_rating: rating,
+            global_rank: 1,
+            games_played: 1,
+            win_rate: 50.0,
+            favorite_civ: "english".to_string(),
+            refreshed_at: chrono::DateTime::UNIX_EPOCH,
+        };
+        let civ = |user_id| LeaderboardCiv {
+            user_id,
+            civilization: "english".to_string(),
+            pick_rate: 100.0,
+            games: 1,
+        };
+        replace_leaderboard(&pool, &[entry(1, 1000), entry(2, 1100)], &[civ(1), civ(2)])
+            .await
+            .unwrap();
+        replace_leaderboard(&pool, &[entry(2, 1200)], &[civ(2)]).await.unwrap();
+
+        assert_eq!(list_leaderboard(&pool).await.unwrap(), vec![entry(2, 1200)]);
+        assert_eq!(list_leaderboard_civs(&pool).await.unwrap(), vec![civ(2)]);
+    }
+
+    #[tokio::test]
+    async fn a_leaderboard_view_keeps_its_filters_and_page() {
+        use crate::db::{create_leaderboard_view, get_leaderboard_view, set_leaderboard_view_page};
+        let pool = test_pool().await;
+        let view = create_leaderboard_view(&pool, 1, "elo", Some("gold"), None)
+            .await
+            .unwrap();
+        assert_eq!(view.page, 0);
+        set_leaderboard_view_page(&pool, view.id, 3).await.unwrap();
+
+        let stored = get_leaderboard_view(&pool, view.id).await.unwrap().unwrap();
+        assert_eq!(
+            (stored.sort.as_str(), stored.tier.as_deref(), stored.civ, stored.page),
+            ("elo", Some("gold"), None, 3)
+        );
+
+        // A day-old view is cleared out by the next one opened.
+        sqlx::query("update leaderboard_views set created_at = ?1 where id = ?2")
+            .bind(chrono::Utc::now() - chrono::Duration::days(2))
+            .bind(view.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        create_leaderboard_view(&pool, 2, "rating", None, None).await.unwrap();
+        assert!(get_leaderboard_view(&pool, view.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn a_closed_season_keeps_the_board_in_final_order_under_a_unique_name() {
+        use crate::db::{LeaderboardEntry, close_season, get_season, list_season_standings};
+        let pool = test_pool().await;
+        let entry = |user_id, rating| LeaderboardEntry {
+            user_id,
+            aoe4_id: user_id * 10,
+            aoe4_name: "P".to_string(),
+            discord_display: "D".to_string(),
+            rank_level: "gold_1".to_string(),
+            rating,
+            elo: rating,
+            recent_max_rating: rating,
+            global_rank: 1,
+            games_played: 1,
+            win_rate: 50.0,
+            favorite_civ: "english".to_string(),
+            refreshed_at: chrono::DateTime::UNIX_EPOCH,
+        };
+        let board = [entry(1, 1000), entry(2, 1100)];
+        let ordered = crate::season::final_order(&board);
+        let season = close_season(&pool, "S9", 7, &ordered).await.unwrap().unwrap();
+        assert_eq!(season.board_refreshed_at, chrono::DateTime::UNIX_EPOCH);
+
+        let standings = list_season_standings(&pool, season.id).await.unwrap();
+        let places = standings
+            .iter()
+            .map(|s| (s.place, s.user_id, s.rating))
+            .collect::<Vec<_>>();
+        assert_eq!(places, vec![(1, 2, 1100), (2, 1, 1000)]);
+
+        // A name is taken whatever its case, and the archive is left alone.
+        assert!(close_season(&pool, "s9", 7, &ordered[..1]).await.unwrap().is_none());
+        assert_eq!(get_season(&pool, "s9").await.unwrap().unwrap().id, season.id);
+        assert_eq!(list_season_standings(&pool, season.id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn season_history_follows_the_member_to_their_other_profiles() {
+        use crate::db::{LeaderboardEntry, close_season, season_history};
+        let pool = test_pool().await;
+        let entry = |user_id, aoe4_id, rating| LeaderboardEntry {
+            user_id,
+            aoe4_id,
+            aoe4_name: "P".to_string(),
+            discord_display: "D".to_string(),
+            rank_level: "gold_1".to_string(),
+            rating,
+            elo: rating,
+            recent_max_rating: rating,
+            global_rank: 1,
+            games_played: 1,
+            win_rate: 50.0,
+            favorite_civ: "english".to_string(),
+            refreshed_at: chrono::DateTime::UNIX_EPOCH,
+        };
+        let (first, second) = (entry(1, 10, 1500), entry(2, 20, 1200));
+        close_season(&pool, "S8", 7, &[&first, &second]).await.unwrap();
+        close_season(&pool, "S9", 7, &[&second]).await.unwrap();
+        // Member 2 has since bound a new profile.
+        bind_account(&pool, 2, 20).await.unwrap();
+        bind_account(&pool, 2, 21).await.unwrap();
+
+        let history = season_history(&pool, 21).await.unwrap();
+        let seasons = history
+            .iter()
+            .map(|r| (r.name.as_str(), r.place, r.of))
+            .collect::<Vec<_>>();
+        assert_eq!(seasons, vec![("S9", 1, 1), ("S8", 2, 2)]);
+        assert!(season_history(&pool, 99).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_new_verification_code_replaces_the_old_one() {
+        use crate::db::{clear_verification, get_verification, start_verification};
+        let pool = test_pool().await;
+        let now = chrono::DateTime::UNIX_EPOCH;
+        start_verification(&pool, 1, 100, "AAAAA", now).await.unwrap();
+        start_verification(&pool, 1, 101, "BBBBB", now).await.unwrap();
+
+        let pending = get_verification(&pool, 1).await.unwrap().unwrap();
+        assert_eq!((pending.aoe4_id, pending.code.as_str()), (101, "BBBBB"));
+
+        clear_verification(&pool, 1).await.unwrap();
+        assert_eq!(get_verification(&pool, 1).await.unwrap(), None);
+    }
+
+    fn snapshot(aoe4_id: i64, taken_at: chrono::DateTime<chrono::Utc>, rating: i64) -> crate::db::RatingSnapshot {
+        crate::db::RatingSnapshot {
+            aoe4_id,
+            aoe4_name: format!("P{aoe4_id}"),
+            taken_at,
+            rating,
+            elo: rating + 50,
+            rank_level: "gold_2".to_string(),
+            global_rank: 12_000,
+            games_count: 40,
+            win_rate: 51.5,
+        }
+    }
+
+    #[tokio::test]
+    async fn snapshots_list_oldest_first_within_the_window_and_per_profile() {
+        use chrono::{Duration, Utc};
+        let pool = test_pool().await;
+        let now = Utc::now();
+
+        // Inserted out of order, and with a second profile and an out-of-window
+        // row mixed in, so the query has to do all three jobs.
+        for row in [
+            snapshot(1, now - Duration::days(1), 1510),
+            snapshot(1, now - Duration::days(200), 900),
+            snapshot(2, now - Duration::days(1), 2000),
+            snapshot(1, now - Duration::days(2), 1490),
+        ] {
+            crate::db::insert_snapshot(&pool, &row).await.unwrap();
+        }
+
+        let rows = crate::db::list_snapshots_since(&pool, 1, now - Duration::days(90))
+            .await
+            .unwrap();
+        let ratings: Vec<i64> = rows.iter().map(|r| r.rating).collect();
+        assert_eq!(ratings, vec![1490, 1510]);
+        assert_eq!(rows[0].rank_level, "gold_2");
+        assert_eq!(rows[0].win_rate, 51.5);
+    }
+
+    #[tokio::test]
+    async fn accounts_for_a_user_are_only_theirs() {
+        let pool = test_pool().await;
+        bind_account(&pool, 1, 100).await.unwrap();
+        bind_account(&pool, 2, 200).await.unwrap();
+        bind_account(&pool, 1, 101).await.unwrap();
+
+        let owned: Vec<i64> = crate::db::list_accounts_for_user(&pool, 1)
+            .await
+            .unwrap()
+            .iter()
+            .map(|a| a.aoe4_id)
+            .collect();
+        assert_eq!(owned, vec![100, 101]);
+    }
+
+    #[tokio::test]
+    async fn keying_the_board_keeps_every_chunk_already_stored_as_the_main_one() {
+        let pool = pool_migrated_to_before(RANKED_BOARD_KEY).await;
+        sqlx::query("insert into ranked_board_messages (ordinal, message_id) values (0, 10), (1, 11)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        let stored = crate::db::list_board_messages(&pool, "main").await.unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[1].message_id, 11);
+    }
+
+    #[tokio::test]
+    async fn board_messages_round_trip_and_trim_from_an_ordinal() {
+        let pool = test_pool().await;
+        for (ordinal, message_id) in [(0, 10), (1, 11), (2, 12)] {
+            crate::db::upsert_board_message(&pool, "main", ordinal, message_id)
+                .await
+                .unwrap();
+        }
+        // A second board at the same ordinals is its own set of rows.
+        crate::db::upsert_board_message(&pool, "team", 0, 90).await.unwrap();
+        // A repost replaces the id at its ordinal rather than adding a row.
+        crate::db::upsert_board_message(&pool, "main", 1, 21).await.unwrap();
+        crate::db::delete_board_messages_from(&pool, "main", 2).await.unwrap();
+
+        let team = crate::db::list_board_messages(&pool, "team").await.unwrap();
+        assert_eq!(team.len(), 1);
+        let stored: Vec<(i64, i64)> = crate::db::list_board_messages(&pool, "main")
+            .await
+            .unwrap()
+            .iter()
+            .map(|m| (m.ordinal, m.message_id))
+            .collect();
+        assert_eq!(stored, vec![(0, 10), (1, 21)]);
+    }
+
+    #[tokio::test]
+    async fn reminder_state_keeps_opt_out_snooze_and_nag_apart() {
+        let pool = test_pool().await;
+        let day = |n: i64| chrono::DateTime::UNIX_EPOCH + chrono::Duration::days(n);
+
+        crate::db::snooze_reminders(&pool, 1, day(5)).await.unwrap();
+        crate::db::mark_nagged(&pool, 1, day(1), day(3)).await.unwrap();
+        crate::db::set_reminder_opt_out(&pool, 2, true).await.unwrap();
+
+        let states = crate::db::list_reminder_states(&pool).await.unwrap();
+        let one = states.iter().find(|state| state.user_id == 1).unwrap();
+        assert!(!one.opted_out);
+        assert_eq!(one.snoozed_until, Some(day(5)), "a nag must not lift a snooze");
+        assert_eq!(one.nagged_for, Some(day(1)));
+        assert!(states.iter().find(|state| state.user_id == 2).unwrap().opted_out);
+
+        // Back on means on: the snooze goes, the nag record stays.
+        crate::db::set_reminder_opt_out(&pool, 1, false).await.unwrap();
+        let states = crate::db::list_reminder_states(&pool).await.unwrap();
+        let one = states.iter().find(|state| state.user_id == 1).unwrap();
+        assert_eq!(one.snoozed_until, None);
+        assert_eq!(one.nagged_for, Some(day(1)));
+    }
+
+    #[tokio::test]
+    async fn the_reminder_table_survives_schema_sql_running_again() {
+        // schema.sql runs on every boot and drops the old `reminders` table.
+        let pool = test_pool().await;
+        crate::db::set_reminder_opt_out(&pool, 1, true).await.unwrap();
+        pool.execute(include_str!("../schema.sql")).await.unwrap();
+        assert_eq!(crate::db::list_reminder_states(&pool).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn feed_state_is_saved_and_overwritten_per_profile() {
+        let pool = test_pool().await;
+        let mut state = crate::db::FeedState {
+            aoe4_id: 700,
+            last_game_at: chrono::DateTime::UNIX_EPOCH,
+            last_game_id: None,
+            rank_level: "gold_1".to_string(),
+            best_rating: 1000,
+            win_streak: 0,
+        };
+        assert_eq!(crate::db::get_feed_state(&pool, 700).await.unwrap(), None);
+
+        crate::db::save_feed_state(&pool, &state).await.unwrap();
+        state.last_game_id = Some(42);
+        state.win_streak = 3;
+        crate::db::save_feed_state(&pool, &state).await.unwrap();
+
+        assert_eq!(crate::db::get_feed_state(&pool, 700).await.unwrap(), Some(state));
+    }
+
+    #[tokio::test]
+    async fn tier_role_grants_are_swapped_revoked_and_logged() {
+        let pool = test_pool().await;
+        crate::db::grant_tier_role(&pool, 1, 40, "diamond_1").await.unwrap();
+        crate::db::revoke_tier_role(&pool, 1, 40, Some("platinum_3"))
+            .await
+            .unwrap();
+        crate::db::grant_tier_role(&pool, 1, 30, "platinum_3").await.unwrap();
+
+        let grants = crate::db::list_tier_role_grants(&pool).await.unwrap();
+        assert_eq!(grants.len(), 1);
+        assert_eq!((grants[0].role_id, grants[0].rank_level.as_str()), (30, "platinum_3"));
+
+        crate::db::revoke_tier_role(&pool, 1, 30, None).await.unwrap();
+        assert!(crate::db::list_tier_role_grants(&pool).await.unwrap().is_empty());
+
+        let log: Vec<(i64, String, Option<String>)> =
+            sqlx::query_as("select role_id, action, rank_level from tier_role_changes order by id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            log,
+            vec![
+                (40, "add".to_string(), Some("diamond_1".to_string())),
+                (40, "remove".to_string(), Some("platinum_3".to_string())),
+                (30, "add".to_string(), Some("platinum_3".to_string())),
+                (30, "remove".to_string(), None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn the_seeded_reaction_rules_all_parse() {
+        let pool = test_pool().await;
+        let rules = crate::db::list_reaction_rules(&pool).await.unwrap();
+        assert!(!rules.is_empty());
+        for rule in rules {
+            assert!(
+                crate::emperor::Trigger::parse(&rule.kind, &rule.pattern).is_ok(),
+                "rule #{} does not parse",
+                rule.id
+            );
+            assert!(
+                crate::emperor::parse_emoji(&rule.emoji).is_ok(),
+                "rule #{} has a bad emoji",
+                rule.id
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn the_seeded_emperor_rules_draw_one_of_three() {
+        let pool = test_pool().await;
+        let rules = crate::db::list_reaction_rules(&pool).await.unwrap();
+        // Every trigger of the usual emoji draws it against a 🍄 and a 🐷,
+        // with the old 1% / 10% / 89% odds.
+        for (kind, pattern) in [
+            ("author", "453010726311821322"),
+            ("mention", "1511740443132428328"),
+            ("substring", "唱歌"),
+        ] {
+            let mut odds = rules
+                .iter()
+                .filter(|rule| rule.kind == kind && rule.pattern == pattern)
+                .map(|rule| {
+                    assert_eq!(rule.draw_group.as_deref(), Some("emperor"));
+                    (rule.emoji.as_str(), rule.probability)
+                })
+                .collect::<Vec<_>>();
+            odds.sort_by(|a, b| a.1.total_cmp(&b.1));
+            assert_eq!(
+                odds,
+                vec![("🍄", 0.01), ("🐷", 0.1), ("<:emoji_93:1299285258457448522>", 0.89)],
+                "{kind} {pattern}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn reaction_rules_are_added_and_removed() {
+        let pool = test_pool().await;
+        let before = crate::db::list_reaction_rules(&pool).await.unwrap().len();
+        let id = crate::db::insert_reaction_rule(&pool, "substring", "象", "🐘", 0.5, 0.0, None, 42)
+            .await
+            .unwrap();
+
+        let rules = crate::db::list_reaction_rules(&pool).await.unwrap();
+        assert_eq!(rules.len(), before + 1);
+        assert_eq!(rules.last().unwrap().probability, 0.5);
+
+        assert!(crate::db::delete_reaction_rule(&pool, id).await.unwrap());
+        assert!(!crate::db::delete_reaction_rule(&pool, id).await.unwrap());
+        assert_check_constraint_failed(
+            crate::db::insert_reaction_rule(&pool, "nickname", "x", "🐘", 1.0, 0.0, None, 42).await,
+        );
+        assert_check_constraint_failed(
+            crate::db::insert_reaction_rule(&pool, "substring", "x", "🐘", 1.5, 0.0, None, 42).await,
+        );
+    }
+
+    #[tokio::test]
+    async fn reaction_opt_outs_are_set_idempotently_and_cleared() {
+        let pool = test_pool().await;
+        crate::db::set_reaction_opt_out(&pool, 7, true).await.unwrap();
+        crate::db::set_reaction_opt_out(&pool, 7, true).await.unwrap();
+        assert_eq!(crate::db::list_reaction_opt_outs(&pool).await.unwrap(), vec![7]);
+
+        crate::db::set_reaction_opt_out(&pool, 7, false).await.unwrap();
+        assert!(crate::db::list_reaction_opt_outs(&pool).await.unwrap().is_empty());
+    }
+
     // Tournament schema (migrations/0002_tournament_schema.sql) gate tests.
 
     async fn setup_round(pool: &SqlitePool) -> (i64, i64) {
@@ -1654,7 +2209,8 @@ mod tests {
             outcome,
             crate::tournament::checkin::ReopenRegistrationOutcome::Reopened {
                 restored_count: 1,
-                cleared_count: 1
+                cleared_count: 1,
+                autopilot_off: false,
             }
         );
 
@@ -2443,39 +2999,518 @@ mod tests {
         assert!(field.iter().any(|e| e.user_id == 4 && e.status == "eliminated"));
     }
 
-    // Result import.
-    //
-    // `import::apply` calls `completion::finish` exactly like the manual
-    // paths do, which needs a Discord `CacheHttp` — but never fails through
-    // that: every Discord call downstream is best-effort and logs its own
-    // error rather than propagating one (`set_thread::close`,
-    // `bracket_view::reconcile`), so a fake token is enough to exercise the
-    // whole path and verify the database it leaves behind.
+    // Double elimination. Same shortcut as above — `decide_and_complete` in
+    // place of `completion::finish` — over a bracket `start` built with
+    // `format = 'double_elim'`. Sets come back by round ordinal: the winners
+    // bracket, then the losers bracket, then the grand final and its reset.
 
-    fn fake_http() -> serenity::all::Http {
-        serenity::all::Http::new("faketoken")
+    async fn setup_running_double(pool: &SqlitePool, n: i64, reset: bool) -> crate::tournament::db::Tournament {
+        let tournament = setup_startable(pool, n).await;
+        crate::tournament::db::set_format(pool, tournament.id, "double_elim", "single_elim", reset)
+            .await
+            .unwrap();
+        let tournament = crate::tournament::db::get_tournament(pool, tournament.id)
+            .await
+            .unwrap()
+            .unwrap();
+        crate::tournament::start::start(pool, &tournament).await.unwrap();
+        crate::tournament::db::get_tournament(pool, tournament.id)
+            .await
+            .unwrap()
+            .unwrap()
     }
 
-    /// A wide-open throttle: these tests exercise `import::apply`/`sync` in
-    /// isolation, one call each, so there is nothing for a throttle window to
-    /// coalesce.
-    fn fake_throttle() -> crate::tournament::throttle::EditThrottle {
-        crate::tournament::throttle::EditThrottle::new(std::time::Duration::ZERO)
+    async fn set_of(pool: &SqlitePool, set_id: i64) -> crate::tournament::db::TournamentSet {
+        crate::tournament::db::get_set(pool, set_id).await.unwrap().unwrap()
     }
 
-    fn draft_seat(claimed: bool) -> crate::drafttool::DraftSeat {
-        crate::drafttool::DraftSeat { claimed }
-    }
+    #[tokio::test]
+    async fn a_double_elimination_loser_drops_and_a_forced_reset_decides_the_event() {
+        let pool = test_pool().await;
+        let tournament = setup_running_double(&pool, 4, true).await;
+        // Winners 1v4, 2v3, final; losers round 1, losers final; grand final, reset.
+        let ids = set_ids(&pool, tournament.id).await;
+        assert_eq!(ids.len(), 7);
 
-    fn draft_game(number: i64, map: Option<&str>, winner_slot: Option<i64>) -> crate::drafttool::DraftGame {
-        crate::drafttool::DraftGame {
-            number,
-            map: map.map(str::to_string),
-            civ_by_slot: crate::drafttool::CivBySlot {
-                slot1: None,
-                slot2: None,
-            },
-            winner_slot,
+        report_games(&pool, ids[0], &[1, 1]).await;
+        let advanced = decide_and_complete(&pool, tournament.id, ids[0]).await.unwrap();
+        assert!(!advanced.loser_eliminated, "a first loss drops, it doesn't eliminate");
+        assert_eq!(status_of(&pool, tournament.id, 4).await, "active");
+        report_games(&pool, ids[1], &[2, 2]).await;
+        decide_and_complete(&pool, tournament.id, ids[1]).await.unwrap();
+
+        let losers_round = set_of(&pool, ids[3]).await;
+        assert_eq!(
+            (losers_round.slot1_user_id, losers_round.slot2_user_id),
+            (Some(4), Some(3))
+        );
+        assert_eq!(losers_round.status, "ready");
+
+        report_games(&pool, ids[2], &[1, 1]).await; // 2 drops to the losers final
+        decide_and_complete(&pool, tournament.id, ids[2]).await.unwrap();
+        report_games(&pool, ids[3], &[3, 3]).await;
+        let advanced = decide_and_complete(&pool, tournament.id, ids[3]).await.unwrap();
+        assert!(advanced.loser_eliminated, "a second loss is the last");
+        assert_eq!(status_of(&pool, tournament.id, 4).await, "eliminated");
+        report_games(&pool, ids[4], &[3, 3]).await;
+        decide_and_complete(&pool, tournament.id, ids[4]).await.unwrap();
+
+        let grand_final = set_of(&pool, ids[5]).await;
+        assert_eq!(
+            (grand_final.slot1_user_id, grand_final.slot2_user_id),
+            (Some(1), Some(3))
+        );
+
+        // The losers champion takes the grand final, so the reset is played.
+        report_games(&pool, ids[5], &[3, 3]).await;
+        let advanced = decide_and_complete(&pool, tournament.id, ids[5]).await.unwrap();
+        assert!(!advanced.tournament_completed);
+        assert!(!advanced.loser_eliminated, "the winners champion had not lost yet");
+        let reset = set_of(&pool, ids[6]).await;
+        assert_eq!((reset.slot1_user_id, reset.slot2_user_id), (Some(3), Some(1)));
+        assert_eq!(reset.status, "ready");
+
+        report_games(&pool, ids[6], &[1, 1]).await;
+        let advanced = decide_and_complete(&pool, tournament.id, ids[6]).await.unwrap();
+        assert!(advanced.tournament_completed);
+        assert!(!advanced.is_third_place);
+        assert_eq!(status_of(&pool, tournament.id, 3).await, "eliminated");
+    }
+
+    #[tokio::test]
+    async fn the_winners_champion_taking_the_grand_final_ends_it_without_a_reset() {
+        let pool = test_pool().await;
+        let tournament = setup_running_double(&pool, 2, true).await;
+        // Winners final, grand final, reset — two entrants have no losers bracket.
+        let ids = set_ids(&pool, tournament.id).await;
+        assert_eq!(ids.len(), 3);
+
+        report_games(&pool, ids[0], &[1, 1]).await;
+        decide_and_complete(&pool, tournament.id, ids[0]).await.unwrap();
+        let grand_final = set_of(&pool, ids[1]).await;
+        assert_eq!(
+            (grand_final.slot1_user_id, grand_final.slot2_user_id),
+            (Some(1), Some(2))
+        );
+
+        report_games(&pool, ids[1], &[1, 1]).await;
+        let advanced = decide_and_complete(&pool, tournament.id, ids[1]).await.unwrap();
+        assert!(advanced.tournament_completed);
+        assert!(advanced.loser_eliminated);
+        assert_eq!(status_of(&pool, tournament.id, 2).await, "eliminated");
+
+        let reset = set_of(&pool, ids[2]).await;
+        assert_eq!(reset.status, "bye", "never needed, so never played");
+        assert_eq!((reset.slot1_user_id, reset.slot2_user_id), (None, None));
+    }
+
+    #[tokio::test]
+    async fn a_losers_set_fed_by_a_bye_passes_its_one_player_straight_through() {
+        let pool = test_pool().await;
+        let tournament = setup_running_double(&pool, 3, false).await;
+        // Winners 1 v bye, 2v3, final; losers round 1, losers final; grand final.
+        let ids = set_ids(&pool, tournament.id).await;
+        assert_eq!(set_of(&pool, ids[0]).await.status, "bye");
+        // Still pending, not a bye: one of its feeders is yet to be played.
+        assert_eq!(set_of(&pool, ids[3]).await.status, "pending");
+
+        report_games(&pool, ids[1], &[2, 2]).await;
+        decide_and_complete(&pool, tournament.id, ids[1]).await.unwrap();
+
+        // The bye's loser doesn't exist, so 3 has nobody to play.
+        let losers_round = set_of(&pool, ids[3]).await;
+        assert_eq!(losers_round.status, "bye");
+        assert_eq!(losers_round.winner_user_id, Some(3));
+        assert_eq!(set_of(&pool, ids[4]).await.slot1_user_id, Some(3));
+
+        report_games(&pool, ids[2], &[1, 1]).await;
+        decide_and_complete(&pool, tournament.id, ids[2]).await.unwrap();
+        let losers_final = set_of(&pool, ids[4]).await;
+        assert_eq!(
+            (losers_final.slot1_user_id, losers_final.slot2_user_id),
+            (Some(3), Some(2))
+        );
+        assert_eq!(losers_final.status, "ready");
+    }
+
+    // Swiss. The same shortcut again, plus `swiss::advance`, which is what
+    // `completion::settle` runs after the transaction to pair the next round.
+
+    async fn setup_running_swiss(pool: &SqlitePool, n: i64, rounds: Option<i64>) -> crate::tournament::db::Tournament {
+        let tournament = setup_startable(pool, n).await;
+        crate::tournament::db::set_format(pool, tournament.id, "swiss", "single_elim", false)
+            .await
+            .unwrap();
+        crate::tournament::db::set_swiss_rounds(pool, tournament.id, rounds)
+            .await
+            .unwrap();
+        let tournament = crate::tournament::db::get_tournament(pool, tournament.id)
+            .await
+            .unwrap()
+            .unwrap();
+        crate::tournament::start::start(pool, &tournament).await.unwrap();
+        crate::tournament::db::get_tournament(pool, tournament.id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    /// Plays every open set of the current round with the lower user id winning,
+    /// then advances the stage the way a settlement would.
+    async fn play_swiss_round(pool: &SqlitePool, tournament_id: i64) -> Option<crate::tournament::swiss::Progress> {
+        for set in crate::tournament::db::list_sets_for_tournament(pool, tournament_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|set| set.status == "ready")
+        {
+            let winner = set.slot1_user_id.unwrap().min(set.slot2_user_id.unwrap());
+            report_games(pool, set.id, &[winner, winner]).await;
+            let advanced = decide_and_complete(pool, tournament_id, set.id).await.unwrap();
+            assert!(!advanced.loser_eliminated, "nobody is knocked out of a Swiss round");
+        }
+        crate::tournament::swiss::advance(pool, tournament_id).await.unwrap()
+    }
+
+    fn opponents(sets: &[crate::tournament::db::TournamentSet]) -> Vec<(i64, i64)> {
+        sets.iter()
+            .filter_map(|set| {
+                let (one, two) = (set.slot1_user_id?, set.slot2_user_id?);
+                Some((one.min(two), one.max(two)))
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn a_swiss_stage_pairs_each_round_once_the_last_one_is_decided() {
+        use crate::tournament::swiss::Progress;
+
+        let pool = test_pool().await;
+        let tournament = setup_running_swiss(&pool, 4, Some(2)).await;
+        let round_one = crate::tournament::db::list_sets_for_tournament(&pool, tournament.id)
+            .await
+            .unwrap();
+        assert_eq!(round_one.len(), 2, "only round one exists at start");
+        assert!(round_one.iter().all(|set| set.status == "ready"));
+
+        assert_eq!(
+            crate::tournament::swiss::advance(&pool, tournament.id).await.unwrap(),
+            Some(Progress::Waiting { last_round: false }),
+            "nothing is paired while round one is being played"
+        );
+        assert_eq!(
+            play_swiss_round(&pool, tournament.id).await,
+            Some(Progress::Paired { round: 2 })
+        );
+        assert_eq!(
+            crate::tournament::swiss::advance(&pool, tournament.id).await.unwrap(),
+            Some(Progress::Waiting { last_round: true }),
+            "a second call must not pair round two again"
+        );
+
+        let sets = crate::tournament::db::list_sets_for_tournament(&pool, tournament.id)
+            .await
+            .unwrap();
+        assert_eq!(sets.len(), 4);
+        let (played, paired) = (opponents(&sets[..2]), opponents(&sets[2..]));
+        assert!(paired.iter().all(|pair| !played.contains(pair)), "no rematches");
+        // 1 and 2 won round one, so they meet in the 1-0 group.
+        assert!(paired.contains(&(1, 2)), "{paired:?}");
+        assert!(sets[2..].iter().all(|set| set.status == "ready"));
+
+        assert_eq!(play_swiss_round(&pool, tournament.id).await, Some(Progress::Finished));
+        let after = crate::tournament::db::get_tournament(&pool, tournament.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(after.status, "completed");
+        assert_ne!(
+            crate::tournament::swiss::advance(&pool, tournament.id).await.unwrap(),
+            Some(Progress::Finished),
+            "only one settlement reports the end"
+        );
+        for user_id in 1..=4 {
+            assert_eq!(status_of(&pool, tournament.id, user_id).await, "active");
+        }
+
+        let entries = crate::tournament::db::list_entries_for_tournament(&pool, tournament.id)
+            .await
+            .unwrap();
+        let (table, finished) = crate::tournament::swiss::table(&pool, tournament.id, &entries)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(finished);
+        assert_eq!((table[0].user_id, table[0].wins), (1, 2));
+    }
+
+    #[tokio::test]
+    async fn an_odd_swiss_field_gives_a_different_player_the_bye_each_round() {
+        let pool = test_pool().await;
+        // Three entrants: two rounds by default, as a 4-bracket would have.
+        let tournament = setup_running_swiss(&pool, 3, None).await;
+        let bye_holder = |sets: &[crate::tournament::db::TournamentSet]| {
+            let bye = sets
+                .iter()
+                .find(|set| set.status == "bye")
+                .expect("an odd round has a bye");
+            bye.winner_user_id
+        };
+
+        let round_one = crate::tournament::db::list_sets_for_tournament(&pool, tournament.id)
+            .await
+            .unwrap();
+        assert_eq!(bye_holder(&round_one), Some(3), "the lowest seed sits out first");
+
+        play_swiss_round(&pool, tournament.id).await;
+        let sets = crate::tournament::db::list_sets_for_tournament(&pool, tournament.id)
+            .await
+            .unwrap();
+        let round_two: Vec<_> = sets.into_iter().skip(round_one.len()).collect();
+        assert_eq!(round_two.len(), 2);
+        let second = bye_holder(&round_two);
+        assert!(second.is_some() && second != Some(3), "{second:?}");
+    }
+
+    #[tokio::test]
+    async fn more_swiss_rounds_than_the_field_can_play_refuses_to_start() {
+        let pool = test_pool().await;
+        let tournament = setup_startable(&pool, 4).await;
+        crate::tournament::db::set_format(&pool, tournament.id, "swiss", "single_elim", false)
+            .await
+            .unwrap();
+        crate::tournament::db::set_swiss_rounds(&pool, tournament.id, Some(4))
+            .await
+            .unwrap();
+        let tournament = crate::tournament::db::get_tournament(&pool, tournament.id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            crate::tournament::start::start(&pool, &tournament).await.unwrap(),
+            crate::tournament::start::StartOutcome::TooManySwissRounds { rounds: 4, max: 3 }
+        );
+        assert!(
+            crate::tournament::db::list_sets_for_tournament(&pool, tournament.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    async fn setup_running_groups(
+        pool: &SqlitePool,
+        n: i64,
+        groups: i64,
+        advance: i64,
+    ) -> crate::tournament::db::Tournament {
+        let tournament = setup_startable(pool, n).await;
+        crate::tournament::db::set_format(pool, tournament.id, "group", "single_elim", false)
+            .await
+            .unwrap();
+        crate::tournament::db::set_group_stage(pool, tournament.id, Some(groups), Some(advance))
+            .await
+            .unwrap();
+        let tournament = crate::tournament::db::get_tournament(pool, tournament.id)
+            .await
+            .unwrap()
+            .unwrap();
+        crate::tournament::start::start(pool, &tournament).await.unwrap();
+        crate::tournament::db::get_tournament(pool, tournament.id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    /// `play_swiss_round` for a group stage: the lower user id wins every open set.
+    async fn play_group_round(pool: &SqlitePool, tournament_id: i64) -> Option<crate::tournament::groups::Progress> {
+        for set in crate::tournament::db::list_sets_for_tournament(pool, tournament_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|set| set.status == "ready")
+        {
+            let winner = set.slot1_user_id.unwrap().min(set.slot2_user_id.unwrap());
+            report_games(pool, set.id, &[winner, winner]).await;
+            let advanced = decide_and_complete(pool, tournament_id, set.id).await.unwrap();
+            assert!(!advanced.loser_eliminated, "nobody is knocked out of a group round");
+        }
+        crate::tournament::groups::advance(pool, tournament_id).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn a_group_stage_plays_its_rounds_then_draws_a_separated_playoff() {
+        use crate::tournament::groups::Progress;
+
+        let pool = test_pool().await;
+        let tournament = setup_running_groups(&pool, 8, 2, 2).await;
+        let round_one = crate::tournament::db::list_sets_for_tournament(&pool, tournament.id)
+            .await
+            .unwrap();
+        assert_eq!(round_one.len(), 4, "two sets from each group of four");
+        assert!(round_one.iter().all(|set| set.status == "ready"));
+        assert_eq!(
+            crate::tournament::groups::advance(&pool, tournament.id).await.unwrap(),
+            Some(Progress::Waiting)
+        );
+
+        // Snake-dealt: A holds seeds 1, 4, 5, 8 and B holds 2, 3, 6, 7.
+        let group_a = [1, 4, 5, 8];
+        let same_group = |one: i64, two: i64| group_a.contains(&one) == group_a.contains(&two);
+        assert!(opponents(&round_one).iter().all(|&(one, two)| same_group(one, two)));
+
+        assert_eq!(
+            play_group_round(&pool, tournament.id).await,
+            Some(Progress::Paired { round: 2 })
+        );
+        assert_eq!(
+            play_group_round(&pool, tournament.id).await,
+            Some(Progress::Paired { round: 3 })
+        );
+        assert_eq!(
+            play_group_round(&pool, tournament.id).await,
+            Some(Progress::PlayoffDrawn)
+        );
+        assert_eq!(
+            crate::tournament::groups::advance(&pool, tournament.id).await.unwrap(),
+            None,
+            "the playoff's own links carry the event from here"
+        );
+
+        let stages = crate::tournament::db::list_stages_for_tournament(&pool, tournament.id)
+            .await
+            .unwrap();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0].status, "completed");
+        let mut stage_of_round = std::collections::HashMap::new();
+        for stage in &stages {
+            for round in crate::tournament::db::list_rounds_for_stage(&pool, stage.id)
+                .await
+                .unwrap()
+            {
+                stage_of_round.insert(round.id, stage.ordinal);
+            }
+        }
+        let (group_sets, playoff): (Vec<_>, Vec<_>) =
+            crate::tournament::db::list_sets_for_tournament(&pool, tournament.id)
+                .await
+                .unwrap()
+                .into_iter()
+                .partition(|set| stage_of_round[&set.round_id] == 1);
+        let mut group_pairs = opponents(&group_sets);
+        group_pairs.sort_unstable();
+        group_pairs.dedup();
+        assert_eq!(
+            group_pairs.len(),
+            12,
+            "everyone in a group meets everyone else in it once"
+        );
+
+        // The lower id won everything: 1 and 2 top their groups, 4 and 3 follow.
+        let ready: Vec<_> = playoff.into_iter().filter(|set| set.status == "ready").collect();
+        let mut pairs = opponents(&ready);
+        pairs.sort_unstable();
+        assert_eq!(
+            pairs,
+            vec![(1, 3), (2, 4)],
+            "each winner meets the other group's runner-up"
+        );
+        for user_id in 1..=4 {
+            assert_eq!(status_of(&pool, tournament.id, user_id).await, "active");
+        }
+        for user_id in 5..=8 {
+            assert_eq!(status_of(&pool, tournament.id, user_id).await, "eliminated");
+        }
+
+        // The playoff plays out like any single-elimination bracket.
+        loop {
+            let ready: Vec<_> = crate::tournament::db::list_sets_for_tournament(&pool, tournament.id)
+                .await
+                .unwrap()
+                .into_iter()
+                .filter(|set| set.status == "ready")
+                .collect();
+            if ready.is_empty() {
+                break;
+            }
+            for set in ready {
+                let winner = set.slot1_user_id.unwrap().min(set.slot2_user_id.unwrap());
+                report_games(&pool, set.id, &[winner, winner]).await;
+                decide_and_complete(&pool, tournament.id, set.id).await.unwrap();
+            }
+        }
+        let finished = crate::tournament::db::get_tournament(&pool, tournament.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(finished.status, "completed");
+    }
+
+    #[tokio::test]
+    async fn a_group_plan_the_field_cannot_fill_refuses_to_start() {
+        let pool = test_pool().await;
+        let tournament = setup_startable(&pool, 5).await;
+        crate::tournament::db::set_format(&pool, tournament.id, "group", "double_elim", false)
+            .await
+            .unwrap();
+        crate::tournament::db::set_group_stage(&pool, tournament.id, Some(3), None)
+            .await
+            .unwrap();
+        let tournament = crate::tournament::db::get_tournament(&pool, tournament.id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            crate::tournament::start::start(&pool, &tournament).await.unwrap(),
+            crate::tournament::start::StartOutcome::GroupsDontFit {
+                entrants: 5,
+                groups: 3,
+                advance: 2,
+            }
+        );
+        assert!(
+            crate::tournament::db::list_sets_for_tournament(&pool, tournament.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    // Result import.
+    //
+    // `import::apply` calls `completion::finish` exactly like the manual
+    // paths do, which needs a Discord `CacheHttp` — but never fails through
+    // that: every Discord call downstream is best-effort and logs its own
+    // error rather than propagating one (`set_thread::close`,
+    // `bracket_view::reconcile`), so a fake token is enough to exercise the
+    // whole path and verify the database it leaves behind.
+
+    fn fake_http() -> serenity::all::Http {
+        serenity::all::Http::new("faketoken")
+    }
+
+    /// A wide-open throttle: these tests exercise `import::apply`/`sync` in
+    /// isolation, one call each, so there is nothing for a throttle window to
+    /// coalesce.
+    fn fake_throttle() -> crate::tournament::throttle::EditThrottle {
+        crate::tournament::throttle::EditThrottle::new(std::time::Duration::ZERO)
+    }
+
+    fn draft_seat(claimed: bool) -> crate::drafttool::DraftSeat {
+        crate::drafttool::DraftSeat { claimed }
+    }
+
+    fn draft_game(number: i64, map: Option<&str>, winner_slot: Option<i64>) -> crate::drafttool::DraftGame {
+        crate::drafttool::DraftGame {
+            number,
+            map: map.map(str::to_string),
+            civ_by_slot: crate::drafttool::CivBySlot {
+                slot1: None,
+                slot2: None,
+            },
+            winner_slot,
         }
     }
 
@@ -4694,4 +5729,274 @@ mod tests {
             }
         );
     }
+
+    // Set scheduling.
+
+    fn at(hour: u32, minute: u32) -> chrono::DateTime<chrono::Utc> {
+        use chrono::TimeZone;
+        chrono::Utc.with_ymd_and_hms(2099, 1, 1, hour, minute, 0).unwrap()
+    }
+
+    async fn reload_set(pool: &SqlitePool, set_id: i64) -> crate::tournament::db::TournamentSet {
+        crate::tournament::db::get_set(pool, set_id).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn a_proposed_time_waits_on_the_opponent_and_confirming_it_plans_its_reminders() {
+        use crate::tournament::schedule::{self, ScheduleOutcome};
+        let pool = test_pool().await;
+        let tournament = setup_running_bracket(&pool).await;
+        let set = reload_set(&pool, set_ids(&pool, tournament.id).await[0]).await;
+        let (one, two) = (set.slot1_user_id.unwrap(), set.slot2_user_id.unwrap());
+        let now = at(9, 0);
+
+        let outcome = schedule::propose(fake_http(), &pool, &tournament, &set, one, false, at(12, 0), now)
+            .await
+            .unwrap();
+        assert_eq!(outcome, ScheduleOutcome::Proposed { at: at(12, 0) });
+        let set = reload_set(&pool, set.id).await;
+        assert_eq!((set.proposed_at, set.proposed_by), (Some(at(12, 0)), Some(one)));
+        assert_eq!(set.scheduled_at, None, "nothing is agreed until the opponent says so");
+
+        let outcome = schedule::confirm(fake_http(), &pool, &tournament, &set, one, true, now)
+            .await
+            .unwrap();
+        assert_eq!(
+            outcome,
+            ScheduleOutcome::OwnProposal,
+            "not even an admin agrees with themselves"
+        );
+
+        let outcome = schedule::confirm(fake_http(), &pool, &tournament, &set, two, false, now)
+            .await
+            .unwrap();
+        assert_eq!(outcome, ScheduleOutcome::Scheduled { at: at(12, 0) });
+        let set = reload_set(&pool, set.id).await;
+        assert_eq!(set.scheduled_at, Some(at(12, 0)));
+        assert_eq!((set.proposed_at, set.proposed_by), (None, None));
+        assert_eq!(
+            crate::tournament::db::list_set_reminders(&pool, set.id).await.unwrap(),
+            vec![(60, at(11, 0)), (10, at(11, 50))],
+            "the default leads"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_confirm_drawn_before_a_newer_proposal_agrees_to_nothing() {
+        use crate::tournament::schedule::{self, ScheduleOutcome};
+        let pool = test_pool().await;
+        let tournament = setup_running_bracket(&pool).await;
+        let set = reload_set(&pool, set_ids(&pool, tournament.id).await[0]).await;
+        let (one, two) = (set.slot1_user_id.unwrap(), set.slot2_user_id.unwrap());
+        let now = at(9, 0);
+
+        schedule::propose(fake_http(), &pool, &tournament, &set, one, false, at(12, 0), now)
+            .await
+            .unwrap();
+        let stale = reload_set(&pool, set.id).await;
+        schedule::propose(fake_http(), &pool, &tournament, &set, one, false, at(13, 0), now)
+            .await
+            .unwrap();
+
+        let outcome = schedule::confirm(fake_http(), &pool, &tournament, &stale, two, false, now)
+            .await
+            .unwrap();
+        assert_eq!(outcome, ScheduleOutcome::NothingProposed);
+        let set = reload_set(&pool, set.id).await;
+        assert_eq!(set.scheduled_at, None);
+        assert_eq!(set.proposed_at, Some(at(13, 0)), "the newer proposal still stands");
+
+        let outcome = schedule::decline(fake_http(), &pool, &tournament, &set, two, false)
+            .await
+            .unwrap();
+        assert_eq!(outcome, ScheduleOutcome::Declined);
+        assert_eq!(reload_set(&pool, set.id).await.proposed_at, None);
+    }
+
+    #[tokio::test]
+    async fn an_organizer_who_is_neither_player_schedules_outright_with_the_configured_leads() {
+        use crate::tournament::schedule::{self, ScheduleOutcome};
+        let pool = test_pool().await;
+        let tournament = setup_running_bracket(&pool).await;
+        crate::tournament::db::set_schedule_reminders(&pool, tournament.id, "30")
+            .await
+            .unwrap();
+        let tournament = reload(&pool, tournament.id).await;
+        let set = reload_set(&pool, set_ids(&pool, tournament.id).await[0]).await;
+
+        let outcome = schedule::propose(fake_http(), &pool, &tournament, &set, 999, false, at(12, 0), at(9, 0))
+            .await
+            .unwrap();
+        assert_eq!(outcome, ScheduleOutcome::NotYours);
+
+        let outcome = schedule::propose(fake_http(), &pool, &tournament, &set, 999, true, at(12, 0), at(9, 0))
+            .await
+            .unwrap();
+        assert_eq!(outcome, ScheduleOutcome::Scheduled { at: at(12, 0) });
+        assert_eq!(
+            crate::tournament::db::list_set_reminders(&pool, set.id).await.unwrap(),
+            vec![(30, at(11, 30))]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_due_reminder_is_found_until_it_is_marked_and_a_new_time_replaces_it() {
+        let pool = test_pool().await;
+        let tournament = setup_running_bracket(&pool).await;
+        let set_id = set_ids(&pool, tournament.id).await[0];
+        crate::tournament::db::set_scheduled_at(&pool, set_id, at(12, 0), &[(60, at(11, 0)), (10, at(11, 50))])
+            .await
+            .unwrap();
+
+        let due = crate::tournament::db::list_due_set_reminders(&pool, at(10, 59))
+            .await
+            .unwrap();
+        assert!(due.is_empty(), "nothing is due before its moment");
+        let due = crate::tournament::db::list_due_set_reminders(&pool, at(11, 55))
+            .await
+            .unwrap();
+        assert_eq!(
+            due.iter().map(|r| r.lead_minutes).collect::<Vec<_>>(),
+            vec![10, 60],
+            "a restart after both moments finds both"
+        );
+        assert_eq!(due[0].scheduled_at, Some(at(12, 0)));
+
+        crate::tournament::db::mark_set_reminder_posted(&pool, set_id, 10)
+            .await
+            .unwrap();
+        let due = crate::tournament::db::list_due_set_reminders(&pool, at(11, 55))
+            .await
+            .unwrap();
+        assert_eq!(due.iter().map(|r| r.lead_minutes).collect::<Vec<_>>(), vec![60]);
+
+        crate::tournament::db::set_scheduled_at(&pool, set_id, at(18, 0), &[(60, at(17, 0))])
+            .await
+            .unwrap();
+        assert!(
+            crate::tournament::db::list_due_set_reminders(&pool, at(11, 55))
+                .await
+                .unwrap()
+                .is_empty(),
+            "the old time's reminders went with it"
+        );
+        assert_eq!(
+            crate::tournament::db::list_set_reminders(&pool, set_id).await.unwrap(),
+            vec![(60, at(17, 0))]
+        );
+    }
+
+    // The autopilot.
+
+    async fn engage_autopilot(pool: &SqlitePool, id: i64, start: chrono::DateTime<chrono::Utc>) {
+        crate::tournament::db::set_scheduled_start_at(pool, id, start)
+            .await
+            .unwrap();
+        crate::tournament::db::set_autopilot(pool, id, true).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn the_autopilot_opens_check_in_to_close_at_the_start() {
+        let pool = test_pool().await;
+        let id = setup_tournament_in_channel(&pool, "relic-cup", 900, 900).await;
+        let start = chrono::Utc::now() + chrono::Duration::minutes(30);
+        engage_autopilot(&pool, id, start).await;
+
+        // The panel post fails against the fake token; the step is already taken.
+        crate::tournament::autopilot::tick(&fake_http(), &pool, chrono::Utc::now()).await;
+
+        let tournament = reload(&pool, id).await;
+        assert_eq!(tournament.status, "checkin");
+        let closes_at = tournament.checkin_closes_at.unwrap();
+        assert!(
+            (closes_at - start).num_seconds().abs() < 60,
+            "closes at {closes_at}, starts at {start}"
+        );
+        assert!(tournament.autopilot, "a step taken is not a refusal");
+    }
+
+    #[tokio::test]
+    async fn reopening_registration_turns_the_autopilot_off_so_check_in_stays_closed() {
+        let pool = test_pool().await;
+        let id = setup_tournament_in_channel(&pool, "relic-cup", 900, 900).await;
+        engage_autopilot(&pool, id, chrono::Utc::now() + chrono::Duration::minutes(30)).await;
+        crate::tournament::autopilot::tick(&fake_http(), &pool, chrono::Utc::now()).await;
+        assert_eq!(reload(&pool, id).await.status, "checkin");
+
+        let outcome = crate::tournament::checkin::reopen_registration(&pool, &reload(&pool, id).await)
+            .await
+            .unwrap();
+        assert!(matches!(
+            outcome,
+            crate::tournament::checkin::ReopenRegistrationOutcome::Reopened {
+                autopilot_off: true,
+                ..
+            }
+        ));
+
+        crate::tournament::autopilot::tick(&fake_http(), &pool, chrono::Utc::now()).await;
+        let tournament = reload(&pool, id).await;
+        assert_eq!(tournament.status, "registration");
+        assert!(!tournament.autopilot);
+    }
+
+    #[tokio::test]
+    async fn the_closing_ping_is_claimed_once_per_close_time() {
+        let pool = test_pool().await;
+        let tournament = setup_tournament(&pool, "checkin").await;
+        let now = chrono::Utc::now();
+
+        assert!(
+            crate::tournament::db::mark_checkin_reminded(&pool, tournament.id, now)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !crate::tournament::db::mark_checkin_reminded(&pool, tournament.id, now)
+                .await
+                .unwrap()
+        );
+
+        // A new close time is owed a ping of its own.
+        crate::tournament::db::set_checkin_closes_at(&pool, tournament.id, Some(now))
+            .await
+            .unwrap();
+        assert_eq!(reload(&pool, tournament.id).await.checkin_reminded_at, None);
+        assert!(
+            crate::tournament::db::mark_checkin_reminded(&pool, tournament.id, now)
+                .await
+                .unwrap()
+        );
+
+        let seeding = setup_tournament_in_channel(&pool, "other", 900, 900).await;
+        crate::tournament::db::update_tournament_status(&pool, seeding, "seeding")
+            .await
+            .unwrap();
+        assert!(
+            !crate::tournament::db::mark_checkin_reminded(&pool, seeding, now)
+                .await
+                .unwrap(),
+            "nothing to remind once check-in is over"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_refused_start_turns_the_autopilot_off_and_leaves_the_tournament_as_it_was() {
+        let pool = test_pool().await;
+        let tournament = setup_tournament(&pool, "seeding").await;
+        // No preset, so `setup` still reports something missing and start refuses.
+        engage_autopilot(&pool, tournament.id, chrono::Utc::now() - chrono::Duration::minutes(1)).await;
+
+        crate::tournament::autopilot::tick(&fake_http(), &pool, chrono::Utc::now()).await;
+
+        let tournament = reload(&pool, tournament.id).await;
+        assert_eq!(tournament.status, "seeding");
+        assert!(!tournament.autopilot);
+        assert!(
+            !crate::tournament::db::disengage_autopilot(&pool, tournament.id)
+                .await
+                .unwrap(),
+            "already off, so a second refusal would not be announced again"
+        );
+    }
 }
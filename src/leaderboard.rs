@@ -0,0 +1,507 @@
+//! `/leaderboard`: the ranked board as a private, paged reply, sortable and
+//! filterable, read from what the last refresh stored in `leaderboard_entries`
+//! rather than from aoe4world — a click costs one query, not a round of API
+//! calls.
+//!
+//! The buttons follow `tournament::action`: a custom_id is
+//! `"<action>:<view_id>"`, and the view's sort, filters and page live in
+//! `leaderboard_views`, the way a tournament button resolves its tournament by
+//! id. `Pager` is the home guild's counterpart of `tournament::dispatch`.
+
+use crate::Error;
+use crate::aoe4world::{civilization_name, rank_level_name};
+use crate::db::{
+    LeaderboardEntry, LeaderboardView, get_leaderboard_view, list_leaderboard, set_leaderboard_view_page, to_db_id,
+};
+use crate::guilds::{Feature, Guilds};
+use crate::locale::Locale;
+use crate::ranked::escape;
+use serenity::all::{
+    ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton, CreateInteractionResponse,
+    CreateInteractionResponseMessage, Interaction,
+};
+use serenity::async_trait;
+use serenity::prelude::*;
+use sqlx::SqlitePool;
+use std::cmp::Ordering;
+use tracing::error;
+
+/// Members per page. Ten one-line entries sit well inside a message and still
+/// fit a phone screen without scrolling.
+const PAGE_SIZE: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Sort {
+    Rating,
+    Elo,
+    RecentMax,
+    Games,
+    WinRate,
+}
+
+impl Sort {
+    /// How `leaderboard_views.sort` spells it.
+    pub(crate) fn tag(self) -> &'static str {
+        match self {
+            Sort::Rating => "rating",
+            Sort::Elo => "elo",
+            Sort::RecentMax => "recent_max",
+            Sort::Games => "games",
+            Sort::WinRate => "win_rate",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "rating" => Some(Sort::Rating),
+            "elo" => Some(Sort::Elo),
+            "recent_max" => Some(Sort::RecentMax),
+            "games" => Some(Sort::Games),
+            "win_rate" => Some(Sort::WinRate),
+            _ => None,
+        }
+    }
+
+    fn name(self, locale: Locale) -> &'static str {
+        match self {
+            Sort::Rating => locale.pick("排名積分", "rating"),
+            Sort::Elo => "Elo",
+            Sort::RecentMax => locale.pick("近期最高積分", "recent max rating"),
+            Sort::Games => locale.pick("場次", "games played"),
+            Sort::WinRate => locale.pick("勝率", "win rate"),
+        }
+    }
+
+    /// Highest first. A tie goes to the higher rating, then the better global
+    /// rank, so a page never reshuffles between two clicks.
+    pub(crate) fn order(self, a: &LeaderboardEntry, b: &LeaderboardEntry) -> Ordering {
+        let key = match self {
+            Sort::Rating => b.rating.cmp(&a.rating),
+            Sort::Elo => b.elo.cmp(&a.elo),
+            Sort::RecentMax => b.recent_max_rating.cmp(&a.recent_max_rating),
+            Sort::Games => b.games_played.cmp(&a.games_played),
+            Sort::WinRate => b.win_rate.total_cmp(&a.win_rate),
+        };
+        key.then_with(|| b.rating.cmp(&a.rating))
+            .then_with(|| a.global_rank.cmp(&b.global_rank))
+            .then_with(|| a.user_id.cmp(&b.user_id))
+    }
+
+    /// The number the entry is sorted by, as its line leads with it.
+    fn value(self, entry: &LeaderboardEntry, locale: Locale) -> String {
+        match self {
+            Sort::Rating => format!("{} {}", locale.pick("積分", "Rating"), entry.rating),
+            Sort::Elo => format!("Elo {}", entry.elo),
+            Sort::RecentMax => format!("{} {}", locale.pick("近期最高", "Max"), entry.recent_max_rating),
+            Sort::Games => format!("{} {}", entry.games_played, locale.pick("場", "games")),
+            Sort::WinRate => format!("{} {}%", locale.pick("勝率", "Won"), entry.win_rate.round()),
+        }
+    }
+}
+
+/// Whether `rank_level` (`diamond_2`) is in `tier` (`diamond`).
+fn in_tier(rank_level: &str, tier: &str) -> bool {
+    rank_level.rsplit_once('_').is_some_and(|(level, _)| level == tier)
+}
+
+/// The entries `view` shows, in its order, across every page.
+pub(crate) fn select<'a>(entries: &'a [LeaderboardEntry], view: &LeaderboardView) -> Vec<&'a LeaderboardEntry> {
+    let sort = Sort::from_tag(&view.sort).unwrap_or(Sort::Rating);
+    let mut selected = entries
+        .iter()
+        .filter(|entry| view.tier.as_deref().is_none_or(|tier| in_tier(&entry.rank_level, tier)))
+        .filter(|entry| view.civ.as_deref().is_none_or(|civ| entry.favorite_civ == civ))
+        .collect::<Vec<_>>();
+    selected.sort_by(|a, b| sort.order(a, b));
+    selected
+}
+
+fn page_count(entries: usize) -> i64 {
+    entries.div_ceil(PAGE_SIZE).max(1) as i64
+}
+
+/// Where a press lands, clamped to the pages there are now. The board may have
+/// shrunk under a view since its last click, so the stored page is clamped too.
+fn turn(action: PageAction, page: i64, pages: i64) -> i64 {
+    let page = match action {
+        PageAction::First => 0,
+        PageAction::Prev => page - 1,
+        PageAction::Next => page + 1,
+        PageAction::Last => pages - 1,
+    };
+    page.clamp(0, pages - 1)
+}
+
+/// `page` of `selected`, numbered by place on the filtered board.
+fn render(selected: &[&LeaderboardEntry], view: &LeaderboardView, page: i64, locale: Locale) -> String {
+    let sort = Sort::from_tag(&view.sort).unwrap_or(Sort::Rating);
+    let pages = page_count(selected.len());
+    let mut heading = locale.pick(
+        format!("**排行榜** · 依{}排序", sort.name(locale)),
+        format!("**Leaderboard** · by {}", sort.name(locale)),
+    );
+    if let Some(tier) = &view.tier {
+        heading.push_str(&format!(" · {}", tier_name(tier, locale)));
+    }
+    if let Some(civ) = &view.civ {
+        let civ = civilization_name(civ, locale);
+        heading.push_str(&locale.pick(format!(" · 愛用{civ}"), format!(" · {civ} players")));
+    }
+
+    let Some(first) = selected.first() else {
+        return format!("{heading}\n{}", locale.pick("沒有符合條件的成員", "Nobody matches"));
+    };
+    let (page_number, members, at) = (page + 1, selected.len(), first.refreshed_at.timestamp());
+    let mut out = locale.pick(
+        format!("{heading}\n第 {page_number}/{pages} 頁 · 共 {members} 人 · 資料更新於 <t:{at}:R>\n"),
+        format!("{heading}\nPage {page_number}/{pages} · {members} members · as of <t:{at}:R>\n"),
+    );
+    let start = page as usize * PAGE_SIZE;
+    for (index, entry) in selected.iter().enumerate().skip(start).take(PAGE_SIZE) {
+        out.push_str(&format!(
+            "\n`{:>3}` **{}** · {} · {}（{}）",
+            index + 1,
+            sort.value(entry, locale),
+            rank_level_name(&entry.rank_level, locale),
+            escape(&entry.discord_display),
+            escape(&entry.aoe4_name),
+        ));
+    }
+    out
+}
+
+/// `conqueror` and friends without a division: `rank_level_name` wants the
+/// `_<n>`, so one is lent and taken back off.
+fn tier_name(tier: &str, locale: Locale) -> String {
+    rank_level_name(&format!("{tier}_1"), locale)
+        .trim_end_matches('1')
+        .trim_end()
+        .to_string()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum PageAction {
+    First,
+    Prev,
+    Next,
+    Last,
+}
+
+impl PageAction {
+    fn tag(self) -> &'static str {
+        match self {
+            PageAction::First => "lbfirst",
+            PageAction::Prev => "lbprev",
+            PageAction::Next => "lbnext",
+            PageAction::Last => "lblast",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "lbfirst" => Some(PageAction::First),
+            "lbprev" => Some(PageAction::Prev),
+            "lbnext" => Some(PageAction::Next),
+            "lblast" => Some(PageAction::Last),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn custom_id(self, view_id: i64) -> String {
+        format!("{}:{view_id}", self.tag())
+    }
+}
+
+/// Pure, like `tournament::action::parse_custom_id`, and `None` for anything
+/// that is not a leaderboard button — a tournament's, or one from an older
+/// deploy.
+pub(crate) fn parse_custom_id(custom_id: &str) -> Option<(PageAction, i64)> {
+    let (tag, view_id) = custom_id.split_once(':')?;
+    let action = PageAction::from_tag(tag)?;
+    let view_id = view_id.parse().ok()?;
+    Some((action, view_id))
+}
+
+/// First/previous/next/last, the ones that would go nowhere disabled. No row
+/// at all for a single page.
+fn render_components(view_id: i64, page: i64, pages: i64) -> Vec<CreateActionRow> {
+    if pages <= 1 {
+        return Vec::new();
+    }
+    let button = |action: PageAction, label: &str, disabled: bool| {
+        CreateButton::new(action.custom_id(view_id))
+            .label(label)
+            .style(ButtonStyle::Secondary)
+            .disabled(disabled)
+    };
+    let at_start = page == 0;
+    let at_end = page == pages - 1;
+    vec![CreateActionRow::Buttons(vec![
+        button(PageAction::First, "⏮", at_start),
+        button(PageAction::Prev, "◀", at_start),
+        button(PageAction::Next, "▶", at_end),
+        button(PageAction::Last, "⏭", at_end),
+    ])]
+}
+
+/// `view` at its stored page, as the reply's text and buttons. `None` when no
+/// refresh has stored a board yet.
+pub(crate) async fn view_message(
+    pool: &SqlitePool,
+    view: &LeaderboardView,
+    locale: Locale,
+) -> Result<Option<(String, Vec<CreateActionRow>)>, Error> {
+    let entries = list_leaderboard(pool).await?;
+    if entries.is_empty() {
+        return Ok(None);
+    }
+    let selected = select(&entries, view);
+    let pages = page_count(selected.len());
+    let page = view.page.clamp(0, pages - 1);
+    Ok(Some((
+        render(&selected, view, page, locale),
+        render_components(view.id, page, pages),
+    )))
+}
+
+/// The leaderboard's buttons, in the home guild.
+pub(crate) struct Pager {
+    guilds: Guilds,
+    pool: SqlitePool,
+}
+
+impl Pager {
+    pub(crate) fn new(guilds: Guilds, pool: SqlitePool) -> Self {
+        Self { guilds, pool }
+    }
+
+    /// Moves the view and redraws the reply in place. A view too old to find —
+    /// cleared out by `create_leaderboard_view` — says so instead.
+    async fn handle(
+        &self,
+        component: &ComponentInteraction,
+        action: PageAction,
+        view_id: i64,
+    ) -> Result<CreateInteractionResponse, Error> {
+        let locale = Locale::from_discord_locale(&component.locale);
+        let Some(mut view) = get_leaderboard_view(&self.pool, view_id).await? else {
+            return Ok(expired(locale));
+        };
+        if to_db_id(component.user.id) != view.user_id {
+            // Ephemeral, so nobody else should ever get to press these.
+            return Ok(expired(locale));
+        }
+        let entries = list_leaderboard(&self.pool).await?;
+        let selected = select(&entries, &view);
+        let pages = page_count(selected.len());
+        view.page = turn(action, view.page, pages);
+        set_leaderboard_view_page(&self.pool, view.id, view.page).await?;
+        Ok(CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .content(render(&selected, &view, view.page, locale))
+                .components(render_components(view.id, view.page, pages)),
+        ))
+    }
+}
+
+fn expired(locale: Locale) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(locale.pick(
+                "這個排行榜已過期，請重新使用 /leaderboard",
+                "This leaderboard has expired; run /leaderboard again",
+            )),
+    )
+}
+
+#[async_trait]
+impl EventHandler for Pager {
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        let Interaction::Component(component) = interaction else {
+            return;
+        };
+        if !self.guilds.allows(Feature::Home, component.guild_id) {
+            return;
+        }
+        let Some((action, view_id)) = parse_custom_id(&component.data.custom_id) else {
+            return;
+        };
+
+        let response = match self.handle(&component, action, view_id).await {
+            Ok(response) => response,
+            Err(err) => {
+                error!("failed to turn leaderboard view {view_id}: {err:?}");
+                return;
+            },
+        };
+        if let Err(err) = component.create_response(&ctx.http, response).await {
+            error!("failed to answer a leaderboard button: {err:?}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn entry(user_id: i64, rating: i64, rank_level: &str, civ: &str) -> LeaderboardEntry {
+        LeaderboardEntry {
+            user_id,
+            aoe4_id: user_id * 10,
+            aoe4_name: format!("P{user_id}"),
+            discord_display: format!("D{user_id}"),
+            rank_level: rank_level.to_string(),
+            rating,
+            elo: 2000 - rating,
+            recent_max_rating: rating + 10,
+            global_rank: 10_000 - rating,
+            games_played: user_id * 5,
+            win_rate: 50.0,
+            favorite_civ: civ.to_string(),
+            refreshed_at: DateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn view(sort: Sort, tier: Option<&str>, civ: Option<&str>) -> LeaderboardView {
+        LeaderboardView {
+            id: 7,
+            user_id: 1,
+            sort: sort.tag().to_string(),
+            tier: tier.map(str::to_string),
+            civ: civ.map(str::to_string),
+            page: 0,
+        }
+    }
+
+    fn board() -> Vec<LeaderboardEntry> {
+        vec![
+            entry(1, 1200, "platinum_1", "english"),
+            entry(2, 1500, "diamond_2", "mongols"),
+            entry(3, 1400, "diamond_1", "english"),
+            entry(4, 900, "gold_3", "french"),
+        ]
+    }
+
+    fn ids(selected: &[&LeaderboardEntry]) -> Vec<i64> {
+        selected.iter().map(|entry| entry.user_id).collect()
+    }
+
+    #[test]
+    fn each_sort_puts_its_highest_first() {
+        let board = board();
+        assert_eq!(ids(&select(&board, &view(Sort::Rating, None, None))), [2, 3, 1, 4]);
+        assert_eq!(ids(&select(&board, &view(Sort::Elo, None, None))), [4, 1, 3, 2]);
+        assert_eq!(ids(&select(&board, &view(Sort::Games, None, None))), [4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn a_tie_goes_to_the_higher_rating() {
+        let board = board();
+        // Every win rate is 50%.
+        assert_eq!(ids(&select(&board, &view(Sort::WinRate, None, None))), [2, 3, 1, 4]);
+    }
+
+    #[test]
+    fn filters_narrow_by_tier_and_by_favourite_civ() {
+        let board = board();
+        assert_eq!(ids(&select(&board, &view(Sort::Rating, Some("diamond"), None))), [2, 3]);
+        assert_eq!(ids(&select(&board, &view(Sort::Rating, None, Some("english")))), [3, 1]);
+        assert_eq!(
+            ids(&select(&board, &view(Sort::Rating, Some("diamond"), Some("english")))),
+            [3]
+        );
+        assert!(select(&board, &view(Sort::Rating, Some("conqueror"), None)).is_empty());
+    }
+
+    #[test]
+    fn a_tier_is_matched_whole_rather_than_by_prefix() {
+        assert!(in_tier("gold_3", "gold"));
+        assert!(!in_tier("unranked", "gold"));
+        assert!(!in_tier("diamond_1", "dia"));
+    }
+
+    #[test]
+    fn pages_turn_within_bounds() {
+        assert_eq!(turn(PageAction::Next, 0, 3), 1);
+        assert_eq!(turn(PageAction::Next, 2, 3), 2);
+        assert_eq!(turn(PageAction::Prev, 0, 3), 0);
+        assert_eq!(turn(PageAction::Last, 0, 3), 2);
+        assert_eq!(turn(PageAction::First, 2, 3), 0);
+        // The board shrank from five pages to two under the view.
+        assert_eq!(turn(PageAction::Prev, 4, 2), 1);
+    }
+
+    #[test]
+    fn a_page_is_numbered_by_place_on_the_filtered_board() {
+        let board = (1..=23)
+            .map(|id| entry(id, 1000 + id, "gold_1", "english"))
+            .collect::<Vec<_>>();
+        let view = view(Sort::Rating, None, None);
+        let selected = select(&board, &view);
+        assert_eq!(page_count(selected.len()), 3);
+        let last = render(&selected, &view, 2, Locale::ZhTw);
+        assert!(last.contains("第 3/3 頁 · 共 23 人"), "{last}");
+        assert!(last.contains("` 21` **積分 1003**"), "{last}");
+        assert_eq!(last.matches("\n`").count(), 3);
+    }
+
+    #[test]
+    fn an_empty_selection_says_so() {
+        let board = board();
+        let view = view(Sort::Rating, Some("conqueror"), None);
+        assert!(render(&select(&board, &view), &view, 0, Locale::ZhTw).ends_with("沒有符合條件的成員"));
+        assert_eq!(page_count(0), 1);
+    }
+
+    #[test]
+    fn every_page_action_round_trips_through_its_custom_id() {
+        for action in [PageAction::First, PageAction::Prev, PageAction::Next, PageAction::Last] {
+            assert_eq!(parse_custom_id(&action.custom_id(42)), Some((action, 42)));
+        }
+        assert_eq!(parse_custom_id("register:42"), None);
+        assert_eq!(parse_custom_id("lbnext:x"), None);
+    }
+
+    #[test]
+    fn the_ends_of_the_board_disable_the_buttons_that_go_past_them() {
+        assert!(render_components(7, 0, 1).is_empty());
+        let CreateActionRow::Buttons(buttons) = &render_components(7, 0, 3)[0] else {
+            panic!("expected a button row");
+        };
+        assert_eq!(
+            buttons[1],
+            CreateButton::new(PageAction::Prev.custom_id(7))
+                .label("◀")
+                .style(ButtonStyle::Secondary)
+                .disabled(true)
+        );
+        assert_eq!(
+            buttons[2],
+            CreateButton::new(PageAction::Next.custom_id(7))
+                .label("▶")
+                .style(ButtonStyle::Secondary)
+                .disabled(false)
+        );
+    }
+
+    #[test]
+    fn a_tier_reads_without_its_division() {
+        assert_eq!(tier_name("diamond", Locale::ZhTw), "鑽石");
+        assert_eq!(tier_name("conqueror", Locale::ZhTw), "征服者");
+        assert_eq!(tier_name("conqueror", Locale::En), "Conqueror");
+    }
+
+    #[test]
+    fn an_english_page_reads_in_english() {
+        let board = board();
+        let view = view(Sort::Games, Some("diamond"), None);
+        let page = render(&select(&board, &view), &view, 0, Locale::En);
+        assert!(
+            page.starts_with("**Leaderboard** · by games played · Diamond\nPage 1/1 · 2 members"),
+            "{page}"
+        );
+        assert!(page.contains("**15 games** · Diamond 1"), "{page}");
+    }
+}
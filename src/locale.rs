@@ -1,5 +1,6 @@
-//! Two languages for the tournament feature's reply text: Traditional Chinese,
-//! and English as the fallback for everything else.
+//! Two languages for reply text — the tournament feature's, and the home
+//! guild's ranked commands': Traditional Chinese, and English as the fallback
+//! for everything else.
 //!
 //! **Detection is per-interaction, not per-guild.** Every interaction carries the
 //! invoking user's own client language; `guild_locale` also exists and is
@@ -7,7 +8,8 @@
 //!
 //! **Shared surfaces don't use this.** A panel is one message many people read
 //! and re-render by pressing its buttons, so picking any one reader's language
-//! would make it flip; `panel` and `checkin_panel` render both languages instead.
+//! would make it flip; `panel`, `checkin_panel` and the ranked board render both
+//! languages instead.
 //! This type is for text with exactly one reader.
 
 use crate::Context;
@@ -1,4 +1,4 @@
-use crate::emperor::Emperor;
+use crate::emperor::{Emperor, ReactionRules};
 use crate::guilds::{Feature, Guilds};
 use crate::refresh::do_refresh;
 use crate::tournament::throttle::EditThrottle;
@@ -15,20 +15,33 @@ use tracing_subscriber::EnvFilter;
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, Data, Error>;
 
+mod account_file;
 mod aoe4world;
+mod card;
+mod civ_stats;
 mod commands;
 mod db;
+mod debounce;
+mod digest;
 mod drafttool;
 mod emperor;
 mod errors;
+mod feed;
 mod guilds;
+mod h2h;
+mod history;
 #[cfg(test)]
 mod integration_tests;
+mod leaderboard;
 mod locale;
 mod ranked;
 mod refresh;
+mod reminder;
 mod reply;
+mod season;
+mod tier_roles;
 mod tournament;
+mod verify;
 
 struct Data {
     database: SqlitePool,
@@ -38,6 +51,9 @@ struct Data {
     // button-triggered one must coalesce against each other, not just within
     // their own path, which is only true if both hold the same instance.
     panel_throttle: Arc<EditThrottle>,
+    // Shared with `emperor::Emperor` the same way, so `/reaction add|remove`
+    // and `/reactions off|on` invalidate what the message handler has cached.
+    reaction_rules: Arc<ReactionRules>,
 }
 
 #[tokio::main]
@@ -84,10 +100,15 @@ async fn main() {
     // the `Dispatcher` event handler (the button path) below — see the field
     // doc comment on `Data::panel_throttle`.
     let panel_throttle = Arc::new(EditThrottle::new(tournament::panel::PANEL_EDIT_MIN_INTERVAL));
+    let reaction_rules = Arc::new(ReactionRules::default());
 
     let pool_cloned = pool.clone();
     let boot_pool = pool.clone();
+    let board_pool = pool.clone();
+    let board_throttle = panel_throttle.clone();
     let panel_throttle_cloned = panel_throttle.clone();
+    let board_reactions = reaction_rules.clone();
+    let reaction_rules_cloned = reaction_rules.clone();
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
             commands: all_commands,
@@ -113,10 +134,24 @@ async fn main() {
                     tournament::startup::reconcile_all(boot_http, &boot_pool).await;
                 });
 
+                // Same shape for the ranked board: a message deleted by hand is
+                // put back rather than waiting up to twelve hours for the cron.
+                let board_http = ctx.http.clone();
+                let board_data = Data {
+                    database: board_pool,
+                    guilds,
+                    panel_throttle: board_throttle,
+                    reaction_rules: board_reactions,
+                };
+                tokio::spawn(async move {
+                    refresh::reconcile_board(&board_http, &board_data).await;
+                });
+
                 Ok(Data {
                     database: pool_cloned,
                     guilds,
                     panel_throttle: panel_throttle_cloned,
+                    reaction_rules: reaction_rules_cloned,
                 })
             })
         })
@@ -129,7 +164,8 @@ async fn main() {
         GatewayIntents::non_privileged() | GatewayIntents::MESSAGE_CONTENT,
     )
     .framework(framework)
-    .event_handler(Emperor::new(guilds))
+    .event_handler(Emperor::new(guilds, pool.clone(), reaction_rules.clone()))
+    .event_handler(leaderboard::Pager::new(guilds, pool.clone()))
     .event_handler(tournament::dispatch::Dispatcher::new(
         guilds,
         pool.clone(),
@@ -139,6 +175,18 @@ async fn main() {
     .expect("Err creating client");
     info!("prepared client");
 
+    let feed_token = token.clone();
+    let feed_pool = pool.clone();
+    let feed_throttle = panel_throttle.clone();
+    let feed_reactions = reaction_rules.clone();
+    let digest_token = token.clone();
+    let digest_pool = pool.clone();
+    let digest_throttle = panel_throttle.clone();
+    let digest_reactions = reaction_rules.clone();
+    let schedule_token = token.clone();
+    let schedule_pool = pool.clone();
+    let schedule_throttle = panel_throttle.clone();
+    let schedule_reactions = reaction_rules.clone();
     let sched = JobScheduler::new().await.unwrap();
     sched
         .add(
@@ -147,15 +195,98 @@ async fn main() {
                     let token_cloned = token.clone();
                     let pool_cloned = pool.clone();
                     let panel_throttle_cloned = panel_throttle.clone();
+                    let reaction_rules_cloned = reaction_rules.clone();
                     async move {
                         let http = Http::new(&token_cloned);
                         let data = Data {
                             database: pool_cloned,
                             guilds,
                             panel_throttle: panel_throttle_cloned,
+                            reaction_rules: reaction_rules_cloned,
                         };
                         info!("refresh triggered by cron");
-                        do_refresh(&http, &data).await.unwrap();
+                        let members = do_refresh(&http, &data).await.unwrap();
+                        reminder::run(&http, &data, &members).await;
+                    }
+                })
+            })
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    // The game feed polls far more often than the board refreshes: a result is
+    // news for an hour, not for half a day.
+    sched
+        .add(
+            Job::new_async("0 */20 * * * *", move |_uuid, _l| {
+                Box::pin({
+                    let token_cloned = feed_token.clone();
+                    let pool_cloned = feed_pool.clone();
+                    let panel_throttle_cloned = feed_throttle.clone();
+                    let reaction_rules_cloned = feed_reactions.clone();
+                    async move {
+                        let http = Http::new(&token_cloned);
+                        let data = Data {
+                            database: pool_cloned,
+                            guilds,
+                            panel_throttle: panel_throttle_cloned,
+                            reaction_rules: reaction_rules_cloned,
+                        };
+                        feed::run(&http, &data).await;
+                    }
+                })
+            })
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    // Monday 00:30 UTC, half an hour after a refresh, so the week ends on fresh
+    // snapshots — Monday morning in Taiwan.
+    sched
+        .add(
+            Job::new_async("0 30 0 * * Mon", move |_uuid, _l| {
+                Box::pin({
+                    let token_cloned = digest_token.clone();
+                    let pool_cloned = digest_pool.clone();
+                    let panel_throttle_cloned = digest_throttle.clone();
+                    let reaction_rules_cloned = digest_reactions.clone();
+                    async move {
+                        let http = Http::new(&token_cloned);
+                        let data = Data {
+                            database: pool_cloned,
+                            guilds,
+                            panel_throttle: panel_throttle_cloned,
+                            reaction_rules: reaction_rules_cloned,
+                        };
+                        digest::run(&http, &data).await;
+                    }
+                })
+            })
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    // Every minute: set reminders and autopilot steps are due to the minute, and
+    // a tick with nothing due is a couple of small queries. What is due lives in
+    // the database, so a restart picks up where the last tick left off.
+    sched
+        .add(
+            Job::new_async("0 * * * * *", move |_uuid, _l| {
+                Box::pin({
+                    let token_cloned = schedule_token.clone();
+                    let pool_cloned = schedule_pool.clone();
+                    let panel_throttle_cloned = schedule_throttle.clone();
+                    let reaction_rules_cloned = schedule_reactions.clone();
+                    async move {
+                        let http = Http::new(&token_cloned);
+                        let data = Data {
+                            database: pool_cloned,
+                            guilds,
+                            panel_throttle: panel_throttle_cloned,
+                            reaction_rules: reaction_rules_cloned,
+                        };
+                        tournament::schedule::run(&http, &data).await;
+                        tournament::autopilot::run(&http, &data).await;
                     }
                 })
             })
@@ -1,13 +1,24 @@
 use crate::Data;
-use crate::aoe4world::{CivData, fetch_profile, rank_level_zh};
-use crate::db::{Account, to_user_id};
+use crate::aoe4world::{
+    ApiError, CivData, Ladder, Modes, Profile, civilization_bilingual, fetch_profile, rank_level_name,
+};
+use crate::card::Card;
+use crate::db::{Account, LeaderboardCiv, LeaderboardEntry, RatingSnapshot, to_user_id};
+use crate::locale::Locale;
 use chrono::{DateTime, Utc};
 use serenity::all::Http;
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use tracing::info;
 
+/// How many civilizations the card shows.
+const CARD_CIVS: usize = 3;
+
 pub(crate) struct RankedPlayer {
+    /// The Discord account it is bound to; `None` for `/查分`'s unbound lookup.
+    user_id: Option<i64>,
+    /// Whether it is that account's chosen main.
+    main: bool,
     aoe4_name: String,
     aoe4_id: i64,
     discord_display: String,
@@ -18,53 +29,257 @@ pub(crate) struct RankedPlayer {
     recent_max_rating: i32,
     elo: i32,
     favorite_civ: CivData,
+    /// Every civilization played on this ladder, most played first — the card
+    /// draws the first `CARD_CIVS`, `/civs` stores them all.
+    civilizations: Vec<CivData>,
     games_played: i32,
     win_rate: f64,
     last_played: DateTime<Utc>,
+    /// Which leaderboard every number above comes from.
+    ladder: Ladder,
+    /// Every ladder the profile appears on, this one included — what `/查分`
+    /// lists under the headline numbers.
+    ladders: Vec<LadderLine>,
     alts: Vec<RankedPlayer>,
 }
 
+/// One ladder's standing, in the short form `/查分` lists all of them in.
+pub(crate) struct LadderLine {
+    ladder: Ladder,
+    rank_level: String,
+    rating: i32,
+    global_rank: Option<i32>,
+    games_played: i32,
+    win_rate: f64,
+}
+
+impl LadderLine {
+    fn render(&self, locale: Locale) -> String {
+        let mut line = format!("{}: ", self.ladder.name(locale));
+        // Quick match has no tiers; `unranked` there is absence, not a placement.
+        if self.rank_level != "unranked" {
+            line.push_str(&format!("{}, ", rank_level_name(&self.rank_level, locale)));
+        }
+        line.push_str(&locale.pick(format!("積分 {}", self.rating), format!("rating {}", self.rating)));
+        if let Some(rank) = self.global_rank {
+            line.push_str(&locale.pick(format!(" (全球第{rank}名)"), format!(" (#{rank} worldwide)")));
+        }
+        let (games, win_rate) = (self.games_played, self.win_rate.round());
+        line.push_str(&locale.pick(
+            format!(", 場次 {games} (勝率: {win_rate}%)"),
+            format!(", {games} games ({win_rate}% won)"),
+        ));
+        line
+    }
+}
+
+/// Which ladder a board ranks members by.
+///
+/// Global ranks on two different ladders are not comparable, so `Best` orders
+/// by rating instead — the one number both ranked ladders share a scale for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BoardMode {
+    /// Ranked solo, the board as it always was.
+    Solo,
+    /// Ranked team.
+    Team,
+    /// Each member on whichever of ranked solo and ranked team they rate
+    /// higher in.
+    Best,
+}
+
+impl BoardMode {
+    /// `solo | team | best`, as `RANK_BOARD_MODE` spells it.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "solo" => Some(BoardMode::Solo),
+            "team" => Some(BoardMode::Team),
+            "best" => Some(BoardMode::Best),
+            _ => None,
+        }
+    }
+
+    /// Top of the board first. Also what picks a member's main out of their
+    /// alts when they have not chosen one with `/bind main`.
+    pub(crate) fn order(self, a: &RankedPlayer, b: &RankedPlayer) -> Ordering {
+        match self {
+            BoardMode::Solo | BoardMode::Team => a.cmp(b),
+            BoardMode::Best => b.rating.cmp(&a.rating).then_with(|| a.cmp(b)),
+        }
+    }
+}
+
 impl RankedPlayer {
     pub(crate) fn append_alt(&mut self, alt: RankedPlayer) {
         self.alts.push(alt);
     }
 
-    pub(crate) fn info(&self) -> String {
-        format!(
-            "遊戲ID: {}\n\
-            階級: {}\n\
-            全球排名: {}, 遊戲場次: {} (勝率: {}%)\n\
-            愛用文明: {} (出場率 {}%), 上次遊玩: {}\n\
-            排名積分: {}, 近期最高積分: {}, Elo: {}",
-            self.aoe4_name,
-            self.rank_level(),
-            self.global_rank,
-            self.games_played,
-            self.win_rate.round(),
-            self.favorite_civ.civilization(),
-            self.favorite_civ.pick_rate.round(),
-            self.last_played(),
-            self.rating,
-            self.recent_max_rating,
-            self.elo
-        )
+    pub(crate) fn info(&self, locale: Locale) -> String {
+        let mut info = match locale {
+            Locale::ZhTw => format!(
+                "遊戲ID: {}\n\
+                階級: {}{}\n\
+                全球排名: {}, 遊戲場次: {} (勝率: {}%)\n\
+                愛用文明: {} (出場率 {}%), 上次遊玩: {}\n\
+                排名積分: {}, 近期最高積分: {}, Elo: {}",
+                self.aoe4_name,
+                self.rank_level(locale),
+                self.ladder_note(locale),
+                self.global_rank,
+                self.games_played,
+                self.win_rate.round(),
+                self.favorite_civ.civilization(locale),
+                self.favorite_civ.pick_rate.round(),
+                self.last_played(locale),
+                self.rating,
+                self.recent_max_rating,
+                self.elo
+            ),
+            Locale::En => format!(
+                "Player: {}\n\
+                Tier: {}{}\n\
+                Global rank: {}, games: {} (win rate: {}%)\n\
+                Favourite civ: {} (picked {}%), last played: {}\n\
+                Rating: {}, recent max: {}, Elo: {}",
+                self.aoe4_name,
+                self.rank_level(locale),
+                self.ladder_note(locale),
+                self.global_rank,
+                self.games_played,
+                self.win_rate.round(),
+                self.favorite_civ.civilization(locale),
+                self.favorite_civ.pick_rate.round(),
+                self.last_played(locale),
+                self.rating,
+                self.recent_max_rating,
+                self.elo
+            ),
+        };
+        if let Some(lines) = self.other_ladders(locale) {
+            info.push_str(locale.pick("\n各模式:", "\nEvery ladder:"));
+            for line in lines {
+                info.push_str(&format!("\n  {line}"));
+            }
+        }
+        info
+    }
+
+    /// Every ladder's line, when there is more than the headline one.
+    fn other_ladders(&self, locale: Locale) -> Option<Vec<String>> {
+        (self.ladders.len() > 1).then(|| self.ladders.iter().map(|line| line.render(locale)).collect())
+    }
+
+    /// Ranked solo is what the board has always meant, so only another ladder
+    /// is worth naming.
+    fn ladder_note(&self, locale: Locale) -> String {
+        match self.ladder {
+            Ladder::RmSolo => String::new(),
+            ladder => format!(" ({})", ladder.name(locale)),
+        }
     }
 
-    pub(crate) fn last_played(&self) -> String {
+    pub(crate) fn last_played(&self, locale: Locale) -> String {
         let days = Utc::now().signed_duration_since(self.last_played).num_days();
-        if days == 0 {
-            "最近".to_string()
-        } else {
-            format!("{}天前", days)
+        match (days, locale) {
+            (0, Locale::ZhTw) => "最近".to_string(),
+            (0, Locale::En) => "recently".to_string(),
+            (1, Locale::En) => "a day ago".to_string(),
+            (days, Locale::ZhTw) => format!("{}天前", days),
+            (days, Locale::En) => format!("{} days ago", days),
         }
     }
 
-    pub(crate) fn rank_level(&self) -> String {
-        rank_level_zh(&self.rank_level)
+    pub(crate) fn rank_level(&self, locale: Locale) -> String {
+        rank_level_name(&self.rank_level, locale)
     }
 
-    pub fn discord_username(&self) -> &str {
-        &self.discord_username
+    /// What `/查分` draws, in place of `info`'s text.
+    pub(crate) fn card(&self, locale: Locale) -> Card {
+        Card {
+            locale,
+            name: self.aoe4_name.clone(),
+            rank_level: self.rank_level.clone(),
+            tier: format!("{}{}", self.rank_level(locale), self.ladder_note(locale)),
+            rating: self.rating,
+            recent_max_rating: self.recent_max_rating,
+            elo: self.elo,
+            global_rank: self.global_rank,
+            games_played: self.games_played,
+            win_rate: self.win_rate,
+            civs: self
+                .civilizations
+                .iter()
+                .take(CARD_CIVS)
+                .map(|civ| (civ.civilization(locale), civ.pick_rate))
+                .collect(),
+            ladders: self.other_ladders(locale).unwrap_or_default(),
+            last_played: self.last_played(locale),
+        }
+    }
+
+    pub(crate) fn user_id(&self) -> Option<i64> {
+        self.user_id
+    }
+
+    pub(crate) fn is_main(&self) -> bool {
+        self.main
+    }
+
+    /// The row `history::record` keeps for this profile. The raw `rank_level`
+    /// rather than `rank_level`'s rendering, so a later change of wording
+    /// does not strand the old rows.
+    pub(crate) fn snapshot(&self, taken_at: DateTime<Utc>) -> RatingSnapshot {
+        RatingSnapshot {
+            aoe4_id: self.aoe4_id,
+            aoe4_name: self.aoe4_name.clone(),
+            taken_at,
+            rating: self.rating.into(),
+            elo: self.elo.into(),
+            rank_level: self.rank_level.clone(),
+            global_rank: self.global_rank.into(),
+            games_count: self.games_played.into(),
+            win_rate: self.win_rate,
+        }
+    }
+}
+
+impl RankedPlayer {
+    /// The row `/leaderboard` reads this player back from. `None` for `/查分`'s
+    /// unbound lookup, which has no Discord account to list under.
+    pub(crate) fn leaderboard_entry(&self, refreshed_at: DateTime<Utc>) -> Option<LeaderboardEntry> {
+        Some(LeaderboardEntry {
+            user_id: self.user_id?,
+            aoe4_id: self.aoe4_id,
+            aoe4_name: self.aoe4_name.clone(),
+            discord_display: self.discord_display.clone(),
+            rank_level: self.rank_level.clone(),
+            rating: self.rating.into(),
+            elo: self.elo.into(),
+            recent_max_rating: self.recent_max_rating.into(),
+            global_rank: self.global_rank.into(),
+            games_played: self.games_played.into(),
+            win_rate: self.win_rate,
+            favorite_civ: self.favorite_civ.civilization.clone(),
+            refreshed_at,
+        })
+    }
+
+    /// The rows `/civs` and `/civ` read this player's civilizations back
+    /// from, with each one's games estimated from its pick rate. Empty where
+    /// `leaderboard_entry` is `None`.
+    pub(crate) fn leaderboard_civs(&self) -> Vec<LeaderboardCiv> {
+        let Some(user_id) = self.user_id else {
+            return Vec::new();
+        };
+        self.civilizations
+            .iter()
+            .map(|civ| LeaderboardCiv {
+                user_id,
+                civilization: civ.civilization.clone(),
+                pick_rate: civ.pick_rate,
+                games: (f64::from(self.games_played) * civ.pick_rate / 100.0).round() as i64,
+            })
+            .collect()
     }
 }
 
@@ -88,39 +303,48 @@ impl Ord for RankedPlayer {
     }
 }
 
+/// The board's entry, in both languages: the board is one set of messages
+/// everyone reads, like a tournament panel, so it cannot follow any one
+/// reader's locale.
 impl Display for RankedPlayer {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut alt_info = String::new();
         if !self.alts.is_empty() {
-            alt_info.push_str("\n其他小號:");
+            alt_info.push_str("\n其他小號 / Alts:");
         }
         for alt in &self.alts {
             alt_info.push_str(format!("\n  {}: {}", escape(&alt.aoe4_name), alt.rating).as_str());
         }
 
+        let both = |zh: String, en: String| if zh == en { zh } else { format!("{zh} / {en}") };
         write!(
             f,
             "{} ({})\n\
-            遊戲ID: [{}](https://aoe4world.com/players/{})\n\
-            階級: {}\n\
-            全球排名: {}, 遊戲場次: {} (勝率: {}%)\n\
-            愛用文明: {} ,出場率 {}%\n\
-            排名積分: {}, 近期最高積分: {}, Elo: {}, 上次遊玩: {}\
+            遊戲ID / Player: [{}](https://aoe4world.com/players/{})\n\
+            階級 / Tier: {}{}\n\
+            全球排名 / Rank: {}, 場次 / Games: {} (勝率 / Win rate: {}%)\n\
+            愛用文明 / Civ: {}, 出場率 / Picked: {}%\n\
+            排名積分 / Rating: {}, 近期最高 / Recent max: {}, Elo: {}\n\
+            上次遊玩 / Last played: {}\
             {}",
             escape(&self.discord_display),
             escape(&self.discord_username),
             escape(&self.aoe4_name),
             self.aoe4_id,
-            escape(&self.rank_level()),
+            escape(&both(self.rank_level(Locale::ZhTw), self.rank_level(Locale::En))),
+            match self.ladder {
+                Ladder::RmSolo => String::new(),
+                ladder => format!(" ({} / {})", ladder.name_zh(), ladder.name_en()),
+            },
             self.global_rank,
             self.games_played,
             self.win_rate.round(),
-            escape(&self.favorite_civ.civilization()),
+            escape(&civilization_bilingual(&self.favorite_civ.civilization)),
             self.favorite_civ.pick_rate.round(),
             self.rating,
             self.recent_max_rating,
             self.elo,
-            self.last_played(),
+            both(self.last_played(Locale::ZhTw), self.last_played(Locale::En)),
             alt_info
         )
     }
@@ -143,9 +367,43 @@ pub(crate) fn escape(input: &str) -> String {
         .replace(']', "\\]")
 }
 
-pub(crate) async fn try_create_ranked_from_account(http: &Http, data: &Data, account: Account) -> Option<RankedPlayer> {
+/// A bound account with everything the board needs fetched: who they are on
+/// Discord and their whole aoe4world profile. Fetched once per refresh and
+/// ranked once per board, so a second board costs no second round of requests.
+pub(crate) struct Member {
+    /// The Discord account it is bound to; `None` for `/查分`'s unbound lookup.
+    user_id: Option<i64>,
+    main: bool,
+    aoe4_id: i64,
+    discord_display: String,
+    discord_username: String,
+    profile: Profile,
+}
+
+impl Member {
+    pub(crate) fn user_id(&self) -> Option<i64> {
+        self.user_id
+    }
+
+    pub(crate) fn aoe4_id(&self) -> i64 {
+        self.aoe4_id
+    }
+
+    /// The most recent game on any ladder; see `Modes::last_game_at`.
+    pub(crate) fn last_game_at(&self) -> Option<DateTime<Utc>> {
+        self.profile.modes.last_game_at()
+    }
+
+    /// The ranked solo tier, e.g. `diamond_2`, or `None` for a profile that has
+    /// never been placed.
+    pub(crate) fn solo_rank_level(&self) -> Option<&str> {
+        self.profile.modes.rm_solo.as_ref().map(|data| data.rank_level.as_str())
+    }
+}
+
+pub(crate) async fn fetch_member(http: &Http, data: &Data, account: Account) -> Option<Member> {
     info!(
-        "try create ranked from account, discord {}, aoe4 {}",
+        "fetch member for account, discord {}, aoe4 {}",
         account.user_id, account.aoe4_id
     );
     let user = http.get_user(to_user_id(account.user_id)).await.ok()?;
@@ -162,81 +420,120 @@ pub(crate) async fn try_create_ranked_from_account(http: &Http, data: &Data, acc
     let discord_display = discord_nickname.unwrap_or(discord_global_name.unwrap_or(discord_username.clone()));
     info!("got discord profile for {}", discord_display);
 
-    let profile = fetch_profile(account.aoe4_id).await?;
+    let profile = fetch_profile(account.aoe4_id).await.ok()?;
     info!("got aoe4 world profile for {}", profile.name);
 
-    let rm_solo = profile.modes.rm_solo?;
-    let rm_1v1_elo = profile.modes.rm_1v1_elo?;
-
-    // unranked or never played: nothing to rank them by
-    let last_played = rm_solo.last_game_at?;
-
-    Some(RankedPlayer {
-        aoe4_name: profile.name.clone(),
+    Some(Member {
+        user_id: Some(account.user_id),
+        main: account.main,
         aoe4_id: account.aoe4_id,
         discord_display,
         discord_username,
-        rank_level: rm_solo.rank_level,
-        global_rank: rm_solo.rank?,
-        rating: rm_solo.rating?,
-        recent_max_rating: rm_solo.max_rating_1m?,
-        elo: rm_1v1_elo.rating,
-        favorite_civ: rm_solo
-            .civilizations
-            .first()
-            .unwrap_or(&CivData {
-                civilization: "未知".to_string(),
-                pick_rate: 0.0,
-            })
-            .clone(),
-        games_played: rm_solo.games_count,
-        win_rate: rm_solo.win_rate.unwrap_or(0.0),
-        last_played,
-        alts: Vec::new(),
+        profile,
     })
 }
 
-pub(crate) async fn try_create_ranked_without_account(aoe4_id: i32) -> Option<RankedPlayer> {
+impl RankedPlayer {
+    /// `member` on the ladder `mode` picks for them. `None` when they have no
+    /// placement there — unranked, or never played it.
+    ///
+    /// `Best` tries each ranked ladder from the highest rating down and takes
+    /// the first one with a full placement, so a ladder aoe4world rates but
+    /// returns half-empty falls back to the other rather than losing the member.
+    pub(crate) fn from_member(member: &Member, mode: BoardMode) -> Option<Self> {
+        let modes = &member.profile.modes;
+        match mode {
+            BoardMode::Solo => Self::on_ladder(member, Ladder::RmSolo),
+            BoardMode::Team => Self::on_ladder(member, Ladder::RmTeam),
+            BoardMode::Best => {
+                let mut ladders = [Ladder::RmSolo, Ladder::RmTeam];
+                ladders.sort_by_key(|ladder| std::cmp::Reverse(modes.ladder(*ladder).and_then(|data| data.rating)));
+                ladders.into_iter().find_map(|ladder| Self::on_ladder(member, ladder))
+            },
+        }
+    }
+
+    fn on_ladder(member: &Member, ladder: Ladder) -> Option<Self> {
+        let modes = &member.profile.modes;
+        let data = modes.ladder(ladder)?;
+
+        Some(RankedPlayer {
+            user_id: member.user_id,
+            main: member.main,
+            aoe4_name: member.profile.name.clone(),
+            aoe4_id: member.aoe4_id,
+            discord_display: member.discord_display.clone(),
+            discord_username: member.discord_username.clone(),
+            rank_level: data.rank_level.clone(),
+            global_rank: data.rank?,
+            rating: data.rating?,
+            recent_max_rating: data.max_rating_1m?,
+            elo: modes.elo(ladder)?,
+            favorite_civ: data
+                .civilizations
+                .first()
+                .unwrap_or(&CivData {
+                    civilization: "未知".to_string(),
+                    pick_rate: 0.0,
+                })
+                .clone(),
+            civilizations: data.civilizations.clone(),
+            games_played: data.games_count,
+            win_rate: data.win_rate.unwrap_or(0.0),
+            // unranked or never played: nothing to rank them by
+            last_played: data.last_game_at?,
+            ladder,
+            ladders: ladder_lines(modes),
+            alts: Vec::new(),
+        })
+    }
+}
+
+fn ladder_lines(modes: &Modes) -> Vec<LadderLine> {
+    Ladder::ALL
+        .into_iter()
+        .filter_map(|ladder| {
+            let data = modes.ladder(ladder)?;
+            Some(LadderLine {
+                ladder,
+                rank_level: data.rank_level.clone(),
+                rating: data.rating?,
+                global_rank: data.rank,
+                games_played: data.games_count,
+                win_rate: data.win_rate.unwrap_or(0.0),
+            })
+        })
+        .collect()
+}
+
+/// `/查分`'s lookup: a profile with no Discord account behind it, headlined by
+/// whichever ranked ladder it rates best in and listing every other one.
+/// `Ok(None)` is a profile that exists but has no ranked placement.
+pub(crate) async fn try_create_ranked_without_account(aoe4_id: i32) -> Result<Option<RankedPlayer>, ApiError> {
     info!("try create ranked without account");
 
     let profile = fetch_profile(aoe4_id.into()).await?;
     info!("got aoe4 world profile for {}", profile.name);
 
-    let rm_solo = profile.modes.rm_solo?;
-    let rm_1v1_elo = profile.modes.rm_1v1_elo?;
-
-    Some(RankedPlayer {
-        aoe4_name: profile.name.clone(),
+    let member = Member {
+        user_id: None,
+        main: false,
         aoe4_id: aoe4_id.into(),
         discord_display: "".to_string(),
         discord_username: "".to_string(),
-        rank_level: rm_solo.rank_level,
-        global_rank: rm_solo.rank?,
-        rating: rm_solo.rating?,
-        recent_max_rating: rm_solo.max_rating_1m?,
-        elo: rm_1v1_elo.rating,
-        favorite_civ: rm_solo
-            .civilizations
-            .first()
-            .unwrap_or(&CivData {
-                civilization: "未知".to_string(),
-                pick_rate: 0.0,
-            })
-            .clone(),
-        games_played: rm_solo.games_count,
-        win_rate: rm_solo.win_rate.unwrap_or(0.0),
-        last_played: rm_solo.last_game_at?,
-        alts: Vec::new(),
-    })
+        profile,
+    };
+    Ok(RankedPlayer::from_member(&member, BoardMode::Best))
 }
 
 // Live-API tests, ignored by default because they need network and third-party
 // data that shifts. Run with `cargo test -- --ignored`.
 #[cfg(test)]
 mod tests {
-    use crate::aoe4world::{fetch_profile, search_players};
+    use crate::aoe4world::{autocomplete_players, fetch_profile};
     use crate::db::Account;
-    use crate::ranked::try_create_ranked_without_account;
+    use crate::locale::Locale;
+    use crate::ranked::{BoardMode, Ladder, Member, RankedPlayer, try_create_ranked_without_account};
     use tracing::info;
 
     #[tokio::test]
@@ -245,6 +542,9 @@ mod tests {
         let account = Account {
             user_id: 720955323183267840,
             aoe4_id: 13753974,
+            main: false,
+            verified: false,
+            bound_at: None,
         };
         let profile = fetch_profile(account.aoe4_id).await.unwrap();
         info!("got aoe4 world profile for {}", profile.name);
@@ -252,28 +552,66 @@ mod tests {
 
     #[tokio::test]
     #[ignore = "hits the live aoe4world API"]
-    async fn search_test() {
-        let profiles = search_players("Jump__").await.unwrap();
+    async fn autocomplete_test() {
+        let profiles = autocomplete_players("Jump__").await.unwrap();
         profiles
             .players
-            .into_iter()
-            .filter_map(|player| {
-                let data = player.leaderboards.rm_solo?;
-                Some(format!(
-                    "{} - 階級: {}, 積分: {}",
-                    player.name,
-                    data.rank_level(),
-                    data.rating()
-                ))
-            })
+            .iter()
+            .map(|player| player.label(Locale::ZhTw))
             .for_each(|x| info!(x));
     }
 
+    fn member(modes: serde_json::Value) -> Member {
+        Member {
+            user_id: Some(1),
+            main: true,
+            aoe4_id: 100,
+            discord_display: "member".to_string(),
+            discord_username: "member".to_string(),
+            profile: serde_json::from_value(serde_json::json!({ "name": "member", "modes": modes })).unwrap(),
+        }
+    }
+
+    #[test]
+    fn best_falls_back_to_the_other_ladder_when_the_higher_one_is_incomplete() {
+        // Team is rated higher but has no rank or recent max, so it cannot be shown.
+        let member = member(serde_json::json!({
+            "rm_solo": {
+                "rank": 500, "rank_level": "gold_2", "rating": 1100, "max_rating_1m": 1120,
+                "games_count": 40, "last_game_at": "2026-01-01T00:00:00Z"
+            },
+            "rm_team": { "rank_level": "platinum_1", "rating": 1300, "games_count": 3 },
+            "rm_1v1_elo": { "rating": 1150 }
+        }));
+        let player = RankedPlayer::from_member(&member, BoardMode::Best).unwrap();
+        assert_eq!(player.ladder, Ladder::RmSolo);
+        assert_eq!(player.rating, 1100);
+    }
+
+    #[test]
+    fn best_prefers_the_higher_rated_ladder_when_both_are_complete() {
+        let ladder = |rating: i32| {
+            serde_json::json!({
+                "rank": 10, "rank_level": "conqueror_1", "rating": rating, "max_rating_1m": rating,
+                "games_count": 40, "last_game_at": "2026-01-01T00:00:00Z"
+            })
+        };
+        let member = member(serde_json::json!({
+            "rm_solo": ladder(1100),
+            "rm_team": ladder(1300),
+            "rm_1v1_elo": { "rating": 1150 },
+            "rm_2v2_elo": { "rating": 1350 }
+        }));
+        let player = RankedPlayer::from_member(&member, BoardMode::Best).unwrap();
+        assert_eq!(player.ladder, Ladder::RmTeam);
+        assert!(RankedPlayer::from_member(&member, BoardMode::Solo).is_some());
+    }
+
     #[tokio::test]
     #[ignore = "hits the live aoe4world API"]
     async fn search_profile_test() {
         let aoe4_id = 7008236;
-        let player = try_create_ranked_without_account(aoe4_id).await.unwrap();
-        info!("{}", player.info());
+        let player = try_create_ranked_without_account(aoe4_id).await.unwrap().unwrap();
+        info!("{}", player.info(Locale::ZhTw));
     }
 }
@@ -1,35 +1,157 @@
-use crate::db::list_all;
-use crate::ranked::{RankedPlayer, try_create_ranked_from_account};
+use crate::db::{
+    delete_board_messages_from, list_all, list_board_messages, replace_leaderboard, to_db_id, to_message_id,
+    upsert_board_message,
+};
+use crate::history;
+use crate::ranked::{BoardMode, Member, RankedPlayer, fetch_member};
+use crate::tier_roles;
+use crate::tournament::panel_check;
 use crate::{Data, Error};
+use chrono::Utc;
 use poise::futures_util::StreamExt;
 use poise::futures_util::stream;
-use serenity::all::{ChannelId, Http};
+use serenity::all::{ChannelId, EditMessage, Http};
+use sqlx::SqlitePool;
 use std::collections::HashMap;
+use std::fmt::Display;
 use tracing::{error, info};
 
 static RANK_CHANNEL_ID: ChannelId = ChannelId::new(1263079883937153105);
 
-pub(crate) async fn do_refresh(http: &Http, data: &Data) -> Result<(), Error> {
+/// How many members `do_refresh` fetches at once. Order does not matter, since
+/// every board is sorted afterwards.
+pub(crate) const CONCURRENT_FETCHES: usize = 4;
+
+/// One ranked board: the channel it lives in, the ladder it ranks by, and the
+/// key its messages are stored under in `ranked_board_messages`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Board {
+    key: &'static str,
+    channel_id: ChannelId,
+    mode: BoardMode,
+}
+
+/// Every board this deploy publishes: the main one in `RANK_CHANNEL_ID`, ranked
+/// by `RANK_BOARD_MODE` (solo unless told otherwise), and a ranked-team board
+/// in `TEAM_RANK_CHANNEL_ID` when that is set.
+fn boards() -> Vec<Board> {
+    resolve_boards(
+        std::env::var("RANK_BOARD_MODE").ok().as_deref(),
+        std::env::var("TEAM_RANK_CHANNEL_ID").ok().as_deref(),
+    )
+}
+
+/// Pure so the configuration can be tested without touching the process
+/// environment, like `guilds::resolve`. Unlike a guild id, a bad value here
+/// is not worth refusing to start over: it is logged, and that board falls
+/// back to what it would be with nothing set.
+fn resolve_boards(main_mode: Option<&str>, team_channel: Option<&str>) -> Vec<Board> {
+    let main_mode = match main_mode.filter(|value| !value.trim().is_empty()) {
+        Some(value) => BoardMode::parse(value).unwrap_or_else(|| {
+            error!("RANK_BOARD_MODE {value:?} is not one of solo, team or best; using solo");
+            BoardMode::Solo
+        }),
+        None => BoardMode::Solo,
+    };
+    let mut boards = vec![Board {
+        key: "main",
+        channel_id: RANK_CHANNEL_ID,
+        mode: main_mode,
+    }];
+
+    if let Some(value) = team_channel.map(str::trim).filter(|value| !value.is_empty()) {
+        match value.parse::<u64>().ok().filter(|id| *id != 0) {
+            Some(id) => boards.push(Board {
+                key: "team",
+                channel_id: ChannelId::new(id),
+                mode: BoardMode::Team,
+            }),
+            None => error!("TEAM_RANK_CHANNEL_ID {value:?} is not a channel id; the team board is off"),
+        }
+    }
+    boards
+}
+
+/// Returns the members it fetched, so the inactivity reminders that follow the
+/// cron refresh can read them instead of fetching every profile again.
+pub(crate) async fn do_refresh(http: &Http, data: &Data) -> Result<Vec<Member>, Error> {
     info!("attempting to refresh");
 
     let accounts = list_all(&data.database).await.inspect_err(|_error| {
         error!("database query failed");
     })?;
-    let players = stream::iter(accounts)
-        .filter_map(|account| try_create_ranked_from_account(http, data, account))
-        .collect::<Vec<RankedPlayer>>()
+    let bound = accounts.iter().fold(HashMap::new(), |mut bound, account| {
+        *bound.entry(account.user_id).or_insert(0) += 1;
+        bound
+    });
+    // Fetched once and ranked once per board, so a second board costs nothing
+    // more from Discord or aoe4world. A few at a time rather than one by one:
+    // the aoe4world client's token bucket is what paces the requests, so
+    // waiting on each in turn only adds every round trip's latency on top.
+    let members = stream::iter(accounts)
+        .map(|account| fetch_member(http, data, account))
+        .buffer_unordered(CONCURRENT_FETCHES)
+        .filter_map(std::future::ready)
+        .collect::<Vec<Member>>()
         .await;
-    let mut unique_players = players
-        .into_iter()
+    info!("finish member collection");
+
+    // Before alts are folded into their mains, so every bound profile keeps its
+    // own history rather than only whichever one ranked highest. Always ranked
+    // solo, whatever the boards show: one series per profile, on one scale.
+    let solo = members
+        .iter()
+        .filter_map(|member| RankedPlayer::from_member(member, BoardMode::Solo))
+        .collect::<Vec<RankedPlayer>>();
+    history::record(&data.database, &solo, Utc::now()).await;
+    tier_roles::sync(http, data, &bound, &members).await;
+
+    for (index, board) in boards().into_iter().enumerate() {
+        let sorted_players = rank(&members, board.mode);
+        // The main board is what `/leaderboard` pages through and `/civs` adds
+        // up. Best-effort, like the history: a failed write leaves the
+        // previous board to browse.
+        if index == 0 {
+            let refreshed_at = Utc::now();
+            let entries = sorted_players
+                .iter()
+                .filter_map(|player| player.leaderboard_entry(refreshed_at))
+                .collect::<Vec<_>>();
+            let civs = sorted_players
+                .iter()
+                .flat_map(|player| player.leaderboard_civs())
+                .collect::<Vec<_>>();
+            if let Err(err) = replace_leaderboard(&data.database, &entries, &civs).await {
+                error!("failed to store the board for /leaderboard: {err:?}");
+            }
+        }
+        info!(
+            "collected and sorted {} players for the {} board",
+            sorted_players.len(),
+            board.key
+        );
+
+        let outcome = publish(http, &data.database, board, &board_chunks(&sorted_players)).await?;
+        info!("{} ranked board published: {outcome:?}", board.key);
+    }
+    Ok(members)
+}
+
+/// One entry per Discord account, best first, with every other profile they
+/// have bound folded in as an alt of their main — the one they chose with
+/// `/bind main`, or else the one `mode` ranks highest. A chosen main with no
+/// placement on this board's ladder is skipped for the best that has one.
+fn rank(members: &[Member], mode: BoardMode) -> Vec<RankedPlayer> {
+    let mut unique_players = members
+        .iter()
+        .filter_map(|member| RankedPlayer::from_member(member, mode))
         .fold(HashMap::new(), |mut acc, player| {
-            acc.entry(String::from(player.discord_username()))
-                .or_insert_with(Vec::new)
-                .push(player);
+            acc.entry(player.user_id()).or_insert_with(Vec::new).push(player);
             acc
         })
         .into_values()
         .filter_map(|mut list| {
-            list.sort();
+            list.sort_by(|a, b| b.is_main().cmp(&a.is_main()).then_with(|| mode.order(a, b)));
             let sorted = list;
             sorted.into_iter().reduce(|mut acc, player| {
                 acc.append_alt(player);
@@ -37,51 +159,229 @@ pub(crate) async fn do_refresh(http: &Http, data: &Data) -> Result<(), Error> {
             })
         })
         .collect::<Vec<RankedPlayer>>();
-    info!("finish ranked player collection");
 
-    unique_players.sort();
-    let sorted_players = unique_players;
-    info!("collected and sorted {} players", sorted_players.len());
+    unique_players.sort_by(|a, b| mode.order(a, b));
+    unique_players
+}
+
+/// Players per message. `RankedPlayer`'s `Display` labels every field in both
+/// languages, so four of them stay comfortably under Discord's 2000-character
+/// limit even with a few alts listed.
+const PLAYERS_PER_MESSAGE: usize = 4;
+
+/// The board's text, one entry per message, numbered from the top. Generic so
+/// the chunking is testable without building a `RankedPlayer`.
+fn board_chunks<T: Display>(players: &[T]) -> Vec<String> {
+    players
+        .chunks(PLAYERS_PER_MESSAGE)
+        .enumerate()
+        .map(|(chunk, players)| {
+            players
+                .iter()
+                .enumerate()
+                .map(|(i, player)| {
+                    let place = chunk * PLAYERS_PER_MESSAGE + i + 1;
+                    format!("第{place}名 / #{place}  {player}\n_ _\n")
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// What `publish` did to the channel, for the log line a cron run leaves.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct BoardOutcome {
+    pub posted: usize,
+    pub edited: usize,
+    pub deleted: usize,
+}
+
+/// Brings the channel in line with `chunks`, reusing the messages already there.
+///
+/// Each chunk is edited in place if a message holds its ordinal, so a refresh
+/// sends no notifications and keeps every message link alive. A chunk with no
+/// message is posted, and any tail past the last chunk is deleted — the same
+/// edit/post/trim split `tournament::bracket_view` applies to a bracket.
+///
+/// The one thing a bracket does not have to care about is order: a post always
+/// lands at the bottom of the channel. So once one chunk has to be posted —
+/// because someone deleted its message by hand — every chunk after it is
+/// reposted too, or the board would read 1–5, 11–15, 6–10.
+async fn publish(http: &Http, pool: &SqlitePool, board: Board, chunks: &[String]) -> Result<BoardOutcome, Error> {
+    let existing = list_board_messages(pool, board.key).await?;
+    if existing.is_empty() {
+        clear_untracked(http, board).await?;
+    }
+
+    let mut outcome = BoardOutcome::default();
+    let mut reposting = false;
+    for (index, chunk) in chunks.iter().enumerate() {
+        let ordinal = i64::try_from(index).unwrap();
+        let stored = existing.iter().find(|m| m.ordinal == ordinal);
+        match stored {
+            Some(message) if !reposting => {
+                let edit = EditMessage::new().content(chunk);
+                match board
+                    .channel_id
+                    .edit_message(http, to_message_id(message.message_id), edit)
+                    .await
+                {
+                    Ok(_) => {
+                        outcome.edited += 1;
+                        continue;
+                    },
+                    Err(err) if panel_check::is_confirmed_missing(&err) => reposting = true,
+                    Err(err) => return Err(err.into()),
+                }
+            },
+            Some(message) => {
+                delete_quietly(http, board, message.message_id).await;
+                outcome.deleted += 1;
+            },
+            None => reposting = true,
+        }
 
-    info!("clearing all existing messages in the channel");
+        let message = board.channel_id.say(http, chunk).await?;
+        upsert_board_message(pool, board.key, ordinal, to_db_id(message.id)).await?;
+        outcome.posted += 1;
+    }
+
+    // Anything past the last chunk belongs to a longer board than this one.
+    let surplus = i64::try_from(chunks.len()).unwrap();
+    for message in existing.iter().filter(|m| m.ordinal >= surplus) {
+        delete_quietly(http, board, message.message_id).await;
+        outcome.deleted += 1;
+    }
+    delete_board_messages_from(pool, board.key, surplus).await?;
+
+    Ok(outcome)
+}
+
+/// With nothing tracked yet the channel still holds the board the old
+/// delete-and-repost refresh left behind, so it is cleared the old way, once.
+/// From then on every message in it is one `publish` knows about.
+async fn clear_untracked(http: &Http, board: Board) -> Result<(), Error> {
+    info!(
+        "no {} ranked board messages tracked yet, clearing the channel",
+        board.key
+    );
     let messages = http
-        .get_messages(RANK_CHANNEL_ID, None, None)
+        .get_messages(board.channel_id, None, None)
         .await
         .inspect_err(|_error| {
             error!("getting message from discord channel failed");
         })?;
+    for message in messages {
+        delete_quietly(http, board, to_db_id(message.id)).await;
+    }
+    Ok(())
+}
 
-    for message_id in messages.iter().map(|message| message.id) {
-        http.delete_message(RANK_CHANNEL_ID, message_id, None)
-            .await
-            .inspect_err(|_error| {
-                error!("deleting existing messages from discord failed");
-            })?;
+/// A failed delete leaves a stray message, which is untidy but not wrong — it
+/// must not stop the rest of the board from being written.
+async fn delete_quietly(http: &Http, board: Board, message_id: i64) {
+    if let Err(err) = board.channel_id.delete_message(http, to_message_id(message_id)).await
+        && !panel_check::is_confirmed_missing(&err)
+    {
+        error!("failed to delete ranked board message {message_id}: {err:?}");
     }
+}
 
-    let mut buffer = String::new();
-    for (i, player) in sorted_players.iter().enumerate() {
-        let text = format!("第{}名  {}\n_ _\n", i + 1, player);
-        buffer = buffer + &text;
+/// On boot, confirms every board message still exists and runs a refresh if
+/// any was deleted by hand — `publish` then reposts from the gap down. Nothing
+/// is touched when the check is inconclusive, the same caution
+/// `tournament::startup` takes, since a hiccup is not a deletion.
+pub(crate) async fn reconcile_board(http: &Http, data: &Data) {
+    let mut missing = false;
+    for board in boards() {
+        let stored = match list_board_messages(&data.database, board.key).await {
+            Ok(stored) => stored,
+            Err(err) => {
+                error!("failed to list ranked board messages for boot reconciliation: {err:?}");
+                return;
+            },
+        };
 
-        if i % 5 == 4 {
-            send_rankings(http, &buffer).await?;
-            buffer = String::new();
+        for message in &stored {
+            match panel_check::message_exists(http, board.channel_id, to_message_id(message.message_id)).await {
+                Ok(true) => {},
+                Ok(false) => {
+                    info!("{} ranked board message {} is gone", board.key, message.ordinal);
+                    missing = true;
+                },
+                Err(err) => {
+                    error!(
+                        "could not confirm {} ranked board message {}: {err:?}",
+                        board.key, message.ordinal
+                    );
+                    return;
+                },
+            }
         }
     }
 
-    if !buffer.is_empty() {
-        send_rankings(http, &buffer).await?;
+    if missing && let Err(err) = do_refresh(http, data).await {
+        error!("failed to repair the ranked board on boot: {err:?}");
     }
-
-    Ok(())
 }
-async fn send_rankings(http: &Http, content: &String) -> Result<(), Error> {
-    http.get_channel(RANK_CHANNEL_ID)
-        .await?
-        .guild()
-        .unwrap()
-        .say(http, content)
-        .await?;
-    Ok(())
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_hold_four_players_and_number_them_across_chunks() {
+        let players: Vec<String> = (1..=10).map(|i| format!("P{i}")).collect();
+        let chunks = board_chunks(&players);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].starts_with("第1名 / #1  P1\n"), "{}", chunks[0]);
+        assert!(chunks[1].starts_with("第5名 / #5  P5\n"), "{}", chunks[1]);
+        assert!(chunks[2].contains("第10名 / #10  P10"), "{}", chunks[2]);
+        assert_eq!(chunks[2].matches("  P").count(), 2);
+    }
+
+    #[test]
+    fn with_nothing_configured_there_is_one_solo_board() {
+        let boards = resolve_boards(None, None);
+        assert_eq!(boards.len(), 1);
+        assert_eq!(boards[0].mode, BoardMode::Solo);
+        assert_eq!(boards[0].channel_id, RANK_CHANNEL_ID);
+    }
+
+    #[test]
+    fn the_main_board_takes_whichever_mode_is_named() {
+        assert_eq!(resolve_boards(Some("best"), None)[0].mode, BoardMode::Best);
+        assert_eq!(resolve_boards(Some(" team "), None)[0].mode, BoardMode::Team);
+    }
+
+    #[test]
+    fn a_mode_nobody_knows_falls_back_to_solo() {
+        assert_eq!(resolve_boards(Some("ffa"), None)[0].mode, BoardMode::Solo);
+        assert_eq!(resolve_boards(Some(""), None)[0].mode, BoardMode::Solo);
+    }
+
+    #[test]
+    fn a_team_channel_adds_a_second_board_under_its_own_key() {
+        let boards = resolve_boards(None, Some("42"));
+        assert_eq!(boards.len(), 2);
+        assert_eq!(boards[1].key, "team");
+        assert_eq!(boards[1].mode, BoardMode::Team);
+        assert_eq!(boards[1].channel_id, ChannelId::new(42));
+        assert_ne!(
+            boards[0].key, boards[1].key,
+            "two boards must not share stored messages"
+        );
+    }
+
+    #[test]
+    fn a_team_channel_that_is_not_an_id_is_ignored() {
+        assert_eq!(resolve_boards(None, Some("ranked-team")).len(), 1);
+        assert_eq!(resolve_boards(None, Some("0")).len(), 1);
+        assert_eq!(resolve_boards(None, Some("  ")).len(), 1);
+    }
+
+    #[test]
+    fn an_empty_board_has_no_chunks() {
+        assert!(board_chunks::<String>(&[]).is_empty());
+    }
 }
@@ -0,0 +1,218 @@
+//! Inactivity reminders: after each cron refresh, pings every bound member who
+//! has not played on any ladder for `INACTIVITY_DAYS`, judged from the profiles
+//! that refresh just fetched rather than fetching them all a second time.
+//!
+//! Once per idle stretch — the nag is remembered against the last game it was
+//! about, and only a newer game makes another one due. `/reminder` lets a member
+//! opt out, back in, or snooze for a few days.
+
+use crate::commands::INTERACTION_CHANNEL_ID;
+use crate::db::{ReminderState, list_all, list_reminder_states, mark_nagged, to_user_id};
+use crate::ranked::Member;
+use crate::{Data, Error};
+use chrono::{DateTime, Duration, Utc};
+use serenity::all::{Http, Mentionable};
+use std::collections::{BTreeMap, HashMap};
+use tracing::{error, info};
+
+/// How long without a game before a member is nagged, unless `INACTIVITY_DAYS`
+/// says otherwise.
+const DEFAULT_INACTIVITY_DAYS: i64 = 14;
+
+fn threshold() -> Duration {
+    resolve_threshold(std::env::var("INACTIVITY_DAYS").ok().as_deref())
+}
+
+/// Pure, like `refresh::resolve_boards`. A bad value is logged and the default
+/// used: a typo here should not silence the reminders, nor stop the bot.
+fn resolve_threshold(days: Option<&str>) -> Duration {
+    let days = match days.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => value.parse::<i64>().ok().filter(|days| *days > 0).unwrap_or_else(|| {
+            error!("INACTIVITY_DAYS {value:?} is not a positive number of days; using {DEFAULT_INACTIVITY_DAYS}");
+            DEFAULT_INACTIVITY_DAYS
+        }),
+        None => DEFAULT_INACTIVITY_DAYS,
+    };
+    Duration::days(days)
+}
+
+/// Whether the member has asked not to be reminded right now.
+fn is_muted(state: &ReminderState, now: DateTime<Utc>) -> bool {
+    state.opted_out || state.snoozed_until.is_some_and(|until| until > now)
+}
+
+/// Whether a member whose latest game was `last_game` should be nagged at `now`.
+fn is_due(last_game: DateTime<Utc>, now: DateTime<Utc>, threshold: Duration, state: &ReminderState) -> bool {
+    if is_muted(state, now) || now - last_game < threshold {
+        return false;
+    }
+    // Already nagged about this stretch, or a later one: they have not played since.
+    state.nagged_for.is_none_or(|nagged_for| nagged_for < last_game)
+}
+
+fn message(user_id: i64, idle_days: i64) -> String {
+    format!(
+        "{} 已經 {} 天沒有打積分了，快回來練練吧！\n\
+        不想收到提醒可以用 `/reminder off`，或用 `/reminder snooze` 暫停幾天。",
+        to_user_id(user_id).mention(),
+        idle_days
+    )
+}
+
+/// One pass over every bound member, given the `members` the refresh fetched.
+/// Best-effort throughout, like `history::record`: a member with a profile the
+/// refresh could not fetch, or whose ping or bookkeeping fails, is skipped and
+/// retried next run, and nothing here fails the refresh.
+pub(crate) async fn run(http: &Http, data: &Data, members: &[Member]) {
+    if let Err(err) = nag_idle(http, data, members).await {
+        error!("inactivity reminders failed: {err:?}");
+    }
+}
+
+/// The latest game on each fetched profile, `None` for one never played. A
+/// profile missing from the map is one the refresh could not fetch.
+fn last_games(members: &[Member]) -> HashMap<i64, Option<DateTime<Utc>>> {
+    members
+        .iter()
+        .map(|member| (member.aoe4_id(), member.last_game_at()))
+        .collect()
+}
+
+/// A member's latest game over all their profiles. `None` when any of them was
+/// not fetched: the missing one may be the one they still play, and nagging on
+/// a transient API failure is worse than waiting a run.
+fn latest_game(aoe4_ids: &[i64], last_games: &HashMap<i64, Option<DateTime<Utc>>>) -> Option<DateTime<Utc>> {
+    aoe4_ids
+        .iter()
+        .map(|aoe4_id| last_games.get(aoe4_id).copied())
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .max()
+        .flatten()
+}
+
+async fn nag_idle(http: &Http, data: &Data, members: &[Member]) -> Result<(), Error> {
+    let pool = &data.database;
+    let now = Utc::now();
+    let threshold = threshold();
+
+    // Grouped by member, since any one of their profiles being played keeps them
+    // off the list.
+    let accounts = list_all(pool)
+        .await?
+        .into_iter()
+        .fold(BTreeMap::<i64, Vec<i64>>::new(), |mut acc, account| {
+            acc.entry(account.user_id).or_default().push(account.aoe4_id);
+            acc
+        });
+    let states = list_reminder_states(pool)
+        .await?
+        .into_iter()
+        .map(|state| (state.user_id, state))
+        .collect::<HashMap<i64, ReminderState>>();
+    let last_games = last_games(members);
+
+    let mut nagged = 0;
+    for (user_id, aoe4_ids) in accounts {
+        let state = states.get(&user_id).cloned().unwrap_or_default();
+        if is_muted(&state, now) {
+            continue;
+        }
+
+        // Never played anything ranked or quick: nothing to be idle from.
+        let Some(last_game) = latest_game(&aoe4_ids, &last_games) else {
+            continue;
+        };
+        if !is_due(last_game, now, threshold, &state) {
+            continue;
+        }
+
+        let idle_days = (now - last_game).num_days();
+        if let Err(err) = INTERACTION_CHANNEL_ID.say(http, message(user_id, idle_days)).await {
+            error!("could not remind {user_id}: {err:?}");
+            continue;
+        }
+        // Logged rather than propagated, like `schedule::run`: one member's
+        // write failing must not skip everyone after them. Unmarked, they are
+        // reminded again next run — the lesser failure.
+        if let Err(err) = mark_nagged(pool, user_id, last_game, now).await {
+            error!("could not record the reminder to {user_id}: {err:?}");
+        }
+        nagged += 1;
+    }
+    info!("reminded {nagged} inactive members");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: i64) -> DateTime<Utc> {
+        DateTime::UNIX_EPOCH + Duration::days(day)
+    }
+
+    #[test]
+    fn a_member_is_judged_on_their_latest_game_only_when_every_profile_was_fetched() {
+        let last_games = HashMap::from([(1, Some(at(3))), (2, Some(at(9))), (3, None)]);
+        assert_eq!(latest_game(&[1, 2], &last_games), Some(at(9)));
+        assert_eq!(
+            latest_game(&[1, 3], &last_games),
+            Some(at(3)),
+            "a never-played alt adds nothing"
+        );
+        assert_eq!(latest_game(&[3], &last_games), None);
+        assert_eq!(latest_game(&[1, 4], &last_games), None, "profile 4 was not fetched");
+    }
+
+    #[test]
+    fn a_member_idle_past_the_threshold_is_due() {
+        let state = ReminderState::default();
+        assert!(is_due(at(0), at(14), Duration::days(14), &state));
+        assert!(!is_due(at(1), at(14), Duration::days(14), &state));
+    }
+
+    #[test]
+    fn one_nag_per_idle_stretch() {
+        let state = ReminderState {
+            nagged_for: Some(at(0)),
+            ..Default::default()
+        };
+        assert!(
+            !is_due(at(0), at(30), Duration::days(14), &state),
+            "still the same stretch"
+        );
+        assert!(
+            is_due(at(10), at(30), Duration::days(14), &state),
+            "played since, idle again"
+        );
+    }
+
+    #[test]
+    fn an_opted_out_member_is_never_due() {
+        let state = ReminderState {
+            opted_out: true,
+            ..Default::default()
+        };
+        assert!(!is_due(at(0), at(100), Duration::days(14), &state));
+    }
+
+    #[test]
+    fn a_snooze_holds_until_it_runs_out() {
+        let state = ReminderState {
+            snoozed_until: Some(at(20)),
+            ..Default::default()
+        };
+        assert!(!is_due(at(0), at(19), Duration::days(14), &state));
+        assert!(is_due(at(0), at(21), Duration::days(14), &state));
+    }
+
+    #[test]
+    fn the_threshold_defaults_and_survives_a_bad_value() {
+        let default = Duration::days(DEFAULT_INACTIVITY_DAYS);
+        assert_eq!(resolve_threshold(None), default);
+        assert_eq!(resolve_threshold(Some(" 30 ")), Duration::days(30));
+        assert_eq!(resolve_threshold(Some("two weeks")), default);
+        assert_eq!(resolve_threshold(Some("0")), default);
+    }
+}
@@ -11,3 +11,23 @@ pub(crate) async fn ephemeral(ctx: Context<'_>, content: impl Into<String>) -> R
         .await?;
     Ok(())
 }
+
+/// Discord's limit on one message's content, in characters.
+const MESSAGE_LIMIT: usize = 2000;
+
+/// `heading` and `lines` as messages under `MESSAGE_LIMIT`, one line per line,
+/// the heading on the first. Measured in bytes, which are never fewer than
+/// characters, so a CJK-heavy chunk ends early rather than running over.
+pub(crate) fn chunk_lines(heading: String, lines: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut chunks = vec![heading];
+    for line in lines {
+        let chunk = chunks.last_mut().unwrap();
+        if chunk.len() + line.len() + 1 > MESSAGE_LIMIT {
+            chunks.push(line);
+        } else {
+            chunk.push('\n');
+            chunk.push_str(&line);
+        }
+    }
+    chunks
+}
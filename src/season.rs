@@ -0,0 +1,206 @@
+//! Season archives: `/season close` freezes the stored board before aoe4world's
+//! season reset wipes it, `/season show` reads an archive back, and `/查分`
+//! lists the seasons a player finished.
+//!
+//! The archive is taken from `leaderboard_entries` — the board as the last
+//! refresh left it — so closing a season asks aoe4world for nothing, and what
+//! it freezes is exactly what members last saw.
+
+use crate::aoe4world::rank_level_name;
+use crate::db::{LeaderboardEntry, Season, SeasonResult, SeasonStanding};
+use crate::leaderboard::Sort;
+use crate::locale::Locale;
+use crate::ranked::escape;
+use crate::reply::chunk_lines;
+
+/// Longest season name, in characters. Names are typed by hand and printed in
+/// headings; anything longer is a mistake.
+pub(crate) const MAX_NAME_CHARS: usize = 32;
+
+/// Seasons listed on a `/查分` reply, newest first. Older ones are still in
+/// `/season show`.
+const HISTORY_SEASONS: usize = 5;
+
+/// `name` trimmed, or `None` when it is empty, too long, or spans lines.
+pub(crate) fn normalize_name(name: &str) -> Option<&str> {
+    let name = name.trim();
+    let valid = !name.is_empty() && name.chars().count() <= MAX_NAME_CHARS && !name.contains('\n');
+    valid.then_some(name)
+}
+
+/// The board in final order — by rating, as `/leaderboard` sorts by default.
+pub(crate) fn final_order(entries: &[LeaderboardEntry]) -> Vec<&LeaderboardEntry> {
+    let mut ordered = entries.iter().collect::<Vec<_>>();
+    ordered.sort_by(|a, b| Sort::Rating.order(a, b));
+    ordered
+}
+
+/// The archive as messages, each under Discord's limit.
+pub(crate) fn render(season: &Season, standings: &[SeasonStanding], locale: Locale) -> Vec<String> {
+    let (name, members, at) = (
+        escape(&season.name),
+        standings.len(),
+        season.board_refreshed_at.timestamp(),
+    );
+    let heading = locale.pick(
+        format!("**{name}** 賽季 · 共 {members} 人 · 排行榜資料截至 <t:{at}:D>"),
+        format!("**{name}** season · {members} members · board as of <t:{at}:D>"),
+    );
+    let lines = standings.iter().map(|standing| {
+        format!(
+            "`{:>3}` **{}** · {} · {}（{}）",
+            standing.place,
+            standing.rating,
+            rank_level_name(&standing.rank_level, locale),
+            escape(&standing.discord_display),
+            escape(&standing.aoe4_name),
+        )
+    });
+    chunk_lines(heading, lines)
+}
+
+/// The seasons a player finished, for `/查分`; `None` when there are none.
+pub(crate) fn history(results: &[SeasonResult], locale: Locale) -> Option<String> {
+    if results.is_empty() {
+        return None;
+    }
+    let mut out = locale.pick("**賽季紀錄**", "**Past seasons**").to_string();
+    for result in results.iter().take(HISTORY_SEASONS) {
+        let (name, place, of, tier, rating) = (
+            escape(&result.name),
+            result.place,
+            result.of,
+            rank_level_name(&result.rank_level, locale),
+            result.rating,
+        );
+        out.push_str(&locale.pick(
+            format!("\n{name}：第 {place} 名（共 {of} 人）· {tier} · {rating}"),
+            format!("\n{name}: #{place} of {of} · {tier} · {rating}"),
+        ));
+    }
+    if results.len() > HISTORY_SEASONS {
+        let more = results.len() - HISTORY_SEASONS;
+        out.push_str(&locale.pick(
+            format!("\n…以及更早的 {more} 個賽季（見 /season show）"),
+            format!("\n…and {more} earlier (see /season show)"),
+        ));
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn entry(user_id: i64, rating: i64) -> LeaderboardEntry {
+        LeaderboardEntry {
+            user_id,
+            aoe4_id: user_id * 10,
+            aoe4_name: format!("p{user_id}"),
+            discord_display: format!("d{user_id}"),
+            rank_level: "gold_2".to_string(),
+            rating,
+            elo: rating,
+            recent_max_rating: rating,
+            global_rank: 1000,
+            games_played: 10,
+            win_rate: 50.0,
+            favorite_civ: "english".to_string(),
+            refreshed_at: Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap(),
+        }
+    }
+
+    fn season() -> Season {
+        Season {
+            id: 1,
+            name: "S9".to_string(),
+            closed_at: Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap(),
+            closed_by: 7,
+            board_refreshed_at: Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap(),
+        }
+    }
+
+    fn standing(place: i64) -> SeasonStanding {
+        SeasonStanding {
+            user_id: place,
+            aoe4_id: place * 10,
+            aoe4_name: format!("player_{place}"),
+            discord_display: format!("member {place}"),
+            place,
+            rank_level: "diamond_2".to_string(),
+            rating: 2000 - place,
+        }
+    }
+
+    fn result(name: &str, place: i64) -> SeasonResult {
+        SeasonResult {
+            name: name.to_string(),
+            place,
+            of: 40,
+            rank_level: "platinum_3".to_string(),
+            rating: 1234,
+        }
+    }
+
+    #[test]
+    fn a_name_is_trimmed_and_bounded() {
+        assert_eq!(normalize_name("  S9 "), Some("S9"));
+        assert_eq!(normalize_name("   "), None);
+        assert_eq!(normalize_name("a\nb"), None);
+        assert_eq!(
+            normalize_name(&"賽".repeat(MAX_NAME_CHARS)),
+            Some("賽".repeat(MAX_NAME_CHARS).as_str())
+        );
+        assert_eq!(normalize_name(&"x".repeat(MAX_NAME_CHARS + 1)), None);
+    }
+
+    #[test]
+    fn the_final_order_is_by_rating() {
+        let entries = vec![entry(1, 1200), entry(2, 1500), entry(3, 1300)];
+        let order = final_order(&entries)
+            .iter()
+            .map(|entry| entry.user_id)
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn an_archive_reads_in_place_order_with_its_heading() {
+        let chunks = render(&season(), &[standing(1), standing(2)], Locale::ZhTw);
+        assert_eq!(chunks.len(), 1);
+        let lines = chunks[0].lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("**S9** 賽季 · 共 2 人"), "{}", lines[0]);
+        assert_eq!(lines[1], "`  1` **1999** · 鑽石2 · member 1（player\\_1）");
+        assert!(lines[2].starts_with("`  2` **1998**"));
+    }
+
+    #[test]
+    fn a_long_archive_is_split_under_the_message_limit() {
+        let standings = (1..=200).map(standing).collect::<Vec<_>>();
+        let chunks = render(&season(), &standings, Locale::En);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 2000));
+        assert_eq!(chunks.iter().map(|chunk| chunk.lines().count()).sum::<usize>(), 201);
+        assert!(
+            chunks[1].starts_with('`'),
+            "a later message starts with a standing, not a blank line"
+        );
+    }
+
+    #[test]
+    fn no_seasons_means_no_history() {
+        assert_eq!(history(&[], Locale::ZhTw), None);
+    }
+
+    #[test]
+    fn history_lists_the_newest_seasons_and_counts_the_rest() {
+        let results = (1..=7).rev().map(|n| result(&format!("S{n}"), n)).collect::<Vec<_>>();
+        let text = history(&results, Locale::En).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "**Past seasons**");
+        assert_eq!(lines[1], "S7: #7 of 40 · Platinum 3 · 1234");
+        assert_eq!(lines.len(), 1 + HISTORY_SEASONS + 1);
+        assert_eq!(lines[6], "…and 2 earlier (see /season show)");
+    }
+}
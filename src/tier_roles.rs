@@ -0,0 +1,315 @@
+//! Rank-tier roles in the home guild: each refresh gives every bound member the
+//! role `TIER_ROLE_IDS` maps their ranked solo tier to, and takes it back when
+//! they move tier, go unranked or unbind.
+//!
+//! Only roles the bot handed out itself are ever removed — those are tracked in
+//! `tier_role_grants` — so a mapped role an admin gave by hand is left alone,
+//! and nothing here needs to list the guild's members. Every add and remove is
+//! written to `tier_role_changes`, which is where a bad mapping gets audited.
+
+use crate::Data;
+use crate::aoe4world::rank_level_order;
+use crate::db::{TierRoleGrant, grant_tier_role, list_tier_role_grants, revoke_tier_role, to_user_id};
+use crate::ranked::Member;
+use crate::tournament::panel_check;
+use serenity::all::{Http, RoleId};
+use std::collections::{HashMap, HashSet};
+use tracing::{error, info};
+
+/// The tiers a mapping may name, as aoe4world spells them before the division.
+const TIERS: [&str; 6] = ["bronze", "silver", "gold", "platinum", "diamond", "conqueror"];
+
+/// Tier name to role, from `TIER_ROLE_IDS`.
+type Mapping = HashMap<String, RoleId>;
+
+fn configured() -> Option<Mapping> {
+    let value = std::env::var("TIER_ROLE_IDS").ok()?;
+    if value.trim().is_empty() {
+        return None;
+    }
+    parse_mapping(&value)
+        .inspect_err(|err| error!("TIER_ROLE_IDS is not a valid mapping, so tier roles are off: {err}"))
+        .ok()
+}
+
+/// `conqueror=123,diamond=456,…` — any subset of the tiers, each with a role
+/// id. All or nothing: a mapping with one bad entry is refused whole, since
+/// handing out roles from half a mapping is how the wrong people get them.
+fn parse_mapping(value: &str) -> Result<Mapping, String> {
+    let mut mapping = Mapping::new();
+    for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let (tier, role) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("{entry:?} is not tier=role_id"))?;
+        let tier = tier.trim().to_lowercase();
+        if !TIERS.contains(&tier.as_str()) {
+            return Err(format!("{tier:?} is not a tier"));
+        }
+        let role = role
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .filter(|id| *id != 0)
+            .ok_or_else(|| format!("{role:?} is not a role id"))?;
+        if mapping.insert(tier.clone(), RoleId::new(role)).is_some() {
+            return Err(format!("{tier:?} is mapped twice"));
+        }
+    }
+    Ok(mapping)
+}
+
+fn role_for(mapping: &Mapping, rank_level: &str) -> Option<RoleId> {
+    rank_level_order(rank_level)?;
+    let (tier, _) = rank_level.rsplit_once('_')?;
+    mapping.get(tier).copied()
+}
+
+/// One member's role change. Either half may be absent: a first grant only
+/// adds, an unbind only removes, a tier move does both.
+#[derive(Debug, PartialEq, Eq)]
+struct Change {
+    user_id: i64,
+    remove: Option<RoleId>,
+    add: Option<RoleId>,
+    /// The tier behind the change, or `None` for an unranked or unbound member.
+    rank_level: Option<String>,
+}
+
+/// What to change, given each fetched member's best tier (`standings`), who is
+/// bound at all (`bound`), and what the bot has already granted.
+///
+/// A member who is bound but missing from `standings` — any one of their
+/// profiles failed to fetch this refresh — is left exactly as they are: a
+/// hiccup at aoe4world is not a demotion.
+fn plan(
+    mapping: &Mapping,
+    standings: &HashMap<i64, Option<String>>,
+    bound: &HashSet<i64>,
+    grants: &[TierRoleGrant],
+) -> Vec<Change> {
+    let granted = grants
+        .iter()
+        .map(|grant| (grant.user_id, RoleId::new(grant.role_id as u64)))
+        .collect::<HashMap<i64, RoleId>>();
+
+    let mut changes = Vec::new();
+    for (&user_id, rank_level) in standings {
+        let want = rank_level.as_deref().and_then(|level| role_for(mapping, level));
+        let have = granted.get(&user_id).copied();
+        if want != have {
+            changes.push(Change {
+                user_id,
+                remove: have,
+                add: want,
+                rank_level: want.and(rank_level.clone()),
+            });
+        }
+    }
+    for (&user_id, &role) in &granted {
+        if !bound.contains(&user_id) {
+            changes.push(Change {
+                user_id,
+                remove: Some(role),
+                add: None,
+                rank_level: None,
+            });
+        }
+    }
+    changes.sort_by_key(|change| change.user_id);
+    changes
+}
+
+/// Each member's best ranked solo tier across every profile fetched for them,
+/// from each fetched profile's owner and tier. `bound` counts each member's
+/// bound profiles: one whose fetch failed this refresh takes its owner out of
+/// the standings altogether, since their best tier may be on exactly the
+/// profile that is missing, and judging them on the rest would demote them.
+fn standings<'a>(
+    profiles: impl IntoIterator<Item = (i64, Option<&'a str>)>,
+    bound: &HashMap<i64, usize>,
+) -> HashMap<i64, Option<String>> {
+    let mut standings: HashMap<i64, Option<String>> = HashMap::new();
+    let mut fetched: HashMap<i64, usize> = HashMap::new();
+    for (user_id, level) in profiles {
+        *fetched.entry(user_id).or_default() += 1;
+        let level = level.filter(|level| rank_level_order(level).is_some());
+        let best = standings.entry(user_id).or_default();
+        if level.and_then(rank_level_order) > best.as_deref().and_then(rank_level_order) {
+            *best = level.map(str::to_string);
+        }
+    }
+    standings.retain(|user_id, _| fetched.get(user_id) >= bound.get(user_id));
+    standings
+}
+
+/// Brings every member's tier role in line with this refresh. Best-effort,
+/// like `history::record`: a failure is logged and retried next refresh, and
+/// never fails the board.
+///
+/// `bound` is how many profiles each member has bound, fetched or not.
+pub(crate) async fn sync(http: &Http, data: &Data, bound: &HashMap<i64, usize>, members: &[Member]) {
+    let Some(mapping) = configured() else {
+        return;
+    };
+    let pool = &data.database;
+    let grants = match list_tier_role_grants(pool).await {
+        Ok(grants) => grants,
+        Err(err) => {
+            error!("could not read tier role grants: {err:?}");
+            return;
+        },
+    };
+
+    let guild = data.guilds.home;
+    let profiles = members
+        .iter()
+        .filter_map(|member| Some((member.user_id()?, member.solo_rank_level())));
+    let standings = standings(profiles, bound);
+    let bound = bound.keys().copied().collect::<HashSet<i64>>();
+    for change in plan(&mapping, &standings, &bound, &grants) {
+        let user = to_user_id(change.user_id);
+        let reason = format!(
+            "rank tier sync: {}",
+            change.rank_level.as_deref().unwrap_or("unranked or unbound")
+        );
+
+        if let Some(role) = change.remove {
+            match http.remove_member_role(guild, user, role, Some(&reason)).await {
+                // Already gone from the guild is as good as removed.
+                Err(err) if !panel_check::is_confirmed_missing(&err) => {
+                    error!("could not remove tier role {role} from {user}: {err:?}");
+                    continue;
+                },
+                _ => {},
+            }
+            info!("tier role {role} removed from {user} ({reason})");
+            if let Err(err) =
+                revoke_tier_role(pool, change.user_id, role.get() as i64, change.rank_level.as_deref()).await
+            {
+                error!("could not record the removal of tier role {role} from {user}: {err:?}");
+            }
+        }
+
+        if let (Some(role), Some(rank_level)) = (change.add, change.rank_level.as_deref()) {
+            if let Err(err) = http.add_member_role(guild, user, role, Some(&reason)).await {
+                error!("could not add tier role {role} to {user}: {err:?}");
+                continue;
+            }
+            info!("tier role {role} added to {user} ({reason})");
+            if let Err(err) = grant_tier_role(pool, change.user_id, role.get() as i64, rank_level).await {
+                error!("could not record tier role {role} for {user}: {err:?}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> Mapping {
+        parse_mapping("diamond=4, platinum=3").unwrap()
+    }
+
+    fn grant(user_id: i64, role_id: i64) -> TierRoleGrant {
+        TierRoleGrant {
+            user_id,
+            role_id,
+            rank_level: "whatever".to_string(),
+        }
+    }
+
+    #[test]
+    fn a_mapping_names_tiers_and_role_ids() {
+        let mapping = mapping();
+        assert_eq!(mapping.get("diamond"), Some(&RoleId::new(4)));
+        assert_eq!(role_for(&mapping, "diamond_2"), Some(RoleId::new(4)));
+        assert_eq!(role_for(&mapping, "gold_3"), None);
+        assert_eq!(role_for(&mapping, "unranked"), None);
+    }
+
+    #[test]
+    fn a_mapping_with_any_bad_entry_is_refused() {
+        assert!(parse_mapping("diamond=4,legend=5").is_err());
+        assert!(parse_mapping("diamond=four").is_err());
+        assert!(parse_mapping("diamond").is_err());
+        assert!(parse_mapping("diamond=4,diamond=5").is_err());
+        assert!(parse_mapping("diamond=0").is_err());
+    }
+
+    #[test]
+    fn a_newly_placed_member_is_given_their_role() {
+        let standings = HashMap::from([(1, Some("diamond_1".to_string()))]);
+        let changes = plan(&mapping(), &standings, &HashSet::from([1]), &[]);
+        assert_eq!(
+            changes,
+            vec![Change {
+                user_id: 1,
+                remove: None,
+                add: Some(RoleId::new(4)),
+                rank_level: Some("diamond_1".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn a_member_who_holds_the_right_role_is_left_alone() {
+        let standings = HashMap::from([(1, Some("diamond_3".to_string()))]);
+        assert!(plan(&mapping(), &standings, &HashSet::from([1]), &[grant(1, 4)]).is_empty());
+    }
+
+    #[test]
+    fn moving_tier_swaps_the_role() {
+        let standings = HashMap::from([(1, Some("platinum_3".to_string()))]);
+        let changes = plan(&mapping(), &standings, &HashSet::from([1]), &[grant(1, 4)]);
+        assert_eq!(changes[0].remove, Some(RoleId::new(4)));
+        assert_eq!(changes[0].add, Some(RoleId::new(3)));
+    }
+
+    #[test]
+    fn going_unranked_or_unmapped_only_removes() {
+        let standings = HashMap::from([(1, None), (2, Some("gold_2".to_string()))]);
+        let changes = plan(
+            &mapping(),
+            &standings,
+            &HashSet::from([1, 2]),
+            &[grant(1, 4), grant(2, 3)],
+        );
+        assert_eq!(changes.len(), 2);
+        assert!(
+            changes
+                .iter()
+                .all(|change| change.add.is_none() && change.remove.is_some())
+        );
+    }
+
+    #[test]
+    fn unbinding_removes_the_role() {
+        let changes = plan(&mapping(), &HashMap::new(), &HashSet::new(), &[grant(1, 4)]);
+        assert_eq!(
+            changes,
+            vec![Change {
+                user_id: 1,
+                remove: Some(RoleId::new(4)),
+                add: None,
+                rank_level: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn a_member_whose_profiles_failed_to_fetch_keeps_their_role() {
+        assert!(plan(&mapping(), &HashMap::new(), &HashSet::from([1]), &[grant(1, 4)]).is_empty());
+    }
+
+    #[test]
+    fn a_member_with_any_profile_unfetched_is_left_out_of_the_standings() {
+        let bound = HashMap::from([(1, 2), (2, 2)]);
+        let standings = standings(
+            [(1, Some("gold_2")), (2, Some("gold_2")), (2, Some("diamond_1"))],
+            &bound,
+        );
+        // Member 1's diamond alt did not come back; gold alone would demote them.
+        assert_eq!(standings, HashMap::from([(2, Some("diamond_1".to_string()))]));
+    }
+}
@@ -13,6 +13,8 @@ pub(crate) enum Action {
     SetDone,
     Redraft,
     CallAdmin,
+    ScheduleConfirm,
+    ScheduleDecline,
 }
 
 impl Action {
@@ -24,6 +26,8 @@ impl Action {
             Action::SetDone => "setdone",
             Action::Redraft => "redraft",
             Action::CallAdmin => "calladmin",
+            Action::ScheduleConfirm => "schedconfirm",
+            Action::ScheduleDecline => "scheddecline",
         }
     }
 
@@ -35,16 +39,22 @@ impl Action {
             "setdone" => Some(Action::SetDone),
             "redraft" => Some(Action::Redraft),
             "calladmin" => Some(Action::CallAdmin),
+            "schedconfirm" => Some(Action::ScheduleConfirm),
+            "scheddecline" => Some(Action::ScheduleDecline),
             _ => None,
         }
     }
 
     /// `Register` (aoe4world lookup), `SetDone` (draft-tool fetch) and `Redraft`
     /// (`POST /api/matches`, possibly behind a re-auth handshake) each make an
-    /// outbound HTTP call that can outlast Discord's 3s ack window; the rest are
-    /// a local DB write and can answer immediately.
+    /// outbound HTTP call that can outlast Discord's 3s ack window, and the two
+    /// schedule answers look up the presser's guild permissions before they can
+    /// decide anything; the rest are a local DB write and can answer immediately.
     pub(crate) fn requires_defer(self) -> bool {
-        matches!(self, Action::Register | Action::SetDone | Action::Redraft)
+        matches!(
+            self,
+            Action::Register | Action::SetDone | Action::Redraft | Action::ScheduleConfirm | Action::ScheduleDecline
+        )
     }
 
     /// The `custom_id` a button carries. Built by every panel that has one — every
@@ -69,13 +79,15 @@ pub(crate) fn parse_custom_id(custom_id: &str) -> Option<(Action, i64)> {
 mod tests {
     use super::*;
 
-    const ALL_ACTIONS: [Action; 6] = [
+    const ALL_ACTIONS: [Action; 8] = [
         Action::Register,
         Action::Withdraw,
         Action::Checkin,
         Action::SetDone,
         Action::Redraft,
         Action::CallAdmin,
+        Action::ScheduleConfirm,
+        Action::ScheduleDecline,
     ];
 
     #[test]
@@ -112,6 +124,8 @@ mod tests {
         assert!(Action::Register.requires_defer());
         assert!(Action::SetDone.requires_defer());
         assert!(Action::Redraft.requires_defer());
+        assert!(Action::ScheduleConfirm.requires_defer());
+        assert!(Action::ScheduleDecline.requires_defer());
         assert!(!Action::Withdraw.requires_defer());
         assert!(!Action::Checkin.requires_defer());
         assert!(!Action::CallAdmin.requires_defer());
@@ -8,17 +8,36 @@
 //! here rather than being written out at each of them.
 
 use serenity::all::User;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display, Formatter};
 use tracing::info;
 
-/// Name *and* id: the name is what makes a log line readable months later, the
-/// id is what still resolves after someone renames themselves.
-///
+/// Who took an action: a member through a command or a button, or the
+/// autopilot on the tournament's timeline.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Actor<'a> {
+    Member(&'a User),
+    Autopilot,
+}
+
+impl Display for Actor<'_> {
+    /// Name *and* id: the name is what makes a log line readable months later, the
+    /// id is what still resolves after someone renames themselves.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Actor::Member(user) => write!(f, "{} ({})", user.name, user.id),
+            Actor::Autopilot => f.write_str("autopilot"),
+        }
+    }
+}
+
 /// `outcome` is the action's own `*Outcome` enum: its `Debug` already names the
 /// variant and carries the counts, so there is nothing to spell out by hand.
 pub(crate) fn log_action(action: &str, tournament_id: i64, slug: &str, actor: &User, outcome: &impl Debug) {
-    info!(
-        "{action} on tournament {tournament_id} ({slug}) by {} ({}): {outcome:?}",
-        actor.name, actor.id
-    );
+    log_action_by(action, tournament_id, slug, Actor::Member(actor), outcome);
+}
+
+/// `log_action` for whichever `Actor` took the step, so a transition the
+/// autopilot ran leaves the same line as one an organizer ran by hand.
+pub(crate) fn log_action_by(action: &str, tournament_id: i64, slug: &str, actor: Actor<'_>, outcome: &impl Debug) {
+    info!("{action} on tournament {tournament_id} ({slug}) by {actor}: {outcome:?}");
 }
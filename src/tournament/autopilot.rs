@@ -0,0 +1,407 @@
+//! The autopilot: an opt-in per tournament that takes the lifecycle's forward
+//! steps on the scheduled timeline instead of waiting for an organizer to.
+//!
+//! | when                                          | step                      |
+//! |-----------------------------------------------|---------------------------|
+//! | `scheduled_start_at - setup::CHECKIN_LEAD`    | open check-in             |
+//! | `CLOSING_PING_LEAD` before check-in closes    | ping whoever is still out |
+//! | `checkin_closes_at`                           | close check-in and seed   |
+//! | `scheduled_start_at`, once in seeding         | start                     |
+//!
+//! Each step goes through `lifecycle`, the same path the commands take, so it
+//! is refused by the same gates and leaves the same audit line — with
+//! `Actor::Autopilot` where a member's name would be. In particular the start
+//! is still `start::start`, which will not run while `setup::missing` lists
+//! anything.
+//!
+//! **A refusal turns the autopilot off.** A refused step is one the tournament
+//! needs a person for — a gap in the seeds, a missing preset — and trying again
+//! every minute would only repeat the refusal. The tournament's creator and
+//! admins are told in the announce channel why, and can run the step by hand or
+//! turn the autopilot back on once it is fixed.
+//!
+//! `next_step` is the pure part, tested here; `run` is ticked every minute from
+//! `main.rs`, alongside the set reminders.
+
+use crate::db::{to_channel_id, to_user_id};
+use crate::locale::Locale;
+use crate::tournament::audit::Actor;
+use crate::tournament::checkin::{self, CloseCheckinOutcome, OpenCheckinOutcome};
+use crate::tournament::db::{self, Tournament};
+use crate::tournament::lifecycle;
+use crate::tournament::setup;
+use crate::tournament::start::StartOutcome;
+use crate::{Data, Error};
+use chrono::{DateTime, Duration, Utc};
+use serenity::all::{CreateAllowedMentions, CreateMessage, Http};
+use sqlx::SqlitePool;
+use tracing::{error, info};
+
+/// How long before check-in closes the last call goes out.
+pub(crate) const CLOSING_PING_LEAD: Duration = Duration::minutes(10);
+
+/// The shortest check-in the autopilot opens. Normally check-in runs the whole
+/// `CHECKIN_LEAD` up to the start; opened late — the bot was down when it came
+/// due — it still gets this long rather than closing on the next tick.
+pub(crate) const LATE_CHECKIN_WINDOW: Duration = Duration::minutes(15);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Step {
+    /// `minutes` is what `checkin::open` is given, so check-in closes at the start.
+    OpenCheckin {
+        minutes: i64,
+    },
+    RemindClosing {
+        closes_at: DateTime<Utc>,
+    },
+    CloseCheckin,
+    Start,
+}
+
+impl Step {
+    fn label(self, locale: Locale) -> &'static str {
+        match self {
+            Step::OpenCheckin { .. } => locale.pick("開放簽到", "open check-in for"),
+            Step::RemindClosing { .. } => locale.pick("提醒簽到即將截止", "send the check-in reminder for"),
+            Step::CloseCheckin => locale.pick("結束簽到", "close check-in for"),
+            Step::Start => locale.pick("開賽", "start"),
+        }
+    }
+}
+
+/// When check-in closes: the time it was opened with, or the start if it was
+/// opened by hand without one.
+pub(crate) fn closes_at(tournament: &Tournament) -> Option<DateTime<Utc>> {
+    tournament.checkin_closes_at.or(tournament.scheduled_start_at)
+}
+
+/// The step that is due for `tournament` at `now`, if any.
+///
+/// A start time still at its placeholder opens nothing: a week after creation
+/// is a tripwire for organizers (see `setup::DEFAULT_START_LEAD`), not a time
+/// anyone chose, and the autopilot must not act on it.
+pub(crate) fn next_step(tournament: &Tournament, now: DateTime<Utc>) -> Option<Step> {
+    if !tournament.autopilot {
+        return None;
+    }
+    match tournament.status.as_str() {
+        "registration" => {
+            let start = tournament.scheduled_start_at?;
+            if setup::start_time_is_default(tournament) || now < setup::checkin_opens_at(start) {
+                return None;
+            }
+            Some(Step::OpenCheckin {
+                minutes: checkin_minutes(start, now),
+            })
+        },
+        "checkin" => {
+            let closes_at = closes_at(tournament)?;
+            if now >= closes_at {
+                Some(Step::CloseCheckin)
+            } else if tournament.checkin_reminded_at.is_none() && now >= closes_at - CLOSING_PING_LEAD {
+                Some(Step::RemindClosing { closes_at })
+            } else {
+                None
+            }
+        },
+        "seeding" => setup::may_start_at(tournament.scheduled_start_at, now).then_some(Step::Start),
+        _ => None,
+    }
+}
+
+/// Whole minutes from `now` to the start, rounded up so check-in closes at the
+/// start rather than a minute short of it, and never under `LATE_CHECKIN_WINDOW`.
+fn checkin_minutes(start: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    let minutes = ((start - now).num_seconds() + 59).div_euclid(60);
+    minutes.max(LATE_CHECKIN_WINDOW.num_minutes())
+}
+
+/// The scheduler's tick. Logs rather than fails, like `schedule::run`: one
+/// tournament whose step errors must not hold up the rest.
+pub(crate) async fn run(http: &Http, data: &Data) {
+    tick(http, &data.database, Utc::now()).await;
+}
+
+/// `run` against an explicit clock, so a test can put it anywhere on a timeline.
+///
+/// An error is not a refusal and does not turn the autopilot off. A step whose
+/// database write failed is simply due again next tick; one whose Discord
+/// follow-up failed has already moved the status, so the next tick moves on.
+pub(crate) async fn tick(http: &Http, pool: &SqlitePool, now: DateTime<Utc>) {
+    let tournaments = match db::list_live_tournaments(pool).await {
+        Ok(tournaments) => tournaments,
+        Err(err) => {
+            error!("failed to list live tournaments for the autopilot: {err:?}");
+            return;
+        },
+    };
+    for tournament in &tournaments {
+        let Some(step) = next_step(tournament, now) else {
+            continue;
+        };
+        if let Err(err) = take(http, pool, tournament, step).await {
+            error!(
+                "autopilot step {step:?} failed for tournament {}: {err:?}",
+                tournament.id
+            );
+        }
+    }
+}
+
+async fn take(http: &Http, pool: &SqlitePool, tournament: &Tournament, step: Step) -> Result<(), Error> {
+    let name = &tournament.name;
+    let refusal = match step {
+        Step::OpenCheckin { minutes } => {
+            let outcome =
+                lifecycle::open_checkin(http, pool, tournament, Some(minutes), Actor::Autopilot, None).await?;
+            (!matches!(outcome, OpenCheckinOutcome::Opened { .. })).then(|| both(|l| outcome.message(name, l)))
+        },
+        Step::RemindClosing { closes_at } => {
+            remind_closing(http, pool, tournament, closes_at).await?;
+            None
+        },
+        Step::CloseCheckin => {
+            let (outcome, _) = lifecycle::close_checkin(http, pool, tournament, Actor::Autopilot).await?;
+            (!matches!(outcome, CloseCheckinOutcome::Closed { .. })).then(|| both(|l| outcome.message(name, l)))
+        },
+        Step::Start => {
+            let outcome = lifecycle::start(http, pool, tournament, Actor::Autopilot).await?;
+            (!matches!(outcome, StartOutcome::Started { .. })).then(|| both(|l| outcome.message(name, l)))
+        },
+    };
+    if let Some(reasons) = refusal {
+        refuse(http, pool, tournament, step, reasons).await?;
+    }
+    Ok(())
+}
+
+/// An outcome's wording in both languages, for a post with no one reader.
+fn both(message: impl Fn(Locale) -> String) -> (String, String) {
+    (message(Locale::ZhTw), message(Locale::En))
+}
+
+/// The last call before check-in closes, to whoever in the check-in pool has
+/// not checked in. Claimed before it is posted, so it goes out at most once per
+/// close time — a failed post is lost rather than repeated every minute.
+async fn remind_closing(
+    http: &Http,
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    closes_at: DateTime<Utc>,
+) -> Result<(), Error> {
+    if !db::mark_checkin_reminded(pool, tournament.id, Utc::now()).await? {
+        return Ok(());
+    }
+    let Some(register_channel_id) = tournament.register_channel_id else {
+        return Ok(());
+    };
+    let entries = db::list_entries_for_tournament(pool, tournament.id).await?;
+    let outstanding: Vec<i64> = checkin::checkin_pool(&entries)
+        .iter()
+        .filter(|e| e.checked_in_at.is_none())
+        .map(|e| e.user_id)
+        .collect();
+    for message in checkin::checkin_closing_messages(closes_at, &outstanding) {
+        to_channel_id(register_channel_id).say(http, message).await?;
+    }
+    info!(
+        "autopilot pinged {} entrants before check-in closes on tournament {} ({})",
+        outstanding.len(),
+        tournament.id,
+        tournament.slug
+    );
+    Ok(())
+}
+
+/// Turns the autopilot off and tells the tournament's creator and admins why, in
+/// the announce channel. Only the tick that actually turned it off posts, so a
+/// refusal is announced once.
+async fn refuse(
+    http: &Http,
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    step: Step,
+    (reason_zh, reason_en): (String, String),
+) -> Result<(), Error> {
+    if !db::disengage_autopilot(pool, tournament.id).await? {
+        return Ok(());
+    }
+    info!(
+        "autopilot turned off on tournament {} ({}) after {step:?} was refused",
+        tournament.id, tournament.slug
+    );
+    let Some(announce_channel_id) = tournament.announce_channel_id else {
+        return Ok(());
+    };
+
+    let mut admins = vec![tournament.created_by];
+    for admin in db::list_admins(pool, tournament.id).await? {
+        if !admins.contains(&admin.user_id) {
+            admins.push(admin.user_id);
+        }
+    }
+    let content = render_refusal(&admins, &tournament.name, step, &reason_zh, &reason_en);
+    let mentions = CreateAllowedMentions::new().users(admins.iter().map(|user_id| to_user_id(*user_id)));
+    to_channel_id(announce_channel_id)
+        .send_message(http, CreateMessage::new().content(content).allowed_mentions(mentions))
+        .await?;
+    Ok(())
+}
+
+fn render_refusal(admins: &[i64], name: &str, step: Step, reason_zh: &str, reason_en: &str) -> String {
+    let mentions = admins.iter().map(|id| format!("<@{id}>")).collect::<Vec<_>>().join(" ");
+    format!(
+        "{mentions}\n\
+         🛑 自動排程無法為 **{name}** {}：{reason_zh}\n\
+         自動排程已關閉 — 處理好之後請手動執行，或用 `/tournament setup autopilot:True` 重新開啟。\n\
+         🛑 The autopilot couldn't {} **{name}**: {reason_en}\n\
+         It is now off — once that's sorted, run the step by hand or turn it back on with \
+         `/tournament setup autopilot:True`.",
+        step.label(Locale::ZhTw),
+        step.label(Locale::En),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2099, 1, 1, hour, minute, 0).unwrap()
+    }
+
+    /// Starting at 20:00, made long before, with the autopilot on.
+    fn tournament(status: &str) -> Tournament {
+        Tournament {
+            id: 1,
+            slug: "relic-cup".to_string(),
+            name: "Relic Cup".to_string(),
+            status: status.to_string(),
+            draft_base_url: None,
+            announce_channel_id: Some(10),
+            category_id: None,
+            register_channel_id: Some(11),
+            register_message_id: None,
+            bracket_channel_id: Some(12),
+            matches_channel_id: Some(13),
+            draft_channel_id: Some(14),
+            checkin_message_id: None,
+            seed_message_id: None,
+            checkin_closes_at: None,
+            entrant_cap: 32,
+            scheduled_start_at: Some(at(20, 0)),
+            seed_source: "suggested".to_string(),
+            registration_mode: "open".to_string(),
+            format: "single_elim".to_string(),
+            grand_final_reset: false,
+            swiss_rounds: None,
+            group_count: None,
+            group_advance: None,
+            playoff_format: "single_elim".to_string(),
+            schedule_reminders: "60,10".to_string(),
+            autopilot: true,
+            checkin_reminded_at: None,
+            created_by: 1,
+            created_at: at(0, 0) - Duration::days(30),
+            started_at: None,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn check_in_opens_an_hour_out_and_runs_until_the_start() {
+        let registration = tournament("registration");
+        assert_eq!(next_step(&registration, at(18, 59)), None);
+        assert_eq!(
+            next_step(&registration, at(19, 0)),
+            Some(Step::OpenCheckin { minutes: 60 })
+        );
+        // A tick a few seconds late still closes at the start, not a minute early.
+        let late_tick = at(19, 0) + Duration::seconds(3);
+        assert_eq!(
+            next_step(&registration, late_tick),
+            Some(Step::OpenCheckin { minutes: 60 })
+        );
+    }
+
+    #[test]
+    fn a_check_in_opened_late_still_gets_a_window() {
+        let registration = tournament("registration");
+        assert_eq!(
+            next_step(&registration, at(19, 55)),
+            Some(Step::OpenCheckin {
+                minutes: LATE_CHECKIN_WINDOW.num_minutes()
+            })
+        );
+        assert_eq!(
+            next_step(&registration, at(21, 0)),
+            Some(Step::OpenCheckin {
+                minutes: LATE_CHECKIN_WINDOW.num_minutes()
+            })
+        );
+    }
+
+    #[test]
+    fn nothing_happens_with_the_autopilot_off_or_on_a_placeholder_start() {
+        let off = Tournament {
+            autopilot: false,
+            ..tournament("registration")
+        };
+        assert_eq!(next_step(&off, at(19, 30)), None);
+
+        let mut placeholder = tournament("registration");
+        placeholder.created_at = at(20, 0) - setup::DEFAULT_START_LEAD;
+        assert_eq!(next_step(&placeholder, at(19, 30)), None);
+    }
+
+    #[test]
+    fn check_in_is_pinged_once_before_it_closes_and_then_closed() {
+        let checkin = Tournament {
+            checkin_closes_at: Some(at(20, 0)),
+            ..tournament("checkin")
+        };
+        assert_eq!(next_step(&checkin, at(19, 49)), None);
+        assert_eq!(
+            next_step(&checkin, at(19, 50)),
+            Some(Step::RemindClosing { closes_at: at(20, 0) })
+        );
+
+        let reminded = Tournament {
+            checkin_reminded_at: Some(at(19, 50)),
+            ..checkin
+        };
+        assert_eq!(next_step(&reminded, at(19, 55)), None);
+        assert_eq!(next_step(&reminded, at(20, 0)), Some(Step::CloseCheckin));
+    }
+
+    #[test]
+    fn a_check_in_opened_by_hand_without_a_close_time_closes_at_the_start() {
+        let checkin = tournament("checkin");
+        assert_eq!(next_step(&checkin, at(20, 0)), Some(Step::CloseCheckin));
+
+        let unscheduled = Tournament {
+            scheduled_start_at: None,
+            ..tournament("checkin")
+        };
+        assert_eq!(next_step(&unscheduled, at(23, 0)), None, "nothing says when to close");
+    }
+
+    #[test]
+    fn the_start_waits_for_its_time_and_nothing_follows_it() {
+        let seeding = tournament("seeding");
+        assert_eq!(next_step(&seeding, at(19, 59)), None);
+        assert_eq!(next_step(&seeding, at(20, 0)), Some(Step::Start));
+        for status in ["running", "completed", "canceled"] {
+            assert_eq!(next_step(&tournament(status), at(23, 0)), None, "{status}");
+        }
+    }
+
+    #[test]
+    fn a_refusal_pings_every_admin_and_says_how_to_carry_on() {
+        let text = render_refusal(&[1, 2], "Relic Cup", Step::Start, "原因", "the reason");
+        assert!(text.starts_with("<@1> <@2>\n"), "{text}");
+        assert!(text.contains("couldn't start **Relic Cup**: the reason"), "{text}");
+        assert!(text.contains("原因") && text.contains("autopilot:True"), "{text}");
+    }
+}
@@ -1,9 +1,12 @@
-//! Single-elimination bracket generation.
+//! Single- and double-elimination bracket generation, and the entry point to a
+//! Swiss stage's first round. A group stage is the one format with no bracket
+//! of its own — `groups` builds its rounds, and the playoff it feeds is one of
+//! the elimination brackets here.
 //!
 //! Pure: no database, no Discord. A bracket is a function of the number of entrants
 //! and the per-round match lengths, so the parts that are easy to get quietly wrong
-//! — the seed order, where byes land, which set feeds which — are testable on their
-//! own.
+//! — the seed order, where byes land, which set feeds which, where a loser drops
+//! to — are testable on their own.
 
 use crate::locale::Locale;
 use std::fmt::{Display, Formatter};
@@ -27,6 +30,94 @@ pub(crate) struct Advancement {
 /// presentational branch that needs to recognize it — one literal so they can't drift.
 pub(crate) const THIRD_PLACE: &str = "Third Place";
 
+/// The double-elimination names that are not built from a set count. Constants for
+/// the same reason as `THIRD_PLACE`: `Side::of_round_name` and the localization
+/// both have to recognize exactly what generation wrote.
+pub(crate) const LOSERS_FINAL: &str = "Losers Final";
+pub(crate) const GRAND_FINAL: &str = "Grand Final";
+pub(crate) const GRAND_FINAL_RESET: &str = "Grand Final Reset";
+const WINNERS_PREFIX: &str = "Winners ";
+const LOSERS_ROUND_PREFIX: &str = "Losers Round ";
+
+/// How a tournament's bracket is played, as chosen in `/tournament setup`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Format {
+    SingleElimination,
+    /// `grand_final_reset`: whether the losers-bracket champion, on beating the
+    /// winners-bracket champion once, forces a second grand final — the only way
+    /// both finalists have lost twice before one of them is out.
+    DoubleElimination {
+        grand_final_reset: bool,
+    },
+    /// Only round one is built here; `swiss::advance` pairs each later round from
+    /// the standings once the one before it is over.
+    Swiss,
+    /// Round-robin groups, then a playoff seeded from them — `tournaments.
+    /// playoff_format` says which bracket. Built by `groups`, which needs the
+    /// group count as well as the field, so `build_for` refuses it.
+    Groups,
+}
+
+impl Format {
+    /// From `tournaments.format` and `tournaments.grand_final_reset`, or from
+    /// `tournaments.playoff_format` and the same flag. Anything this build does
+    /// not generate is single elimination, which is what the column defaults to.
+    /// `round_robin` is a group stage of one group, so it reads as `Groups`.
+    pub(crate) fn from_db(format: &str, grand_final_reset: bool) -> Self {
+        match format {
+            "double_elim" => Format::DoubleElimination { grand_final_reset },
+            "swiss" => Format::Swiss,
+            "group" | "round_robin" => Format::Groups,
+            _ => Format::SingleElimination,
+        }
+    }
+
+    /// The `tournaments.format` / `tournament_stages.format` value.
+    pub(crate) fn as_db(self) -> &'static str {
+        match self {
+            Format::SingleElimination => "single_elim",
+            Format::DoubleElimination { .. } => "double_elim",
+            Format::Swiss => "swiss",
+            Format::Groups => "group",
+        }
+    }
+}
+
+/// Which part of a double-elimination bracket a round belongs to. A single
+/// elimination round has no side, which is `Round::side` being `None`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Side {
+    Winners,
+    Losers,
+    GrandFinal,
+}
+
+impl Side {
+    /// The `tournament_rounds.bracket` value.
+    pub(crate) fn as_db(self) -> &'static str {
+        match self {
+            Side::Winners => "winners",
+            Side::Losers => "losers",
+            Side::GrandFinal => "grand_final",
+        }
+    }
+
+    /// Reads the side back off a stored round name, the way `localize_round_name`
+    /// does, so a surface holding only a `render::Round` can still tell the halves
+    /// apart.
+    pub(crate) fn of_round_name(name: &str) -> Option<Self> {
+        if name.starts_with(WINNERS_PREFIX) {
+            Some(Side::Winners)
+        } else if name.starts_with(LOSERS_ROUND_PREFIX) || name == LOSERS_FINAL {
+            Some(Side::Losers)
+        } else if name == GRAND_FINAL || name == GRAND_FINAL_RESET {
+            Some(Side::GrandFinal)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub(crate) struct Set {
     /// 1-based, top to bottom within the round.
@@ -35,11 +126,13 @@ pub(crate) struct Set {
     /// results land, and an absent seed in round one is what leaves a bye.
     pub(crate) slot1: Option<u32>,
     pub(crate) slot2: Option<u32>,
-    /// `None` only on the final and the 3rd place match — neither sends its winner
-    /// anywhere else.
+    /// `None` only on a set whose winner is done: the final, the 3rd place match,
+    /// and the last grand final.
     pub(crate) winner_advances_to: Option<Advancement>,
-    /// `Some` only on the two semifinal sets, pointing at the 3rd place match: single
-    /// elimination otherwise has nothing for a loser to do.
+    /// In single elimination, `Some` only on the two semifinal sets, pointing at the
+    /// 3rd place match. In double elimination, every winners-bracket set drops its
+    /// loser into the losers bracket, and a grand final with a reset sends its loser
+    /// to the reset.
     pub(crate) loser_advances_to: Option<Advancement>,
 }
 
@@ -59,6 +152,13 @@ pub(crate) struct Round {
     pub(crate) ordinal: usize,
     pub(crate) name: String,
     pub(crate) best_of: u8,
+    /// `None` in single elimination.
+    pub(crate) side: Option<Side>,
+    /// The main-bracket round whose `best_of` and draft preset this round runs: its
+    /// own ordinal, unless it borrows another's — the 3rd place match takes the
+    /// semifinal's, a losers-bracket round the winners round whose losers it takes,
+    /// and the grand final the winners final's.
+    pub(crate) preset_from: usize,
     pub(crate) sets: Vec<Set>,
 }
 
@@ -74,6 +174,8 @@ pub(crate) enum BracketError {
     /// One `best_of` per round, no more and no fewer — match length is stored per
     /// round rather than per tournament, so there is no sensible default to fill in.
     RoundCountMismatch { rounds: usize, best_of: usize },
+    /// `build_for` was handed a group stage, which `groups` builds instead.
+    NotABracket,
 }
 
 impl Display for BracketError {
@@ -85,6 +187,7 @@ impl Display for BracketError {
             Self::RoundCountMismatch { rounds, best_of } => {
                 write!(f, "this bracket has {rounds} rounds but {best_of} best_of values")
             },
+            Self::NotABracket => write!(f, "a group stage is not built as a bracket"),
         }
     }
 }
@@ -117,6 +220,26 @@ pub(crate) fn seed_order(size: usize) -> Vec<u32> {
     order
 }
 
+/// Build the whole bracket from a finalized field, in whichever format the
+/// tournament plays. `best_of` is one value per main-bracket round either way —
+/// per Swiss round, for a Swiss stage, of which only the first exists yet.
+pub(crate) fn build_for(format: Format, entrants: usize, best_of: &[u8]) -> Result<Bracket, BracketError> {
+    match format {
+        Format::SingleElimination => build(entrants, best_of),
+        Format::DoubleElimination { grand_final_reset } => build_double(entrants, best_of, grand_final_reset),
+        Format::Swiss => {
+            if entrants < 2 {
+                return Err(BracketError::TooFewEntrants(entrants));
+            }
+            let Some(&first) = best_of.first() else {
+                return Err(BracketError::RoundCountMismatch { rounds: 1, best_of: 0 });
+            };
+            Ok(crate::tournament::swiss::first_round(entrants, first))
+        },
+        Format::Groups => Err(BracketError::NotABracket),
+    }
+}
+
 /// Build the whole bracket from a finalized field.
 ///
 /// `entrants` is a count, not a list, because seeds are required to be 1..=n and
@@ -125,6 +248,221 @@ pub(crate) fn seed_order(size: usize) -> Vec<u32> {
 /// the appended 3rd place round, when one exists, reuses the semifinal's own value
 /// rather than requiring an extra entry.
 pub(crate) fn build(entrants: usize, best_of: &[u8]) -> Result<Bracket, BracketError> {
+    let mut rounds = elimination_rounds(entrants, best_of)?;
+    let round_count = rounds.len();
+
+    // The 3rd place match: the two semifinal losers play each other, using the
+    // semifinal's own best_of. `round_count >= 2` is checked before indexing back
+    // into `rounds` — with only one round there is no semifinal to take it from.
+    // Skipped when a semifinal set is a bye, since a bye leaves only one loser to
+    // fill the match (only possible when round_count == 2, i.e. 3 entrants — for
+    // round_count >= 3 every semifinal set always fills both slots).
+    if round_count >= 2 {
+        let semifinal_ordinal = round_count - 1;
+        let semifinal = &mut rounds[semifinal_ordinal - 1];
+        if !semifinal.sets[0].is_bye() && !semifinal.sets[1].is_bye() {
+            let third_place_ordinal = round_count + 1;
+            let semifinal_best_of = semifinal.best_of;
+            semifinal.sets[0].loser_advances_to = Some(Advancement {
+                round: third_place_ordinal,
+                position: 1,
+                slot: Slot::One,
+            });
+            semifinal.sets[1].loser_advances_to = Some(Advancement {
+                round: third_place_ordinal,
+                position: 1,
+                slot: Slot::Two,
+            });
+
+            rounds.push(Round {
+                ordinal: third_place_ordinal,
+                name: THIRD_PLACE.to_owned(),
+                best_of: semifinal_best_of,
+                side: None,
+                preset_from: semifinal_ordinal,
+                sets: vec![empty_set(1)],
+            });
+        }
+    }
+
+    Ok(Bracket { rounds })
+}
+
+/// Build a double-elimination bracket: the single-elimination tree as the winners
+/// bracket, a losers bracket its losers drop into, and a grand final between the
+/// two champions — plus, with `grand_final_reset`, a second grand final that is
+/// only played if the losers-bracket champion wins the first.
+///
+/// Ordinals run on from the winners bracket through the losers bracket to the
+/// grand final, so every round in the stage still has its own, and an
+/// `Advancement` means the same thing it does in single elimination.
+///
+/// The losers bracket alternates two kinds of round. Its first pairs the
+/// round-one losers; after that, each *drop* round sets the survivors against the
+/// next winners round's losers, and each round between two drops halves the field
+/// again. With `k` winners rounds that is `2(k - 1)` losers rounds, the last of
+/// them the losers final against whoever lost the winners final.
+///
+/// `best_of` is one value per winners round, as in `build`. A losers round plays
+/// alongside the winners round whose losers it takes, so it runs that round's
+/// length, and the grand final runs the winners final's.
+pub(crate) fn build_double(entrants: usize, best_of: &[u8], grand_final_reset: bool) -> Result<Bracket, BracketError> {
+    let mut rounds = elimination_rounds(entrants, best_of)?;
+    let winners_rounds = rounds.len();
+    let size = size(entrants);
+    let losers_rounds = 2 * (winners_rounds - 1);
+    let grand_final = winners_rounds + losers_rounds + 1;
+
+    for round in &mut rounds {
+        round.name = format!("{WINNERS_PREFIX}{}", round.name);
+        round.side = Some(Side::Winners);
+    }
+    rounds[winners_rounds - 1].sets[0].winner_advances_to = Some(Advancement {
+        round: grand_final,
+        position: 1,
+        slot: Slot::One,
+    });
+
+    for losers_round in 1..=losers_rounds {
+        let ordinal = winners_rounds + losers_round;
+        // The winners round this one plays alongside: round one's losers are
+        // paired while round two is played, and each later pair of losers rounds
+        // takes one more winners round's losers.
+        let alongside = losers_round.div_ceil(2) + 1;
+        let set_count = size >> alongside;
+        let last = losers_round == losers_rounds;
+
+        if losers_round == 1 {
+            for set in &mut rounds[0].sets {
+                set.loser_advances_to = Some(Advancement {
+                    round: ordinal,
+                    position: set.position.div_ceil(2),
+                    slot: if set.position % 2 == 1 { Slot::One } else { Slot::Two },
+                });
+            }
+        } else if losers_round % 2 == 0 {
+            // A drop round: the survivors keep their positions in slot one, and the
+            // winners round's losers drop into slot two.
+            for set in &mut rounds[ordinal - 2].sets {
+                set.winner_advances_to = Some(Advancement {
+                    round: ordinal,
+                    position: set.position,
+                    slot: Slot::One,
+                });
+            }
+            for set in &mut rounds[alongside - 1].sets {
+                set.loser_advances_to = Some(Advancement {
+                    round: ordinal,
+                    position: drop_position(set.position, set_count),
+                    slot: Slot::Two,
+                });
+            }
+        } else {
+            for set in &mut rounds[ordinal - 2].sets {
+                set.winner_advances_to = Some(Advancement {
+                    round: ordinal,
+                    position: set.position.div_ceil(2),
+                    slot: if set.position % 2 == 1 { Slot::One } else { Slot::Two },
+                });
+            }
+        }
+
+        rounds.push(Round {
+            ordinal,
+            name: if last {
+                LOSERS_FINAL.to_owned()
+            } else {
+                format!("{LOSERS_ROUND_PREFIX}{losers_round}")
+            },
+            best_of: best_of[alongside - 1],
+            side: Some(Side::Losers),
+            preset_from: alongside,
+            sets: (1..=set_count).map(empty_set).collect(),
+        });
+    }
+
+    // The losers champion takes slot two of the grand final. With a single winners
+    // round there is no losers bracket at all: whoever loses the only set goes
+    // straight there.
+    if losers_rounds == 0 {
+        rounds[0].sets[0].loser_advances_to = Some(Advancement {
+            round: grand_final,
+            position: 1,
+            slot: Slot::Two,
+        });
+    } else {
+        rounds[grand_final - 2].sets[0].winner_advances_to = Some(Advancement {
+            round: grand_final,
+            position: 1,
+            slot: Slot::Two,
+        });
+    }
+
+    let final_best_of = best_of[winners_rounds - 1];
+    let mut first = empty_set(1);
+    if grand_final_reset {
+        // Both players go on to the reset, in the same slots. Whether it is played
+        // at all is decided when the first one is: see
+        // `db::complete_set_and_advance`.
+        first.winner_advances_to = Some(Advancement {
+            round: grand_final + 1,
+            position: 1,
+            slot: Slot::One,
+        });
+        first.loser_advances_to = Some(Advancement {
+            round: grand_final + 1,
+            position: 1,
+            slot: Slot::Two,
+        });
+    }
+    rounds.push(Round {
+        ordinal: grand_final,
+        name: GRAND_FINAL.to_owned(),
+        best_of: final_best_of,
+        side: Some(Side::GrandFinal),
+        preset_from: winners_rounds,
+        sets: vec![first],
+    });
+    if grand_final_reset {
+        rounds.push(Round {
+            ordinal: grand_final + 1,
+            name: GRAND_FINAL_RESET.to_owned(),
+            best_of: final_best_of,
+            side: Some(Side::GrandFinal),
+            preset_from: winners_rounds,
+            sets: vec![empty_set(1)],
+        });
+    }
+
+    Ok(Bracket { rounds })
+}
+
+/// Where the loser of winners set `position` lands in a drop round of `set_count`
+/// sets: the position beside its own, swapped in pairs.
+///
+/// The survivors holding a drop round's slot one all came up through the part of
+/// the losers bracket fed by the same stretch of the draw as the winners set in
+/// the same position — so the straight drop would put a player against someone
+/// they already beat. Pairs swap them into the neighbouring stretch instead, which
+/// nobody in the dropping set has played. Only the losers final, a single set, has
+/// nowhere else to send anyone.
+fn drop_position(position: usize, set_count: usize) -> usize {
+    if set_count == 1 { 1 } else { ((position - 1) ^ 1) + 1 }
+}
+
+fn empty_set(position: usize) -> Set {
+    Set {
+        position,
+        slot1: None,
+        slot2: None,
+        winner_advances_to: None,
+        loser_advances_to: None,
+    }
+}
+
+/// The single-elimination tree both formats are built on: round one seeded by
+/// reflection, every winner advancing one round, and no loser going anywhere yet.
+fn elimination_rounds(entrants: usize, best_of: &[u8]) -> Result<Vec<Round>, BracketError> {
     if entrants < 2 {
         return Err(BracketError::TooFewEntrants(entrants));
     }
@@ -143,7 +481,7 @@ pub(crate) fn build(entrants: usize, best_of: &[u8]) -> Result<Bracket, BracketE
     // opponent unopposed. Reflection puts those against the top seeds.
     let seated = |seed: u32| (seed as usize <= entrants).then_some(seed);
 
-    let mut rounds: Vec<Round> = (1..=round_count)
+    Ok((1..=round_count)
         .map(|ordinal| {
             let set_count = size >> ordinal;
             let last = ordinal == round_count;
@@ -177,50 +515,12 @@ pub(crate) fn build(entrants: usize, best_of: &[u8]) -> Result<Bracket, BracketE
                 ordinal,
                 name: round_name(set_count, last),
                 best_of: best_of[ordinal - 1],
+                side: None,
+                preset_from: ordinal,
                 sets,
             }
         })
-        .collect();
-
-    // The 3rd place match: the two semifinal losers play each other, using the
-    // semifinal's own best_of. `round_count >= 2` is checked before indexing back
-    // into `rounds` — with only one round there is no semifinal to take it from.
-    // Skipped when a semifinal set is a bye, since a bye leaves only one loser to
-    // fill the match (only possible when round_count == 2, i.e. 3 entrants — for
-    // round_count >= 3 every semifinal set always fills both slots).
-    if round_count >= 2 {
-        let semifinal_ordinal = round_count - 1;
-        let semifinal = &mut rounds[semifinal_ordinal - 1];
-        if !semifinal.sets[0].is_bye() && !semifinal.sets[1].is_bye() {
-            let third_place_ordinal = round_count + 1;
-            let semifinal_best_of = semifinal.best_of;
-            semifinal.sets[0].loser_advances_to = Some(Advancement {
-                round: third_place_ordinal,
-                position: 1,
-                slot: Slot::One,
-            });
-            semifinal.sets[1].loser_advances_to = Some(Advancement {
-                round: third_place_ordinal,
-                position: 1,
-                slot: Slot::Two,
-            });
-
-            rounds.push(Round {
-                ordinal: third_place_ordinal,
-                name: THIRD_PLACE.to_owned(),
-                best_of: semifinal_best_of,
-                sets: vec![Set {
-                    position: 1,
-                    slot1: None,
-                    slot2: None,
-                    winner_advances_to: None,
-                    loser_advances_to: None,
-                }],
-            });
-        }
-    }
-
-    Ok(Bracket { rounds })
+        .collect())
 }
 
 /// Also used by the setup panel to name a preset's scope, so the two agree.
@@ -239,16 +539,38 @@ pub(crate) fn round_name(set_count: usize, last: bool) -> String {
 }
 
 /// A round's name as a reader sees it. Only the closing three have Chinese names
-/// worth giving them; `RoX` is already language-neutral and stays as it is.
+/// worth giving them; `RoX` is already language-neutral and stays as it is. A
+/// double-elimination name is its side plus the rest, localized the same way.
 ///
 /// Maps the stored English name rather than taking a depth, so every surface holding
 /// a `tournament_rounds.name` can use it without knowing the bracket's shape.
 pub(crate) fn localize_round_name(name: &str, locale: Locale) -> String {
+    if locale == Locale::ZhTw {
+        if let Some(rest) = name.strip_prefix(WINNERS_PREFIX) {
+            let rest = localize_round_name(rest, locale);
+            // `勝部 Ro16` rather than `勝部Ro16`: the spacing a reader expects
+            // between Chinese and Latin text.
+            let gap = if rest.is_ascii() { " " } else { "" };
+            return format!("勝部{gap}{rest}");
+        }
+        if let Some(number) = name.strip_prefix(LOSERS_ROUND_PREFIX) {
+            return format!("敗部第 {number} 輪");
+        }
+        if let Some(number) = name.strip_prefix(crate::tournament::swiss::ROUND_PREFIX) {
+            return format!("瑞士制第 {number} 輪");
+        }
+        if let Some(number) = name.strip_prefix(crate::tournament::groups::ROUND_PREFIX) {
+            return format!("小組賽第 {number} 輪");
+        }
+    }
     match (locale, name) {
         (Locale::ZhTw, "Final") => "決賽".to_owned(),
         (Locale::ZhTw, "Semifinal") => "準決賽".to_owned(),
         (Locale::ZhTw, "Quarterfinal") => "八強".to_owned(),
         (Locale::ZhTw, THIRD_PLACE) => "季軍賽".to_owned(),
+        (Locale::ZhTw, LOSERS_FINAL) => "敗部決賽".to_owned(),
+        (Locale::ZhTw, GRAND_FINAL) => "總決賽".to_owned(),
+        (Locale::ZhTw, GRAND_FINAL_RESET) => "總決賽重置賽".to_owned(),
         _ => name.to_owned(),
     }
 }
@@ -265,7 +587,11 @@ pub(crate) fn round_name_bilingual(name: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{Advancement, Bracket, BracketError, Slot, THIRD_PLACE, build, round_count, seed_order, size};
+    use super::{
+        Advancement, Bracket, BracketError, GRAND_FINAL, GRAND_FINAL_RESET, LOSERS_FINAL, Side, Slot, THIRD_PLACE,
+        build, build_double, round_count, seed_order, size,
+    };
+    use std::collections::BTreeSet;
 
     /// Round one as seed pairs, `None` for an absent seed.
     fn pairings(bracket: &Bracket) -> Vec<(Option<u32>, Option<u32>)> {
@@ -637,7 +963,8 @@ mod tests {
         use crate::locale::Locale;
 
         for entrants in 2..=64 {
-            for round in bracket_for(entrants).rounds {
+            let double = double_for(entrants, true);
+            for round in bracket_for(entrants).rounds.into_iter().chain(double.rounds) {
                 let zh = localize_round_name(&round.name, Locale::ZhTw);
                 assert!(
                     !zh.is_ascii() || round.name.starts_with("Ro"),
@@ -720,4 +1047,264 @@ mod tests {
             );
         }
     }
+
+    // Double elimination.
+
+    fn double_for(entrants: usize, reset: bool) -> Bracket {
+        let rounds = round_count(size(entrants));
+        build_double(entrants, &bo3(rounds), reset).expect("a valid field")
+    }
+
+    /// Every seed that could ever stand in each slot of each set, by following
+    /// both the winner and the loser links forwards from round one. Keyed by
+    /// (ordinal, position); links only ever point at a later ordinal, so one pass
+    /// in order is enough.
+    fn reachable(bracket: &Bracket) -> std::collections::HashMap<(usize, usize), [BTreeSet<u32>; 2]> {
+        let mut pools: std::collections::HashMap<(usize, usize), [BTreeSet<u32>; 2]> = std::collections::HashMap::new();
+        for round in &bracket.rounds {
+            for set in &round.sets {
+                let entry = pools.entry((round.ordinal, set.position)).or_default();
+                entry[0].extend(set.slot1);
+                entry[1].extend(set.slot2);
+                let occupants: BTreeSet<u32> = entry[0].union(&entry[1]).copied().collect();
+
+                for target in [set.winner_advances_to, set.loser_advances_to].into_iter().flatten() {
+                    let slot = usize::from(target.slot == Slot::Two);
+                    pools.entry((target.round, target.position)).or_default()[slot].extend(&occupants);
+                }
+            }
+        }
+        pools
+    }
+
+    #[test]
+    fn a_double_elimination_bracket_has_both_sides_and_a_grand_final() {
+        let names: Vec<String> = double_for(8, false).rounds.into_iter().map(|r| r.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Winners Quarterfinal",
+                "Winners Semifinal",
+                "Winners Final",
+                "Losers Round 1",
+                "Losers Round 2",
+                "Losers Round 3",
+                LOSERS_FINAL,
+                GRAND_FINAL,
+            ]
+        );
+
+        let sixteen = double_for(16, true);
+        let shape: Vec<(Option<Side>, usize)> = sixteen
+            .rounds
+            .iter()
+            .map(|round| (round.side, round.sets.len()))
+            .collect();
+        assert_eq!(
+            shape,
+            vec![
+                (Some(Side::Winners), 8),
+                (Some(Side::Winners), 4),
+                (Some(Side::Winners), 2),
+                (Some(Side::Winners), 1),
+                (Some(Side::Losers), 4),
+                (Some(Side::Losers), 4),
+                (Some(Side::Losers), 2),
+                (Some(Side::Losers), 2),
+                (Some(Side::Losers), 1),
+                (Some(Side::Losers), 1),
+                (Some(Side::GrandFinal), 1),
+                (Some(Side::GrandFinal), 1),
+            ]
+        );
+        // Ordinals run on across the sides, so every round keeps its own.
+        let ordinals: Vec<usize> = sixteen.rounds.iter().map(|round| round.ordinal).collect();
+        assert_eq!(ordinals, (1..=12).collect::<Vec<usize>>());
+        assert!(!sixteen.rounds.iter().any(|round| round.name == THIRD_PLACE));
+    }
+
+    #[test]
+    fn every_later_slot_of_a_double_elimination_bracket_is_fed_exactly_once() {
+        for entrants in [2, 3, 4, 5, 8, 11, 16, 32] {
+            for reset in [false, true] {
+                let bracket = double_for(entrants, reset);
+                let mut fed: std::collections::HashMap<(usize, usize, bool), usize> = std::collections::HashMap::new();
+                for set in bracket.rounds.iter().flat_map(|round| &round.sets) {
+                    for target in [set.winner_advances_to, set.loser_advances_to].into_iter().flatten() {
+                        *fed.entry((target.round, target.position, target.slot == Slot::Two))
+                            .or_default() += 1;
+                    }
+                }
+
+                for round in &bracket.rounds {
+                    for set in &round.sets {
+                        for two in [false, true] {
+                            let expected = usize::from(round.ordinal != 1);
+                            assert_eq!(
+                                fed.get(&(round.ordinal, set.position, two)).copied().unwrap_or(0),
+                                expected,
+                                "{} set {} slot {} of {entrants} (reset: {reset})",
+                                round.name,
+                                set.position,
+                                if two { 2 } else { 1 }
+                            );
+                        }
+                    }
+                }
+
+                // Nobody is out after one loss: every winners set sends its loser on.
+                for round in bracket.rounds.iter().filter(|round| round.side == Some(Side::Winners)) {
+                    assert!(
+                        round.sets.iter().all(|set| set.loser_advances_to.is_some()),
+                        "{}",
+                        round.name
+                    );
+                }
+                // And only the last grand final has nowhere to send its winner.
+                let rootless: Vec<&str> = bracket
+                    .rounds
+                    .iter()
+                    .filter(|round| round.sets.iter().any(|set| set.winner_advances_to.is_none()))
+                    .map(|round| round.name.as_str())
+                    .collect();
+                assert_eq!(rootless, vec![if reset { GRAND_FINAL_RESET } else { GRAND_FINAL }]);
+            }
+        }
+    }
+
+    #[test]
+    fn a_dropped_player_never_meets_someone_they_already_beat() {
+        // Whoever drops into a losers round's slot two came out of one winners set,
+        // so everyone they have played is among that set's possible occupants. If
+        // nobody who can reach slot one could also have stood in that set, the
+        // pairing cannot be a rematch, however the results fall. The losers final
+        // is excluded: it is a single set, so there is nowhere else to put anyone.
+        for entrants in [8, 16, 32, 64] {
+            let bracket = double_for(entrants, false);
+            let pools = reachable(&bracket);
+            let drops: BTreeSet<(usize, usize)> = bracket
+                .rounds
+                .iter()
+                .filter(|round| round.side == Some(Side::Winners))
+                .flat_map(|round| &round.sets)
+                .filter_map(|set| set.loser_advances_to.map(|target| (target.round, target.position)))
+                .collect();
+            for round in &bracket.rounds {
+                if round.name == LOSERS_FINAL {
+                    continue;
+                }
+                for set in round
+                    .sets
+                    .iter()
+                    .filter(|set| drops.contains(&(round.ordinal, set.position)))
+                {
+                    let [one, two] = &pools[&(round.ordinal, set.position)];
+                    assert!(
+                        one.is_disjoint(two),
+                        "{} set {} of {entrants} could be a rematch: {:?} against {:?}",
+                        round.name,
+                        set.position,
+                        one,
+                        two
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn the_losers_champion_takes_slot_two_of_the_grand_final() {
+        let bracket = double_for(8, true);
+        let by_name = |name: &str| bracket.rounds.iter().find(|round| round.name == name).unwrap();
+        let grand_final = by_name(GRAND_FINAL).ordinal;
+
+        assert_eq!(
+            by_name("Winners Final").sets[0].winner_advances_to,
+            Some(Advancement {
+                round: grand_final,
+                position: 1,
+                slot: Slot::One
+            })
+        );
+        assert_eq!(
+            by_name(LOSERS_FINAL).sets[0].winner_advances_to,
+            Some(Advancement {
+                round: grand_final,
+                position: 1,
+                slot: Slot::Two
+            })
+        );
+
+        // The reset keeps both players in the slots they had.
+        let first = &by_name(GRAND_FINAL).sets[0];
+        let reset = by_name(GRAND_FINAL_RESET).ordinal;
+        assert_eq!(
+            first.winner_advances_to.map(|a| (a.round, a.slot)),
+            Some((reset, Slot::One))
+        );
+        assert_eq!(
+            first.loser_advances_to.map(|a| (a.round, a.slot)),
+            Some((reset, Slot::Two))
+        );
+    }
+
+    #[test]
+    fn two_entrants_have_no_losers_bracket_and_the_loser_goes_straight_to_the_grand_final() {
+        let bracket = double_for(2, false);
+        let names: Vec<&str> = bracket.rounds.iter().map(|round| round.name.as_str()).collect();
+        assert_eq!(names, vec!["Winners Final", GRAND_FINAL]);
+        assert_eq!(
+            bracket.rounds[0].sets[0].loser_advances_to,
+            Some(Advancement {
+                round: 2,
+                position: 1,
+                slot: Slot::Two
+            })
+        );
+    }
+
+    #[test]
+    fn a_losers_round_runs_the_length_of_the_winners_round_it_plays_alongside() {
+        // Bo3 early, Bo5 semifinal, Bo7 final.
+        let bracket = build_double(8, &[3, 5, 7], true).expect("a valid field");
+        let lengths: Vec<(&str, u8, usize)> = bracket
+            .rounds
+            .iter()
+            .map(|round| (round.name.as_str(), round.best_of, round.preset_from))
+            .collect();
+        assert_eq!(
+            lengths,
+            vec![
+                ("Winners Quarterfinal", 3, 1),
+                ("Winners Semifinal", 5, 2),
+                ("Winners Final", 7, 3),
+                ("Losers Round 1", 5, 2),
+                ("Losers Round 2", 5, 2),
+                ("Losers Round 3", 7, 3),
+                (LOSERS_FINAL, 7, 3),
+                (GRAND_FINAL, 7, 3),
+                (GRAND_FINAL_RESET, 7, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn double_elimination_names_are_translated_and_their_side_read_back() {
+        use super::{localize_round_name, round_name_bilingual};
+        use crate::locale::Locale;
+
+        assert_eq!(localize_round_name("Winners Final", Locale::ZhTw), "勝部決賽");
+        assert_eq!(localize_round_name("Winners Ro16", Locale::ZhTw), "勝部 Ro16");
+        assert_eq!(localize_round_name("Losers Round 3", Locale::ZhTw), "敗部第 3 輪");
+        assert_eq!(localize_round_name(LOSERS_FINAL, Locale::ZhTw), "敗部決賽");
+        assert_eq!(round_name_bilingual(GRAND_FINAL), "總決賽 / Grand Final");
+        assert_eq!(localize_round_name("Losers Round 3", Locale::En), "Losers Round 3");
+
+        for round in double_for(16, true).rounds {
+            assert_eq!(Side::of_round_name(&round.name), round.side, "{}", round.name);
+        }
+        for round in bracket_for(16).rounds {
+            assert_eq!(Side::of_round_name(&round.name), None, "{}", round.name);
+        }
+    }
 }
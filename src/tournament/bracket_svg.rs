@@ -23,14 +23,15 @@ const BASELINE_PX: f64 = 19.0;
 
 /// Matches the dark code block this replaces, so the drawing reads the same
 /// in either Discord theme rather than depending on which one the viewer has.
-const BACKGROUND: &str = "#2b2d31";
-const FOREGROUND: &str = "#dcddde";
-const STROKE: &str = "#949ba4";
+pub(crate) const BACKGROUND: &str = "#2b2d31";
+pub(crate) const FOREGROUND: &str = "#dcddde";
+pub(crate) const STROKE: &str = "#949ba4";
 const STROKE_WIDTH: f64 = 2.0;
 
 /// The font stack a `<text>` element asks for — the bundled faces loaded by
-/// `bracket_raster`, by name.
-const FONT_FAMILY: &str = "Noto Sans, Noto Sans CJK TC";
+/// `bracket_raster`, by name. Also `history`'s, which rasterizes through the
+/// same database.
+pub(crate) const FONT_FAMILY: &str = "Noto Sans, Noto Sans CJK TC";
 
 /// Every box-drawing character `render::grid` can emit. `place` is exhaustive
 /// over this set — anything else is text — so a glyph added to `grid` later
@@ -170,7 +171,7 @@ fn text_svg(row: usize, cell: usize, text: &str, width: usize) -> String {
 /// A player's display name is free text, not markup — `render::sanitize`
 /// only guards the code-fence path, so `<`, `&` and quotes reach here
 /// unescaped and have to be handled at this boundary instead.
-fn escape_xml(text: &str) -> String {
+pub(crate) fn escape_xml(text: &str) -> String {
     let mut out = String::with_capacity(text.len());
     for ch in text.chars() {
         match ch {
@@ -12,12 +12,12 @@
 
 use crate::Error;
 use crate::db::{to_channel_id, to_db_id, to_message_id};
-use crate::tournament::db::{self, Tournament, TournamentEntry, TournamentRound, TournamentSet};
+use crate::tournament::db::{self, Tournament, TournamentEntry, TournamentRound, TournamentSet, TournamentStage};
 use crate::tournament::panel_check;
 use crate::tournament::registration::RegistrationState;
 use crate::tournament::seeding;
 use crate::tournament::throttle::EditThrottle;
-use crate::tournament::{bracket, bracket_raster, bracket_svg, render};
+use crate::tournament::{bracket, bracket_raster, bracket_svg, groups, render, swiss};
 use serenity::all::{CacheHttp, CreateAttachment, CreateMessage, EditAttachments, EditMessage, MessageId};
 use sqlx::SqlitePool;
 use std::time::Instant;
@@ -159,7 +159,11 @@ fn pad_with_open_seats(order: &mut Vec<render::Entrant>, target: usize) {
 ///
 /// `None` below two entrants, where `bracket::build` correctly refuses. Padding
 /// happens first, so a target of its own is enough to draw from an empty field.
-pub(crate) fn preview_rounds(entries: &[TournamentEntry], open_seats_to: Option<usize>) -> Option<Vec<render::Round>> {
+pub(crate) fn preview_rounds(
+    entries: &[TournamentEntry],
+    open_seats_to: Option<usize>,
+    format: bracket::Format,
+) -> Option<Vec<render::Round>> {
     let mut order = draw_order(entries);
     if let Some(target) = open_seats_to {
         pad_with_open_seats(&mut order, target);
@@ -172,7 +176,7 @@ pub(crate) fn preview_rounds(entries: &[TournamentEntry], open_seats_to: Option<
     // that already exists grows the bracket to hold everyone instead of dropping
     // whoever no longer fits.
     let round_count = bracket::round_count(bracket::size(order.len()));
-    let built = bracket::build(order.len(), &vec![RENDER_ONLY_BEST_OF; round_count]).ok()?;
+    let built = bracket::build_for(format, order.len(), &vec![RENDER_ONLY_BEST_OF; round_count]).ok()?;
 
     Some(
         built
@@ -218,7 +222,15 @@ pub(crate) fn played_rounds(
     rounds
         .iter()
         .map(|round| {
-            let mut round_sets: Vec<&TournamentSet> = sets.iter().filter(|s| s.round_id == round.id).collect();
+            // A bye nobody was in — a losers-bracket set both of whose feeders
+            // were byes, or a grand final reset that was never needed — has
+            // nothing to show. Only ever outside the winners tree, whose layout
+            // needs every set.
+            let mut round_sets: Vec<&TournamentSet> = sets
+                .iter()
+                .filter(|s| s.round_id == round.id)
+                .filter(|s| !(s.status == "bye" && s.slot1_user_id.is_none() && s.slot2_user_id.is_none()))
+                .collect();
             round_sets.sort_by_key(|s| s.position);
 
             render::Round {
@@ -296,6 +308,76 @@ fn third_place_line(round: render::Round) -> String {
     format!("\n{}", render::render_round_list(&localized))
 }
 
+/// Pulls a double-elimination drawing's losers and grand final rounds out of
+/// its rounds, for the same reason `split_third_place` exists: `render::grid`
+/// draws one binary tree, and only the winners bracket is one. Empty for single
+/// elimination, whose rounds carry no side.
+fn split_sides(rounds: Vec<render::Round>) -> (Vec<render::Round>, Vec<render::Round>, Vec<render::Round>) {
+    let (mut tree, mut losers, mut grand_final) = (Vec::new(), Vec::new(), Vec::new());
+    for round in rounds {
+        match bracket::Side::of_round_name(&round.name) {
+            Some(bracket::Side::Losers) => losers.push(round),
+            Some(bracket::Side::GrandFinal) => grand_final.push(round),
+            Some(bracket::Side::Winners) | None => tree.push(round),
+        }
+    }
+    (tree, losers, grand_final)
+}
+
+/// The losers bracket and grand final as round lists, localized the way
+/// `third_place_line` is, then the champion once the grand final has one. As
+/// many messages as it takes: a 32-player losers bracket alone runs past one.
+fn side_chunks(losers: Vec<render::Round>, grand_final: Vec<render::Round>) -> Vec<String> {
+    let champion = render::grand_final_champion_line(&grand_final);
+    let mut lines: Vec<String> = losers
+        .into_iter()
+        .chain(grand_final)
+        // A round with nothing left to show: the reset nobody needed.
+        .filter(|round| !round.matches.is_empty())
+        .map(|round| {
+            render::render_round_list(&render::Round {
+                name: bracket::round_name_bilingual(&round.name),
+                matches: round.matches,
+            })
+        })
+        .collect();
+    if !champion.is_empty() {
+        lines.push(champion);
+    }
+    crate::reply::chunk_lines("**敗部 / Losers bracket**".to_string(), lines)
+}
+
+/// A stage played round by round — Swiss, or a group stage — as messages: the
+/// heading and the tables, then every round's pairings newest first, so the
+/// round being played sits right under them.
+fn table_chunks(heading: String, tables: Vec<String>, rounds: Vec<render::Round>) -> Vec<String> {
+    let lists = rounds.into_iter().rev().map(|round| {
+        render::render_round_list(&render::Round {
+            name: bracket::round_name_bilingual(&round.name),
+            matches: round.matches,
+        })
+    });
+    crate::reply::chunk_lines(heading, tables.into_iter().chain(lists))
+}
+
+/// A group stage before `start`: who would be in which group, dealt from the
+/// current draw order the way `start` will deal the seeds.
+fn group_preview_lines(order: &[render::Entrant], group_count: usize) -> Vec<String> {
+    let order: Vec<&render::Entrant> = order.iter().collect();
+    groups::assign(&order, group_count)
+        .iter()
+        .enumerate()
+        .map(|(index, members)| {
+            let group = groups::letter(index);
+            let members: Vec<String> = members
+                .iter()
+                .map(|entrant| format!("`{}` {}", entrant.seed, crate::ranked::escape(&entrant.name)))
+                .collect();
+            format!("**{group} 組 / Group {group}**\n{}", members.join("\n"))
+        })
+        .collect()
+}
+
 /// Which drawing this is, which is what the heading above it has to say.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Drawing {
@@ -344,23 +426,25 @@ fn decorate(name: &str, chunks: Vec<String>, drawing: Drawing) -> Vec<String> {
 }
 
 /// The drawn bracket once `start` has written one, `None` while it has not —
-/// which is what separates the real thing from a preview.
-async fn persisted_rounds(
+/// which is what separates the real thing from a preview. Stage by stage, so a
+/// group stage and the playoff it feeds can be drawn apart.
+async fn persisted_stages(
     pool: &SqlitePool,
     tournament_id: i64,
     entries: &[TournamentEntry],
-) -> Result<Option<Vec<render::Round>>, Error> {
+) -> Result<Option<Vec<(TournamentStage, Vec<render::Round>)>>, Error> {
     let sets = db::list_sets_for_tournament(pool, tournament_id).await?;
     if sets.is_empty() {
         return Ok(None);
     }
 
-    let mut rounds = Vec::new();
+    let mut stages = Vec::new();
     for stage in db::list_stages_for_tournament(pool, tournament_id).await? {
-        rounds.extend(db::list_rounds_for_stage(pool, stage.id).await?);
+        let rounds = db::list_rounds_for_stage(pool, stage.id).await?;
+        let drawn = played_rounds(&rounds, &sets, entries);
+        stages.push((stage, drawn));
     }
-
-    Ok(Some(played_rounds(&rounds, &sets, entries)))
+    Ok(Some(stages))
 }
 
 /// Whether this message's edit should be skipped this pass. `None` (the
@@ -376,6 +460,177 @@ async fn bracket_image(rounds: &[render::Round]) -> Result<Vec<u8>, Error> {
     Ok(tokio::task::spawn_blocking(move || bracket_raster::rasterize(&svg)).await??)
 }
 
+/// What a drawing posts as: its messages in order, and the image the first one
+/// carries with the content that goes alongside it, when it fits one.
+type Drawn = (Vec<String>, Option<Vec<u8>>, Option<String>);
+
+/// Every format but a group stage: one stage, drawn as a tree or, for Swiss, a
+/// table. `None` for a preview of fewer than two entrants.
+async fn bracket_chunks(
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    entries: &[TournamentEntry],
+    open_seats_to: Option<usize>,
+    format: bracket::Format,
+) -> Result<Option<Drawn>, Error> {
+    // Whether this is still a preview is the same question as which drawing we
+    // have, so it is answered once rather than read off the status separately.
+    let (rounds, drawing) = match persisted_stages(pool, tournament.id, entries).await? {
+        Some(stages) => (
+            stages.into_iter().flat_map(|(_, rounds)| rounds).collect(),
+            Drawing::Real,
+        ),
+        None => match preview_rounds(entries, open_seats_to, format) {
+            Some(rounds) if open_seats_to.is_some() => (rounds, Drawing::PreviewWithOpenSeats),
+            Some(rounds) => (rounds, Drawing::Preview),
+            None => return Ok(None),
+        },
+    };
+    // A Swiss stage is a table and a list of rounds, never a tree — and so never
+    // an image either.
+    if format == bracket::Format::Swiss {
+        let standings = match swiss::table(pool, tournament.id, entries).await? {
+            Some((table, finished)) => swiss::render_standings(&table, entries, finished),
+            // A preview: nobody has played, so there is nothing to rank yet.
+            None => Vec::new(),
+        };
+        let heading = heading(&tournament.name, drawing).trim_end().to_string();
+        return Ok(Some((table_chunks(heading, standings, rounds), None, None)));
+    }
+    Ok(Some(tree_chunks(tournament, rounds, drawing).await))
+}
+
+/// A group stage, and the playoff it feeds once that is drawn. The playoff comes
+/// first, as the tree any other bracket is — it is what the event is now about,
+/// and the image can only ride on the first message — and the groups below it:
+/// every group's table, then the group rounds newest first. Before `start`, the
+/// groups the current field would be dealt into.
+async fn group_stage_chunks(
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    entries: &[TournamentEntry],
+    open_seats_to: Option<usize>,
+) -> Result<Option<Drawn>, Error> {
+    let Some(stages) = persisted_stages(pool, tournament.id, entries).await? else {
+        let mut order = draw_order(entries);
+        if let Some(target) = open_seats_to {
+            pad_with_open_seats(&mut order, target);
+        }
+        if order.len() < MIN_ENTRANTS {
+            return Ok(None);
+        }
+        let drawing = if open_seats_to.is_some() {
+            Drawing::PreviewWithOpenSeats
+        } else {
+            Drawing::Preview
+        };
+        // Whatever `start` would deal, short of refusing: a count the field cannot
+        // fill yet is drawn as the most groups it can.
+        let group_count = tournament
+            .group_count
+            .and_then(|count| usize::try_from(count).ok())
+            .unwrap_or_else(|| groups::default_group_count(order.len()))
+            .clamp(1, (order.len() / 2).max(1));
+        let heading = heading(&tournament.name, drawing).trim_end().to_string();
+        return Ok(Some((
+            crate::reply::chunk_lines(heading, group_preview_lines(&order, group_count)),
+            None,
+            None,
+        )));
+    };
+
+    let (group_rounds, playoff): (Vec<_>, Vec<_>) = stages
+        .into_iter()
+        .partition(|(stage, _)| stage.format == bracket::Format::Groups.as_db());
+    let group_rounds: Vec<render::Round> = group_rounds.into_iter().flat_map(|(_, rounds)| rounds).collect();
+    let playoff: Vec<render::Round> = playoff.into_iter().flat_map(|(_, rounds)| rounds).collect();
+
+    let tables = match groups::tables(pool, tournament.id, entries).await? {
+        Some((tables, advance, finished)) => tables
+            .iter()
+            .enumerate()
+            .flat_map(|(index, table)| groups::render_standings(index, table, entries, advance, finished))
+            .collect(),
+        None => Vec::new(),
+    };
+    const GROUP_STAGE: &str = "**小組賽 / Group stage**";
+    if playoff.is_empty() {
+        let heading = format!("{}\n{GROUP_STAGE}", heading(&tournament.name, Drawing::Real).trim_end());
+        return Ok(Some((table_chunks(heading, tables, group_rounds), None, None)));
+    }
+    let (mut chunks, image, image_content) = tree_chunks(tournament, playoff, Drawing::Real).await;
+    chunks.extend(table_chunks(GROUP_STAGE.to_string(), tables, group_rounds));
+    Ok(Some((chunks, image, image_content)))
+}
+
+/// An elimination bracket: the tree, its 3rd place match and a
+/// double-elimination bracket's other two sides, and the image when the tree
+/// fits one message.
+async fn tree_chunks(tournament: &Tournament, rounds: Vec<render::Round>, drawing: Drawing) -> Drawn {
+    // Peeled out once, here — everything below this line, tree or image, must
+    // never hand the 3rd place round to `render::grid`.
+    let (rounds, third_place) = split_third_place(rounds);
+    let third_place_line = third_place.map(third_place_line);
+    // And the same for a double-elimination bracket's other two sides: only the
+    // winners tree is drawn as one, and its last round crowns nobody.
+    let (rounds, losers, grand_final) = split_sides(rounds);
+    let double = !grand_final.is_empty();
+
+    let tree = if double {
+        render::render_uncrowned(&rounds, render::DEFAULT_WIDTH)
+    } else {
+        render::render(&rounds, render::DEFAULT_WIDTH)
+    };
+    let tree_chunks = tree.len();
+    let mut chunks = decorate(&tournament.name, tree, drawing);
+    // On the last chunk, alongside where the champion line lands for the image
+    // path below — not `chunks[0]`, which may not even be the chunk holding the
+    // final.
+    if let (Some(line), Some(last)) = (&third_place_line, chunks.last_mut()) {
+        last.push_str(line);
+    }
+    if double {
+        chunks.extend(side_chunks(losers, grand_final));
+    }
+
+    // Only a tree that already fits one Discord message gets the image
+    // treatment — a larger one keeps the text renderer, which already knows
+    // how to split across several messages. The lists after it stay text.
+    // A render failure falls back to the text chunk already sitting in
+    // `chunks[0]` rather than failing the whole reconcile over it.
+    let image = if tree_chunks == 1 {
+        match bracket_image(&rounds).await {
+            Ok(png) => Some(png),
+            Err(err) => {
+                tracing::error!(
+                    "failed to render the bracket image for tournament {}, falling back to text: {err:?}",
+                    tournament.id
+                );
+                None
+            },
+        }
+    } else {
+        None
+    };
+    // The heading, the champion line and the 3rd place line, not the fenced
+    // grid — an image has nowhere to put markdown of its own, so all three stay
+    // in `content` instead. A double-elimination champion is crowned under the
+    // grand final instead, in the lists.
+    let image_content = image.is_some().then(|| {
+        format!(
+            "{}{}{}",
+            heading(&tournament.name, drawing),
+            if double {
+                String::new()
+            } else {
+                render::champion_line(&rounds)
+            },
+            third_place_line.as_deref().unwrap_or_default()
+        )
+    });
+    (chunks, image, image_content)
+}
+
 /// Draws the current bracket into `#{slug}-bracket`, reusing the messages that
 /// are already there. Coalesces edits under load, the same split `panel::refresh`
 /// uses — [`reconcile_now`] is the unconditional counterpart, for a repair path
@@ -429,63 +684,16 @@ async fn reconcile_inner(
     let open_seats_to = (RegistrationState::of(tournament) == RegistrationState::InviteOnly)
         .then(|| bracket::size(usize::try_from(tournament.entrant_cap).unwrap_or(MIN_ENTRANTS)));
 
-    // Whether this is still a preview is the same question as which drawing we
-    // have, so it is answered once rather than read off the status separately.
-    let (rounds, drawing) = match persisted_rounds(pool, tournament.id, &entries).await? {
-        Some(rounds) => (rounds, Drawing::Real),
-        None => match preview_rounds(&entries, open_seats_to) {
-            Some(rounds) if open_seats_to.is_some() => (rounds, Drawing::PreviewWithOpenSeats),
-            Some(rounds) => (rounds, Drawing::Preview),
-            None => return Ok(ReconcileOutcome::TooFewEntrants),
-        },
-    };
-    // Peeled out once, here — everything below this line, tree or image, must
-    // never hand the 3rd place round to `render::grid`.
-    let (rounds, third_place) = split_third_place(rounds);
-    let third_place_line = third_place.map(third_place_line);
+    let format = bracket::Format::from_db(&tournament.format, tournament.grand_final_reset);
 
-    let mut chunks = decorate(
-        &tournament.name,
-        render::render(&rounds, render::DEFAULT_WIDTH),
-        drawing,
-    );
-    // On the last chunk, alongside where the champion line lands for the image
-    // path below — not `chunks[0]`, which may not even be the chunk holding the
-    // final.
-    if let (Some(line), Some(last)) = (&third_place_line, chunks.last_mut()) {
-        last.push_str(line);
-    }
-
-    // Only a bracket that already fits one Discord message gets the image
-    // treatment — a larger one keeps the text renderer, which already knows
-    // how to split across several messages.
-    // A render failure falls back to the text chunk already sitting in
-    // `chunks[0]` rather than failing the whole reconcile over it.
-    let image = if chunks.len() == 1 {
-        match bracket_image(&rounds).await {
-            Ok(png) => Some(png),
-            Err(err) => {
-                tracing::error!(
-                    "failed to render the bracket image for tournament {}, falling back to text: {err:?}",
-                    tournament.id
-                );
-                None
-            },
-        }
+    let drawn = if format == bracket::Format::Groups {
+        group_stage_chunks(pool, tournament, &entries, open_seats_to).await?
     } else {
-        None
+        bracket_chunks(pool, tournament, &entries, open_seats_to, format).await?
+    };
+    let Some((chunks, image, image_content)) = drawn else {
+        return Ok(ReconcileOutcome::TooFewEntrants);
     };
-    // The heading, the champion line and the 3rd place line, not the fenced
-    // grid — an image has nowhere to put markdown of its own, so all three stay
-    // in `content` instead.
-    let image_content = image.is_some().then(|| {
-        format!(
-            "{}{}{}",
-            heading(&tournament.name, drawing),
-            render::champion_line(&rounds),
-            third_place_line.as_deref().unwrap_or_default()
-        )
-    });
 
     let (mut posted, mut edited, mut deleted) = (0, 0, 0);
     let existing = db::list_bracket_messages(pool, tournament.id).await?;
@@ -626,6 +834,8 @@ mod tests {
         }
     }
 
+    const SINGLE: bracket::Format = bracket::Format::SingleElimination;
+
     fn field(n: i64) -> Vec<TournamentEntry> {
         // Descending ELO so the suggested order matches the numbering.
         (1..=n).map(|i| entry(i, &format!("P{i}"), Some(2000 - i))).collect()
@@ -666,8 +876,8 @@ mod tests {
 
     #[test]
     fn there_is_nothing_to_draw_below_two_entrants() {
-        assert!(preview_rounds(&[], None).is_none());
-        assert!(preview_rounds(&field(1), None).is_none());
+        assert!(preview_rounds(&[], None, SINGLE).is_none());
+        assert!(preview_rounds(&field(1), None, SINGLE).is_none());
     }
 
     #[test]
@@ -675,7 +885,7 @@ mod tests {
         // Three of eight invited. Without padding this is a 4-bracket that will
         // reshape twice more; with it the organizer sees the eight seats they
         // are actually filling.
-        let (rounds, _) = split_third_place(preview_rounds(&field(3), Some(8)).unwrap());
+        let (rounds, _) = split_third_place(preview_rounds(&field(3), Some(8), SINGLE).unwrap());
         assert_eq!(rounds.len(), 3, "an 8-bracket is three rounds");
         assert_eq!(rounds[0].matches.len(), 4);
 
@@ -700,7 +910,7 @@ mod tests {
             with_seed(entry(2, "B", Some(1900)), 2),
             with_seed(entry(3, "C", Some(1800)), 5),
         ];
-        let rounds = preview_rounds(&entries, Some(8)).unwrap();
+        let rounds = preview_rounds(&entries, Some(8), SINGLE).unwrap();
         let mut seeds: Vec<u32> = drawn(&rounds).into_iter().map(|e| e.seed).collect();
         seeds.sort_unstable();
         assert_eq!(seeds, vec![1, 2, 3, 4, 5, 6, 7, 8]);
@@ -719,7 +929,7 @@ mod tests {
     fn an_invite_only_bracket_is_drawn_before_anyone_is_in_it() {
         // The whole target field, every seat open. This is what an organizer sees
         // the moment they mark an event invite-only, and it fills in from there.
-        let (rounds, _) = split_third_place(preview_rounds(&[], Some(8)).unwrap());
+        let (rounds, _) = split_third_place(preview_rounds(&[], Some(8), SINGLE).unwrap());
         assert_eq!(rounds.len(), 3);
         let names: Vec<&str> = drawn(&rounds).into_iter().map(|e| e.name.as_str()).collect();
         assert_eq!(names.len(), 8);
@@ -731,7 +941,7 @@ mod tests {
 
     #[test]
     fn a_full_field_needs_no_open_seats() {
-        let rounds = preview_rounds(&field(8), Some(8)).unwrap();
+        let rounds = preview_rounds(&field(8), Some(8), SINGLE).unwrap();
         let names: Vec<&str> = drawn(&rounds).into_iter().map(|e| e.name.as_str()).collect();
         assert!(!names.iter().any(|n| n.starts_with('<')), "{names:?}");
     }
@@ -740,14 +950,14 @@ mod tests {
     fn a_field_larger_than_its_cap_grows_the_bracket_rather_than_dropping_anyone() {
         // Reachable only by lowering the cap under a field that already exists.
         // Nobody may vanish from the drawing over it.
-        let (rounds, _) = split_third_place(preview_rounds(&field(5), Some(4)).unwrap());
+        let (rounds, _) = split_third_place(preview_rounds(&field(5), Some(4), SINGLE).unwrap());
         assert_eq!(rounds.len(), 3, "five entrants still need an 8-bracket");
         assert_eq!(drawn(&rounds).len(), 5);
     }
 
     #[test]
     fn two_entrants_are_a_single_final() {
-        let rounds = preview_rounds(&field(2), None).unwrap();
+        let rounds = preview_rounds(&field(2), None, SINGLE).unwrap();
         assert_eq!(rounds.len(), 1);
         assert_eq!(rounds[0].name, "Final");
         assert_eq!(rounds[0].matches.len(), 1);
@@ -756,7 +966,7 @@ mod tests {
     #[test]
     fn a_field_that_is_not_a_power_of_two_gets_byes_on_the_top_seeds() {
         // 5 entrants play an 8-bracket, so seeds 1, 2 and 3 are unopposed.
-        let (rounds, _) = split_third_place(preview_rounds(&field(5), None).unwrap());
+        let (rounds, _) = split_third_place(preview_rounds(&field(5), None, SINGLE).unwrap());
         assert_eq!(rounds.len(), 3);
 
         let unopposed: Vec<u32> = rounds[0]
@@ -776,7 +986,7 @@ mod tests {
             entry(2, "Middle", Some(1500)),
             entry(3, "Strong", Some(2000)),
         ];
-        let rounds = preview_rounds(&entries, None).unwrap();
+        let rounds = preview_rounds(&entries, None, SINGLE).unwrap();
         let top = rounds[0]
             .matches
             .iter()
@@ -809,7 +1019,7 @@ mod tests {
             with_seed(entry(2, "Middle", Some(1500)), 2),
             with_seed(entry(3, "Strong", Some(2000)), 3),
         ];
-        let rounds = preview_rounds(&entries, None).unwrap();
+        let rounds = preview_rounds(&entries, None, SINGLE).unwrap();
         let top = drawn(&rounds)
             .into_iter()
             .find(|e| e.seed == 1)
@@ -827,7 +1037,7 @@ mod tests {
             with_seed(entry(3, "C", Some(1800)), 4),
             with_seed(entry(4, "D", Some(1700)), 5),
         ];
-        let rounds = preview_rounds(&entries, None).unwrap();
+        let rounds = preview_rounds(&entries, None, SINGLE).unwrap();
         let mut seeds: Vec<u32> = drawn(&rounds).into_iter().map(|e| e.seed).collect();
         seeds.sort_unstable();
         assert_eq!(seeds, vec![1, 2, 4, 5]);
@@ -843,7 +1053,7 @@ mod tests {
             entry(2, "Middle", Some(1500)),
             entry(3, "Strong", Some(2000)),
         ];
-        let rounds = preview_rounds(&entries, None).unwrap();
+        let rounds = preview_rounds(&entries, None, SINGLE).unwrap();
         let placed: Vec<(u32, &str)> = drawn(&rounds).into_iter().map(|e| (e.seed, e.name.as_str())).collect();
 
         // Seed 1 stands; the unseeded pair follow it in rating order.
@@ -867,7 +1077,7 @@ mod tests {
             entry(2, "Deepshine", None),
             pinned(entry(3, "Lun", Some(1680)), 4),
         ];
-        let rounds = preview_rounds(&entries, Some(4)).unwrap();
+        let rounds = preview_rounds(&entries, Some(4), SINGLE).unwrap();
         let placed: Vec<(u32, &str)> = drawn(&rounds).into_iter().map(|e| (e.seed, e.name.as_str())).collect();
 
         assert!(placed.contains(&(4, "Lun")), "{placed:?}");
@@ -890,7 +1100,7 @@ mod tests {
             with_seed(entry(3, "C", Some(1800)), 5),
             entry(4, "Latecomer", Some(1700)),
         ];
-        let rounds = preview_rounds(&entries, None).unwrap();
+        let rounds = preview_rounds(&entries, None, SINGLE).unwrap();
         let mut seeds: Vec<u32> = drawn(&rounds).into_iter().map(|e| e.seed).collect();
         seeds.sort_unstable();
         assert_eq!(seeds, vec![1, 2, 5, 6]);
@@ -898,7 +1108,7 @@ mod tests {
 
     #[test]
     fn no_scores_before_anything_is_played() {
-        let rounds = preview_rounds(&field(4), None).unwrap();
+        let rounds = preview_rounds(&field(4), None, SINGLE).unwrap();
         assert!(rounds.iter().flat_map(|r| &r.matches).all(|m| m.score.is_none()));
     }
 
@@ -906,7 +1116,7 @@ mod tests {
     fn withdrawn_entrants_are_not_in_the_draw() {
         let mut entries = field(4);
         entries[3].status = "withdrawn".to_string();
-        let rounds = preview_rounds(&entries, None).unwrap();
+        let rounds = preview_rounds(&entries, None, SINGLE).unwrap();
         // Three entrants play a 4-bracket: two rounds, one bye.
         assert_eq!(rounds.len(), 2);
         assert_eq!(rounds[0].matches.len(), 2);
@@ -949,6 +1159,8 @@ mod tests {
             loser_advances_to_slot: None,
             scheduled_at: None,
             completed_at: None,
+            proposed_at: None,
+            proposed_by: None,
         }
     }
 
@@ -1035,7 +1247,7 @@ mod tests {
         // deliberately, so this test actually exercises the peel on both, rather
         // than comparing two trees that happen to have nothing to peel.
         let entries = seeded_field(4);
-        let (preview, preview_third) = split_third_place(preview_rounds(&entries, None).unwrap());
+        let (preview, preview_third) = split_third_place(preview_rounds(&entries, None, SINGLE).unwrap());
         let (played, played_third) = split_third_place(played_rounds(
             &[
                 round(10, 1, "Semifinal"),
@@ -1101,9 +1313,73 @@ mod tests {
     fn a_large_field_splits_into_several_messages() {
         // The split starts at 16, which is what makes the message count
         // vary with the field and `reconcile` necessary.
-        let (rounds, _) = split_third_place(preview_rounds(&field(16), None).unwrap());
+        let (rounds, _) = split_third_place(preview_rounds(&field(16), None, SINGLE).unwrap());
         let chunks = render::render(&rounds, render::DEFAULT_WIDTH);
         assert!(chunks.len() > 1, "16 entrants should not fit one message");
         assert!(chunks.iter().all(|c| c.len() <= 2000));
     }
+
+    #[test]
+    fn a_double_elimination_preview_draws_the_winners_tree_and_lists_the_rest() {
+        let format = bracket::Format::DoubleElimination {
+            grand_final_reset: true,
+        };
+        let rounds = preview_rounds(&field(8), None, format).unwrap();
+        let (tree, losers, grand_final) = split_sides(rounds);
+
+        // Only the winners bracket is a tree `render::grid` can lay out.
+        assert_eq!(tree.len(), 3);
+        assert_eq!(losers.len(), 4);
+        assert_eq!(grand_final.len(), 2);
+
+        let chunks = side_chunks(losers, grand_final);
+        let lists = chunks.join("\n");
+        for name in [
+            "敗部第 1 輪",
+            "Losers Round 1",
+            "敗部決賽",
+            "Losers Final",
+            "總決賽",
+            "Grand Final Reset",
+        ] {
+            assert!(lists.contains(name), "{name} missing from {lists}");
+        }
+        assert!(!lists.contains('🏆'), "nothing has been played");
+    }
+
+    #[test]
+    fn a_swiss_preview_lists_round_one_under_the_heading() {
+        let rounds = preview_rounds(&field(6), None, bracket::Format::Swiss).unwrap();
+        assert_eq!(rounds.len(), 1, "later rounds are paired from results");
+
+        let heading = heading("Relic Cup", Drawing::Preview).trim_end().to_string();
+        let chunks = table_chunks(heading, Vec::new(), rounds);
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].contains("Relic Cup") && chunks[0].contains("瑞士制第 1 輪 / Swiss Round 1"));
+        assert!(!chunks[0].contains("```"), "no tree and no table: {}", chunks[0]);
+    }
+
+    #[test]
+    fn a_group_preview_deals_the_field_in_a_snake() {
+        let order = draw_order(&field(6));
+        let lines = group_preview_lines(&order, 2);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("**A 組 / Group A**"), "{}", lines[0]);
+        let seeds = |line: &str| {
+            line.lines()
+                .skip(1)
+                .map(|member| member[1..2].to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(seeds(&lines[0]), vec!["1", "4", "5"]);
+        assert_eq!(seeds(&lines[1]), vec!["2", "3", "6"]);
+    }
+
+    #[test]
+    fn a_single_elimination_bracket_has_no_other_side() {
+        let rounds = preview_rounds(&field(8), None, SINGLE).unwrap();
+        let (tree, losers, grand_final) = split_sides(rounds);
+        assert_eq!(tree.len(), 4, "three rounds and the 3rd place match");
+        assert!(losers.is_empty() && grand_final.is_empty());
+    }
 }
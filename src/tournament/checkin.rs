@@ -68,19 +68,39 @@ pub(crate) fn checkin_counts(entries: &[TournamentEntry]) -> (i64, i64) {
 /// risks either. Empty input is `vec![]`, not a message with nobody to ping.
 const PING_CHUNK_SIZE: usize = 80;
 
-pub(crate) fn checkin_ping_messages(locale: Locale, user_ids: &[i64]) -> Vec<String> {
+fn ping_messages(user_ids: &[i64], render: impl Fn(&str) -> String) -> Vec<String> {
     user_ids
         .chunks(PING_CHUNK_SIZE)
-        .map(|chunk| {
-            let mentions = chunk.iter().map(|id| format!("<@{id}>")).collect::<Vec<_>>().join(" ");
-            locale.pick(
-                format!("簽到已開放，請盡快完成簽到：{mentions}"),
-                format!("Check-in is now open — please check in: {mentions}"),
-            )
-        })
+        .map(|chunk| render(&chunk.iter().map(|id| format!("<@{id}>")).collect::<Vec<_>>().join(" ")))
         .collect()
 }
 
+/// The nudge that goes with opening check-in. `None` is for an opening nobody
+/// in particular asked for — the autopilot's — which, like a panel, has no one
+/// reader's language to pick and says it in both.
+pub(crate) fn checkin_ping_messages(locale: Option<Locale>, user_ids: &[i64]) -> Vec<String> {
+    ping_messages(user_ids, |mentions| match locale {
+        Some(locale) => locale.pick(
+            format!("簽到已開放，請盡快完成簽到：{mentions}"),
+            format!("Check-in is now open — please check in: {mentions}"),
+        ),
+        None => format!("簽到已開放，請盡快完成簽到 · Check-in is now open — please check in: {mentions}"),
+    })
+}
+
+/// The autopilot's last call before it closes check-in, to whoever in
+/// `checkin_pool` has not checked in yet. Always both languages, for the same
+/// reason as an unrequested `checkin_ping_messages`.
+pub(crate) fn checkin_closing_messages(closes_at: DateTime<Utc>, user_ids: &[i64]) -> Vec<String> {
+    ping_messages(user_ids, |mentions| {
+        format!(
+            "⏰ 簽到將於 <t:{0}:R> 截止，尚未簽到的請盡快完成 · Check-in closes <t:{0}:R> — \
+             check in now or be marked a no-show: {mentions}",
+            closes_at.timestamp()
+        )
+    })
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum CheckinOutcome {
     CheckedIn { checked_in_count: i64, total_count: i64 },
@@ -297,9 +317,16 @@ pub(crate) async fn close(pool: &SqlitePool, tournament: &Tournament) -> Result<
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum ReopenRegistrationOutcome {
-    Reopened { restored_count: u64, cleared_count: u64 },
+    Reopened {
+        restored_count: u64,
+        cleared_count: u64,
+        /// The autopilot was on and has been turned off.
+        autopilot_off: bool,
+    },
     AlreadyInRegistration,
-    NotReopenable { current_status: String },
+    NotReopenable {
+        current_status: String,
+    },
 }
 
 impl ReopenRegistrationOutcome {
@@ -308,18 +335,30 @@ impl ReopenRegistrationOutcome {
             ReopenRegistrationOutcome::Reopened {
                 restored_count,
                 cleared_count,
-            } => locale.pick(
-                format!(
-                    "**{tournament_name}** 重新開放報名 — 簽到已重置\
-                     （清除 {cleared_count} 筆簽到，恢復 {restored_count} 位未到者）。\
-                     要重新簽到時請用 `/tournament open-checkin`。"
-                ),
-                format!(
-                    "Registration is open again for **{tournament_name}** — check-in was reset \
-                     ({cleared_count} check-ins cleared, {restored_count} no-shows restored). \
-                     Use `/tournament open-checkin` when you're ready to run check-in again."
-                ),
-            ),
+                autopilot_off,
+            } => {
+                let message = locale.pick(
+                    format!(
+                        "**{tournament_name}** 重新開放報名 — 簽到已重置\
+                         （清除 {cleared_count} 筆簽到，恢復 {restored_count} 位未到者）。\
+                         要重新簽到時請用 `/tournament open-checkin`。"
+                    ),
+                    format!(
+                        "Registration is open again for **{tournament_name}** — check-in was reset \
+                         ({cleared_count} check-ins cleared, {restored_count} no-shows restored). \
+                         Use `/tournament open-checkin` when you're ready to run check-in again."
+                    ),
+                );
+                if !autopilot_off {
+                    return message;
+                }
+                let note = locale.pick(
+                    "自動駕駛已關閉，否則它會馬上再開簽到；要恢復時請用 `/tournament setup autopilot:True`。",
+                    "The autopilot is now off, since it would otherwise open check-in again at once; turn it \
+                     back on with `/tournament setup autopilot:True`.",
+                );
+                format!("{message}\n{note}")
+            },
             ReopenRegistrationOutcome::AlreadyInRegistration => locale.pick(
                 format!("**{tournament_name}** 已經在報名階段 — 沒有需要重開的東西。"),
                 format!("**{tournament_name}** is already in registration — nothing to reopen."),
@@ -347,7 +386,9 @@ impl ReopenRegistrationOutcome {
 /// rather than an edit of a message the caller has since deleted. Deleting those
 /// messages is the caller's job — this module stays Discord-free.
 ///
-/// A seed order the organizers set by hand is the one thing that survives.
+/// A seed order the organizers set by hand is the one thing that survives. The
+/// autopilot does not: it is turned off, and the organizers turn it back on
+/// once the timeline is right again.
 ///
 /// Not transactional, for the same reason `close` isn't (see its doc comment):
 /// each statement is independently atomic and nothing else races these rows.
@@ -364,6 +405,10 @@ pub(crate) async fn reopen_registration(
         });
     }
 
+    // First, so a tick running alongside finds it off. Left on, the autopilot
+    // would see a tournament in registration past its check-in time and open
+    // check-in again on the next tick, undoing the reopen.
+    let autopilot_off = db::disengage_autopilot(pool, tournament.id).await?;
     let restored_count = db::revert_no_shows(pool, tournament.id).await?;
     let cleared_count = db::clear_checkins(pool, tournament.id).await?;
     // A suggested order is stale the moment the field can change again; one the
@@ -379,6 +424,7 @@ pub(crate) async fn reopen_registration(
     Ok(ReopenRegistrationOutcome::Reopened {
         restored_count,
         cleared_count,
+        autopilot_off,
     })
 }
 
@@ -496,13 +542,13 @@ mod tests {
 
     #[test]
     fn a_field_with_nobody_to_ping_gets_no_message() {
-        assert_eq!(checkin_ping_messages(Locale::En, &[]), Vec::<String>::new());
+        assert_eq!(checkin_ping_messages(Some(Locale::En), &[]), Vec::<String>::new());
     }
 
     #[test]
     fn a_field_under_the_chunk_size_pings_everyone_in_one_message() {
         let ids: Vec<i64> = (1..=10).collect();
-        let messages = checkin_ping_messages(Locale::En, &ids);
+        let messages = checkin_ping_messages(Some(Locale::En), &ids);
         assert_eq!(messages.len(), 1, "{messages:?}");
         for id in &ids {
             assert!(messages[0].contains(&format!("<@{id}>")), "{}", messages[0]);
@@ -512,7 +558,7 @@ mod tests {
     #[test]
     fn a_field_over_the_chunk_size_splits_into_multiple_messages_with_no_id_lost_or_duplicated() {
         let ids: Vec<i64> = (1..=180).collect();
-        let messages = checkin_ping_messages(Locale::ZhTw, &ids);
+        let messages = checkin_ping_messages(Some(Locale::ZhTw), &ids);
         assert_eq!(messages.len(), 3, "180 ids over an 80-sized chunk is 3 messages");
         for id in &ids {
             let mentioning = messages.iter().filter(|m| m.contains(&format!("<@{id}>"))).count();
@@ -523,6 +569,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn the_closing_ping_names_the_deadline_and_everyone_still_out() {
+        let closes_at = Utc::now();
+        let messages = checkin_closing_messages(closes_at, &[7, 8]);
+        assert_eq!(messages.len(), 1, "{messages:?}");
+        assert!(
+            messages[0].contains(&format!("<t:{}:R>", closes_at.timestamp())),
+            "{}",
+            messages[0]
+        );
+        assert!(
+            messages[0].contains("<@7>") && messages[0].contains("<@8>"),
+            "{}",
+            messages[0]
+        );
+        assert!(checkin_closing_messages(closes_at, &[]).is_empty());
+    }
+
     #[test]
     fn registration_is_reopenable_only_from_checkin_and_seeding() {
         assert!(registration_is_reopenable("checkin"));
@@ -537,7 +601,8 @@ mod tests {
         assert!(
             ReopenRegistrationOutcome::Reopened {
                 restored_count: 1,
-                cleared_count: 2
+                cleared_count: 2,
+                autopilot_off: false,
             }
             .changed_state()
         );
@@ -12,7 +12,7 @@ use crate::locale::Locale;
 use crate::tournament::bracket::Slot;
 use crate::tournament::db::{self, Tournament, TournamentGame, TournamentSet};
 use crate::tournament::throttle::EditThrottle;
-use crate::tournament::{bracket_view, set_thread};
+use crate::tournament::{bracket_view, groups, set_thread, swiss};
 use serenity::all::CacheHttp;
 use sqlx::SqlitePool;
 
@@ -102,6 +102,9 @@ pub(crate) enum CompleteOutcome {
         /// Whether the set just settled was the 3rd place match — its own reply,
         /// since it neither opens a next set nor ends the tournament by itself.
         is_third_place: bool,
+        /// False in double elimination until a player's second loss: they drop
+        /// to their next set rather than out of the event.
+        loser_eliminated: bool,
         settlement: Settlement,
     },
     /// Nobody has a majority yet, so nothing was written.
@@ -127,6 +130,7 @@ impl CompleteOutcome {
                 opened_next,
                 tournament_complete,
                 is_third_place,
+                loser_eliminated,
                 settlement,
             } => {
                 let (score, next) = (
@@ -143,14 +147,24 @@ impl CompleteOutcome {
                         ),
                     },
                 );
-                match settlement {
-                    Settlement::Played => locale.pick(
+                match (settlement, loser_eliminated) {
+                    (Settlement::Played, true) => locale.pick(
                         format!("**{winner_name}** 以 {score} 擊敗 **{loser_name}**。{next}"),
                         format!("**{winner_name}** beat **{loser_name}** {score}. {next}"),
                     ),
+                    (Settlement::Played, false) => locale.pick(
+                        format!(
+                            "**{winner_name}** 以 {score} 擊敗 **{loser_name}**。\
+                             **{loser_name}** 尚未淘汰，將繼續出賽。{next}"
+                        ),
+                        format!(
+                            "**{winner_name}** beat **{loser_name}** {score}. **{loser_name}** isn't out yet and \
+                             plays on. {next}"
+                        ),
+                    ),
                     // Says what it cost, not just what it did: an award eliminates
                     // someone, and no command puts them back.
-                    Settlement::Walkover => locale.pick(
+                    (Settlement::Walkover, true) => locale.pick(
                         format!(
                             "已將這場對戰判給 **{winner_name}**：**{loser_name}** 未完賽，已遭淘汰，\
                              比分記為 {score}。⚠️ **此結果無法復原。**{next}"
@@ -160,6 +174,19 @@ impl CompleteOutcome {
                              eliminated. Recorded {score}. ⚠️ **This can't be undone.** {next}"
                         ),
                     ),
+                    // Still a loss that can't be taken back, even when it isn't the
+                    // last one they had.
+                    (Settlement::Walkover, false) => locale.pick(
+                        format!(
+                            "已將這場對戰判給 **{winner_name}**：**{loser_name}** 未完賽，記為一敗，\
+                             尚未淘汰，將繼續出賽。比分記為 {score}。⚠️ **此結果無法復原。**{next}"
+                        ),
+                        format!(
+                            "Awarded to **{winner_name}** — **{loser_name}** didn't play it out and takes the \
+                             loss, though they aren't out yet and play on. Recorded {score}. ⚠️ **This can't be \
+                             undone.** {next}"
+                        ),
+                    ),
                 }
             },
             CompleteOutcome::StillPlaying { tally, needed } => {
@@ -313,9 +340,61 @@ async fn settle(
         return Ok(CompleteOutcome::AlreadyComplete);
     }
 
+    // A Swiss round has no advancement links: the next round is paired from the
+    // standings once this was its last open set. Not part of the transaction above
+    // — `advance` is idempotent, so a failure here is repaired by the next
+    // settlement or `/tournament refresh` rather than by rolling the result back.
+    let swiss = swiss::advance(pool, tournament.id).await.unwrap_or_else(|err| {
+        tracing::error!(
+            "failed to advance the Swiss stage after set {} settled: {err:?}",
+            set.id
+        );
+        None
+    });
+    // A group stage opens its rounds the same way, and draws the playoff after
+    // its last one — which is when anyone who did not go through is out.
+    let group = groups::advance(pool, tournament.id).await.unwrap_or_else(|err| {
+        tracing::error!(
+            "failed to advance the group stage after set {} settled: {err:?}",
+            set.id
+        );
+        None
+    });
+    let knocked_out_of_groups = group == Some(groups::Progress::PlayoffDrawn)
+        && db::get_entry(pool, tournament.id, loser_user_id)
+            .await?
+            .is_some_and(|entry| entry.status == "eliminated");
+
+    // Nobody is knocked out of a Swiss stage, but nobody plays on past its last
+    // round either.
+    let loser_eliminated = advanced.loser_eliminated
+        || knocked_out_of_groups
+        || matches!(
+            swiss,
+            Some(swiss::Progress::Finished | swiss::Progress::Waiting { last_round: true })
+        );
+    let opened_next = advanced.target_became_ready
+        || matches!(swiss, Some(swiss::Progress::Paired { .. }))
+        || matches!(
+            group,
+            Some(groups::Progress::Paired { .. } | groups::Progress::PlayoffDrawn)
+        );
+    let tournament_complete = advanced.tournament_completed || swiss == Some(swiss::Progress::Finished);
+
     let winner = set_thread::player(pool, tournament.id, winner_user_id).await?;
     let loser = set_thread::player(pool, tournament.id, loser_user_id).await?;
-    set_thread::close(http, pool, tournament, set, &winner, &loser, &tally, settlement).await;
+    set_thread::close(
+        http,
+        pool,
+        tournament,
+        set,
+        &winner,
+        &loser,
+        &tally,
+        settlement,
+        loser_eliminated,
+    )
+    .await;
 
     // The bracket is redrawn from the rows just written: `played_match` derives a
     // winner from `winner_user_id` and never reads `status`, so this needs no
@@ -329,9 +408,10 @@ async fn settle(
         winner_name: winner.name,
         loser_name: loser.name,
         tally,
-        opened_next: advanced.target_became_ready,
-        tournament_complete: advanced.tournament_completed,
+        opened_next,
+        tournament_complete,
         is_third_place: advanced.is_third_place,
+        loser_eliminated,
         settlement,
     })
 }
@@ -461,6 +541,7 @@ mod tests {
             opened_next: true,
             tournament_complete: false,
             is_third_place: false,
+            loser_eliminated: true,
             settlement: Settlement::Played,
         };
         let zh = outcome.message(Locale::ZhTw);
@@ -488,6 +569,7 @@ mod tests {
             opened_next: false,
             tournament_complete: true,
             is_third_place: false,
+            loser_eliminated: true,
             settlement: Settlement::Played,
         };
         assert!(outcome.message(Locale::En).contains("tournament is over"));
@@ -508,6 +590,7 @@ mod tests {
             opened_next: false,
             tournament_complete: false,
             is_third_place: true,
+            loser_eliminated: true,
             settlement: Settlement::Played,
         };
         assert!(outcome.message(Locale::En).contains("Third place decided"));
@@ -547,6 +630,7 @@ mod tests {
             opened_next: true,
             tournament_complete: false,
             is_third_place: false,
+            loser_eliminated: true,
             settlement,
         };
         let awarded = settled(Settlement::Walkover);
@@ -576,6 +660,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn a_loser_who_drops_to_the_losers_bracket_is_not_called_eliminated() {
+        let settled = |settlement| CompleteOutcome::Completed {
+            winner_name: "MarineLorD".to_string(),
+            loser_name: "Beasty".to_string(),
+            tally: Tally {
+                slot1_wins: 2,
+                slot2_wins: 0,
+            },
+            opened_next: false,
+            tournament_complete: false,
+            is_third_place: false,
+            loser_eliminated: false,
+            settlement,
+        };
+        for settlement in [Settlement::Played, Settlement::Walkover] {
+            let en = settled(settlement).message(Locale::En);
+            assert!(en.contains("out yet") && !en.contains("eliminated"), "{en}");
+            let zh = settled(settlement).message(Locale::ZhTw);
+            assert!(zh.contains("尚未淘汰") && !zh.contains("已遭淘汰"), "{zh}");
+        }
+    }
+
     #[test]
     fn still_playing_names_what_it_would_take_to_win() {
         let outcome = CompleteOutcome::StillPlaying {
@@ -7,7 +7,7 @@
 
 use crate::tournament::bracket::Slot;
 use chrono::{DateTime, Utc};
-use sqlx::{AssertSqlSafe, FromRow, SqlitePool};
+use sqlx::{AssertSqlSafe, FromRow, SqliteConnection, SqlitePool};
 use tracing::error;
 
 fn log_db_error(err: &sqlx::Error) {
@@ -44,6 +44,27 @@ pub(crate) struct Tournament {
     /// `open | invite_only`. Which door into the field is open, as opposed to
     /// `status`, which says whether any door is.
     pub registration_mode: String,
+    /// `single_elim | double_elim | swiss | group`; see `bracket::Format::from_db`.
+    pub format: String,
+    /// Double elimination only, whether as `format` or as `playoff_format`.
+    pub grand_final_reset: bool,
+    /// Swiss only: the organizer's round count, `None` for the field-size default.
+    pub swiss_rounds: Option<i64>,
+    /// Group stage only: how many groups, `None` for the field-size default.
+    pub group_count: Option<i64>,
+    /// Group stage only: places each group sends to the playoff, `None` for
+    /// `groups::DEFAULT_ADVANCE`.
+    pub group_advance: Option<i64>,
+    /// Group stage only: `single_elim | double_elim`, the bracket it feeds.
+    pub playoff_format: String,
+    /// Minutes before a confirmed set time at which its thread is reminded,
+    /// comma separated; see `schedule::parse_leads`.
+    pub schedule_reminders: String,
+    /// Whether `autopilot` runs check-in and the start on the scheduled timeline.
+    pub autopilot: bool,
+    /// When the current check-in round's closing ping went out; see
+    /// `mark_checkin_reminded`.
+    pub checkin_reminded_at: Option<DateTime<Utc>>,
     pub created_by: i64,
     pub created_at: DateTime<Utc>,
     pub started_at: Option<DateTime<Utc>>,
@@ -83,7 +104,14 @@ pub(crate) async fn get_tournament(pool: &SqlitePool, id: i64) -> Result<Option<
         select id, slug, name, status, draft_base_url, announce_channel_id, category_id,
                register_channel_id, register_message_id, bracket_channel_id, matches_channel_id,
                draft_channel_id, checkin_message_id, seed_message_id, checkin_closes_at,
-               entrant_cap, scheduled_start_at, seed_source, registration_mode, created_by, created_at,
+               entrant_cap, scheduled_start_at, seed_source, registration_mode, format, grand_final_reset,
+               swiss_rounds,
+               group_count,
+               group_advance,
+               playoff_format,
+               schedule_reminders,
+               autopilot, checkin_reminded_at,
+               created_by, created_at,
                started_at, completed_at
         from tournaments
         where id = ?1
@@ -101,7 +129,14 @@ pub(crate) async fn get_tournament_by_slug(pool: &SqlitePool, slug: &str) -> Res
         select id, slug, name, status, draft_base_url, announce_channel_id, category_id,
                register_channel_id, register_message_id, bracket_channel_id, matches_channel_id,
                draft_channel_id, checkin_message_id, seed_message_id, checkin_closes_at,
-               entrant_cap, scheduled_start_at, seed_source, registration_mode, created_by, created_at,
+               entrant_cap, scheduled_start_at, seed_source, registration_mode, format, grand_final_reset,
+               swiss_rounds,
+               group_count,
+               group_advance,
+               playoff_format,
+               schedule_reminders,
+               autopilot, checkin_reminded_at,
+               created_by, created_at,
                started_at, completed_at
         from tournaments
         where slug = ?1
@@ -233,14 +268,15 @@ pub(crate) async fn set_seed_message_id(
     Ok(())
 }
 
-/// When check-in closes on its own — informational
-/// only today; nothing polls this to auto-close.
+/// When check-in closes — shown on the panel, and acted on only by the autopilot;
+/// without it, closing is still `/tournament close-checkin`.
 pub(crate) async fn set_checkin_closes_at(
     pool: &SqlitePool,
     id: i64,
     closes_at: Option<DateTime<Utc>>,
 ) -> Result<(), sqlx::Error> {
-    sqlx::query(r"update tournaments set checkin_closes_at = ?1 where id = ?2")
+    // A new close time is owed its own closing ping.
+    sqlx::query(r"update tournaments set checkin_closes_at = ?1, checkin_reminded_at = null where id = ?2")
         .bind(closes_at)
         .bind(id)
         .execute(pool)
@@ -307,6 +343,118 @@ pub(crate) async fn set_registration_mode(
     Ok(())
 }
 
+/// Only ever written before `start`, which copies it into the stage it creates.
+pub(crate) async fn set_format(
+    pool: &SqlitePool,
+    id: i64,
+    format: &str,
+    playoff_format: &str,
+    grand_final_reset: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(r"update tournaments set format = ?1, playoff_format = ?2, grand_final_reset = ?3 where id = ?4")
+        .bind(format)
+        .bind(playoff_format)
+        .bind(grand_final_reset)
+        .bind(id)
+        .execute(pool)
+        .await
+        .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+/// `None` lets `start` choose from the field size. Like `set_format`, only read
+/// at start, which writes the count into the stage's `config`.
+pub(crate) async fn set_swiss_rounds(pool: &SqlitePool, id: i64, swiss_rounds: Option<i64>) -> Result<(), sqlx::Error> {
+    sqlx::query(r"update tournaments set swiss_rounds = ?1 where id = ?2")
+        .bind(swiss_rounds)
+        .bind(id)
+        .execute(pool)
+        .await
+        .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+/// Either may be `None` to leave it as it is — unlike `set_swiss_rounds`, where
+/// `None` is a value. Only read at start, like the rest of the format.
+pub(crate) async fn set_group_stage(
+    pool: &SqlitePool,
+    id: i64,
+    group_count: Option<i64>,
+    group_advance: Option<i64>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r"
+        update tournaments
+        set
+            group_count = coalesce(?1, group_count),
+            group_advance = coalesce(?2, group_advance)
+        where id = ?3
+        ",
+    )
+    .bind(group_count)
+    .bind(group_advance)
+    .bind(id)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+/// Stored already normalized by `schedule::parse_leads`, so it reads back as
+/// the organizer will see it in the setup summary.
+pub(crate) async fn set_schedule_reminders(pool: &SqlitePool, id: i64, leads: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(r"update tournaments set schedule_reminders = ?1 where id = ?2")
+        .bind(leads)
+        .bind(id)
+        .execute(pool)
+        .await
+        .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+pub(crate) async fn set_autopilot(pool: &SqlitePool, id: i64, autopilot: bool) -> Result<(), sqlx::Error> {
+    sqlx::query(r"update tournaments set autopilot = ?1 where id = ?2")
+        .bind(autopilot)
+        .bind(id)
+        .execute(pool)
+        .await
+        .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+/// Turns the autopilot off after a step it could not take. Conditional, so of
+/// two ticks that both hit the refusal only one wins and tells the organizers.
+pub(crate) async fn disengage_autopilot(pool: &SqlitePool, id: i64) -> Result<bool, sqlx::Error> {
+    let disengaged = sqlx::query(r"update tournaments set autopilot = false where id = ?1 and autopilot")
+        .bind(id)
+        .execute(pool)
+        .await
+        .inspect_err(log_db_error)?
+        .rows_affected();
+    Ok(disengaged > 0)
+}
+
+/// Claims the current check-in round's closing ping. The first statement is
+/// the lock: `false` means it was already claimed, and nothing is posted.
+pub(crate) async fn mark_checkin_reminded(pool: &SqlitePool, id: i64, now: DateTime<Utc>) -> Result<bool, sqlx::Error> {
+    let claimed = sqlx::query(
+        r"
+        update tournaments
+        set checkin_reminded_at = ?1
+        where id = ?2
+          and status = 'checkin'
+          and checkin_reminded_at is null
+        ",
+    )
+    .bind(now)
+    .bind(id)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?
+    .rows_affected();
+    Ok(claimed > 0)
+}
+
 /// Entrants occupying a slot. `withdrawn` and `no_show` rows persist but are
 /// not in the field, so withdrawing genuinely frees a place against the cap.
 pub(crate) async fn count_active_entries(pool: &SqlitePool, tournament_id: i64) -> Result<i64, sqlx::Error> {
@@ -405,7 +553,14 @@ pub(crate) async fn get_tournament_by_any_channel_id(
         select id, slug, name, status, draft_base_url, announce_channel_id, category_id,
                register_channel_id, register_message_id, bracket_channel_id, matches_channel_id,
                draft_channel_id, checkin_message_id, seed_message_id, checkin_closes_at,
-               entrant_cap, scheduled_start_at, seed_source, registration_mode, created_by, created_at,
+               entrant_cap, scheduled_start_at, seed_source, registration_mode, format, grand_final_reset,
+               swiss_rounds,
+               group_count,
+               group_advance,
+               playoff_format,
+               schedule_reminders,
+               autopilot, checkin_reminded_at,
+               created_by, created_at,
                started_at, completed_at
         from tournaments
         where announce_channel_id = ?1
@@ -468,7 +623,14 @@ pub(crate) async fn list_live_tournaments(pool: &SqlitePool) -> Result<Vec<Tourn
         select id, slug, name, status, draft_base_url, announce_channel_id, category_id,
                register_channel_id, register_message_id, bracket_channel_id, matches_channel_id,
                draft_channel_id, checkin_message_id, seed_message_id, checkin_closes_at,
-               entrant_cap, scheduled_start_at, seed_source, registration_mode, created_by, created_at,
+               entrant_cap, scheduled_start_at, seed_source, registration_mode, format, grand_final_reset,
+               swiss_rounds,
+               group_count,
+               group_advance,
+               playoff_format,
+               schedule_reminders,
+               autopilot, checkin_reminded_at,
+               created_by, created_at,
                started_at, completed_at
         from tournaments
         where status in ({LIVE_STATUSES})
@@ -1362,8 +1524,12 @@ pub(crate) struct TournamentSet {
     pub winner_advances_to_slot: Option<i64>,
     pub loser_advances_to_set_id: Option<i64>,
     pub loser_advances_to_slot: Option<i64>,
+    /// A time both players agreed to, through `/set schedule` and its confirm button.
     pub scheduled_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
+    /// A time one player proposed and the other has not yet confirmed.
+    pub proposed_at: Option<DateTime<Utc>>,
+    pub proposed_by: Option<i64>,
 }
 
 const TOURNAMENT_SET_COLUMNS: &str = r"
@@ -1371,7 +1537,7 @@ const TOURNAMENT_SET_COLUMNS: &str = r"
     slot2_wins, winner_user_id, status, draft_external_id, draft_synced_at,
     draft_announce_message_id, redraft_count, thread_id, panel_message_id,
     winner_advances_to_set_id, winner_advances_to_slot, loser_advances_to_set_id,
-    loser_advances_to_slot, scheduled_at, completed_at
+    loser_advances_to_slot, scheduled_at, completed_at, proposed_at, proposed_by
 ";
 
 pub(crate) async fn insert_set(
@@ -1418,58 +1584,86 @@ pub(crate) async fn insert_set(
 /// `per_round` holds the resolved preset for each round in `bracket.rounds` order, so
 /// each round records the preset its drafts are created from — without it
 /// `set_thread::create_room` has no preset and no set ever gets a room. **Must be
-/// exactly `bracket.rounds.len()` long** — when a 3rd place round exists, the
-/// caller (`start::start`) is responsible for giving it one more entry (the
-/// semifinal's own preset) before calling this, since the 3rd place round itself
-/// resolves no preset of its own.
+/// exactly `bracket.rounds.len()` long** — a round that borrows another's preset
+/// (the 3rd place match, every losers-bracket round, the grand final) still needs
+/// its own entry, which the caller (`start::start`) resolves through
+/// `Round::preset_from`.
+///
+/// `config` is the stage's format-specific JSON — a Swiss stage's round count —
+/// and `None` for the elimination formats, which have nothing to configure.
 pub(crate) async fn insert_bracket(
     pool: &SqlitePool,
     tournament_id: i64,
+    format: crate::tournament::bracket::Format,
+    config: Option<&str>,
     bracket: &crate::tournament::bracket::Bracket,
     seed_to_user: &std::collections::HashMap<u32, i64>,
     per_round: &[&RoundPreset],
 ) -> Result<(), sqlx::Error> {
-    use crate::tournament::bracket::Slot;
-
-    // A short slice would write nulls and silently disable draft creation.
-    debug_assert_eq!(per_round.len(), bracket.rounds.len());
-
     let mut tx = pool.begin().await.inspect_err(log_db_error)?;
 
+    let stage_name = match format {
+        crate::tournament::bracket::Format::Swiss => "Swiss Stage",
+        crate::tournament::bracket::Format::Groups => "Group Stage",
+        _ => "Main Bracket",
+    };
     let stage = sqlx::query(
         r"
-        insert into tournament_stages (tournament_id, ordinal, name, format)
-        values (?1, 1, 'Main Bracket', 'single_elim')
+        insert into tournament_stages (tournament_id, ordinal, name, format, config, status)
+        values (?1, 1, ?2, ?3, ?4, 'running')
         ",
     )
     .bind(tournament_id)
+    .bind(stage_name)
+    .bind(format.as_db())
+    .bind(config)
     .execute(&mut *tx)
     .await
     .inspect_err(log_db_error)?
     .last_insert_rowid();
 
+    write_rounds(&mut tx, tournament_id, stage, bracket, seed_to_user, per_round).await?;
+
+    tx.commit().await.inspect_err(log_db_error)?;
+    Ok(())
+}
+
+/// `insert_bracket`'s rounds and sets, under a stage that already exists — the
+/// part a playoff drawn from a finished group stage shares with it.
+async fn write_rounds(
+    conn: &mut SqliteConnection,
+    tournament_id: i64,
+    stage: i64,
+    bracket: &crate::tournament::bracket::Bracket,
+    seed_to_user: &std::collections::HashMap<u32, i64>,
+    per_round: &[&RoundPreset],
+) -> Result<(), sqlx::Error> {
+    use crate::tournament::bracket::Slot;
+
+    // A short slice would write nulls and silently disable draft creation.
+    debug_assert_eq!(per_round.len(), bracket.rounds.len());
+
     // (round ordinal, position) -> set id, for the linking pass below.
     let mut set_ids: std::collections::HashMap<(usize, usize), i64> = std::collections::HashMap::new();
 
-    // Zipped rather than indexed by `round.ordinal - 1`: a 3rd place round's
-    // ordinal is `round_count + 1`, one past `per_round`'s own rounds, and the
-    // caller is the one responsible for extending `per_round` to match (with the
-    // semifinal's own preset) before calling this. Zipping means a caller that
-    // forgets loses the round entirely to the `debug_assert_eq!` above rather than
+    // Zipped rather than indexed by `round.ordinal - 1`: `per_round` is the
+    // caller's, resolved per round, and zipping means a caller that comes up short
+    // loses the round entirely to the `debug_assert_eq!` above rather than
     // silently indexing past the real presets into a NULL draft_preset_id.
     for (round, preset) in bracket.rounds.iter().zip(per_round.iter()) {
         let round_id = sqlx::query(
             r"
-            insert into tournament_rounds (stage_id, ordinal, name, best_of, draft_preset_id)
-            values (?1, ?2, ?3, ?4, ?5)
+            insert into tournament_rounds (stage_id, ordinal, name, best_of, bracket, draft_preset_id)
+            values (?1, ?2, ?3, ?4, ?5, ?6)
             ",
         )
         .bind(stage)
         .bind(i64::try_from(round.ordinal).unwrap())
         .bind(&round.name)
         .bind(i64::from(round.best_of))
+        .bind(round.side.map(|side| side.as_db()))
         .bind(preset.draft_preset_id.as_str())
-        .execute(&mut *tx)
+        .execute(&mut *conn)
         .await
         .inspect_err(log_db_error)?
         .last_insert_rowid();
@@ -1488,7 +1682,7 @@ pub(crate) async fn insert_bracket(
             .bind(i64::try_from(set.position).unwrap())
             .bind(slot1)
             .bind(slot2)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await
             .inspect_err(log_db_error)?
             .last_insert_rowid();
@@ -1498,8 +1692,9 @@ pub(crate) async fn insert_bracket(
 
     // Winner and loser links are written independently — never behind a shared
     // `continue` — so a set with one but not the other (every set has a winner
-    // link except the final and the 3rd place match; only the two semifinal sets
-    // have a loser link) never has the other silently skipped alongside it.
+    // link except the final and the 3rd place match; in single elimination only
+    // the two semifinal sets have a loser link) never has the other silently
+    // skipped alongside it.
     for round in &bracket.rounds {
         for set in &round.sets {
             let set_id = set_ids[&(round.ordinal, set.position)];
@@ -1523,7 +1718,7 @@ pub(crate) async fn insert_bracket(
                 .bind(target)
                 .bind(slot)
                 .bind(set_id)
-                .execute(&mut *tx)
+                .execute(&mut *conn)
                 .await
                 .inspect_err(log_db_error)?;
             }
@@ -1547,17 +1742,93 @@ pub(crate) async fn insert_bracket(
                 .bind(target)
                 .bind(slot)
                 .bind(set_id)
-                .execute(&mut *tx)
+                .execute(&mut *conn)
                 .await
                 .inspect_err(log_db_error)?;
             }
         }
     }
 
-    tx.commit().await.inspect_err(log_db_error)?;
     Ok(())
 }
 
+/// A group stage's playoff, as the tournament's second stage.
+pub(crate) struct NewPlayoff<'a> {
+    pub tournament_id: i64,
+    pub group_stage_id: i64,
+    pub format: crate::tournament::bracket::Format,
+    /// Seeded from the group placements: seed `n` is the `n`th entry.
+    pub bracket: &'a crate::tournament::bracket::Bracket,
+    pub seed_to_user: &'a std::collections::HashMap<u32, i64>,
+    pub per_round: &'a [&'a RoundPreset],
+    /// Everyone the group stage leaves behind.
+    pub eliminated: &'a [i64],
+}
+
+/// Draws the playoff and closes the group stage that fed it, in one transaction:
+/// the bracket as `insert_bracket` writes one, the group stage `completed`, those
+/// who did not advance eliminated, and the playoff's first round opened. False,
+/// writing nothing, when the playoff already exists.
+///
+/// **The first statement is the lock**, as in `insert_paired_round`: the stage
+/// is only inserted if its ordinal is free, so two settlements finishing the
+/// group stage at once draw it once.
+pub(crate) async fn insert_playoff(pool: &SqlitePool, playoff: NewPlayoff<'_>) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+
+    let inserted = sqlx::query(
+        r"
+        insert into tournament_stages (tournament_id, ordinal, name, format, status)
+        select ?1, 2, 'Playoffs', ?2, 'running'
+        where not exists (select 1 from tournament_stages where tournament_id = ?1 and ordinal = 2)
+        ",
+    )
+    .bind(playoff.tournament_id)
+    .bind(playoff.format.as_db())
+    .execute(&mut *tx)
+    .await
+    .inspect_err(log_db_error)?;
+    if inserted.rows_affected() == 0 {
+        tx.rollback().await.inspect_err(log_db_error)?;
+        return Ok(false);
+    }
+
+    write_rounds(
+        &mut tx,
+        playoff.tournament_id,
+        inserted.last_insert_rowid(),
+        playoff.bracket,
+        playoff.seed_to_user,
+        playoff.per_round,
+    )
+    .await?;
+
+    sqlx::query(r"update tournament_stages set status = 'completed' where id = ?1")
+        .bind(playoff.group_stage_id)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    for user_id in playoff.eliminated {
+        sqlx::query(
+            r"
+            update tournament_entries
+            set status = 'eliminated'
+            where tournament_id = ?1
+              and user_id = ?2
+            ",
+        )
+        .bind(playoff.tournament_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    }
+    resolve_byes(&mut tx, playoff.tournament_id).await?;
+
+    tx.commit().await.inspect_err(log_db_error)?;
+    Ok(true)
+}
+
 /// When the event actually began, as opposed to when it was scheduled to.
 pub(crate) async fn set_tournament_started_at(
     pool: &SqlitePool,
@@ -1848,21 +2119,24 @@ pub(crate) struct Advanced {
     /// False when the set was already decided — nothing was written at all.
     pub completed: bool,
     /// Whether the *winner's* target became ready — never the loser's target
-    /// (the 3rd place match), so this keeps meaning "the next set in the main
-    /// bracket is now open" and completing a semifinal never claims to have
-    /// opened a set it only half-fed.
+    /// (the 3rd place match, or a losers-bracket set), so this keeps meaning "the
+    /// next set on the winner's path is now open" and completing a semifinal never
+    /// claims to have opened a set it only half-fed.
     pub target_became_ready: bool,
     pub tournament_completed: bool,
     /// True when the set just settled was the 3rd place match — the other set
-    /// with no winner target, distinguished from the final by whether some other
-    /// set names it as a *loser* target.
+    /// with no winner target, distinguished from the final by being fed only by
+    /// losers.
     pub is_third_place: bool,
+    /// Whether the loser is out. Always, in single elimination; in double
+    /// elimination only once there is nowhere left for them to drop to.
+    pub loser_eliminated: bool,
 }
 
 /// Completing a set, **in one transaction**: the set is decided, the loser is
-/// eliminated, the winner is written into the next set (and the loser into the
-/// 3rd place match, when this was a semifinal), and either target opens once it
-/// has both players.
+/// eliminated or dropped to their next set, the winner is written into the next
+/// set (and the loser into the 3rd place match, when this was a semifinal), and
+/// either target opens once it has both players.
 ///
 /// Assembled inline for the same reason `insert_bracket` is — `record_set_result`,
 /// `update_entry_status`, `set_slot` and `update_set_status` each take a
@@ -1908,30 +2182,31 @@ pub(crate) async fn complete_set_and_advance(pool: &SqlitePool, result: SetResul
             target_became_ready: false,
             tournament_completed: false,
             is_third_place: false,
+            loser_eliminated: false,
         });
     }
 
-    sqlx::query(
+    let mut advancement: Option<(i64, i64)> = sqlx::query_as(
         r"
-        update tournament_entries
-        set status = 'eliminated'
-        where tournament_id = ?1
-          and user_id = ?2
+        select winner_advances_to_set_id, winner_advances_to_slot
+        from tournament_sets
+        where id = ?1
+          and winner_advances_to_set_id is not null
+          and winner_advances_to_slot is not null
         ",
     )
-    .bind(result.tournament_id)
-    .bind(result.loser_user_id)
-    .execute(&mut *tx)
+    .bind(result.set_id)
+    .fetch_optional(&mut *tx)
     .await
     .inspect_err(log_db_error)?;
 
-    let advancement: Option<(i64, i64)> = sqlx::query_as(
+    let mut loser_advancement: Option<(i64, i64)> = sqlx::query_as(
         r"
-        select winner_advances_to_set_id, winner_advances_to_slot
+        select loser_advances_to_set_id, loser_advances_to_slot
         from tournament_sets
         where id = ?1
-          and winner_advances_to_set_id is not null
-          and winner_advances_to_slot is not null
+          and loser_advances_to_set_id is not null
+          and loser_advances_to_slot is not null
         ",
     )
     .bind(result.set_id)
@@ -1939,8 +2214,69 @@ pub(crate) async fn complete_set_and_advance(pool: &SqlitePool, result: SetResul
     .await
     .inspect_err(log_db_error)?;
 
+    // `bracket` is null outside double elimination, which is what keeps every
+    // single-elimination loser eliminated — including a semifinal loser on their
+    // way to the 3rd place match, who has no title left to play for.
+    let (bracket, slot1_user_id, stage_format): (Option<String>, Option<i64>, String) = sqlx::query_as(
+        r"
+        select r.bracket, s.slot1_user_id, st.format
+        from tournament_sets s
+        join tournament_rounds r on r.id = s.round_id
+        join tournament_stages st on st.id = r.stage_id
+        where s.id = ?1
+        ",
+    )
+    .bind(result.set_id)
+    .fetch_one(&mut *tx)
+    .await
+    .inspect_err(log_db_error)?;
+
+    // The winners-bracket champion holds slot one of the grand final. Winning it
+    // gives the losers-bracket champion their second loss, so the reset is never
+    // played: it is closed as a bye with nobody in it, and this set ends the event.
+    if bracket.as_deref() == Some("grand_final")
+        && slot1_user_id == Some(result.winner_user_id)
+        && let Some((reset, _)) = advancement.take()
+    {
+        sqlx::query(
+            r"
+            update tournament_sets
+            set status = 'bye', completed_at = ?1
+            where id = ?2
+            ",
+        )
+        .bind(Utc::now())
+        .bind(reset)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+        loser_advancement = None;
+    }
+
+    // A Swiss or group set links to nothing, yet nobody in it is out: everyone
+    // plays every round, and the stage's own `advance` decides what comes after
+    // it rather than a final.
+    let round_by_round = matches!(stage_format.as_str(), "swiss" | "group");
+    let loser_eliminated = !round_by_round && (bracket.is_none() || loser_advancement.is_none());
+    if loser_eliminated {
+        sqlx::query(
+            r"
+            update tournament_entries
+            set status = 'eliminated'
+            where tournament_id = ?1
+              and user_id = ?2
+            ",
+        )
+        .bind(result.tournament_id)
+        .bind(result.loser_user_id)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    }
+
     let (mut target_became_ready, mut tournament_completed, mut is_third_place) = (false, false, false);
     match advancement {
+        None if round_by_round => {},
         Some((target, slot)) => {
             // The column name is chosen here, never bound — two static queries
             // rather than one interpolated one, as `set_slot` does.
@@ -1975,21 +2311,25 @@ pub(crate) async fn complete_set_and_advance(pool: &SqlitePool, result: SetResul
             .rows_affected()
                 > 0;
         },
-        // No winner target means either the final or the 3rd place match — the
-        // only two rootless sets. Distinguished structurally: a set that some
-        // *other* set names as a loser target is the 3rd place match; if nothing
-        // does, it's the final, and the event ends with it. Order-independent —
-        // whichever of the two is decided first, only the final flips the
-        // tournament's status.
+        // No winner target means the winner is done: the final, the 3rd place
+        // match, or a grand final. Distinguished structurally: a set that only
+        // ever receives losers is the 3rd place match; anything a winner advances
+        // into is a final, and the event ends with it. Order-independent —
+        // whichever of the 3rd place match and the final is decided first, only
+        // the final flips the tournament's status.
         None => {
-            let is_loser_target: bool =
-                sqlx::query_scalar(r"select exists(select 1 from tournament_sets where loser_advances_to_set_id = ?1)")
-                    .bind(result.set_id)
-                    .fetch_one(&mut *tx)
-                    .await
-                    .inspect_err(log_db_error)?;
-
-            if is_loser_target {
+            let only_fed_by_losers: bool = sqlx::query_scalar(
+                r"
+                select exists(select 1 from tournament_sets where loser_advances_to_set_id = ?1)
+                   and not exists(select 1 from tournament_sets where winner_advances_to_set_id = ?1)
+                ",
+            )
+            .bind(result.set_id)
+            .fetch_one(&mut *tx)
+            .await
+            .inspect_err(log_db_error)?;
+
+            if only_fed_by_losers {
                 is_third_place = true;
             } else {
                 sqlx::query(
@@ -2012,22 +2352,8 @@ pub(crate) async fn complete_set_and_advance(pool: &SqlitePool, result: SetResul
     }
 
     // The loser's own advancement, symmetric to the winner's above but never
-    // touching `target_became_ready` — its target is the 3rd place match, not the
-    // next set in the main bracket.
-    let loser_advancement: Option<(i64, i64)> = sqlx::query_as(
-        r"
-        select loser_advances_to_set_id, loser_advances_to_slot
-        from tournament_sets
-        where id = ?1
-          and loser_advances_to_set_id is not null
-          and loser_advances_to_slot is not null
-        ",
-    )
-    .bind(result.set_id)
-    .fetch_optional(&mut *tx)
-    .await
-    .inspect_err(log_db_error)?;
-
+    // touching `target_became_ready` — its target is the 3rd place match or a
+    // losers-bracket set, not the next set on the winner's path.
     if let Some((target, slot)) = loser_advancement {
         let sql = if slot == 1 {
             r"update tournament_sets set slot1_user_id = ?1 where id = ?2"
@@ -2057,22 +2383,232 @@ pub(crate) async fn complete_set_and_advance(pool: &SqlitePool, result: SetResul
         .inspect_err(log_db_error)?;
     }
 
+    // Whoever just landed may be facing a slot nothing will ever fill — a
+    // losers-bracket set fed by a round-one bye — and passes straight through.
+    resolve_byes(&mut tx, result.tournament_id).await?;
+
     tx.commit().await.inspect_err(log_db_error)?;
     Ok(Advanced {
         completed: true,
         target_became_ready,
         tournament_completed,
         is_third_place,
+        loser_eliminated,
     })
 }
 
-pub(crate) async fn set_scheduled_at(
+/// Ends a tournament whose last set is not a final — the last round of a Swiss
+/// stage. False when it had already ended, so of two settlements racing to the
+/// same last set, only one reports it.
+pub(crate) async fn complete_tournament(pool: &SqlitePool, id: i64) -> Result<bool, sqlx::Error> {
+    let ended = sqlx::query(
+        r"
+        update tournaments
+        set
+            status = 'completed',
+            completed_at = ?1
+        where id = ?2
+          and status = 'running'
+        ",
+    )
+    .bind(Utc::now())
+    .bind(id)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?
+    .rows_affected();
+    Ok(ended > 0)
+}
+
+/// A round written once the one before it is over — a Swiss round paired by
+/// `swiss::advance`, or a group round scheduled by `groups::advance`: one set per
+/// pairing, a bye where the second player is `None`.
+pub(crate) struct NewPairedRound {
+    pub tournament_id: i64,
+    pub stage_id: i64,
+    pub ordinal: usize,
+    pub name: String,
+    pub best_of: i64,
+    pub draft_preset_id: Option<String>,
+    pub pairings: Vec<(i64, Option<i64>)>,
+}
+
+/// Writes a round-by-round stage's next round and opens it, in one transaction.
+/// False, with nothing written, when the round already exists.
+///
+/// **The first statement is the lock**, as in `complete_set_and_advance`: the
+/// last two sets of a round settling at once both see the round over and both
+/// pair the next one, and only the round insert guarded by `not exists` decides
+/// which of them writes it. `unique (stage_id, ordinal, bracket)` cannot — such a
+/// round's `bracket` is null, and SQLite never counts two nulls as equal.
+pub(crate) async fn insert_paired_round(pool: &SqlitePool, round: NewPairedRound) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+
+    let inserted = sqlx::query(
+        r"
+        insert into tournament_rounds (stage_id, ordinal, name, best_of, draft_preset_id)
+        select ?1, ?2, ?3, ?4, ?5
+        where not exists (select 1 from tournament_rounds where stage_id = ?1 and ordinal = ?2)
+        ",
+    )
+    .bind(round.stage_id)
+    .bind(i64::try_from(round.ordinal).unwrap())
+    .bind(&round.name)
+    .bind(round.best_of)
+    .bind(round.draft_preset_id.as_deref())
+    .execute(&mut *tx)
+    .await
+    .inspect_err(log_db_error)?;
+    if inserted.rows_affected() == 0 {
+        tx.rollback().await.inspect_err(log_db_error)?;
+        return Ok(false);
+    }
+    let round_id = inserted.last_insert_rowid();
+
+    for (index, (one, two)) in round.pairings.iter().enumerate() {
+        sqlx::query(
+            r"
+            insert into tournament_sets (tournament_id, round_id, position, slot1_user_id, slot2_user_id, status)
+            values (?1, ?2, ?3, ?4, ?5, 'pending')
+            ",
+        )
+        .bind(round.tournament_id)
+        .bind(round_id)
+        .bind(i64::try_from(index + 1).unwrap())
+        .bind(one)
+        .bind(two)
+        .execute(&mut *tx)
+        .await
+        .inspect_err(log_db_error)?;
+    }
+
+    // The bye, if the field is odd, and every other set ready to play.
+    resolve_byes(&mut tx, round.tournament_id).await?;
+
+    tx.commit().await.inspect_err(log_db_error)?;
+    Ok(true)
+}
+
+/// `resolve_byes` on its own, for a bracket that has just been written.
+pub(crate) async fn open_bracket(pool: &SqlitePool, tournament_id: i64) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+    resolve_byes(&mut tx, tournament_id).await?;
+    tx.commit().await.inspect_err(log_db_error)?;
+    Ok(())
+}
+
+/// Settles every set that can no longer get a second player, and opens every set
+/// that has both.
+///
+/// A slot is **dead** once it is empty and nothing still undecided feeds it: a
+/// round-one slot no seed was drawn into, or one fed by a bye's loser, which does
+/// not exist. A set with one player and a dead slot is a bye for that player; a
+/// set with two dead slots is a bye for nobody, and kills the slot it would have
+/// fed. Single elimination only ever produces the first kind, in round one. A
+/// losers bracket fed by round-one byes produces both, and they cascade — hence
+/// running to a fixed point rather than a single pass.
+///
+/// Takes a connection rather than the pool, so it runs inside the transaction of
+/// whatever just changed the bracket.
+pub(crate) async fn resolve_byes(conn: &mut SqliteConnection, tournament_id: i64) -> Result<(), sqlx::Error> {
+    loop {
+        let sets: Vec<TournamentSet> = sqlx::query_as(AssertSqlSafe(format!(
+            r"
+            select {TOURNAMENT_SET_COLUMNS}
+            from tournament_sets
+            where tournament_id = ?1
+            "
+        )))
+        .bind(tournament_id)
+        .fetch_all(&mut *conn)
+        .await
+        .inspect_err(log_db_error)?;
+
+        let undecided = |set: &TournamentSet| !matches!(set.status.as_str(), "completed" | "walkover" | "bye");
+        let dead = |set: &TournamentSet, slot: i64, occupant: Option<i64>| {
+            occupant.is_none()
+                && !sets.iter().any(|feeder| {
+                    undecided(feeder)
+                        && ((feeder.winner_advances_to_set_id == Some(set.id)
+                            && feeder.winner_advances_to_slot == Some(slot))
+                            || (feeder.loser_advances_to_set_id == Some(set.id)
+                                && feeder.loser_advances_to_slot == Some(slot)))
+                })
+        };
+
+        let settled = sets.iter().filter(|set| undecided(set)).find_map(|set| {
+            match (dead(set, 1, set.slot1_user_id), dead(set, 2, set.slot2_user_id)) {
+                (true, true) => Some((set, None)),
+                (false, true) if set.slot1_user_id.is_some() => Some((set, set.slot1_user_id)),
+                (true, false) if set.slot2_user_id.is_some() => Some((set, set.slot2_user_id)),
+                _ => None,
+            }
+        });
+        let Some((set, winner)) = settled else {
+            break;
+        };
+
+        sqlx::query(
+            r"
+            update tournament_sets
+            set winner_user_id = ?1, status = 'bye', completed_at = ?2
+            where id = ?3
+            ",
+        )
+        .bind(winner)
+        .bind(Utc::now())
+        .bind(set.id)
+        .execute(&mut *conn)
+        .await
+        .inspect_err(log_db_error)?;
+
+        if let (Some(winner), Some(target), Some(slot)) =
+            (winner, set.winner_advances_to_set_id, set.winner_advances_to_slot)
+        {
+            let sql = if slot == 1 {
+                r"update tournament_sets set slot1_user_id = ?1 where id = ?2"
+            } else {
+                r"update tournament_sets set slot2_user_id = ?1 where id = ?2"
+            };
+            sqlx::query(sql)
+                .bind(winner)
+                .bind(target)
+                .execute(&mut *conn)
+                .await
+                .inspect_err(log_db_error)?;
+        }
+    }
+
+    // Readiness is both slots being filled, whichever round the set is in: a set
+    // fed by two byes is playable the moment the bracket opens.
+    sqlx::query(
+        r"
+        update tournament_sets
+        set status = 'ready'
+        where tournament_id = ?1
+          and status = 'pending'
+          and slot1_user_id is not null
+          and slot2_user_id is not null
+        ",
+    )
+    .bind(tournament_id)
+    .execute(&mut *conn)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+/// Records `proposed_by`'s proposal, replacing any earlier one. The agreed
+/// `scheduled_at`, if any, stands until this one is confirmed.
+pub(crate) async fn propose_schedule(
     pool: &SqlitePool,
     id: i64,
-    scheduled_at: DateTime<Utc>,
+    proposed_at: DateTime<Utc>,
+    proposed_by: i64,
 ) -> Result<(), sqlx::Error> {
-    sqlx::query(r"update tournament_sets set scheduled_at = ?1 where id = ?2")
-        .bind(scheduled_at)
+    sqlx::query(r"update tournament_sets set proposed_at = ?1, proposed_by = ?2 where id = ?3")
+        .bind(proposed_at)
+        .bind(proposed_by)
         .bind(id)
         .execute(pool)
         .await
@@ -2080,6 +2616,118 @@ pub(crate) async fn set_scheduled_at(
     Ok(())
 }
 
+/// Drops the pending proposal. False when there was none to drop.
+pub(crate) async fn clear_proposal(pool: &SqlitePool, id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        r"
+        update tournament_sets
+        set proposed_at = null, proposed_by = null
+        where id = ?1
+          and proposed_at is not null
+        ",
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Confirms the proposal for `proposed_at` and replaces the set's reminders
+/// with `reminders` (lead minutes, when due), in one transaction. False,
+/// writing nothing, when the pending proposal is no longer that one — it was
+/// withdrawn, declined or replaced after the button was drawn.
+///
+/// **The first statement is the lock**, as in `insert_playoff`: it only matches
+/// the proposal the caller read, so a confirm racing a fresh proposal cannot
+/// agree to a time nobody confirmed.
+pub(crate) async fn confirm_proposal(
+    pool: &SqlitePool,
+    id: i64,
+    proposed_at: DateTime<Utc>,
+    reminders: &[(i64, DateTime<Utc>)],
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+
+    let confirmed = sqlx::query(
+        r"
+        update tournament_sets
+        set scheduled_at = proposed_at, proposed_at = null, proposed_by = null
+        where id = ?1
+          and proposed_at = ?2
+        ",
+    )
+    .bind(id)
+    .bind(proposed_at)
+    .execute(&mut *tx)
+    .await
+    .inspect_err(log_db_error)?;
+    if confirmed.rows_affected() == 0 {
+        tx.rollback().await.inspect_err(log_db_error)?;
+        return Ok(false);
+    }
+
+    replace_set_reminders(&mut tx, id, reminders).await?;
+    tx.commit().await.inspect_err(log_db_error)?;
+    Ok(true)
+}
+
+/// An organizer's time for the set, agreed on nobody's behalf: written
+/// straight to `scheduled_at`, clearing any proposal, with its reminders
+/// replaced in the same transaction.
+pub(crate) async fn set_scheduled_at(
+    pool: &SqlitePool,
+    id: i64,
+    scheduled_at: DateTime<Utc>,
+    reminders: &[(i64, DateTime<Utc>)],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await.inspect_err(log_db_error)?;
+    sqlx::query(
+        r"
+        update tournament_sets
+        set scheduled_at = ?1, proposed_at = null, proposed_by = null
+        where id = ?2
+        ",
+    )
+    .bind(scheduled_at)
+    .bind(id)
+    .execute(&mut *tx)
+    .await
+    .inspect_err(log_db_error)?;
+    replace_set_reminders(&mut tx, id, reminders).await?;
+    tx.commit().await.inspect_err(log_db_error)?;
+    Ok(())
+}
+
+/// Whatever reminders the set's previous time was owed go with it, posted or
+/// not: they were about a time that no longer stands.
+async fn replace_set_reminders(
+    conn: &mut SqliteConnection,
+    set_id: i64,
+    reminders: &[(i64, DateTime<Utc>)],
+) -> Result<(), sqlx::Error> {
+    sqlx::query(r"delete from set_schedule_reminders where set_id = ?1")
+        .bind(set_id)
+        .execute(&mut *conn)
+        .await
+        .inspect_err(log_db_error)?;
+    for (lead_minutes, remind_at) in reminders {
+        sqlx::query(
+            r"
+            insert into set_schedule_reminders (set_id, lead_minutes, remind_at)
+            values (?1, ?2, ?3)
+            ",
+        )
+        .bind(set_id)
+        .bind(lead_minutes)
+        .bind(remind_at)
+        .execute(&mut *conn)
+        .await
+        .inspect_err(log_db_error)?;
+    }
+    Ok(())
+}
+
 // 7. tournament_games
 
 #[derive(FromRow, Clone)]
@@ -2468,3 +3116,84 @@ pub(crate) async fn list_bracket_messages(
     .await
     .inspect_err(log_db_error)
 }
+
+// 11. set_schedule_reminders
+
+/// A reminder that has come due, with what deciding whether it is still worth
+/// posting needs from its set.
+#[derive(FromRow, Debug, Clone)]
+pub(crate) struct DueSetReminder {
+    pub set_id: i64,
+    pub lead_minutes: i64,
+    pub remind_at: DateTime<Utc>,
+    pub tournament_id: i64,
+    pub status: String,
+    pub thread_id: Option<i64>,
+    pub slot1_user_id: Option<i64>,
+    pub slot2_user_id: Option<i64>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+}
+
+/// Every unposted reminder due by `now`, however long ago — the scheduler
+/// decides what a late one is still good for, not this query.
+pub(crate) async fn list_due_set_reminders(
+    pool: &SqlitePool,
+    now: DateTime<Utc>,
+) -> Result<Vec<DueSetReminder>, sqlx::Error> {
+    sqlx::query_as(
+        r"
+        select r.set_id, r.lead_minutes, r.remind_at, s.tournament_id, s.status, s.thread_id,
+               s.slot1_user_id, s.slot2_user_id, s.scheduled_at
+        from set_schedule_reminders r
+        join tournament_sets s on s.id = r.set_id
+        where r.posted_at is null
+          and r.remind_at <= ?1
+        order by r.set_id, r.lead_minutes
+        ",
+    )
+    .bind(now)
+    .fetch_all(pool)
+    .await
+    .inspect_err(log_db_error)
+}
+
+/// Stamps a reminder as dealt with — posted, or passed over as too late to be
+/// worth posting. Either way it is never looked at again.
+pub(crate) async fn mark_set_reminder_posted(
+    pool: &SqlitePool,
+    set_id: i64,
+    lead_minutes: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r"
+        update set_schedule_reminders
+        set posted_at = datetime('now')
+        where set_id = ?1
+          and lead_minutes = ?2
+        ",
+    )
+    .bind(set_id)
+    .bind(lead_minutes)
+    .execute(pool)
+    .await
+    .inspect_err(log_db_error)?;
+    Ok(())
+}
+
+pub(crate) async fn list_set_reminders(
+    pool: &SqlitePool,
+    set_id: i64,
+) -> Result<Vec<(i64, DateTime<Utc>)>, sqlx::Error> {
+    sqlx::query_as(
+        r"
+        select lead_minutes, remind_at
+        from set_schedule_reminders
+        where set_id = ?1
+        order by lead_minutes desc
+        ",
+    )
+    .bind(set_id)
+    .fetch_all(pool)
+    .await
+    .inspect_err(log_db_error)
+}
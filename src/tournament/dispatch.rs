@@ -13,8 +13,9 @@ use crate::tournament::action::{self, Action};
 use crate::tournament::checkin::CheckinOutcome;
 use crate::tournament::throttle::EditThrottle;
 use crate::tournament::{
-    access, audit, bracket_view, checkin, checkin_panel, db, import, panel, redraft, registration, seed_panel,
+    access, audit, bracket_view, checkin, checkin_panel, db, import, panel, redraft, registration, schedule, seed_panel,
 };
+use chrono::Utc;
 use serenity::all::{
     ComponentInteraction, CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse,
     Interaction,
@@ -81,6 +82,9 @@ impl EventHandler for Dispatcher {
             Action::Redraft => self.handle_redraft(&ctx, &component, entity_id).await,
             Action::CallAdmin => self.handle_call_admin(&ctx, &component, entity_id).await,
             Action::SetDone => self.handle_set_done(&ctx, &component, entity_id).await,
+            Action::ScheduleConfirm | Action::ScheduleDecline => {
+                self.handle_schedule_answer(&ctx, &component, action, entity_id).await;
+            },
         }
     }
 }
@@ -263,6 +267,66 @@ impl Dispatcher {
         }
     }
 
+    /// The confirm and decline buttons a proposed time adds to the set panel —
+    /// one handler, since they resolve and reply identically. Resolved by set,
+    /// like `handle_redraft`, and checked the same way: either player, or an
+    /// admin, with `schedule::confirm` refusing the proposer their own time.
+    async fn handle_schedule_answer(
+        &self,
+        ctx: &Context,
+        component: &ComponentInteraction,
+        action: Action,
+        set_id: i64,
+    ) {
+        let Ok(Some(set)) = db::get_set(&self.pool, set_id).await else {
+            error!("schedule button for unknown set {set_id}");
+            return;
+        };
+        let Ok(Some(tournament)) = db::get_tournament(&self.pool, set.tournament_id).await else {
+            error!("schedule button for set {set_id} with no tournament");
+            return;
+        };
+
+        let actor_user_id = to_db_id(component.user.id);
+        let is_admin = self.is_admin(ctx, component, &tournament, actor_user_id).await;
+        let answered = if action == Action::ScheduleConfirm {
+            schedule::confirm(
+                &ctx.http,
+                &self.pool,
+                &tournament,
+                &set,
+                actor_user_id,
+                is_admin,
+                Utc::now(),
+            )
+            .await
+        } else {
+            schedule::decline(&ctx.http, &self.pool, &tournament, &set, actor_user_id, is_admin).await
+        };
+        let outcome = match answered {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                error!("schedule button failed for set {set_id}: {err:?}");
+                return;
+            },
+        };
+        audit::log_action(
+            "schedule button",
+            tournament.id,
+            &tournament.slug,
+            &component.user,
+            &outcome,
+        );
+        let locale = Locale::from_discord_locale(&component.locale);
+
+        // Deferred (Action::ScheduleConfirm.requires_defer() == true), so the
+        // reply edits the initial deferred response rather than creating a new one.
+        let response = EditInteractionResponse::new().content(outcome.message(locale));
+        if let Err(err) = component.edit_response(&ctx.http, response).await {
+            error!("failed to edit the schedule response for set {set_id}: {err:?}");
+        }
+    }
+
     /// Whether the presser holds admin-tier access to `tournament` —
     /// `handle_redraft`'s equivalent of `access::access_for`, which needs a
     /// poise `Context` this `EventHandler` never has; built instead from the
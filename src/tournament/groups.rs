@@ -0,0 +1,688 @@
+//! Group stages: round-robin groups, their standings, and the playoff they feed.
+//!
+//! Written a round at a time, like a Swiss stage — every group's `n`th round
+//! shares one `Group Round n` row, so the stage opens round by round rather than
+//! handing each player every set of their group at once. Scheduling, standings
+//! and the playoff's seeding are pure; `advance` is the one async entry point,
+//! which `completion::settle` calls after every set. The last group round being
+//! decided draws the playoff as the tournament's second stage.
+
+use crate::tournament::db::{self, TournamentEntry, TournamentRound, TournamentSet, TournamentStage};
+use crate::tournament::{bracket, completion, render, setup, start, swiss};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// The round name prefix. `Group Round 2`, and `小組賽第 2 輪` once localized.
+pub(crate) const ROUND_PREFIX: &str = "Group Round ";
+
+/// Places each group sends to the playoff when the organizer never said.
+pub(crate) const DEFAULT_ADVANCE: usize = 2;
+
+/// Groups are lettered, and the alphabet is where the letters run out.
+pub(crate) const MAX_GROUPS: usize = 26;
+
+pub(crate) fn round_name(ordinal: usize) -> String {
+    format!("{ROUND_PREFIX}{ordinal}")
+}
+
+/// `A` for the first group.
+pub(crate) fn letter(index: usize) -> char {
+    char::from(b'A' + u8::try_from(index % MAX_GROUPS).unwrap_or(0))
+}
+
+/// `tournament_stages.config` for a group stage: who is in which group, in seed
+/// order, and how many of each go through.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Config {
+    pub(crate) groups: Vec<Vec<i64>>,
+    pub(crate) advance: usize,
+}
+
+impl Config {
+    pub(crate) fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("lists of integers always serialize")
+    }
+
+    /// `None` for a stage with no config, or one written by something else.
+    pub(crate) fn from_json(config: Option<&str>) -> Option<Self> {
+        serde_json::from_str(config?).ok()
+    }
+
+    /// The stage's round count: the largest group's.
+    pub(crate) fn rounds(&self) -> usize {
+        self.groups.iter().map(|group| schedule(group).len()).max().unwrap_or(0)
+    }
+}
+
+/// The group count an organizer who never chose one gets: groups of about four.
+pub(crate) fn default_group_count(entrants: usize) -> usize {
+    ((entrants + 2) / 4).max(1)
+}
+
+/// Whether `entrants` make `groups` groups of at least two, each able to send
+/// `advance` players on, with a playoff of at least two at the end of it.
+pub(crate) fn fits(entrants: usize, groups: usize, advance: usize) -> bool {
+    (1..=MAX_GROUPS).contains(&groups)
+        && entrants / groups >= 2
+        && advance <= entrants / groups
+        && groups * advance >= 2
+}
+
+/// Deals `order` — best seed first — into `groups` groups in a snake: left to
+/// right, then back, so every group gets one of the top seeds and the strength
+/// evens out down the order.
+pub(crate) fn assign<T: Copy>(order: &[T], groups: usize) -> Vec<Vec<T>> {
+    let mut dealt: Vec<Vec<T>> = vec![Vec::new(); groups.max(1)];
+    for (index, &player) in order.iter().enumerate() {
+        let (lap, seat) = (index / dealt.len(), index % dealt.len());
+        let group = if lap % 2 == 0 { seat } else { dealt.len() - 1 - seat };
+        dealt[group].push(player);
+    }
+    dealt
+}
+
+/// Every pairing of one group, a round at a time, by the circle method: one
+/// player stays put while the rest rotate past them, so everyone meets everyone
+/// once. An odd group sits a different player out each round rather than giving
+/// anyone a bye — a bye in a round robin would be a free win. Within a pairing
+/// the better seed, the one earlier in `members`, takes slot one.
+pub(crate) fn schedule<T: Copy>(members: &[T]) -> Vec<Vec<(T, T)>> {
+    let mut ring: Vec<Option<usize>> = (0..members.len()).map(Some).collect();
+    if ring.len() % 2 == 1 {
+        ring.push(None);
+    }
+    let n = ring.len();
+    let mut rounds = Vec::new();
+    for _ in 1..n {
+        let round = (0..n / 2)
+            .filter_map(|i| {
+                let (one, two) = (ring[i]?, ring[n - 1 - i]?);
+                Some((members[one.min(two)], members[one.max(two)]))
+            })
+            .collect();
+        rounds.push(round);
+        ring[1..].rotate_right(1);
+    }
+    rounds
+}
+
+/// Every group's `ordinal`th round, group by group. Empty once every group has
+/// played out.
+pub(crate) fn round_pairings<T: Copy>(groups: &[Vec<T>], ordinal: usize) -> Vec<(T, T)> {
+    groups
+        .iter()
+        .flat_map(|group| schedule(group).into_iter().nth(ordinal - 1).unwrap_or_default())
+        .collect()
+}
+
+/// Round one as a `bracket::Bracket`, so `start` persists it the way it does any
+/// other format's: seeds in, no advancement links.
+pub(crate) fn first_round(groups: &[Vec<u32>], best_of: u8) -> bracket::Bracket {
+    let sets = round_pairings(groups, 1)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (one, two))| bracket::Set {
+            position: index + 1,
+            slot1: Some(one),
+            slot2: Some(two),
+            winner_advances_to: None,
+            loser_advances_to: None,
+        })
+        .collect();
+    bracket::Bracket {
+        rounds: vec![bracket::Round {
+            ordinal: 1,
+            name: round_name(1),
+            best_of,
+            side: None,
+            preset_from: 1,
+            sets,
+        }],
+    }
+}
+
+/// One row of a group's table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Standing {
+    pub(crate) user_id: i64,
+    pub(crate) seed: u32,
+    pub(crate) wins: u32,
+    pub(crate) losses: u32,
+    /// Games won less games lost, across every decided set.
+    pub(crate) game_difference: i64,
+}
+
+/// A group's table, best first: sets won, then game difference, then seed — the
+/// last so two players nothing else separates still sit in a stable order.
+///
+/// `members` is the group with each player's seed; `sets` may hold the whole
+/// stage, since only decided sets between two members count.
+pub(crate) fn standings(members: &[(i64, u32)], sets: &[TournamentSet]) -> Vec<Standing> {
+    let in_group: HashSet<i64> = members.iter().map(|&(user_id, _)| user_id).collect();
+    let mut record: HashMap<i64, (u32, u32, i64)> = HashMap::new();
+    for set in sets.iter().filter(|set| completion::is_decided(&set.status)) {
+        let (Some(one), Some(two), Some(winner)) = (set.slot1_user_id, set.slot2_user_id, set.winner_user_id) else {
+            continue;
+        };
+        if !in_group.contains(&one) || !in_group.contains(&two) {
+            continue;
+        }
+        let loser = if winner == one { two } else { one };
+        let margin = (set.slot1_wins - set.slot2_wins).abs();
+        let won = record.entry(winner).or_default();
+        won.0 += 1;
+        won.2 += margin;
+        let lost = record.entry(loser).or_default();
+        lost.1 += 1;
+        lost.2 -= margin;
+    }
+
+    let mut table: Vec<Standing> = members
+        .iter()
+        .map(|&(user_id, seed)| {
+            let (wins, losses, game_difference) = record.get(&user_id).copied().unwrap_or_default();
+            Standing {
+                user_id,
+                seed,
+                wins,
+                losses,
+                game_difference,
+            }
+        })
+        .collect();
+    table.sort_by(|a, b| {
+        b.wins
+            .cmp(&a.wins)
+            .then(b.game_difference.cmp(&a.game_difference))
+            .then(a.seed.cmp(&b.seed))
+    });
+    table
+}
+
+/// The playoff's seed order, best first, from each group's final table.
+///
+/// By place before anything else: every group winner is seeded above every
+/// runner-up, and so on down. Within a place, the better record goes first.
+///
+/// Then **same-group separation**: round one pairs seed `s` with seed
+/// `size + 1 - s`, so with an odd number of groups a winner can draw their own
+/// group's runner-up. Each place after the first is rotated until no round-one
+/// set pairs two players from the same group — the rotation that disturbs the
+/// order least, or the one with the fewest such sets when none avoids them all.
+pub(crate) fn playoff_order(tables: &[Vec<Standing>], advance: usize) -> Vec<i64> {
+    let size = bracket::size(tables.iter().map(|table| table.len().min(advance)).sum());
+    let mut order: Vec<(i64, usize)> = Vec::new();
+    for place in 0..advance {
+        let mut tier: Vec<(usize, &Standing)> = tables
+            .iter()
+            .enumerate()
+            .filter_map(|(group, table)| Some((group, table.get(place)?)))
+            .collect();
+        tier.sort_by(|(a_group, a), (b_group, b)| {
+            b.wins
+                .cmp(&a.wins)
+                .then(b.game_difference.cmp(&a.game_difference))
+                .then(a_group.cmp(b_group))
+        });
+        let tier: Vec<(i64, usize)> = tier
+            .iter()
+            .map(|&(group, standing)| (standing.user_id, group))
+            .collect();
+        order = separated(order, tier, size);
+    }
+    order.into_iter().map(|(user_id, _)| user_id).collect()
+}
+
+/// `order` with `tier` appended, rotated to the turn with the fewest round-one
+/// meetings between groupmates — the first such turn, so the least disturbed.
+fn separated(order: Vec<(i64, usize)>, tier: Vec<(i64, usize)>, size: usize) -> Vec<(i64, usize)> {
+    (0..tier.len().max(1))
+        .map(|turn| {
+            let mut rotated = tier.clone();
+            rotated.rotate_left(turn);
+            let mut candidate = order.clone();
+            candidate.extend(rotated);
+            candidate
+        })
+        .min_by_key(|candidate| same_group_meetings(candidate, size))
+        .unwrap_or(order)
+}
+
+/// Round-one sets between two players from the same group, among the seeds placed
+/// so far in a bracket of `size`.
+fn same_group_meetings(order: &[(i64, usize)], size: usize) -> usize {
+    (1..=order.len())
+        .filter(|&seed| {
+            let opponent = size + 1 - seed;
+            seed < opponent && opponent <= order.len() && order[seed - 1].1 == order[opponent - 1].1
+        })
+        .count()
+}
+
+/// A tournament's group stage with its rounds in order and their sets.
+struct Stage {
+    stage: TournamentStage,
+    config: Config,
+    rounds: Vec<TournamentRound>,
+    sets: Vec<TournamentSet>,
+}
+
+impl Stage {
+    /// `None` when the tournament has no group stage, or its config is unreadable.
+    async fn load(pool: &sqlx::SqlitePool, tournament_id: i64) -> Result<Option<Self>, sqlx::Error> {
+        let stages = db::list_stages_for_tournament(pool, tournament_id).await?;
+        let Some(stage) = stages
+            .into_iter()
+            .find(|stage| stage.format == bracket::Format::Groups.as_db())
+        else {
+            return Ok(None);
+        };
+        let Some(config) = Config::from_json(stage.config.as_deref()) else {
+            return Ok(None);
+        };
+        let rounds = db::list_rounds_for_stage(pool, stage.id).await?;
+        let round_ids: HashSet<i64> = rounds.iter().map(|round| round.id).collect();
+        let sets = db::list_sets_for_tournament(pool, tournament_id)
+            .await?
+            .into_iter()
+            .filter(|set| round_ids.contains(&set.round_id))
+            .collect();
+        Ok(Some(Stage {
+            stage,
+            config,
+            rounds,
+            sets,
+        }))
+    }
+
+    /// Every group's table as it stands.
+    fn tables(&self, entries: &[TournamentEntry]) -> Vec<Vec<Standing>> {
+        let seed = |user_id: i64| {
+            entries
+                .iter()
+                .find(|e| e.user_id == user_id)
+                .and_then(|e| e.seed)
+                .and_then(|seed| u32::try_from(seed).ok())
+                .unwrap_or(u32::MAX)
+        };
+        self.config
+            .groups
+            .iter()
+            .map(|group| {
+                let members: Vec<(i64, u32)> = group.iter().map(|&user_id| (user_id, seed(user_id))).collect();
+                standings(&members, &self.sets)
+            })
+            .collect()
+    }
+}
+
+/// Every group's table, the places that go through, and whether the stage is
+/// over. `None` when the tournament has no group stage.
+pub(crate) async fn tables(
+    pool: &sqlx::SqlitePool,
+    tournament_id: i64,
+    entries: &[TournamentEntry],
+) -> Result<Option<(Vec<Vec<Standing>>, usize, bool)>, sqlx::Error> {
+    let Some(stage) = Stage::load(pool, tournament_id).await? else {
+        return Ok(None);
+    };
+    Ok(Some((
+        stage.tables(entries),
+        stage.config.advance,
+        stage.stage.status == "completed",
+    )))
+}
+
+/// One group's table as it goes into `#{slug}-bracket`, split the way a Swiss
+/// table is (see `swiss::ROWS_PER_BLOCK`): fenced, since it is columns, under a
+/// bilingual heading saying how many go through. Once the stage is over, those
+/// who did are marked.
+pub(crate) fn render_standings(
+    index: usize,
+    table: &[Standing],
+    entries: &[TournamentEntry],
+    advance: usize,
+    finished: bool,
+) -> Vec<String> {
+    let name = |user_id: i64| {
+        entries
+            .iter()
+            .find(|e| e.user_id == user_id)
+            .map_or_else(|| "?".to_owned(), |e| e.display_name.clone())
+    };
+    let header = format!(
+        "{:>3}  {}  {:>5}  {:>4}",
+        "#",
+        render::fit("", render::DEFAULT_WIDTH),
+        "W-L",
+        "+/-"
+    );
+    let rows: Vec<String> = table
+        .iter()
+        .enumerate()
+        .map(|(rank, standing)| {
+            let through = if finished && rank < advance { " >" } else { "" };
+            format!(
+                "{:>3}  {}  {:>5}  {:>+4}{through}",
+                rank + 1,
+                render::fit(&render::sanitize(&name(standing.user_id)), render::DEFAULT_WIDTH),
+                format!("{}-{}", standing.wins, standing.losses),
+                standing.game_difference
+            )
+        })
+        .collect();
+    let mut blocks: Vec<String> = rows
+        .chunks(swiss::ROWS_PER_BLOCK)
+        .map(|rows| format!("```\n{}\n{}\n```", header.trim_end(), rows.join("\n").trim_end()))
+        .collect();
+    if let Some(first) = blocks.first_mut() {
+        let group = letter(index);
+        first.insert_str(
+            0,
+            &format!("**{group} 組 / Group {group}** — 前 {advance} 名晉級 / top {advance} advance\n"),
+        );
+    }
+    blocks
+}
+
+/// What settling a set did to its group stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Progress {
+    /// Sets in the round are still being played.
+    Waiting,
+    /// Every set in the round is decided, and the next one is open.
+    Paired { round: usize },
+    /// The last round is over, and the playoff is drawn from the tables.
+    PlayoffDrawn,
+}
+
+/// Opens the next group round once every set in the current one is decided, or
+/// draws the playoff once the last one is. `None` when the tournament has no
+/// group stage, or its group stage is already over — from then on the playoff's
+/// own links carry the event, so callers need not know the format.
+///
+/// Safe to call any number of times, from any number of settlements at once:
+/// the next round and the playoff are each only ever written by whichever call
+/// gets there first (see `db::insert_paired_round` and `db::insert_playoff`).
+pub(crate) async fn advance(pool: &sqlx::SqlitePool, tournament_id: i64) -> Result<Option<Progress>, sqlx::Error> {
+    let Some(stage) = Stage::load(pool, tournament_id).await? else {
+        return Ok(None);
+    };
+    if stage.stage.status == "completed" {
+        return Ok(None);
+    }
+    let Some(current) = stage.rounds.last() else {
+        return Ok(None);
+    };
+    if stage
+        .sets
+        .iter()
+        .any(|set| set.round_id == current.id && !completion::is_decided(&set.status))
+    {
+        return Ok(Some(Progress::Waiting));
+    }
+
+    let ordinal = usize::try_from(current.ordinal).unwrap_or(usize::MAX);
+    if ordinal < stage.config.rounds() {
+        return pair_next(pool, tournament_id, &stage, current, ordinal + 1).await;
+    }
+    draw_playoff(pool, tournament_id, &stage).await
+}
+
+/// Writes group round `next`, on the default preset as every group round is.
+async fn pair_next(
+    pool: &sqlx::SqlitePool,
+    tournament_id: i64,
+    stage: &Stage,
+    current: &TournamentRound,
+    next: usize,
+) -> Result<Option<Progress>, sqlx::Error> {
+    let presets = db::list_round_presets(pool, tournament_id).await?;
+    let (best_of, draft_preset_id) = match setup::default_preset(&presets) {
+        Some(preset) => (preset.best_of, Some(preset.draft_preset_id.clone())),
+        None => (current.best_of, current.draft_preset_id.clone()),
+    };
+    let inserted = db::insert_paired_round(
+        pool,
+        db::NewPairedRound {
+            tournament_id,
+            stage_id: stage.stage.id,
+            ordinal: next,
+            name: round_name(next),
+            best_of,
+            draft_preset_id,
+            pairings: round_pairings(&stage.config.groups, next)
+                .into_iter()
+                .map(|(one, two)| (one, Some(two)))
+                .collect(),
+        },
+    )
+    .await?;
+    Ok(Some(if inserted {
+        Progress::Paired { round: next }
+    } else {
+        Progress::Waiting
+    }))
+}
+
+/// Seeds the playoff from the final tables and writes it. A playoff the presets
+/// no longer cover is logged and left undrawn — the stage stays open, and
+/// `/tournament refresh` draws it once an organizer has fixed them.
+async fn draw_playoff(
+    pool: &sqlx::SqlitePool,
+    tournament_id: i64,
+    stage: &Stage,
+) -> Result<Option<Progress>, sqlx::Error> {
+    let Some(tournament) = db::get_tournament(pool, tournament_id).await? else {
+        return Ok(None);
+    };
+    let entries = db::list_entries_for_tournament(pool, tournament_id).await?;
+    let order = playoff_order(&stage.tables(&entries), stage.config.advance);
+    let format = bracket::Format::from_db(&tournament.playoff_format, tournament.grand_final_reset);
+
+    let presets = db::list_round_presets(pool, tournament_id).await?;
+    let main = setup::presets_per_round(&presets, bracket::round_count(bracket::size(order.len())));
+    let Some((built, per_round)) = main.and_then(|main| start::build_with_presets(format, order.len(), &main)) else {
+        tracing::error!(
+            "tournament {tournament_id}'s group stage is over, but its presets no longer cover a playoff of {}",
+            order.len()
+        );
+        return Ok(Some(Progress::Waiting));
+    };
+
+    let seed_to_user: HashMap<u32, i64> = (1..).zip(order.iter().copied()).collect();
+    let eliminated: Vec<i64> = stage
+        .config
+        .groups
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|user_id| !order.contains(user_id))
+        .collect();
+    let drawn = db::insert_playoff(
+        pool,
+        db::NewPlayoff {
+            tournament_id,
+            group_stage_id: stage.stage.id,
+            format,
+            bracket: &built,
+            seed_to_user: &seed_to_user,
+            per_round: &per_round,
+            eliminated: &eliminated,
+        },
+    )
+    .await?;
+    Ok(Some(if drawn {
+        Progress::PlayoffDrawn
+    } else {
+        Progress::Waiting
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standing(user_id: i64, wins: u32) -> Standing {
+        Standing {
+            user_id,
+            seed: u32::try_from(user_id).unwrap(),
+            wins,
+            losses: 0,
+            game_difference: 0,
+        }
+    }
+
+    fn decided(one: i64, two: i64, score: (i64, i64)) -> TournamentSet {
+        TournamentSet {
+            id: one * 100 + two,
+            tournament_id: 1,
+            round_id: 1,
+            position: 1,
+            slot1_user_id: Some(one),
+            slot2_user_id: Some(two),
+            slot1_wins: score.0,
+            slot2_wins: score.1,
+            winner_user_id: Some(if score.0 > score.1 { one } else { two }),
+            status: "completed".to_string(),
+            draft_external_id: None,
+            draft_synced_at: None,
+            draft_announce_message_id: None,
+            redraft_count: 0,
+            thread_id: None,
+            panel_message_id: None,
+            winner_advances_to_set_id: None,
+            winner_advances_to_slot: None,
+            loser_advances_to_set_id: None,
+            loser_advances_to_slot: None,
+            scheduled_at: None,
+            completed_at: None,
+            proposed_at: None,
+            proposed_by: None,
+        }
+    }
+
+    #[test]
+    fn seeds_are_dealt_in_a_snake() {
+        let order: Vec<u32> = (1..=8).collect();
+        assert_eq!(assign(&order, 2), vec![vec![1, 4, 5, 8], vec![2, 3, 6, 7]]);
+        assert_eq!(assign(&order, 3), vec![vec![1, 6, 7], vec![2, 5, 8], vec![3, 4]]);
+    }
+
+    #[test]
+    fn a_round_robin_meets_everyone_once() {
+        for size in 2..=7 {
+            let members: Vec<u32> = (1..=size).collect();
+            let rounds = schedule(&members);
+            assert_eq!(rounds.len(), if size % 2 == 0 { size - 1 } else { size } as usize);
+
+            let mut met = HashSet::new();
+            for round in &rounds {
+                let mut playing = HashSet::new();
+                for &(one, two) in round {
+                    assert!(one < two, "the better seed takes slot one");
+                    assert!(met.insert((one, two)), "{one} and {two} met twice");
+                    assert!(playing.insert(one) && playing.insert(two), "a double booking");
+                }
+            }
+            assert_eq!(met.len() as u32, size * (size - 1) / 2);
+        }
+    }
+
+    #[test]
+    fn every_groups_round_shares_one_round_row() {
+        let groups = vec![vec![1u32, 4, 5, 8], vec![2, 3, 6, 7]];
+        let built = first_round(&groups, 3);
+        assert_eq!(built.rounds.len(), 1);
+        assert_eq!(built.rounds[0].name, "Group Round 1");
+        assert_eq!(built.rounds[0].sets.len(), 4, "two sets from each group of four");
+        // A five-player group plays on after a four-player one has finished.
+        let uneven = vec![vec![1, 2, 3, 4, 5], vec![6, 7, 8, 9]];
+        assert_eq!(round_pairings(&uneven, 4).len(), 2);
+        assert_eq!(round_pairings(&uneven, 5).len(), 2);
+        assert!(round_pairings(&uneven, 6).is_empty());
+    }
+
+    #[test]
+    fn a_group_table_ranks_by_wins_then_game_difference() {
+        let members = [(1, 1), (2, 2), (3, 3)];
+        // Everyone wins once; 3's win was the most lopsided, 1's loss too.
+        let sets = [decided(1, 2, (2, 1)), decided(2, 3, (2, 1)), decided(1, 3, (0, 2))];
+        let table = standings(&members, &sets);
+        let order: Vec<i64> = table.iter().map(|s| s.user_id).collect();
+        assert_eq!(order, vec![3, 2, 1]);
+        assert_eq!(table[0].game_difference, 1);
+        assert_eq!((table[2].wins, table[2].losses, table[2].game_difference), (1, 1, -1));
+    }
+
+    #[test]
+    fn a_set_outside_the_group_does_not_count() {
+        let table = standings(&[(1, 1), (2, 2)], &[decided(1, 9, (2, 0))]);
+        assert!(table.iter().all(|s| s.wins == 0 && s.losses == 0));
+    }
+
+    #[test]
+    fn the_playoff_seeds_every_winner_above_every_runner_up() {
+        let tables = vec![
+            vec![standing(1, 3), standing(4, 2)],
+            vec![standing(2, 3), standing(3, 1)],
+        ];
+        // Winners 1, 2; runners-up 4 then 3. Seed 1 meets seed 4, 2 meets 3 — the
+        // other group's runner-up either way.
+        assert_eq!(playoff_order(&tables, 2), vec![1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn an_odd_group_count_still_keeps_groupmates_apart_in_round_one() {
+        // Three groups of two through, in an 8-bracket: seed s meets seed 9 - s.
+        // Unrotated, the runners-up line up A, B, C, and seed 3 — C's winner —
+        // meets seed 6, C's runner-up.
+        let tables: Vec<Vec<Standing>> = (0..3)
+            .map(|group| vec![standing(10 + group, 2), standing(20 + group, 1)])
+            .collect();
+        let order = playoff_order(&tables, 2);
+        let group_of = |user_id: i64| user_id % 10;
+        for seed in 1..=order.len() {
+            let opponent = 9 - seed;
+            if opponent <= order.len() {
+                assert_ne!(group_of(order[seed - 1]), group_of(order[opponent - 1]), "{order:?}");
+            }
+        }
+        assert_eq!(&order[..3], &[10, 11, 12], "the winners keep their order");
+    }
+
+    #[test]
+    fn a_field_fits_only_groups_of_two_that_can_send_a_playoff_on() {
+        assert!(fits(8, 2, 2));
+        assert!(fits(8, 4, 2));
+        assert!(!fits(8, 5, 1), "a group of one");
+        assert!(!fits(8, 2, 5), "more advancing than a group holds");
+        assert!(!fits(4, 1, 1), "a playoff of one");
+        assert!(!fits(60, 27, 1), "past the alphabet");
+        assert_eq!(default_group_count(8), 2);
+        assert_eq!(default_group_count(3), 1);
+    }
+
+    #[test]
+    fn the_config_survives_the_stage_column() {
+        let config = Config {
+            groups: vec![vec![1, 4], vec![2, 3]],
+            advance: 1,
+        };
+        assert_eq!(Config::from_json(Some(&config.to_json())), Some(config.clone()));
+        assert_eq!(config.rounds(), 1);
+        assert_eq!(Config::from_json(Some("{\"rounds\":3}")), None);
+    }
+
+    #[test]
+    fn a_finished_group_marks_who_went_through() {
+        let table = vec![standing(1, 2), standing(2, 0)];
+        let running = render_standings(0, &table, &[], 1, false).concat();
+        assert!(
+            running.starts_with("**A 組 / Group A**") && !running.contains(" >"),
+            "{running}"
+        );
+        let finished = render_standings(0, &table, &[], 1, true).concat();
+        assert_eq!(finished.matches(" >").count(), 1, "{finished}");
+    }
+}
@@ -0,0 +1,168 @@
+//! The lifecycle's forward edges — open check-in, close it, start — each with
+//! everything that has to follow it on Discord.
+//!
+//! Two callers take these steps: the slash commands, when an organizer runs
+//! them by hand, and `autopilot`, when the tournament's timeline comes due. Both
+//! come through here, so a step taken on schedule writes the same rows, posts
+//! the same panels and leaves the same audit line as one taken by hand. The
+//! business rules themselves stay in `checkin`, `seeding` and `start`; this is
+//! only the glue that used to live inside each command.
+//!
+//! Every function returns its step's own outcome and leaves the wording to the
+//! caller: a command replies to one person in their language, the autopilot
+//! only speaks up when a step is refused.
+
+use crate::Error;
+use crate::db::{to_channel_id, to_db_id};
+use crate::locale::Locale;
+use crate::tournament::audit::{self, Actor};
+use crate::tournament::checkin::{self, CloseCheckinOutcome, OpenCheckinOutcome};
+use crate::tournament::db::{self, Tournament};
+use crate::tournament::panel_check::PanelOutcome;
+use crate::tournament::seeding::{self, RefreshOutcome, SeedPolicy};
+use crate::tournament::start::{self, StartOutcome};
+use crate::tournament::{bracket_view, checkin_panel, panel, seed_panel, set_thread};
+use serenity::all::Http;
+use sqlx::SqlitePool;
+
+/// Opens check-in, posts its panel, closes the registration panel's doors and
+/// pings the check-in pool once. `ping_locale` is the language of that ping —
+/// see `checkin::checkin_ping_messages` for why the autopilot passes `None`.
+pub(crate) async fn open_checkin(
+    http: &Http,
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    minutes: Option<i64>,
+    actor: Actor<'_>,
+    ping_locale: Option<Locale>,
+) -> Result<OpenCheckinOutcome, Error> {
+    let outcome = checkin::open(pool, tournament, minutes).await?;
+    audit::log_action_by("open-checkin", tournament.id, &tournament.slug, actor, &outcome);
+
+    if let OpenCheckinOutcome::Opened { closes_at } = outcome {
+        // Always set by `create()` when the tournament was made.
+        let register_channel_id = to_channel_id(tournament.register_channel_id.unwrap());
+        let message_id = checkin_panel::post_initial(
+            http,
+            pool,
+            register_channel_id,
+            tournament.id,
+            &tournament.name,
+            closes_at,
+            true,
+        )
+        .await?;
+        db::set_checkin_message_id(pool, tournament.id, Some(to_db_id(message_id))).await?;
+
+        // Registration closes here, so the panel must stop inviting
+        // sign-ups the gate would now refuse. Re-read: the status has moved.
+        let tournament = db::get_tournament(pool, tournament.id).await?.unwrap();
+        panel::refresh_now(http, pool, &tournament).await?;
+
+        // A one-time nudge: the panel alone only reaches whoever happens to be
+        // looking. Invitees are exempt — check-in was never asked of them.
+        let entries = db::list_entries_for_tournament(pool, tournament.id).await?;
+        let user_ids: Vec<i64> = checkin::checkin_pool(&entries).iter().map(|e| e.user_id).collect();
+        for message in checkin::checkin_ping_messages(ping_locale, &user_ids) {
+            register_channel_id.say(http, message).await?;
+        }
+    }
+    Ok(outcome)
+}
+
+/// Closes check-in and, if it closed, seeds the field under the tournament's own
+/// policy — the order is whatever the organizers left it as, since closing
+/// check-in is the edge that used to overwrite a hand-made one.
+pub(crate) async fn close_checkin(
+    http: &Http,
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    actor: Actor<'_>,
+) -> Result<(CloseCheckinOutcome, Option<Seeded>), Error> {
+    let outcome = checkin::close(pool, tournament).await?;
+    audit::log_action_by("close-checkin", tournament.id, &tournament.slug, actor, &outcome);
+
+    if !matches!(outcome, CloseCheckinOutcome::Closed { .. }) {
+        return Ok((outcome, None));
+    }
+
+    checkin_panel::close(http, pool, tournament).await?;
+    let policy = SeedPolicy::from_source(&tournament.seed_source);
+    let seeded = seed(http, pool, tournament, policy, actor).await?;
+    Ok((outcome, Some(seeded)))
+}
+
+/// A seeding pass and whether its panel made it into `#{slug}-bracket`.
+#[derive(Debug)]
+pub(crate) struct Seeded {
+    pub outcome: RefreshOutcome,
+    pub panel: PanelOutcome,
+}
+
+impl Seeded {
+    pub(crate) fn message(&self, tournament_name: &str, locale: Locale) -> String {
+        let message = self.outcome.message(tournament_name, locale);
+        match self.panel {
+            PanelOutcome::Present | PanelOutcome::Reposted | PanelOutcome::NotConfigured => message,
+            // Said out loud rather than swallowed: the seeding itself succeeded, and
+            // an organizer who cannot see the panel needs to know it is the panel
+            // that is missing, not the seeding.
+            PanelOutcome::Failed => format!(
+                "{message}\n{}",
+                locale.pick(
+                    "種子名單：無法確認或張貼，請確認機器人可在賽程頻道發言。",
+                    "Seeding panel: could not confirm or post — check the bot can send messages in the bracket \
+                     channel.",
+                )
+            ),
+            // The seeding panel has no phase gate — `ensure` never returns this for it.
+            PanelOutcome::NotExpected => unreachable!("the seeding panel has no phase gate"),
+        }
+    }
+}
+
+/// Fetches ratings, writes the seed order under `policy` and puts the seeding
+/// panel in front of the organizers.
+///
+/// **Best-effort by design.** By the time this runs after close-checkin the
+/// tournament has already advanced to `seeding`, so an aoe4world outage must not
+/// fail the step and strand the lifecycle — it seeds from whatever ratings are
+/// stored, and `Seeded::message` says so. The panel is best-effort for the same
+/// reason: `seed_panel::ensure` never propagates, so a stale `seed_message_id`
+/// is reposted rather than turned into an error after the status has moved.
+pub(crate) async fn seed(
+    http: &Http,
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    policy: SeedPolicy,
+    actor: Actor<'_>,
+) -> Result<Seeded, Error> {
+    let outcome = seeding::refresh_ratings(pool, tournament, policy).await?;
+    audit::log_action_by("seed", tournament.id, &tournament.slug, actor, &outcome);
+    let panel = seed_panel::ensure(http, pool, tournament).await;
+    Ok(Seeded { outcome, panel })
+}
+
+/// Starts the tournament and, if it started, turns the preview into the real
+/// bracket and opens every set that is ready to play.
+pub(crate) async fn start(
+    http: &Http,
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    actor: Actor<'_>,
+) -> Result<StartOutcome, Error> {
+    let outcome = start::start(pool, tournament).await?;
+    audit::log_action_by("start", tournament.id, &tournament.slug, actor, &outcome);
+
+    if matches!(outcome, StartOutcome::Started { .. }) {
+        // The preview messages become the real bracket in place — re-read so the
+        // status is `running` and the provisional label comes off.
+        let tournament = db::get_tournament(pool, tournament.id).await?.unwrap();
+        bracket_view::reconcile_now(http, pool, &tournament).await?;
+        panel::refresh_now(http, pool, &tournament).await?;
+        // The same opener a completed set runs, so round one and every round
+        // after it are opened by one piece of code.
+        set_thread::open_ready(http, pool, &tournament).await;
+    }
+    Ok(outcome)
+}
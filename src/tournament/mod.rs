@@ -22,6 +22,9 @@ pub(crate) mod action;
 // One log line per tournament action, shared by the slash-command and button
 // surfaces so a destructive one leaves the same record either way.
 pub(crate) mod audit;
+// The opt-in autopilot: open-checkin, the closing ping, close-checkin and start
+// run on the scheduled timeline, ticked from `main.rs`.
+pub(crate) mod autopilot;
 // Row types and queries for every tournament table — see the per-section notes
 // in db.rs itself for what each one holds.
 #[allow(dead_code)]
@@ -40,6 +43,9 @@ pub(crate) mod completion;
 // separate from `Emperor`, which is home-guild meme/reaction logic with no
 // tournament knowledge; registered as a second handler in `main.rs`.
 pub(crate) mod dispatch;
+// The lifecycle's forward edges with their Discord follow-ups, shared by the
+// slash commands and `autopilot` so a scheduled step is the same step.
+pub(crate) mod lifecycle;
 // `/tournament invite|uninvite`: the organizers' own door into the field, for an
 // entrant who has no aoe4world profile and never signed themselves up.
 pub(crate) mod invite;
@@ -58,6 +64,9 @@ pub(crate) mod render;
 // An organizer's own record of a game, for a set played outside the draft tool
 // or a draft that was abandoned. The fallback, not the primary path.
 pub(crate) mod report;
+// `/set schedule`: a proposed time, the other player's confirmation, and the
+// reminders the scheduler in `main.rs` posts as an agreed time approaches.
+pub(crate) mod schedule;
 // `/tournament register|rebind|withdraw`'s business logic.
 pub(crate) mod registration;
 // `/set redraft`: abandons a set's current draft room for a fresh one from the
@@ -77,6 +86,12 @@ pub(crate) mod seed_panel;
 // Set threads: a private thread per set, its draft room and
 // the pinned panel telling each player which seat to take.
 pub(crate) mod set_thread;
+// Group stages: round-robin groups a round at a time, their tables, and the
+// playoff drawn from them once the last group round is decided.
+pub(crate) mod groups;
+// Swiss stages: pairing each round by score group, the standings and their
+// tiebreaks, and pairing the next round once the current one is over.
+pub(crate) mod swiss;
 // `/tournament start`: the gates, then the generated
 // bracket persisted and round one opened.
 pub(crate) mod start;
@@ -22,7 +22,7 @@ use crate::locale::Locale;
 use crate::tournament::bracket;
 use crate::tournament::completion;
 use crate::tournament::db::{self, Tournament, TournamentSet};
-use crate::tournament::set_thread::{self, SetHeading};
+use crate::tournament::set_thread::{self, Schedule, SetHeading};
 use serenity::all::{CacheHttp, CreateAllowedMentions, CreateMessage, EditMessage};
 use sqlx::SqlitePool;
 use tracing::error;
@@ -165,6 +165,7 @@ pub(crate) async fn run(
         position: set.position,
         best_of: round.best_of,
         is_third_place: round.name == bracket::THIRD_PLACE,
+        loser_plays_on: false,
     };
 
     if set.draft_external_id.is_none() {
@@ -176,7 +177,7 @@ pub(crate) async fn run(
         };
 
         if let (Some(thread_id), Some(panel_id)) = (set.thread_id.map(to_channel_id), set.panel_message_id) {
-            let (content, components) = set_thread::render_panel(&heading, &one, &two, Some(&room));
+            let (content, components) = set_thread::render_panel(&heading, &one, &two, Some(&room), &Schedule::of(set));
             if let Err(err) = thread_id
                 .edit_message(
                     &http,
@@ -260,7 +261,7 @@ pub(crate) async fn run(
             error!("failed to post the redraft notice for set {}: {err:?}", set.id);
         }
 
-        let (content, components) = set_thread::render_panel(&heading, &one, &two, Some(&room));
+        let (content, components) = set_thread::render_panel(&heading, &one, &two, Some(&room), &Schedule::of(set));
         match thread_id
             .send_message(&http, CreateMessage::new().content(content).components(components))
             .await
@@ -310,6 +311,8 @@ mod tests {
             loser_advances_to_slot: None,
             scheduled_at: None,
             completed_at: None,
+            proposed_at: None,
+            proposed_by: None,
         }
     }
 
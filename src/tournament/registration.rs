@@ -256,7 +256,7 @@ impl RegisterOutcome {
 /// Best-effort: a missing or unreachable rating leaves the column null, which
 /// seeding already tolerates. The entrant is registered either way.
 pub(crate) async fn snapshot_entry_elo(pool: &SqlitePool, tournament_id: i64, user_id: i64, aoe4_id: i64) {
-    let Some(profile) = aoe4world::fetch_profile(aoe4_id).await else {
+    let Ok(profile) = aoe4world::fetch_profile(aoe4_id).await else {
         return;
     };
     let Some(elo) = profile.modes.rm_1v1_elo.map(|e| i64::from(e.rating)) else {
@@ -323,7 +323,7 @@ pub(crate) async fn claim_profile(pool: &SqlitePool, user_id: i64, aoe4_id: i64)
             other_display_name: other.display_name,
         });
     }
-    let Some(profile) = aoe4world::fetch_profile(aoe4_id).await else {
+    let Ok(profile) = aoe4world::fetch_profile(aoe4_id).await else {
         return Ok(Claim::LookupFailed);
     };
     let elo = profile.modes.rm_1v1_elo.map(|data| i64::from(data.rating));
@@ -491,7 +491,7 @@ pub(crate) async fn register(
                 });
             }
 
-            let Some(profile) = aoe4world::fetch_profile(aoe4_id).await else {
+            let Ok(profile) = aoe4world::fetch_profile(aoe4_id).await else {
                 return Ok(RegisterOutcome::LookupFailed);
             };
             let elo = profile.modes.rm_1v1_elo.map(|data| i64::from(data.rating));
@@ -693,7 +693,7 @@ pub(crate) async fn rebind(pool: &SqlitePool, user_id: i64, aoe4_id: i64) -> Res
             other_user_id: other.user_id,
         });
     }
-    let Some(profile) = aoe4world::fetch_profile(aoe4_id).await else {
+    let Ok(profile) = aoe4world::fetch_profile(aoe4_id).await else {
         return Ok(RebindOutcome::LookupFailed);
     };
     let elo = profile.modes.rm_1v1_elo.map(|data| i64::from(data.rating));
@@ -65,11 +65,22 @@ pub(crate) struct Round {
 /// own message, recursing until each part fits. A half of a bracket is just a smaller
 /// bracket, which is why this needs no separate code path.
 pub(crate) fn render(rounds: &[Round], width: usize) -> Vec<String> {
+    render_tree(rounds, width, true)
+}
+
+/// `render` without the trophy: a winners bracket, whose last round decides who
+/// reaches the grand final rather than who wins the event.
+pub(crate) fn render_uncrowned(rounds: &[Round], width: usize) -> Vec<String> {
+    render_tree(rounds, width, false)
+}
+
+fn render_tree(rounds: &[Round], width: usize, crown: bool) -> Vec<String> {
     if rounds.is_empty() {
         return Vec::new();
     }
 
-    let body = fenced(&grid(rounds, width), &champion_line(rounds));
+    let trophy = if crown { champion_line(rounds) } else { String::new() };
+    let body = fenced(&grid(rounds, width), &trophy);
     if body.len() <= MESSAGE_LIMIT || rounds.len() == 1 {
         // A single round that still does not fit cannot be split any further. Better
         // an over-long body that Discord rejects loudly than a silent truncation.
@@ -95,9 +106,9 @@ pub(crate) fn render(rounds: &[Round], width: usize) -> Vec<String> {
             .collect()
     };
 
-    let mut messages = render(&half(true), width);
-    messages.extend(render(&half(false), width));
-    messages.extend(render(closing, width));
+    let mut messages = render_tree(&half(true), width, crown);
+    messages.extend(render_tree(&half(false), width, crown));
+    messages.extend(render_tree(closing, width, crown));
     messages
 }
 
@@ -158,7 +169,36 @@ fn champion(rounds: &[Round]) -> Option<String> {
 /// fence-safety pass — and `pub(crate)` so `bracket_view`'s image path can
 /// reuse it as plain message content alongside the drawing.
 pub(crate) fn champion_line(rounds: &[Round]) -> String {
-    match champion(rounds) {
+    trophy(champion(rounds))
+}
+
+/// The champion of a double-elimination event, from its grand final rounds —
+/// the grand final, then the reset when there is one.
+///
+/// The winners champion holds slot one, so winning the grand final ends it. The
+/// losers champion winning it forces the reset, if the event plays one, and
+/// whoever wins that is champion instead.
+fn grand_final_champion(rounds: &[Round]) -> Option<String> {
+    let first = rounds.first()?.matches.first()?;
+    let decider = match first.winner? {
+        Slot::One => first,
+        Slot::Two => match rounds.get(1) {
+            Some(reset) => reset.matches.first()?,
+            None => first,
+        },
+    };
+    Some(decider.entrant(decider.winner?)?.name.clone())
+}
+
+/// `champion_line` for a double-elimination event.
+pub(crate) fn grand_final_champion_line(rounds: &[Round]) -> String {
+    trophy(grand_final_champion(rounds))
+}
+
+/// The trophy line for a champion named some other way than by a final — a
+/// Swiss stage's table.
+pub(crate) fn trophy(champion: Option<String>) -> String {
+    match champion {
         Some(name) => format!("\n🏆 **{}**", crate::ranked::escape(&name)),
         None => String::new(),
     }
@@ -342,7 +382,10 @@ pub(crate) fn fit(text: &str, width: usize) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{DEFAULT_WIDTH, Entrant, Match, Round, fit, render, render_round_list, sanitize};
+    use super::{
+        DEFAULT_WIDTH, Entrant, Match, Round, fit, grand_final_champion_line, render, render_round_list,
+        render_uncrowned, sanitize,
+    };
     use crate::tournament::bracket::Slot;
     use unicode_width::UnicodeWidthChar;
 
@@ -714,6 +757,43 @@ mod tests {
         assert!(!render(&undecided, DEFAULT_WIDTH)[0].contains('🏆'));
     }
 
+    #[test]
+    fn a_winners_bracket_drawn_uncrowned_names_no_champion() {
+        let decided = vec![Round {
+            name: "Winners Final".to_owned(),
+            matches: vec![played((1, "MarineLorD"), (2, "Beasty"), (2, 1))],
+        }];
+        assert!(!render_uncrowned(&decided, DEFAULT_WIDTH)[0].contains('🏆'));
+    }
+
+    #[test]
+    fn the_grand_final_crowns_the_winners_champion_or_goes_to_the_reset() {
+        let grand_final = |score| Round {
+            name: "Grand Final".to_owned(),
+            matches: vec![played((1, "MarineLorD"), (2, "Beasty"), score)],
+        };
+        let reset = |matches| Round {
+            name: "Grand Final Reset".to_owned(),
+            matches,
+        };
+
+        // The winners champion wins it outright; the reset is never played.
+        let outright = vec![grand_final((2, 0)), reset(vec![])];
+        assert_eq!(grand_final_champion_line(&outright), "\n🏆 **MarineLorD**");
+
+        // The losers champion forces the reset, and nobody is champion until it ends.
+        let forced = vec![grand_final((0, 2)), reset(vec![pending()])];
+        assert_eq!(grand_final_champion_line(&forced), "");
+        let decided = vec![
+            grand_final((0, 2)),
+            reset(vec![played((1, "MarineLorD"), (2, "Beasty"), (2, 1))]),
+        ];
+        assert_eq!(grand_final_champion_line(&decided), "\n🏆 **MarineLorD**");
+
+        // Without a reset, the grand final is the last word either way.
+        assert_eq!(grand_final_champion_line(&[grand_final((0, 2))]), "\n🏆 **Beasty**");
+    }
+
     #[test]
     fn the_round_list_renders_for_any_round() {
         let round = Round {
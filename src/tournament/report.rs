@@ -187,6 +187,8 @@ mod tests {
             loser_advances_to_slot: None,
             scheduled_at: None,
             completed_at: None,
+            proposed_at: None,
+            proposed_by: None,
         }
     }
 
@@ -0,0 +1,723 @@
+//! `/set schedule`: one player proposes when a set is played, the other confirms
+//! or declines it with the buttons the proposal adds to the set panel, and the
+//! thread is reminded as the agreed time approaches.
+//!
+//! Times are typed as a UTC+8 wall time by default — the same rule as
+//! `/tournament setup start_time` — or with an explicit offset, or pasted as a
+//! Discord `<t:…>` timestamp. They are stored UTC and only ever shown back as
+//! Discord timestamps, which every reader sees in their own zone, so no reader
+//! has to convert anything.
+//!
+//! **Reminders are rows, not timers.** Confirming a time writes one
+//! `set_schedule_reminders` row per lead the tournament is configured with, and
+//! `run`, ticked every minute from `main.rs`, posts whatever has come due. A
+//! restart loses nothing: the first tick after it finds every reminder that
+//! came due meanwhile, and `triage` decides which are still worth posting.
+//!
+//! `parse_time`, `parse_leads`, `plan`, `refuse` and `triage` are the pure parts,
+//! tested here.
+
+use crate::db::{to_channel_id, to_user_id};
+use crate::locale::Locale;
+use crate::tournament::completion;
+use crate::tournament::db::{self, DueSetReminder, Tournament, TournamentSet};
+use crate::tournament::set_thread;
+use crate::{Data, Error};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, Utc};
+use serenity::all::{CacheHttp, CreateAllowedMentions, CreateMessage, Http};
+use sqlx::SqlitePool;
+use tracing::{error, info};
+
+/// Taiwan is UTC+8 year round with no daylight saving, so a fixed offset is exact
+/// and saves a `chrono-tz` dependency. A time typed without an offset is read as
+/// this wall time.
+pub(crate) const LOCAL_OFFSET_HOURS: i32 = 8;
+
+/// More than a handful of reminders per set is a thread full of pings.
+const MAX_LEADS: usize = 5;
+
+/// A week: past it a "reminder" is a schedule announcement, which the
+/// confirmation notice already is.
+const MAX_LEAD_MINUTES: i64 = 7 * 24 * 60;
+
+/// `YYYY-MM-DD HH:MM` as a UTC+8 wall time. Pure, so the parsing rules are
+/// testable without a Discord context.
+pub(crate) fn parse_local(input: &str) -> Option<DateTime<Utc>> {
+    at_offset(input.trim(), FixedOffset::east_opt(LOCAL_OFFSET_HOURS * 3600)?)
+}
+
+/// What `/set schedule` accepts: `parse_local`'s wall time, optionally followed
+/// by the zone it is in (`UTC`, `+09:00`, `UTC-5`), or a Discord timestamp as
+/// a timestamp generator pastes it (`<t:1755689400:F>`).
+pub(crate) fn parse_time(input: &str) -> Option<DateTime<Utc>> {
+    let input = input.trim();
+    if let Some(at) = parse_discord_timestamp(input) {
+        return Some(at);
+    }
+
+    let mut parts = input.split_whitespace();
+    let wall_time = format!("{} {}", parts.next()?, parts.next()?);
+    let offset = match parts.next() {
+        Some(zone) => parse_offset(zone)?,
+        None => FixedOffset::east_opt(LOCAL_OFFSET_HOURS * 3600)?,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    at_offset(&wall_time, offset)
+}
+
+fn at_offset(wall_time: &str, offset: FixedOffset) -> Option<DateTime<Utc>> {
+    let naive = NaiveDateTime::parse_from_str(wall_time, "%Y-%m-%d %H:%M").ok()?;
+    Some(naive.and_local_timezone(offset).single()?.to_utc())
+}
+
+/// `Z`, `UTC`, `GMT`, or a signed offset with or without either prefix:
+/// `+9`, `+09`, `+0930`, `UTC-05:00`. Anything past ±14 hours is no real zone.
+fn parse_offset(zone: &str) -> Option<FixedOffset> {
+    let zone = zone.to_ascii_uppercase();
+    let zone = zone
+        .strip_prefix("UTC")
+        .or_else(|| zone.strip_prefix("GMT"))
+        .unwrap_or(&zone);
+    if zone.is_empty() || zone == "Z" {
+        return FixedOffset::east_opt(0);
+    }
+
+    let (sign, digits) = match zone.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if digits.len() <= 2 => (digits, "0"),
+        None if digits.len() == 4 => digits.split_at(2),
+        None => return None,
+    };
+    let (hours, minutes): (i32, i32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    if !(0..=14).contains(&hours) || !(0..60).contains(&minutes) {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// `<t:1755689400>` or `<t:1755689400:F>`, any style letter.
+fn parse_discord_timestamp(input: &str) -> Option<DateTime<Utc>> {
+    let inner = input.strip_prefix("<t:")?.strip_suffix('>')?;
+    let seconds = match inner.split_once(':') {
+        Some((seconds, style)) if style.len() == 1 && style.chars().all(|c| c.is_ascii_alphabetic()) => seconds,
+        Some(_) => return None,
+        None => inner,
+    };
+    DateTime::from_timestamp(seconds.parse().ok()?, 0)
+}
+
+/// A time as every surface shows it: the full date in the reader's own zone,
+/// then how far off it is.
+pub(crate) fn timestamp(at: DateTime<Utc>) -> String {
+    format!("<t:{0}:F> (<t:{0}:R>)", at.timestamp())
+}
+
+/// `/tournament setup reminders`: minutes before the agreed time, separated by
+/// commas or spaces, or `off` for none. Normalized largest first with repeats
+/// dropped, so `format_leads` stores one spelling of each configuration.
+pub(crate) fn parse_leads(input: &str) -> Option<Vec<i64>> {
+    let input = input.trim();
+    if input.is_empty() || input.eq_ignore_ascii_case("off") || input.eq_ignore_ascii_case("none") {
+        return Some(Vec::new());
+    }
+    let mut leads = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|lead| !lead.is_empty())
+        .map(|lead| {
+            lead.parse()
+                .ok()
+                .filter(|minutes| (1..=MAX_LEAD_MINUTES).contains(minutes))
+        })
+        .collect::<Option<Vec<i64>>>()?;
+    leads.sort_unstable_by(|a, b| b.cmp(a));
+    leads.dedup();
+    (leads.len() <= MAX_LEADS).then_some(leads)
+}
+
+pub(crate) fn format_leads(leads: &[i64]) -> String {
+    leads.iter().map(i64::to_string).collect::<Vec<_>>().join(",")
+}
+
+/// The tournament's reminder leads. Stored already normalized, so a value that
+/// no longer parses was written by hand and reads as none rather than a guess.
+pub(crate) fn leads(tournament: &Tournament) -> Vec<i64> {
+    parse_leads(&tournament.schedule_reminders).unwrap_or_default()
+}
+
+/// The reminders a time confirmed at `now` is owed: one per lead, less any
+/// whose moment has already passed — a set agreed for ten minutes from now
+/// gets no "starts in an hour", and the confirmation notice already said when.
+pub(crate) fn plan(at: DateTime<Utc>, leads: &[i64], now: DateTime<Utc>) -> Vec<(i64, DateTime<Utc>)> {
+    leads
+        .iter()
+        .map(|lead| (*lead, at - Duration::minutes(*lead)))
+        .filter(|(_, remind_at)| *remind_at > now)
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScheduleOutcome {
+    /// Written as a proposal, waiting on the other player's confirmation.
+    Proposed {
+        at: DateTime<Utc>,
+    },
+    /// Agreed: the other player confirmed it, or an organizer who is neither
+    /// player set it outright.
+    Scheduled {
+        at: DateTime<Utc>,
+    },
+    /// The pending proposal was declined — or withdrawn, by whoever made it.
+    Declined,
+    /// A finished set has nothing left to schedule.
+    AlreadyComplete,
+    /// A slot is still empty, so there is nobody to agree with yet.
+    NotPlayable,
+    /// Neither of the two players, nor an admin.
+    NotYours,
+    /// Not something `parse_time` reads.
+    Unreadable,
+    InThePast,
+    /// Confirm or decline with no proposal waiting — already answered, or
+    /// replaced after the button was drawn.
+    NothingProposed,
+    /// The proposer pressing confirm on their own proposal.
+    OwnProposal,
+}
+
+impl ScheduleOutcome {
+    pub(crate) fn message(self, locale: Locale) -> String {
+        match self {
+            ScheduleOutcome::Proposed { at } => locale.pick(
+                format!("已提議 {}，等待對手在面板上確認。", timestamp(at)),
+                format!(
+                    "Proposed {} — your opponent confirms it on the set panel.",
+                    timestamp(at)
+                ),
+            ),
+            ScheduleOutcome::Scheduled { at } => locale.pick(
+                format!("比賽時間已確定：{}。", timestamp(at)),
+                format!("Scheduled for {}.", timestamp(at)),
+            ),
+            ScheduleOutcome::Declined => locale.pick(
+                "已取消提議的時間。".to_string(),
+                "The proposed time was declined.".to_string(),
+            ),
+            ScheduleOutcome::AlreadyComplete => locale.pick(
+                "這場對戰已經結束，沒有需要排定的時間。".to_string(),
+                "That set is already finished, so there's nothing to schedule.".to_string(),
+            ),
+            ScheduleOutcome::NotPlayable => locale.pick(
+                "這場對戰還沒有兩位選手，無法排定時間。".to_string(),
+                "That set doesn't have both players yet, so it can't be scheduled.".to_string(),
+            ),
+            ScheduleOutcome::NotYours => locale.pick(
+                "只有這場對戰的選手，或管理員，才能排定時間。".to_string(),
+                "Only the two players in this set, or an admin, can schedule it.".to_string(),
+            ),
+            ScheduleOutcome::Unreadable => locale.pick(
+                "看不懂這個時間 — 請用 `YYYY-MM-DD HH:MM`（UTC+8），或在後面加上時區，例如 \
+                 `2026-08-20 19:30` 或 `2026-08-20 20:30 +09:00`。"
+                    .to_string(),
+                "Couldn't read that time — use `YYYY-MM-DD HH:MM` (UTC+8), optionally followed by \
+                 its offset, e.g. `2026-08-20 19:30` or `2026-08-20 12:30 UTC`."
+                    .to_string(),
+            ),
+            ScheduleOutcome::InThePast => locale.pick(
+                "這個時間已經過了，請提議一個未來的時間。".to_string(),
+                "That time has already passed — propose one in the future.".to_string(),
+            ),
+            ScheduleOutcome::NothingProposed => locale.pick(
+                "目前沒有待確認的時間，可能已被回覆或更改。".to_string(),
+                "There's no proposed time waiting — it may already have been answered or changed.".to_string(),
+            ),
+            ScheduleOutcome::OwnProposal => locale.pick(
+                "這是你自己提議的時間，需要由對手確認。".to_string(),
+                "That's your own proposal — your opponent has to confirm it.".to_string(),
+            ),
+        }
+    }
+}
+
+/// Why scheduling would be refused, if it would be — for all three verbs, in
+/// the order `redraft::refuse` checks the same three things.
+pub(crate) fn refuse(set: &TournamentSet, is_player: bool, is_admin: bool) -> Option<ScheduleOutcome> {
+    if completion::is_decided(&set.status) {
+        return Some(ScheduleOutcome::AlreadyComplete);
+    }
+    if set.slot1_user_id.is_none() || set.slot2_user_id.is_none() {
+        return Some(ScheduleOutcome::NotPlayable);
+    }
+    if !is_player && !is_admin {
+        return Some(ScheduleOutcome::NotYours);
+    }
+    None
+}
+
+fn is_player(set: &TournamentSet, user_id: i64) -> bool {
+    set.slot1_user_id == Some(user_id) || set.slot2_user_id == Some(user_id)
+}
+
+/// `/set schedule`. A player's time is a proposal for the other to confirm; an
+/// organizer who is neither player has nobody to ask and sets it outright.
+///
+/// A new proposal replaces any pending one, and leaves an agreed time standing
+/// until it is itself confirmed — so a rescheduling that is never agreed to
+/// changes nothing.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn propose(
+    http: impl CacheHttp,
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    set: &TournamentSet,
+    actor_user_id: i64,
+    is_admin: bool,
+    at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<ScheduleOutcome, Error> {
+    let is_player = is_player(set, actor_user_id);
+    if let Some(refusal) = refuse(set, is_player, is_admin) {
+        return Ok(refusal);
+    }
+    if at <= now {
+        return Ok(ScheduleOutcome::InThePast);
+    }
+
+    if !is_player {
+        db::set_scheduled_at(pool, set.id, at, &plan(at, &leads(tournament), now)).await?;
+        notify(&http, set, render_scheduled_notice(at), &[]).await;
+        set_thread::refresh_panel(&http, pool, tournament, set.id).await;
+        return Ok(ScheduleOutcome::Scheduled { at });
+    }
+
+    db::propose_schedule(pool, set.id, at, actor_user_id).await?;
+    // Both slots are `Some` — `refuse` already returned `NotPlayable` otherwise.
+    let opponent = if set.slot1_user_id == Some(actor_user_id) {
+        set.slot2_user_id
+    } else {
+        set.slot1_user_id
+    }
+    .unwrap_or_default();
+    notify(
+        &http,
+        set,
+        render_proposal_notice(actor_user_id, opponent, at),
+        &[opponent],
+    )
+    .await;
+    set_thread::refresh_panel(&http, pool, tournament, set.id).await;
+    Ok(ScheduleOutcome::Proposed { at })
+}
+
+/// The panel's confirm button: the other player agreeing to the pending
+/// proposal, or an organizer agreeing on their behalf. Never the proposer,
+/// admin or not — agreeing with yourself is not agreement.
+pub(crate) async fn confirm(
+    http: impl CacheHttp,
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    set: &TournamentSet,
+    actor_user_id: i64,
+    is_admin: bool,
+    now: DateTime<Utc>,
+) -> Result<ScheduleOutcome, Error> {
+    if let Some(refusal) = refuse(set, is_player(set, actor_user_id), is_admin) {
+        return Ok(refusal);
+    }
+    let (Some(at), Some(proposed_by)) = (set.proposed_at, set.proposed_by) else {
+        return Ok(ScheduleOutcome::NothingProposed);
+    };
+    if proposed_by == actor_user_id {
+        return Ok(ScheduleOutcome::OwnProposal);
+    }
+    if at <= now {
+        return Ok(ScheduleOutcome::InThePast);
+    }
+    if !db::confirm_proposal(pool, set.id, at, &plan(at, &leads(tournament), now)).await? {
+        return Ok(ScheduleOutcome::NothingProposed);
+    }
+
+    notify(&http, set, render_scheduled_notice(at), &[proposed_by]).await;
+    set_thread::refresh_panel(&http, pool, tournament, set.id).await;
+    Ok(ScheduleOutcome::Scheduled { at })
+}
+
+/// The panel's decline button. Either player may press it — the proposer
+/// withdrawing their own time is the same act — and so may an organizer.
+pub(crate) async fn decline(
+    http: impl CacheHttp,
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    set: &TournamentSet,
+    actor_user_id: i64,
+    is_admin: bool,
+) -> Result<ScheduleOutcome, Error> {
+    if let Some(refusal) = refuse(set, is_player(set, actor_user_id), is_admin) {
+        return Ok(refusal);
+    }
+    if !db::clear_proposal(pool, set.id).await? {
+        return Ok(ScheduleOutcome::NothingProposed);
+    }
+
+    notify(&http, set, render_declined_notice(actor_user_id), &[]).await;
+    set_thread::refresh_panel(&http, pool, tournament, set.id).await;
+    Ok(ScheduleOutcome::Declined)
+}
+
+fn render_proposal_notice(proposer: i64, opponent: i64, at: DateTime<Utc>) -> String {
+    format!(
+        "🗓️ <@{proposer}> 提議比賽時間：{0}，請 <@{opponent}> 在上方面板確認。\n\
+         🗓️ <@{proposer}> proposed {0} — <@{opponent}>, confirm or decline it on the pinned panel.",
+        timestamp(at)
+    )
+}
+
+fn render_scheduled_notice(at: DateTime<Utc>) -> String {
+    format!(
+        "✅ 比賽時間已確定：{0}\n✅ This set is scheduled for {0}.",
+        timestamp(at)
+    )
+}
+
+fn render_declined_notice(actor_user_id: i64) -> String {
+    format!(
+        "<@{actor_user_id}> 取消了提議的時間。\n\
+         <@{actor_user_id}> declined the proposed time."
+    )
+}
+
+fn render_reminder(slot1: i64, slot2: i64, at: DateTime<Utc>) -> String {
+    format!(
+        "⏰ <@{slot1}> <@{slot2}> 這場對戰將於 <t:{0}:R> 開始（<t:{0}:F>）。\n\
+         ⏰ This set starts <t:{0}:R> — <t:{0}:F>.",
+        at.timestamp()
+    )
+}
+
+/// Posts into the set's thread, pinging only `pings` whatever the text mentions.
+/// Best-effort: the thread is where the answer lives, but the panel redraw and
+/// the caller's own reply both still say what happened.
+async fn notify(http: impl CacheHttp, set: &TournamentSet, content: String, pings: &[i64]) {
+    let Some(thread_id) = set.thread_id else {
+        return;
+    };
+    let mentions = CreateAllowedMentions::new().users(pings.iter().map(|user_id| to_user_id(*user_id)));
+    if let Err(err) = to_channel_id(thread_id)
+        .send_message(&http, CreateMessage::new().content(content).allowed_mentions(mentions))
+        .await
+    {
+        error!("failed to post a schedule notice for set {}: {err:?}", set.id);
+    }
+}
+
+/// What the scheduler does with one due reminder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Due {
+    Post,
+    /// Marked as dealt with, never posted: too late to be any use.
+    Skip,
+}
+
+/// Which due reminders to post. Only ever more than one per set after the bot
+/// was down across several leads, and then only the nearest is posted — an
+/// hour's warning ten minutes out says less than the ten-minute one. Nothing is
+/// posted for a set already decided, or whose time has come and gone.
+pub(crate) fn triage(due: &[DueSetReminder], now: DateTime<Utc>) -> Vec<Due> {
+    due.iter()
+        .map(|reminder| {
+            let superseded = due
+                .iter()
+                .any(|other| other.set_id == reminder.set_id && other.lead_minutes < reminder.lead_minutes);
+            let live = !completion::is_decided(&reminder.status)
+                && reminder.thread_id.is_some()
+                && reminder.scheduled_at.is_some_and(|at| at > now);
+            if live && !superseded { Due::Post } else { Due::Skip }
+        })
+        .collect()
+}
+
+/// The scheduler's tick. Logs rather than fails, like `reminder::run`: one set
+/// that cannot be reminded must not stop the rest.
+///
+/// A reminder whose post failed stays unmarked and is tried again on the next
+/// tick, until its set's time passes and `triage` skips it.
+pub(crate) async fn run(http: &Http, data: &Data) {
+    let pool = &data.database;
+    let now = Utc::now();
+    let due = match db::list_due_set_reminders(pool, now).await {
+        Ok(due) => due,
+        Err(err) => {
+            error!("failed to list due set reminders: {err:?}");
+            return;
+        },
+    };
+
+    let mut posted = 0;
+    for (reminder, verdict) in due.iter().zip(triage(&due, now)) {
+        if verdict == Due::Post {
+            if let Err(err) = post_reminder(http, reminder).await {
+                error!(
+                    "failed to post the {}-minute reminder for set {}: {err:?}",
+                    reminder.lead_minutes, reminder.set_id
+                );
+                continue;
+            }
+            posted += 1;
+        }
+        if let Err(err) = db::mark_set_reminder_posted(pool, reminder.set_id, reminder.lead_minutes).await {
+            error!(
+                "failed to mark the {}-minute reminder for set {}: {err:?}",
+                reminder.lead_minutes, reminder.set_id
+            );
+        }
+    }
+    if posted > 0 {
+        info!("posted {posted} set reminders");
+    }
+}
+
+async fn post_reminder(http: &Http, reminder: &DueSetReminder) -> Result<(), Error> {
+    // `triage` only posts a reminder whose set has a thread, both players and a time.
+    let (Some(thread_id), Some(slot1), Some(slot2), Some(at)) = (
+        reminder.thread_id,
+        reminder.slot1_user_id,
+        reminder.slot2_user_id,
+        reminder.scheduled_at,
+    ) else {
+        return Ok(());
+    };
+    let mentions = CreateAllowedMentions::new().users([to_user_id(slot1), to_user_id(slot2)]);
+    to_channel_id(thread_id)
+        .send_message(
+            http,
+            CreateMessage::new()
+                .content(render_reminder(slot1, slot2, at))
+                .allowed_mentions(mentions),
+        )
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, TimeZone, Timelike};
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn a_bare_wall_time_is_read_as_utc_plus_eight() {
+        assert_eq!(parse_time("2026-08-20 19:30"), Some(utc(2026, 8, 20, 11, 30)));
+        assert_eq!(parse_time("2026-08-20 19:30"), parse_local("2026-08-20 19:30"));
+    }
+
+    #[test]
+    fn an_explicit_zone_overrides_the_local_default() {
+        for (input, expected) in [
+            ("2026-08-20 12:30 UTC", utc(2026, 8, 20, 12, 30)),
+            ("2026-08-20 12:30 Z", utc(2026, 8, 20, 12, 30)),
+            ("2026-08-20 21:30 +09:00", utc(2026, 8, 20, 12, 30)),
+            ("2026-08-20 21:30 UTC+9", utc(2026, 8, 20, 12, 30)),
+            ("2026-08-20 07:30 utc-5", utc(2026, 8, 20, 12, 30)),
+            ("2026-08-20 18:00 +0530", utc(2026, 8, 20, 12, 30)),
+        ] {
+            assert_eq!(parse_time(input), Some(expected), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn a_pasted_discord_timestamp_is_taken_as_is() {
+        let at = utc(2026, 8, 20, 12, 30);
+        assert_eq!(parse_time(&format!("<t:{}>", at.timestamp())), Some(at));
+        assert_eq!(parse_time(&format!(" <t:{}:F> ", at.timestamp())), Some(at));
+    }
+
+    #[test]
+    fn malformed_times_and_zones_are_rejected_rather_than_guessed() {
+        for input in [
+            "",
+            "tomorrow",
+            "2026-08-20",
+            "19:30",
+            "2026-08-20 25:00",
+            "2026-08-20 19:30 PST",
+            "2026-08-20 19:30 +15",
+            "2026-08-20 19:30 +09:00 extra",
+            "<t:abc>",
+            "<t:1755689400:FF>",
+        ] {
+            assert_eq!(parse_time(input), None, "{input:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn a_wall_time_before_the_offset_rolls_back_a_day_in_utc() {
+        let parsed = parse_local("2026-08-20 07:00").unwrap();
+        assert_eq!((parsed.day(), parsed.hour(), parsed.minute()), (19, 23, 0));
+    }
+
+    #[test]
+    fn leads_are_normalized_largest_first_without_repeats() {
+        assert_eq!(parse_leads("10, 60"), Some(vec![60, 10]));
+        assert_eq!(parse_leads("60 10 60"), Some(vec![60, 10]));
+        assert_eq!(parse_leads("1440"), Some(vec![1440]));
+        assert_eq!(format_leads(&parse_leads("10,1440,60").unwrap()), "1440,60,10");
+    }
+
+    #[test]
+    fn off_means_no_reminders_rather_than_an_error() {
+        assert_eq!(parse_leads("off"), Some(vec![]));
+        assert_eq!(parse_leads("None"), Some(vec![]));
+        assert_eq!(format_leads(&[]), "");
+    }
+
+    #[test]
+    fn leads_that_are_not_a_sane_number_of_minutes_are_refused() {
+        for input in ["0", "-10", "ten", "60,,10x", "20000", "1,2,3,4,5,6"] {
+            assert_eq!(parse_leads(input), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn a_reminder_whose_moment_has_passed_is_not_planned() {
+        let at = utc(2026, 8, 20, 12, 0);
+        let now = utc(2026, 8, 20, 11, 30);
+        assert_eq!(plan(at, &[60, 10], now), vec![(10, utc(2026, 8, 20, 11, 50))]);
+        assert_eq!(
+            plan(at, &[60, 10], utc(2026, 8, 19, 0, 0)),
+            vec![(60, utc(2026, 8, 20, 11, 0)), (10, utc(2026, 8, 20, 11, 50))]
+        );
+    }
+
+    fn set(status: &str, slot2: Option<i64>) -> TournamentSet {
+        TournamentSet {
+            id: 1,
+            tournament_id: 1,
+            round_id: 1,
+            position: 1,
+            slot1_user_id: Some(7),
+            slot2_user_id: slot2,
+            slot1_wins: 0,
+            slot2_wins: 0,
+            winner_user_id: None,
+            status: status.to_string(),
+            draft_external_id: None,
+            draft_synced_at: None,
+            draft_announce_message_id: None,
+            redraft_count: 0,
+            thread_id: Some(100),
+            panel_message_id: None,
+            winner_advances_to_set_id: None,
+            winner_advances_to_slot: None,
+            loser_advances_to_set_id: None,
+            loser_advances_to_slot: None,
+            scheduled_at: None,
+            completed_at: None,
+            proposed_at: None,
+            proposed_by: None,
+        }
+    }
+
+    #[test]
+    fn refusals_come_in_the_order_a_caller_hits_them() {
+        assert_eq!(
+            refuse(&set("completed", Some(9)), false, false),
+            Some(ScheduleOutcome::AlreadyComplete),
+            "a finished set first, whoever asks"
+        );
+        assert_eq!(
+            refuse(&set("pending", None), false, false),
+            Some(ScheduleOutcome::NotPlayable)
+        );
+        assert_eq!(
+            refuse(&set("ready", Some(9)), false, false),
+            Some(ScheduleOutcome::NotYours)
+        );
+        assert_eq!(refuse(&set("ready", Some(9)), true, false), None);
+        assert_eq!(refuse(&set("ready", Some(9)), false, true), None);
+    }
+
+    fn due(set_id: i64, lead_minutes: i64, status: &str, scheduled_at: DateTime<Utc>) -> DueSetReminder {
+        DueSetReminder {
+            set_id,
+            lead_minutes,
+            remind_at: scheduled_at - Duration::minutes(lead_minutes),
+            tournament_id: 1,
+            status: status.to_string(),
+            thread_id: Some(100 + set_id),
+            slot1_user_id: Some(7),
+            slot2_user_id: Some(9),
+            scheduled_at: Some(scheduled_at),
+        }
+    }
+
+    #[test]
+    fn a_due_reminder_for_a_set_still_to_play_is_posted() {
+        let at = utc(2026, 8, 20, 12, 0);
+        let now = utc(2026, 8, 20, 11, 0);
+        assert_eq!(triage(&[due(1, 60, "ready", at)], now), vec![Due::Post]);
+    }
+
+    #[test]
+    fn after_downtime_only_the_nearest_of_several_due_reminders_is_posted() {
+        // Down from before the hour's warning until after the ten-minute one:
+        // both are due on the first tick back, and one ping says it all.
+        let at = utc(2026, 8, 20, 12, 0);
+        let now = utc(2026, 8, 20, 11, 55);
+        assert_eq!(
+            triage(&[due(1, 10, "ready", at), due(1, 60, "ready", at)], now),
+            vec![Due::Post, Due::Skip]
+        );
+    }
+
+    #[test]
+    fn reminders_for_other_sets_do_not_supersede_each_other() {
+        let at = utc(2026, 8, 20, 12, 0);
+        let now = utc(2026, 8, 20, 11, 55);
+        assert_eq!(
+            triage(&[due(1, 10, "ready", at), due(2, 60, "ready", at)], now),
+            vec![Due::Post, Due::Post]
+        );
+    }
+
+    #[test]
+    fn a_reminder_is_skipped_once_its_set_is_decided_or_its_time_has_passed() {
+        let at = utc(2026, 8, 20, 12, 0);
+        assert_eq!(
+            triage(&[due(1, 10, "completed", at)], utc(2026, 8, 20, 11, 55)),
+            vec![Due::Skip]
+        );
+        assert_eq!(
+            triage(&[due(1, 10, "walkover", at)], utc(2026, 8, 20, 11, 55)),
+            vec![Due::Skip]
+        );
+        assert_eq!(
+            triage(&[due(1, 10, "ready", at)], utc(2026, 8, 20, 12, 5)),
+            vec![Due::Skip],
+            "back up after the set was meant to start: too late to remind"
+        );
+    }
+
+    #[test]
+    fn a_reminder_pings_both_players_with_a_relative_and_an_absolute_time() {
+        let at = utc(2026, 8, 20, 12, 0);
+        let content = render_reminder(7, 9, at);
+        assert!(content.contains("<@7>") && content.contains("<@9>"), "{content}");
+        assert!(content.contains(&format!("<t:{}:R>", at.timestamp())), "{content}");
+        assert!(content.contains(&format!("<t:{}:F>", at.timestamp())), "{content}");
+    }
+
+    #[test]
+    fn a_proposal_notice_names_the_opponent_who_has_to_answer_it() {
+        let content = render_proposal_notice(7, 9, utc(2026, 8, 20, 12, 0));
+        assert!(content.contains("<@9>, confirm or decline"), "{content}");
+    }
+}
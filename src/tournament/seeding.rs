@@ -276,6 +276,7 @@ pub(crate) async fn refresh_ratings(
     for entry in &field {
         let elo = aoe4world::fetch_profile(entry.aoe4_id)
             .await
+            .ok()
             .and_then(|p| p.modes.rm_1v1_elo.map(|e| i64::from(e.rating)));
         let atr = atr_by_id.get(&entry.aoe4_id).copied();
         if atr.is_some() {
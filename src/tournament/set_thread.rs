@@ -15,10 +15,11 @@ use crate::db::{to_channel_id, to_message_id, to_user_id};
 use crate::drafttool::{self, DraftError};
 use crate::ranked::escape;
 use crate::tournament::action::Action;
-use crate::tournament::bracket;
-use crate::tournament::completion::{Settlement, Tally};
+use crate::tournament::completion::{self, Settlement, Tally};
 use crate::tournament::db::{self, Tournament, TournamentRound, TournamentSet};
 use crate::tournament::render;
+use crate::tournament::{bracket, groups, schedule};
+use chrono::{DateTime, Utc};
 use serenity::all::{
     ButtonStyle, CacheHttp, ChannelType, CreateActionRow, CreateAllowedMentions, CreateButton, CreateMessage,
     CreateThread, EditMessage, EditThread,
@@ -46,7 +47,14 @@ pub(crate) fn third_place_thread_name(position: i64, one: &str, two: &str) -> St
     prefixed_thread_name(&format!("3rd Place M{position}"), one, two)
 }
 
-/// The width-fitting and length-limiting both thread-name functions share.
+/// `Losers Round 2 M3 · MarineLorD vs Beasty` — a double-elimination round by
+/// name: its ordinal counts on from the winners bracket, so `R5M3` would name
+/// the losers bracket's first round as if it were the fifth of one path.
+pub(crate) fn named_round_thread_name(round_name: &str, position: i64, one: &str, two: &str) -> String {
+    prefixed_thread_name(&format!("{round_name} M{position}"), one, two)
+}
+
+/// The width-fitting and length-limiting the thread-name functions share.
 fn prefixed_thread_name(prefix: &str, one: &str, two: &str) -> String {
     let name = format!(
         "{prefix} · {} vs {}",
@@ -65,11 +73,15 @@ fn prefixed_thread_name(prefix: &str, one: &str, two: &str) -> String {
 /// `room` is `None` before a draft has been created for this set — creation is
 /// deliberately deferred to the first press of the button below, not minted
 /// eagerly when the thread opens.
+///
+/// A pending proposal adds a row of its own to either state, so confirming a
+/// time never waits on the draft and the draft never waits on a time.
 pub(crate) fn render_panel(
     set: &SetHeading,
     one: &Player,
     two: &Player,
     room: Option<&Room>,
+    schedule: &Schedule,
 ) -> (String, Vec<CreateActionRow>) {
     // Names are player-editable aoe4world strings, so they are escaped where they sit
     // in markdown and `sanitize`d where they sit inside a code span — a backtick
@@ -86,24 +98,27 @@ pub(crate) fn render_panel(
         two.seed,
         escape(&two.name)
     );
+    let header = format!("{header}{}", schedule_lines(schedule));
 
     let Some(room) = room else {
+        // Call-admin here too: nothing has failed yet, so there is nobody to
+        // name outright, but a player stuck on this state still needs a way
+        // to reach one.
+        let mut components = vec![CreateActionRow::Buttons(vec![
+            CreateButton::new(Action::Redraft.custom_id(set.id))
+                .label("➕ 建立 Draft / Create draft")
+                .style(ButtonStyle::Primary),
+            CreateButton::new(Action::CallAdmin.custom_id(set.id))
+                .label("呼叫管理員 / Call an organizer")
+                .style(ButtonStyle::Secondary),
+        ])];
+        components.extend(schedule_row(set.id, schedule));
         return (
             format!(
                 "{header}\nDraft 房間尚未建立，請按下方按鈕建立。\n\
                  **No draft room yet — press the button below to create one.**\n"
             ),
-            // Call-admin here too: nothing has failed yet, so there is nobody to
-            // name outright, but a player stuck on this state still needs a way
-            // to reach one.
-            vec![CreateActionRow::Buttons(vec![
-                CreateButton::new(Action::Redraft.custom_id(set.id))
-                    .label("➕ 建立 Draft / Create draft")
-                    .style(ButtonStyle::Primary),
-                CreateButton::new(Action::CallAdmin.custom_id(set.id))
-                    .label("呼叫管理員 / Call an organizer")
-                    .style(ButtonStyle::Secondary),
-            ])],
+            components,
         );
     };
 
@@ -132,7 +147,7 @@ pub(crate) fn render_panel(
     // nothing on this panel names an organizer. Set complete is safe to press
     // early — it syncs and reports "still in progress" rather than acting on
     // an undecided draft — so it carries no confirmation of its own.
-    let components = vec![CreateActionRow::Buttons(vec![
+    let mut components = vec![CreateActionRow::Buttons(vec![
         CreateButton::new_link(&room.watch_url).label("觀戰 / Watch draft"),
         CreateButton::new(Action::Redraft.custom_id(set.id))
             .label("🔄 重新產生 Draft / Regenerate draft")
@@ -144,10 +159,48 @@ pub(crate) fn render_panel(
             .label("✅ 回報完成 / Set complete")
             .style(ButtonStyle::Success),
     ])];
+    components.extend(schedule_row(set.id, schedule));
 
     (body, components)
 }
 
+/// The panel's schedule line: the agreed time, then any proposal still waiting
+/// on the other player, both as Discord timestamps so every reader sees them in
+/// their own zone. With neither, a pointer to `/set schedule` — nothing else on
+/// the panel mentions it.
+fn schedule_lines(schedule: &Schedule) -> String {
+    let mut lines = String::new();
+    if let Some(at) = schedule.agreed {
+        lines.push_str(&format!("🗓️ 比賽時間 / Scheduled: {}\n", schedule::timestamp(at)));
+    }
+    if let Some((at, by)) = schedule.proposal {
+        lines.push_str(&format!(
+            "🗓️ <@{by}> 提議 / proposed: {} — 等待對手確認 / awaiting confirmation\n",
+            schedule::timestamp(at)
+        ));
+    }
+    if lines.is_empty() {
+        lines.push_str(
+            "🗓️ 尚未排定比賽時間，可用 `/set schedule` 提議。\n\
+             No time set yet — propose one with `/set schedule`.\n",
+        );
+    }
+    lines
+}
+
+/// Confirm and decline, only while a proposal is waiting.
+fn schedule_row(set_id: i64, schedule: &Schedule) -> Option<CreateActionRow> {
+    schedule.proposal?;
+    Some(CreateActionRow::Buttons(vec![
+        CreateButton::new(Action::ScheduleConfirm.custom_id(set_id))
+            .label("🗓️ 確認時間 / Confirm time")
+            .style(ButtonStyle::Success),
+        CreateButton::new(Action::ScheduleDecline.custom_id(set_id))
+            .label("拒絕 / Decline")
+            .style(ButtonStyle::Danger),
+    ]))
+}
+
 /// A player's in-game name, as the seat instruction addresses them by it.
 ///
 /// An invited entrant's name is whatever the organizer typed, and this line is
@@ -357,6 +410,10 @@ pub(crate) struct SetHeading {
     /// logic, which stays structural): it decides whether the result reads as an
     /// "advance" or a podium finish.
     pub is_third_place: bool,
+    /// Whether the loser drops to another set rather than out of the event —
+    /// double elimination before their second loss. Only known once the set is
+    /// decided, so false everywhere but `close`.
+    pub loser_plays_on: bool,
 }
 
 /// A set's occupant, as the panel needs them.
@@ -370,6 +427,23 @@ pub(crate) struct Player {
     pub verified: bool,
 }
 
+/// When the set is to be played, as the panel shows it: the time both sides
+/// agreed to, and a proposal still waiting on the other player with who made it.
+#[derive(Default)]
+pub(crate) struct Schedule {
+    pub agreed: Option<DateTime<Utc>>,
+    pub proposal: Option<(DateTime<Utc>, i64)>,
+}
+
+impl Schedule {
+    pub(crate) fn of(set: &TournamentSet) -> Self {
+        Self {
+            agreed: set.scheduled_at,
+            proposal: set.proposed_at.zip(set.proposed_by),
+        }
+    }
+}
+
 /// The two links a draft room has: one to play in, one to watch.
 pub(crate) struct Room {
     pub match_url: String,
@@ -420,6 +494,9 @@ pub(crate) async fn open(
     let channel_id = to_channel_id(matches_channel_id);
     let name = if is_third_place {
         third_place_thread_name(set.position, &one.name, &two.name)
+    } else if bracket::Side::of_round_name(&round.name).is_some() || round.name.starts_with(groups::ROUND_PREFIX) {
+        // A group round too, so `R1M1` stays the playoff's own first set.
+        named_round_thread_name(&round.name, set.position, &one.name, &two.name)
     } else {
         thread_name(round.ordinal, set.position, &one.name, &two.name)
     };
@@ -442,8 +519,9 @@ pub(crate) async fn open(
         position: set.position,
         best_of: round.best_of,
         is_third_place,
+        loser_plays_on: false,
     };
-    let (content, components) = render_panel(&heading, &one, &two, None);
+    let (content, components) = render_panel(&heading, &one, &two, None, &Schedule::of(set));
     let message = thread
         .id
         .send_message(&http, CreateMessage::new().content(content).components(components))
@@ -489,6 +567,69 @@ pub(crate) async fn open_ready(http: &impl CacheHttp, pool: &SqlitePool, tournam
     }
 }
 
+/// Redraws the pinned panel in place from the set as stored, for a change that
+/// leaves its room alone — a proposed or confirmed time. Re-read rather than
+/// passed in, so the panel shows what was written, not the snapshot the caller
+/// acted on.
+///
+/// Best-effort like `open_ready`: a panel that failed to redraw still has the
+/// notice in the thread beneath it. A decided set is left alone — `close`
+/// already drew its final panel, with nothing left to press.
+pub(crate) async fn refresh_panel(http: impl CacheHttp, pool: &SqlitePool, tournament: &Tournament, set_id: i64) {
+    if let Err(err) = redraw_panel(http, pool, tournament, set_id).await {
+        error!("failed to redraw the panel for set {set_id}: {err:?}");
+    }
+}
+
+async fn redraw_panel(
+    http: impl CacheHttp,
+    pool: &SqlitePool,
+    tournament: &Tournament,
+    set_id: i64,
+) -> Result<(), Error> {
+    let Some(set) = db::get_set(pool, set_id).await? else {
+        return Ok(());
+    };
+    let (Some(thread_id), Some(panel_id), Some(slot1), Some(slot2)) = (
+        set.thread_id,
+        set.panel_message_id,
+        set.slot1_user_id,
+        set.slot2_user_id,
+    ) else {
+        return Ok(());
+    };
+    if completion::is_decided(&set.status) {
+        return Ok(());
+    }
+    let Some(round) = db::get_round(pool, set.round_id).await? else {
+        return Ok(());
+    };
+
+    let one = player(pool, tournament.id, slot1).await?;
+    let two = player(pool, tournament.id, slot2).await?;
+    let heading = SetHeading {
+        id: set.id,
+        round_name: round.name.clone(),
+        position: set.position,
+        best_of: round.best_of,
+        is_third_place: round.name == bracket::THIRD_PLACE,
+        loser_plays_on: false,
+    };
+    let room = set
+        .draft_external_id
+        .as_deref()
+        .map(|draft_id| Room::for_draft(tournament, draft_id));
+    let (content, components) = render_panel(&heading, &one, &two, room.as_ref(), &Schedule::of(&set));
+    to_channel_id(thread_id)
+        .edit_message(
+            &http,
+            to_message_id(panel_id),
+            EditMessage::new().content(content).components(components),
+        )
+        .await?;
+    Ok(())
+}
+
 /// The result line posted into the thread when a set is decided.
 ///
 /// Pure, and bilingual for the same reason the panel is: the thread's readers are
@@ -508,7 +649,13 @@ pub(crate) fn render_result(
     // players know it was not played out, and the record should agree with them.
     // A 3rd place win reads as a podium finish, never as "advancing" — there is
     // nothing after it to advance into.
+    // A loser who drops to another set isn't thanked for playing — they still are.
     let verdict = match (settlement, set.is_third_place) {
+        (Settlement::Played, false) if set.loser_plays_on => format!(
+            "**{winner_name}** (#{}) 獲勝，晉級下一輪。 / **{winner_name}** (#{}) wins and advances.\n\
+             **{loser_name}** (#{}) 尚未淘汰，將繼續出賽。 / **{loser_name}** (#{}) isn't out yet and plays on.",
+            winner.seed, winner.seed, loser.seed, loser.seed
+        ),
         (Settlement::Played, false) => format!(
             "**{winner_name}** (#{}) 獲勝，晉級下一輪。 / **{winner_name}** (#{}) wins and advances.\n\
              感謝 **{loser_name}** (#{}) 的參賽。 / Thanks for playing, **{loser_name}** (#{}).",
@@ -519,6 +666,11 @@ pub(crate) fn render_result(
              感謝 **{loser_name}** (#{}) 的參賽。 / Thanks for playing, **{loser_name}** (#{}).",
             winner.seed, winner.seed, loser.seed, loser.seed
         ),
+        (Settlement::Walkover, false) if set.loser_plays_on => format!(
+            "由管理員判給 **{winner_name}** (#{}) 晉級，**{loser_name}** 未完賽，尚未淘汰，將繼續出賽。 / \
+             Awarded to **{winner_name}** (#{}) — **{loser_name}** didn't play it out, and isn't out yet.",
+            winner.seed, winner.seed
+        ),
         (Settlement::Walkover, false) => format!(
             "由管理員判給 **{winner_name}** (#{}) 晉級，**{loser_name}** 未完賽。 / \
              Awarded to **{winner_name}** (#{}) — **{loser_name}** didn't play it out.",
@@ -553,6 +705,7 @@ pub(crate) async fn close(
     loser: &Player,
     tally: &Tally,
     settlement: Settlement,
+    loser_eliminated: bool,
 ) {
     let Some(thread_id) = set.thread_id else {
         return; // a set decided before its thread ever opened
@@ -569,6 +722,7 @@ pub(crate) async fn close(
         position: set.position,
         best_of: round.best_of,
         is_third_place: round.name == bracket::THIRD_PLACE,
+        loser_plays_on: !loser_eliminated,
     };
 
     // Posted before the lock, not after: a locked thread is a bad place to try to
@@ -792,6 +946,7 @@ mod tests {
             position,
             best_of,
             is_third_place: round_name == bracket::THIRD_PLACE,
+            loser_plays_on: false,
         }
     }
 
@@ -864,6 +1019,7 @@ mod tests {
             &player(7, 1, "MarineLorD"),
             &player(9, 8, "Beasty"),
             Some(&room()),
+            &Schedule::default(),
         );
         // One ping each, in the header — the seat lines address them by
         // in-game name instead of mentioning them again.
@@ -880,6 +1036,7 @@ mod tests {
             &player(7, 1, "MarineLorD"),
             &player(9, 8, "Beasty"),
             Some(&room()),
+            &Schedule::default(),
         );
         assert!(content.contains("`Beasty` takes seat Player 2"), "{content}");
         assert!(!content.to_lowercase().contains("unverified"), "{content}");
@@ -895,6 +1052,7 @@ mod tests {
             &player(7, 1, "MarineLorD"),
             &invitee(9, 8, "Beasty"),
             Some(&room()),
+            &Schedule::default(),
         );
         assert!(
             content.contains("`Beasty` (name unverified) takes seat Player 2"),
@@ -913,6 +1071,7 @@ mod tests {
             &player(7, 1, "A"),
             &player(9, 2, "B"),
             Some(&room()),
+            &Schedule::default(),
         );
         // `/watch/` cannot claim a seat, so the body must carry `/match/`.
         assert!(content.contains("/match/65f1"), "{content}");
@@ -930,6 +1089,7 @@ mod tests {
             &player(7, 1, "A"),
             &player(9, 4, "B"),
             Some(&room()),
+            &Schedule::default(),
         );
         assert!(content.contains("Semifinal · Match 2 — Bo5"), "{content}");
     }
@@ -943,6 +1103,7 @@ mod tests {
             &player(7, 1, "MarineLorD"),
             &player(9, 8, "Beasty"),
             Some(&room()),
+            &Schedule::default(),
         );
         assert!(
             content.contains("`MarineLorD` takes seat Player 1** and hosts the lobby in game"),
@@ -964,8 +1125,14 @@ mod tests {
         // call-admin is on both states — set-done is the one thing that makes no
         // sense before there is a draft to have played.
         let set = heading(77, "Round 1", 1, 3);
-        let (_, with_room) = render_panel(&set, &player(7, 1, "A"), &player(9, 8, "B"), Some(&room()));
-        let (_, without) = render_panel(&set, &player(7, 1, "A"), &player(9, 8, "B"), None);
+        let (_, with_room) = render_panel(
+            &set,
+            &player(7, 1, "A"),
+            &player(9, 8, "B"),
+            Some(&room()),
+            &Schedule::default(),
+        );
+        let (_, without) = render_panel(&set, &player(7, 1, "A"), &player(9, 8, "B"), None, &Schedule::default());
 
         assert!(labels(&with_room).contains("calladmin:77"), "{}", labels(&with_room));
         assert!(labels(&with_room).contains("Watch draft"), "{}", labels(&with_room));
@@ -989,6 +1156,7 @@ mod tests {
             &player(7, 1, "A"),
             &player(9, 8, "B"),
             None,
+            &Schedule::default(),
         );
         assert!(content.contains("No draft room yet"), "{content}");
         assert!(!content.contains("could not be created"), "{content}");
@@ -997,6 +1165,58 @@ mod tests {
         assert!(labels(&components).contains("redraft:1"), "{components:?}");
     }
 
+    #[test]
+    fn an_agreed_time_shows_as_a_discord_timestamp_with_nothing_left_to_press() {
+        let at = DateTime::from_timestamp(1_787_400_000, 0).unwrap();
+        let schedule = Schedule {
+            agreed: Some(at),
+            proposal: None,
+        };
+        let (content, components) = render_panel(
+            &heading(1, "Round 1", 1, 3),
+            &player(7, 1, "A"),
+            &player(9, 8, "B"),
+            Some(&room()),
+            &schedule,
+        );
+        assert!(content.contains("<t:1787400000:F> (<t:1787400000:R>)"), "{content}");
+        assert!(!content.contains("/set schedule"), "{content}");
+        assert!(!labels(&components).contains("schedconfirm"), "{components:?}");
+    }
+
+    #[test]
+    fn a_pending_proposal_adds_its_own_row_to_either_panel_state() {
+        let schedule = Schedule {
+            agreed: None,
+            proposal: Some((DateTime::from_timestamp(1_787_400_000, 0).unwrap(), 9)),
+        };
+        for room in [Some(room()), None] {
+            let (content, components) = render_panel(
+                &heading(77, "Round 1", 1, 3),
+                &player(7, 1, "A"),
+                &player(9, 8, "B"),
+                room.as_ref(),
+                &schedule,
+            );
+            assert!(content.contains("proposed: <t:1787400000:F>"), "{content}");
+            assert_eq!(components.len(), 2, "the proposal's row comes after the panel's own");
+            assert!(labels(&components[1..]).contains("schedconfirm:77"), "{components:?}");
+            assert!(labels(&components[1..]).contains("scheddecline:77"), "{components:?}");
+        }
+    }
+
+    #[test]
+    fn an_unscheduled_set_points_at_the_command_that_schedules_it() {
+        let (content, _) = render_panel(
+            &heading(1, "Round 1", 1, 3),
+            &player(7, 1, "A"),
+            &player(9, 8, "B"),
+            None,
+            &Schedule::default(),
+        );
+        assert!(content.contains("`/set schedule`"), "{content}");
+    }
+
     // The public draft-channel post.
 
     #[test]
@@ -1116,6 +1336,7 @@ mod tests {
             &player(7, 1, "a`b"),
             &player(9, 8, "B"),
             Some(&room()),
+            &Schedule::default(),
         );
         assert!(content.contains("`a'b` takes seat Player 1"), "{content}");
         assert!(!content.contains("a`b"), "no raw backtick survives: {content}");
@@ -1128,6 +1349,7 @@ mod tests {
             &player(7, 1, "*Bea*sty_"),
             &player(9, 8, "B"),
             Some(&room()),
+            &Schedule::default(),
         );
         assert!(content.contains(r"\*Bea\*sty\_"), "{content}");
     }
@@ -1160,6 +1382,34 @@ mod tests {
         assert!(content.contains("Match 3"), "{content}");
     }
 
+    #[test]
+    fn a_loser_who_drops_a_bracket_is_told_they_play_on_rather_than_thanked() {
+        let heading = SetHeading {
+            loser_plays_on: true,
+            ..heading(1, "Winners Semifinal", 1, 3)
+        };
+        let content = render_result(
+            &heading,
+            &player(7, 1, "MarineLorD"),
+            &player(9, 4, "Beasty"),
+            &tally(2, 0),
+            Settlement::Played,
+        );
+        assert!(
+            content.contains("plays on") && content.contains("尚未淘汰"),
+            "{content}"
+        );
+        assert!(!content.contains("Thanks for playing"), "{content}");
+    }
+
+    #[test]
+    fn a_double_elimination_thread_is_named_by_its_round_not_its_ordinal() {
+        assert_eq!(
+            named_round_thread_name("Losers Round 2", 3, "MarineLorD", "Beasty"),
+            "Losers Round 2 M3 · MarineLorD vs Beasty"
+        );
+    }
+
     #[test]
     fn an_awarded_set_says_so_in_the_thread_it_closes() {
         let awarded = render_result(
@@ -92,6 +92,13 @@ pub(crate) fn presets_per_round(assignments: &[RoundPreset], round_count: usize)
         .collect()
 }
 
+/// The assignment covering every round. What a group stage's rounds run: they
+/// sit before the playoff rather than at any depth from its final, so an
+/// assignment scoped to the final is the playoff's alone.
+pub(crate) fn default_preset(assignments: &[RoundPreset]) -> Option<&RoundPreset> {
+    assignments.iter().find(|a| a.from_depth == DEFAULT_DEPTH)
+}
+
 /// Which depths a newly assigned preset is written to, default first.
 ///
 /// A scoped assignment made while no default exists becomes the default as well, so
@@ -423,6 +430,15 @@ mod tests {
             scheduled_start_at: scheduled.then(Utc::now),
             seed_source: "suggested".to_string(),
             registration_mode: "open".to_string(),
+            format: "single_elim".to_string(),
+            grand_final_reset: false,
+            swiss_rounds: None,
+            group_count: None,
+            group_advance: None,
+            playoff_format: "single_elim".to_string(),
+            schedule_reminders: "60,10".to_string(),
+            autopilot: false,
+            checkin_reminded_at: None,
             created_by: 1,
             created_at: Utc::now(),
             started_at: None,
@@ -5,8 +5,8 @@
 //! refusal is testable without touching a database or Discord.
 
 use crate::locale::Locale;
-use crate::tournament::db::{self, Tournament, TournamentEntry};
-use crate::tournament::{bracket, seeding, setup};
+use crate::tournament::db::{self, RoundPreset, Tournament, TournamentEntry};
+use crate::tournament::{bracket, groups, seeding, setup, swiss};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
@@ -37,12 +37,31 @@ pub(crate) fn seeded_field(entries: &[TournamentEntry]) -> Option<Vec<&Tournamen
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum StartOutcome {
-    Started { entrants: usize, rounds: usize },
-    NotSeeding { current_status: String },
+    Started {
+        entrants: usize,
+        rounds: usize,
+    },
+    NotSeeding {
+        current_status: String,
+    },
     NotConfigured,
     SeedsNotContiguous,
     TooFewEntrants,
-    TooEarly { scheduled_start_at: DateTime<Utc> },
+    TooEarly {
+        scheduled_start_at: DateTime<Utc>,
+    },
+    /// More Swiss rounds than the field can play without a rematch.
+    TooManySwissRounds {
+        rounds: usize,
+        max: usize,
+    },
+    /// A group plan the field cannot fill: a group of one, or more advancing from
+    /// a group than it holds.
+    GroupsDontFit {
+        entrants: usize,
+        groups: usize,
+        advance: usize,
+    },
 }
 
 impl StartOutcome {
@@ -76,6 +95,31 @@ impl StartOutcome {
                 "至少要有兩位已簽到的參賽者才能開賽。".to_string(),
                 "A bracket needs at least two checked-in entrants.".to_string(),
             ),
+            StartOutcome::TooManySwissRounds { rounds, max } => locale.pick(
+                format!(
+                    "瑞士制設定了 {rounds} 輪，但這個人數最多只能打 {max} 輪而不重複對手 — \
+                     請用 `/tournament setup swiss_rounds:` 調整。"
+                ),
+                format!(
+                    "The Swiss stage is set to {rounds} rounds, but this field can only play {max} without a \
+                     rematch — change it with `/tournament setup swiss_rounds:`."
+                ),
+            ),
+            StartOutcome::GroupsDontFit {
+                entrants,
+                groups,
+                advance,
+            } => locale.pick(
+                format!(
+                    "{entrants} 位參賽者分不成 {groups} 組、每組 {advance} 人晉級 — 每組至少要有兩人，且晉級人數\
+                     不能超過組內人數。請用 `/tournament setup groups:` 或 `advance:` 調整。"
+                ),
+                format!(
+                    "{entrants} entrants can't make {groups} groups with {advance} advancing from each — every \
+                     group needs at least two players, and no more advancing than it holds. Change it with \
+                     `/tournament setup groups:` or `advance:`."
+                ),
+            ),
             StartOutcome::TooEarly { scheduled_start_at } => locale.pick(
                 format!(
                     "預定開賽時間是 <t:{0}:F>（<t:{0}:R>）。如果時間不對，請用 `/tournament setup start_time:` 更新。",
@@ -122,38 +166,84 @@ pub(crate) async fn start(pool: &sqlx::SqlitePool, tournament: &Tournament) -> R
         return Ok(StartOutcome::TooFewEntrants);
     }
 
-    let round_count = bracket::round_count(bracket::size(field.len()));
-    // One resolution, two shapes taken off it: the series lengths the bracket is
-    // built from, and the preset ids its rounds record.
-    let Some(mut per_round) = setup::presets_per_round(&presets, round_count) else {
-        return Ok(StartOutcome::NotConfigured);
-    };
-    let Some(best_of) = per_round
-        .iter()
-        .map(|preset| u8::try_from(preset.best_of).ok())
-        .collect::<Option<Vec<u8>>>()
-    else {
-        return Ok(StartOutcome::NotConfigured);
-    };
-    let Ok(built) = bracket::build(field.len(), &best_of) else {
-        return Ok(StartOutcome::TooFewEntrants);
+    let format = bracket::Format::from_db(&tournament.format, tournament.grand_final_reset);
+    let (built, per_round, config, round_count) = if format == bracket::Format::Groups {
+        let group_count = tournament
+            .group_count
+            .and_then(|count| usize::try_from(count).ok())
+            .unwrap_or_else(|| groups::default_group_count(field.len()));
+        let advance = tournament
+            .group_advance
+            .and_then(|advance| usize::try_from(advance).ok())
+            .unwrap_or(groups::DEFAULT_ADVANCE);
+        // Checked here rather than at setup, which cannot know the field.
+        if !groups::fits(field.len(), group_count, advance) {
+            return Ok(StartOutcome::GroupsDontFit {
+                entrants: field.len(),
+                groups: group_count,
+                advance,
+            });
+        }
+        // Group rounds run the default preset; scoped ones belong to the playoff,
+        // which resolves them when it is drawn.
+        let Some(default) = setup::default_preset(&presets) else {
+            return Ok(StartOutcome::NotConfigured);
+        };
+        let Ok(best_of) = u8::try_from(default.best_of) else {
+            return Ok(StartOutcome::NotConfigured);
+        };
+        let seeds: Vec<u32> = (1..).take(field.len()).collect();
+        let user_ids: Vec<i64> = field.iter().map(|e| e.user_id).collect();
+        let config = groups::Config {
+            groups: groups::assign(&user_ids, group_count),
+            advance,
+        };
+        let built = groups::first_round(&groups::assign(&seeds, group_count), best_of);
+        let rounds = config.rounds();
+        (built, vec![default], Some(config.to_json()), rounds)
+    } else {
+        let round_count = match format {
+            bracket::Format::Swiss => {
+                let rounds = tournament
+                    .swiss_rounds
+                    .and_then(|rounds| usize::try_from(rounds).ok())
+                    .unwrap_or_else(|| swiss::default_rounds(field.len()));
+                // Checked here rather than at setup, which cannot know the field.
+                if rounds > swiss::max_rounds(field.len()) {
+                    return Ok(StartOutcome::TooManySwissRounds {
+                        rounds,
+                        max: swiss::max_rounds(field.len()),
+                    });
+                }
+                rounds
+            },
+            _ => bracket::round_count(bracket::size(field.len())),
+        };
+        let Some(main) = setup::presets_per_round(&presets, round_count) else {
+            return Ok(StartOutcome::NotConfigured);
+        };
+        let Some((built, per_round)) = build_with_presets(format, field.len(), &main) else {
+            return Ok(StartOutcome::NotConfigured);
+        };
+        let config = (format == bracket::Format::Swiss).then(|| swiss::Config { rounds: round_count }.to_json());
+        (built, per_round, config, round_count)
     };
 
-    // A 3rd place round resolves no preset of its own — it borrows the
-    // semifinal's, so `per_round` needs the same entry appended a second time to
-    // stay `insert_bracket`'s one-entry-per-round length (checked by its
-    // `debug_assert_eq!`, and load-bearing: falling short there would silently
-    // leave the 3rd place set with no draft room).
-    if built.rounds.len() == round_count + 1 {
-        per_round.push(per_round[round_count - 2]);
-    }
-
     let seed_to_user: HashMap<u32, i64> = field
         .iter()
         .filter_map(|e| Some((u32::try_from(e.seed?).ok()?, e.user_id)))
         .collect();
 
-    db::insert_bracket(pool, tournament.id, &built, &seed_to_user, &per_round).await?;
+    db::insert_bracket(
+        pool,
+        tournament.id,
+        format,
+        config.as_deref(),
+        &built,
+        &seed_to_user,
+        &per_round,
+    )
+    .await?;
     open_round_one(pool, tournament.id).await?;
 
     db::update_tournament_status(pool, tournament.id, "running").await?;
@@ -163,48 +253,55 @@ pub(crate) async fn start(pool: &sqlx::SqlitePool, tournament: &Tournament) -> R
         entrants: field.len(),
         // Not `built.rounds.len()`: the 3rd place round, when present, is not a
         // round an organizer should be told to expect — it plays out alongside
-        // the round before it, not as a new stage of the event.
+        // the round before it, not as a new stage of the event. Losers rounds are
+        // the same; the count is the winners path.
         rounds: round_count,
     })
 }
 
+/// Builds `format` for `entrants` from the preset each main-bracket round runs,
+/// outermost first, and maps every round of the result — losers rounds and the
+/// 3rd place match included — onto its preset. `None` when a preset's `best_of`
+/// is unusable, or there are too few entrants for a bracket.
+///
+/// One resolution, two shapes taken off it: the series lengths the bracket is
+/// built from, and the preset ids its rounds record. Shared with a group stage's
+/// playoff, which is drawn the same way once the groups are over.
+pub(crate) fn build_with_presets<'a>(
+    format: bracket::Format,
+    entrants: usize,
+    main: &[&'a RoundPreset],
+) -> Option<(bracket::Bracket, Vec<&'a RoundPreset>)> {
+    let best_of = main
+        .iter()
+        .map(|preset| u8::try_from(preset.best_of).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    let built = bracket::build_for(format, entrants, &best_of).ok()?;
+
+    // Every round borrows its preset from a main-bracket round — its own, or the
+    // one it plays alongside: the 3rd place match the semifinal's, a losers round
+    // the winners round whose losers it takes. Mapped per round so `per_round`
+    // stays `insert_bracket`'s one-entry-per-round length (checked by its
+    // `debug_assert_eq!`, and load-bearing: falling short there would silently
+    // leave a set with no draft room).
+    let per_round = built.rounds.iter().map(|round| main[round.preset_from - 1]).collect();
+    Some((built, per_round))
+}
+
 /// Resolves byes and marks every playable set ready.
 ///
 /// A bye is a set with one occupant, which generation places against the top
-/// seeds. It is
-/// decided the moment the bracket opens: recorded `bye` with its occupant as
-/// winner, and that occupant written into the next set.
+/// seeds. It is decided the moment the bracket opens: recorded `bye` with its
+/// occupant as winner, and that occupant written into the next set.
 ///
 /// **Readiness is then decided by both slots being filled, not by being in round
 /// one.** With 5 entrants in an 8-bracket, round two's lower set is fed by two
-/// byes and is playable immediately. Byes never cascade further than that:
-/// `next_power_of_two` leaves under half the slots empty and reflection puts each
-/// against a distinct seed, so no set is ever fully empty.
+/// byes and is playable immediately. In single elimination byes go no further
+/// than that; a losers bracket fed by byes cascades, which `db::resolve_byes`
+/// follows to the end — in one transaction, so a half-opened bracket is never
+/// visible.
 async fn open_round_one(pool: &sqlx::SqlitePool, tournament_id: i64) -> Result<(), sqlx::Error> {
-    let sets = db::list_sets_for_tournament(pool, tournament_id).await?;
-    for set in &sets {
-        let ((Some(winner), None) | (None, Some(winner))) = (set.slot1_user_id, set.slot2_user_id) else {
-            continue;
-        };
-        db::record_set_result(pool, set.id, 0, 0, Some(winner), "bye", Some(Utc::now())).await?;
-
-        if let (Some(next), Some(slot)) = (set.winner_advances_to_set_id, set.winner_advances_to_slot) {
-            let slot = if slot == 1 {
-                bracket::Slot::One
-            } else {
-                bracket::Slot::Two
-            };
-            db::set_slot(pool, next, slot, winner).await?;
-        }
-    }
-
-    // Re-read: the byes above have filled slots in later rounds.
-    for set in db::list_sets_for_tournament(pool, tournament_id).await? {
-        if set.status == "pending" && set.slot1_user_id.is_some() && set.slot2_user_id.is_some() {
-            db::update_set_status(pool, set.id, "ready").await?;
-        }
-    }
-    Ok(())
+    db::open_bracket(pool, tournament_id).await
 }
 
 #[cfg(test)]
@@ -0,0 +1,675 @@
+//! Swiss stages: a fixed number of rounds, everyone playing every round, each
+//! round paired from the standings the one before it left.
+//!
+//! The pairing and the standings are pure, over the stage's decided sets, so the
+//! parts that are easy to get quietly wrong — who floats down a score group, who
+//! gets the bye, how a tie is broken — are testable without a database. `advance`
+//! is the one async entry point: `completion::settle` calls it after every set.
+
+use crate::tournament::db::{self, TournamentEntry, TournamentRound, TournamentSet, TournamentStage};
+use crate::tournament::{bracket, completion, render, setup};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// The round name prefix. `Swiss Round 3`, and `瑞士制第 3 輪` once localized.
+pub(crate) const ROUND_PREFIX: &str = "Swiss Round ";
+
+/// How many pairing attempts one round may cost before rematches are allowed. A
+/// field of any size Discord can hold pairs in a handful; this only bounds the
+/// case where no rematch-free pairing exists at all and the search would
+/// otherwise try every one.
+const PAIRING_BUDGET: usize = 100_000;
+
+pub(crate) fn round_name(ordinal: usize) -> String {
+    format!("{ROUND_PREFIX}{ordinal}")
+}
+
+/// `tournament_stages.config` for a Swiss stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Config {
+    pub(crate) rounds: usize,
+}
+
+impl Config {
+    pub(crate) fn to_json(self) -> String {
+        serde_json::to_string(&self).expect("a struct of integers always serializes")
+    }
+
+    /// `None` for a stage with no config, or one written by something else.
+    pub(crate) fn from_json(config: Option<&str>) -> Option<Self> {
+        serde_json::from_str(config?).ok()
+    }
+}
+
+/// The round count an organizer who never chose one gets: enough rounds for a
+/// single undefeated player to be left, as a knockout of the same field has.
+pub(crate) fn default_rounds(entrants: usize) -> usize {
+    bracket::round_count(bracket::size(entrants)).max(1)
+}
+
+/// The most rounds `entrants` can play without a rematch: everyone meets everyone
+/// once, and an odd field adds a round for each player's bye.
+pub(crate) fn max_rounds(entrants: usize) -> usize {
+    if entrants.is_multiple_of(2) {
+        entrants - 1
+    } else {
+        entrants
+    }
+}
+
+/// A decided set of the stage, as the standings see it. `two` is `None` for a bye.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Played {
+    pub(crate) one: i64,
+    pub(crate) two: Option<i64>,
+    pub(crate) winner: Option<i64>,
+}
+
+impl Played {
+    /// A decided set, or `None` for one still being played — or a bye nobody was
+    /// in, which a Swiss round never has but costs nothing to rule out.
+    pub(crate) fn of(set: &TournamentSet) -> Option<Self> {
+        if !completion::is_decided(&set.status) {
+            return None;
+        }
+        let (one, two) = match (set.slot1_user_id, set.slot2_user_id) {
+            (Some(one), two) => (one, two),
+            (None, Some(one)) => (one, None),
+            (None, None) => return None,
+        };
+        Some(Played {
+            one,
+            two,
+            winner: set.winner_user_id,
+        })
+    }
+}
+
+/// One line of the table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Standing {
+    pub(crate) user_id: i64,
+    pub(crate) seed: u32,
+    /// A bye counts as a win: the player was ready to play and had nobody to.
+    pub(crate) wins: u32,
+    pub(crate) losses: u32,
+    /// The sum of every opponent's wins — how hard a record was to earn.
+    pub(crate) buchholz: u32,
+    /// Buchholz without the best and the worst opponent, so one lopsided pairing
+    /// decides less. Only from three opponents on; below that it is Buchholz.
+    pub(crate) median: u32,
+}
+
+/// The table, best first: wins, then Buchholz, then median Buchholz, then seed —
+/// the last so two players nothing else separates still sit in a stable order.
+///
+/// `field` is every player in the stage with their seed; `played` every decided
+/// set. A bye has no opponent and so adds nothing to anyone's Buchholz.
+pub(crate) fn standings(field: &[(i64, u32)], played: &[Played]) -> Vec<Standing> {
+    let mut wins: HashMap<i64, u32> = HashMap::new();
+    let mut losses: HashMap<i64, u32> = HashMap::new();
+    let mut opponents: HashMap<i64, Vec<i64>> = HashMap::new();
+    for set in played {
+        let Some(winner) = set.winner else { continue };
+        *wins.entry(winner).or_default() += 1;
+        if let Some(two) = set.two {
+            let loser = if winner == set.one { two } else { set.one };
+            *losses.entry(loser).or_default() += 1;
+            opponents.entry(set.one).or_default().push(two);
+            opponents.entry(two).or_default().push(set.one);
+        }
+    }
+
+    let mut table: Vec<Standing> = field
+        .iter()
+        .map(|&(user_id, seed)| {
+            let mut faced: Vec<u32> = opponents
+                .get(&user_id)
+                .map(|them| them.iter().map(|o| wins.get(o).copied().unwrap_or(0)).collect())
+                .unwrap_or_default();
+            faced.sort_unstable();
+            let buchholz = faced.iter().sum();
+            let median = if faced.len() >= 3 {
+                faced[1..faced.len() - 1].iter().sum()
+            } else {
+                buchholz
+            };
+            Standing {
+                user_id,
+                seed,
+                wins: wins.get(&user_id).copied().unwrap_or(0),
+                losses: losses.get(&user_id).copied().unwrap_or(0),
+                buchholz,
+                median,
+            }
+        })
+        .collect();
+    table.sort_by(|a, b| {
+        b.wins
+            .cmp(&a.wins)
+            .then(b.buchholz.cmp(&a.buchholz))
+            .then(b.median.cmp(&a.median))
+            .then(a.seed.cmp(&b.seed))
+    });
+    table
+}
+
+/// The next round's pairings: `(one, Some(two))` for a set, `(one, None)` for the
+/// bye an odd field needs.
+///
+/// Players are taken by wins and then seed — not by tiebreak, which would let
+/// last round's luck of the draw decide this round's too. Within a score group the
+/// top half meets the bottom half, as round one's 1 v 5, 2 v 6 does in a field of
+/// eight; a group of odd size floats its last player down into the next. Nobody
+/// meets an opponent twice, and nobody has a second bye while someone has yet to
+/// have one — searched for rather than greedily assumed, since the greedy pairing
+/// of the top can leave the bottom with only rematches. If no such pairing exists
+/// at all (more rounds than the field allows), rematches are allowed rather than
+/// stalling the stage.
+pub(crate) fn pair(field: &[(i64, u32)], played: &[Played]) -> Vec<(i64, Option<i64>)> {
+    let table = standings(field, played);
+    let mut ranked: Vec<(i64, u32)> = table.iter().map(|s| (s.user_id, s.wins)).collect();
+    let seeds: HashMap<i64, u32> = field.iter().copied().collect();
+    ranked.sort_by_key(|&(user_id, wins)| (std::cmp::Reverse(wins), seeds.get(&user_id).copied()));
+
+    let met: HashSet<(i64, i64)> = played
+        .iter()
+        .filter_map(|set| set.two.map(|two| (set.one.min(two), set.one.max(two))))
+        .collect();
+    let had_bye: HashSet<i64> = played
+        .iter()
+        .filter(|set| set.two.is_none())
+        .map(|set| set.one)
+        .collect();
+
+    pair_avoiding(&ranked, &met, &had_bye)
+        .or_else(|| pair_avoiding(&ranked, &HashSet::new(), &HashSet::new()))
+        .expect("with nothing to avoid, any field pairs")
+}
+
+fn pair_avoiding(
+    ranked: &[(i64, u32)],
+    met: &HashSet<(i64, i64)>,
+    had_bye: &HashSet<i64>,
+) -> Option<Vec<(i64, Option<i64>)>> {
+    let mut budget = PAIRING_BUDGET;
+    if ranked.len().is_multiple_of(2) {
+        let sets = pair_all(ranked, met, &mut budget)?;
+        return Some(sets.into_iter().map(|(one, two)| (one, Some(two))).collect());
+    }
+
+    // The bye goes to the lowest-ranked player who has not had one, or failing
+    // that whoever above them leaves a field that still pairs.
+    let mut candidates: Vec<usize> = (0..ranked.len())
+        .rev()
+        .filter(|&i| !had_bye.contains(&ranked[i].0))
+        .collect();
+    if candidates.is_empty() {
+        candidates = (0..ranked.len()).rev().collect();
+    }
+    for index in candidates {
+        let mut rest = ranked.to_vec();
+        let (bye, _) = rest.remove(index);
+        if let Some(sets) = pair_all(&rest, met, &mut budget) {
+            let mut pairings: Vec<(i64, Option<i64>)> = sets.into_iter().map(|(one, two)| (one, Some(two))).collect();
+            pairings.push((bye, None));
+            return Some(pairings);
+        }
+    }
+    None
+}
+
+/// Pairs the top player first, trying opponents in the order `pair` describes,
+/// and backs off when what is left cannot be paired.
+fn pair_all(ranked: &[(i64, u32)], met: &HashSet<(i64, i64)>, budget: &mut usize) -> Option<Vec<(i64, i64)>> {
+    let Some(&(top, wins)) = ranked.first() else {
+        return Some(Vec::new());
+    };
+    if *budget == 0 {
+        return None;
+    }
+    *budget -= 1;
+
+    // The rest of the top player's score group, rotated so the first one tried
+    // sits half the group below them; then everyone below the group, in order.
+    let group = ranked.iter().take_while(|&&(_, w)| w == wins).count();
+    let start = (group / 2).saturating_sub(1);
+    let candidates = (1..group)
+        .skip(start)
+        .chain((1..group).take(start))
+        .chain(group..ranked.len());
+
+    for candidate in candidates {
+        let opponent = ranked[candidate].0;
+        if met.contains(&(top.min(opponent), top.max(opponent))) {
+            continue;
+        }
+        let rest: Vec<(i64, u32)> = ranked[1..]
+            .iter()
+            .filter(|&&(user_id, _)| user_id != opponent)
+            .copied()
+            .collect();
+        if let Some(mut sets) = pair_all(&rest, met, budget) {
+            sets.insert(0, (top, opponent));
+            return Some(sets);
+        }
+    }
+    None
+}
+
+/// Round one as a `bracket::Bracket`, so `start` persists it the way it does any
+/// other format's: seeds in, no advancement links — later rounds do not exist
+/// until the one before them is over.
+pub(crate) fn first_round(entrants: usize, best_of: u8) -> bracket::Bracket {
+    let field: Vec<(i64, u32)> = (1..=entrants as u32).map(|seed| (i64::from(seed), seed)).collect();
+    let sets = pair(&field, &[])
+        .into_iter()
+        .enumerate()
+        .map(|(index, (one, two))| bracket::Set {
+            position: index + 1,
+            slot1: u32::try_from(one).ok(),
+            slot2: two.and_then(|two| u32::try_from(two).ok()),
+            winner_advances_to: None,
+            loser_advances_to: None,
+        })
+        .collect();
+    bracket::Bracket {
+        rounds: vec![bracket::Round {
+            ordinal: 1,
+            name: round_name(1),
+            best_of,
+            side: None,
+            preset_from: 1,
+            sets,
+        }],
+    }
+}
+
+/// The players in a stage, with the seeds they were drawn with — everyone round
+/// one placed, which in a Swiss stage is everyone who plays any round.
+pub(crate) fn field(
+    rounds: &[TournamentRound],
+    sets: &[TournamentSet],
+    entries: &[TournamentEntry],
+) -> Vec<(i64, u32)> {
+    let Some(round_one) = rounds.first() else {
+        return Vec::new();
+    };
+    sets.iter()
+        .filter(|set| set.round_id == round_one.id)
+        .flat_map(|set| [set.slot1_user_id, set.slot2_user_id])
+        .flatten()
+        .map(|user_id| {
+            let seed = entries
+                .iter()
+                .find(|e| e.user_id == user_id)
+                .and_then(|e| e.seed)
+                .and_then(|seed| u32::try_from(seed).ok())
+                .unwrap_or(u32::MAX);
+            (user_id, seed)
+        })
+        .collect()
+}
+
+/// Standings rows per fenced block. A row is at most about 40 bytes — a name is
+/// fitted to `render::DEFAULT_WIDTH` cells, three bytes each when CJK — so this
+/// keeps a block well inside one message with the heading above it.
+pub(crate) const ROWS_PER_BLOCK: usize = 40;
+
+/// The table as it goes into `#{slug}-bracket`: fenced, since it is columns, and
+/// names through the fence-safety pass. Bilingual heading above it, the champion
+/// below it once the stage is over. One block per `ROWS_PER_BLOCK` players, each
+/// with its own header row, so a large field splits across messages cleanly.
+pub(crate) fn render_standings(table: &[Standing], entries: &[TournamentEntry], finished: bool) -> Vec<String> {
+    let name = |user_id: i64| {
+        entries
+            .iter()
+            .find(|e| e.user_id == user_id)
+            .map_or_else(|| "?".to_owned(), |e| e.display_name.clone())
+    };
+    let header = format!(
+        "{:>3}  {}  {:>5}  {:>3}  {:>3}",
+        "#",
+        render::fit("", render::DEFAULT_WIDTH),
+        "W-L",
+        "Bu",
+        "Med"
+    );
+    let rows: Vec<String> = table
+        .iter()
+        .enumerate()
+        .map(|(rank, standing)| {
+            format!(
+                "{:>3}  {}  {:>5}  {:>3}  {:>3}",
+                rank + 1,
+                render::fit(&render::sanitize(&name(standing.user_id)), render::DEFAULT_WIDTH),
+                format!("{}-{}", standing.wins, standing.losses),
+                standing.buchholz,
+                standing.median
+            )
+        })
+        .collect();
+    let mut blocks: Vec<String> = rows
+        .chunks(ROWS_PER_BLOCK)
+        .map(|rows| format!("```\n{}\n{}\n```", header.trim_end(), rows.join("\n").trim_end()))
+        .collect();
+    if let Some(first) = blocks.first_mut() {
+        first.insert_str(
+            0,
+            "**積分榜 / Standings**\n\
+             依勝場、Buchholz、中位 Buchholz 排序。 / By wins, then Buchholz, then median Buchholz.\n",
+        );
+    }
+    if finished && let Some(last) = blocks.last_mut() {
+        last.push_str(&render::trophy(table.first().map(|standing| name(standing.user_id))));
+    }
+    blocks
+}
+
+/// A tournament's Swiss stage with its rounds in order and their sets.
+struct Stage {
+    stage: TournamentStage,
+    rounds: Vec<TournamentRound>,
+    sets: Vec<TournamentSet>,
+}
+
+impl Stage {
+    /// `None` when the tournament has no Swiss stage.
+    async fn load(pool: &sqlx::SqlitePool, tournament_id: i64) -> Result<Option<Self>, sqlx::Error> {
+        let stages = db::list_stages_for_tournament(pool, tournament_id).await?;
+        let Some(stage) = stages
+            .into_iter()
+            .find(|stage| stage.format == bracket::Format::Swiss.as_db())
+        else {
+            return Ok(None);
+        };
+        let rounds = db::list_rounds_for_stage(pool, stage.id).await?;
+        let round_ids: HashSet<i64> = rounds.iter().map(|round| round.id).collect();
+        let sets = db::list_sets_for_tournament(pool, tournament_id)
+            .await?
+            .into_iter()
+            .filter(|set| round_ids.contains(&set.round_id))
+            .collect();
+        Ok(Some(Stage { stage, rounds, sets }))
+    }
+}
+
+/// The current standings of a tournament's Swiss stage, and whether the stage
+/// is over. `None` when it has none.
+pub(crate) async fn table(
+    pool: &sqlx::SqlitePool,
+    tournament_id: i64,
+    entries: &[TournamentEntry],
+) -> Result<Option<(Vec<Standing>, bool)>, sqlx::Error> {
+    let Some(Stage { stage, rounds, sets }) = Stage::load(pool, tournament_id).await? else {
+        return Ok(None);
+    };
+    let played: Vec<Played> = sets.iter().filter_map(Played::of).collect();
+    Ok(Some((
+        standings(&field(&rounds, &sets, entries), &played),
+        stage.status == "completed",
+    )))
+}
+
+/// What settling a set did to its Swiss stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Progress {
+    /// Sets in the round are still being played. `last_round`: this is the
+    /// stage's final round, so nobody who finishes in it plays again.
+    Waiting { last_round: bool },
+    /// Every set in the round is decided, and the next one is paired.
+    Paired { round: usize },
+    /// The last round is over, and with it the tournament.
+    Finished,
+}
+
+/// Pairs the next round once every set in the current one is decided, or ends
+/// the tournament once the last one is. `None` when the tournament has no Swiss
+/// stage, so callers need not know the format.
+///
+/// Safe to call any number of times, from any number of settlements at once:
+/// the next round is only ever written by whichever call gets there first (see
+/// `db::insert_paired_round`), and only one call reports `Finished`.
+pub(crate) async fn advance(pool: &sqlx::SqlitePool, tournament_id: i64) -> Result<Option<Progress>, sqlx::Error> {
+    let Some(Stage { stage, rounds, sets }) = Stage::load(pool, tournament_id).await? else {
+        return Ok(None);
+    };
+    let Some(current) = rounds.last() else {
+        return Ok(None);
+    };
+    let total = Config::from_json(stage.config.as_deref()).map_or(rounds.len(), |config| config.rounds);
+    let ordinal = usize::try_from(current.ordinal).unwrap_or(total);
+    let last_round = ordinal >= total;
+
+    if sets
+        .iter()
+        .any(|set| set.round_id == current.id && !completion::is_decided(&set.status))
+    {
+        return Ok(Some(Progress::Waiting { last_round }));
+    }
+
+    if last_round {
+        db::update_stage_status(pool, stage.id, "completed").await?;
+        return Ok(Some(if db::complete_tournament(pool, tournament_id).await? {
+            Progress::Finished
+        } else {
+            Progress::Waiting { last_round }
+        }));
+    }
+
+    let entries = db::list_entries_for_tournament(pool, tournament_id).await?;
+    let played: Vec<Played> = sets.iter().filter_map(Played::of).collect();
+    let pairings = pair(&field(&rounds, &sets, &entries), &played);
+
+    // The preset is resolved the way `start` resolved round one's, so a round
+    // runs what `/tournament preset` says it should; the current round's is the
+    // fallback for presets changed since into something that no longer covers it.
+    let next = ordinal + 1;
+    let presets = db::list_round_presets(pool, tournament_id).await?;
+    let (best_of, draft_preset_id) = match setup::presets_per_round(&presets, total) {
+        Some(per_round) => (
+            per_round[next - 1].best_of,
+            Some(per_round[next - 1].draft_preset_id.clone()),
+        ),
+        None => (current.best_of, current.draft_preset_id.clone()),
+    };
+
+    let inserted = db::insert_paired_round(
+        pool,
+        db::NewPairedRound {
+            tournament_id,
+            stage_id: stage.id,
+            ordinal: next,
+            name: round_name(next),
+            best_of,
+            draft_preset_id,
+            pairings,
+        },
+    )
+    .await?;
+    Ok(Some(if inserted {
+        Progress::Paired { round: next }
+    } else {
+        Progress::Waiting { last_round }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(n: i64) -> Vec<(i64, u32)> {
+        (1..=n).map(|user_id| (user_id, user_id as u32)).collect()
+    }
+
+    fn won(winner: i64, loser: i64) -> Played {
+        Played {
+            one: winner,
+            two: Some(loser),
+            winner: Some(winner),
+        }
+    }
+
+    fn bye(user_id: i64) -> Played {
+        Played {
+            one: user_id,
+            two: None,
+            winner: Some(user_id),
+        }
+    }
+
+    /// Plays `pairings` out with the lower user id winning every set.
+    fn favourites_win(pairings: &[(i64, Option<i64>)]) -> Vec<Played> {
+        pairings
+            .iter()
+            .map(|&(one, two)| match two {
+                Some(two) => won(one.min(two), one.max(two)),
+                None => bye(one),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_one_sets_the_top_half_against_the_bottom_half() {
+        assert_eq!(
+            pair(&field(8), &[]),
+            vec![(1, Some(5)), (2, Some(6)), (3, Some(7)), (4, Some(8))]
+        );
+    }
+
+    #[test]
+    fn an_odd_field_gives_the_bye_to_the_lowest_seed() {
+        let pairings = pair(&field(5), &[]);
+        assert_eq!(pairings.last(), Some(&(5, None)));
+        assert_eq!(pairings.len(), 3);
+    }
+
+    #[test]
+    fn later_rounds_pair_within_score_groups() {
+        let round_one = favourites_win(&pair(&field(8), &[]));
+        let round_two = pair(&field(8), &round_one);
+        // 1-4 won, 5-8 lost: each group plays itself, top half against bottom.
+        assert_eq!(round_two, vec![(1, Some(3)), (2, Some(4)), (5, Some(7)), (6, Some(8))]);
+    }
+
+    #[test]
+    fn nobody_meets_the_same_opponent_twice_over_a_full_stage() {
+        for n in [4, 6, 7, 8, 9, 12, 16] {
+            let mut played = Vec::new();
+            for _ in 0..max_rounds(n as usize).min(6) {
+                let pairings = pair(&field(n), &played);
+                played.extend(favourites_win(&pairings));
+            }
+            let mut seen = HashSet::new();
+            for set in &played {
+                if let Some(two) = set.two {
+                    assert!(
+                        seen.insert((set.one.min(two), set.one.max(two))),
+                        "{n} entrants: {} and {two} met twice",
+                        set.one
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nobody_has_a_second_bye_while_someone_has_not_had_one() {
+        let mut played = Vec::new();
+        for _ in 0..5 {
+            played.extend(favourites_win(&pair(&field(5), &played)));
+        }
+        let byes: Vec<i64> = played
+            .iter()
+            .filter(|set| set.two.is_none())
+            .map(|set| set.one)
+            .collect();
+        let distinct: HashSet<i64> = byes.iter().copied().collect();
+        assert_eq!(distinct.len(), byes.len(), "a repeated bye: {byes:?}");
+    }
+
+    #[test]
+    fn a_field_with_no_rematch_free_pairing_left_still_pairs() {
+        // Four players, four rounds: the fourth can only be a rematch.
+        let mut played = Vec::new();
+        for _ in 0..4 {
+            let pairings = pair(&field(4), &played);
+            assert_eq!(pairings.len(), 2);
+            played.extend(favourites_win(&pairings));
+        }
+    }
+
+    #[test]
+    fn ties_on_wins_are_broken_by_buchholz_then_median() {
+        // 1 beat 2, 4 beat 3; then 1 beat 4, 3 beat 2. Both 3 and 4 are 1-1, but
+        // 4's opponents won three sets between them to 3's one — enough to put
+        // the lower seed ahead.
+        let played = [won(1, 2), won(4, 3), won(1, 4), won(3, 2)];
+        let table = standings(&field(4), &played);
+        let order: Vec<i64> = table.iter().map(|s| s.user_id).collect();
+        assert_eq!(order, vec![1, 4, 3, 2]);
+        assert_eq!((table[1].buchholz, table[2].buchholz), (3, 1));
+    }
+
+    #[test]
+    fn the_median_drops_the_best_and_worst_opponent() {
+        let played = [won(1, 2), won(1, 3), won(1, 4), won(2, 3), won(2, 4), won(3, 4)];
+        let table = standings(&field(4), &played);
+        // 1 faced 2 (2 wins), 3 (1), 4 (0): Buchholz 3, median 1.
+        assert_eq!((table[0].buchholz, table[0].median), (3, 1));
+    }
+
+    #[test]
+    fn a_bye_is_a_win_that_adds_to_nobody_s_buchholz() {
+        let table = standings(&field(3), &[won(1, 2), bye(3)]);
+        let three = table.iter().find(|s| s.user_id == 3).unwrap();
+        assert_eq!((three.wins, three.losses, three.buchholz), (1, 0, 0));
+    }
+
+    #[test]
+    fn the_config_round_trips_through_the_stage_column() {
+        let config = Config { rounds: 5 };
+        assert_eq!(Config::from_json(Some(&config.to_json())), Some(config));
+        assert_eq!(Config::from_json(None), None);
+        assert_eq!(Config::from_json(Some("not json")), None);
+    }
+
+    #[test]
+    fn the_default_round_count_follows_the_field() {
+        assert_eq!(default_rounds(2), 1);
+        assert_eq!(default_rounds(8), 3);
+        assert_eq!(default_rounds(9), 4);
+        assert_eq!(max_rounds(8), 7);
+        assert_eq!(max_rounds(7), 7);
+    }
+
+    #[test]
+    fn the_standings_table_names_everyone_and_crowns_the_leader_once_finished() {
+        let entries: Vec<TournamentEntry> = (1..=2)
+            .map(|user_id| TournamentEntry {
+                tournament_id: 1,
+                user_id,
+                aoe4_id: user_id * 100,
+                invited_by: None,
+                seed: Some(user_id),
+                suggested_seed: None,
+                manual_seed: None,
+                display_name: format!("P{user_id}"),
+                elo: None,
+                atr: None,
+                atr_source: None,
+                status: "active".to_string(),
+                registered_at: chrono::Utc::now(),
+                checked_in_at: None,
+            })
+            .collect();
+        let table = standings(&field(2), &[won(2, 1)]);
+        let running = render_standings(&table, &entries, false).concat();
+        assert!(
+            running.contains("P1") && running.contains("P2") && running.contains("1-0"),
+            "{running}"
+        );
+        assert!(!running.contains('🏆'));
+        assert!(render_standings(&table, &entries, true).concat().ends_with("🏆 **P2**"));
+    }
+}
@@ -87,6 +87,15 @@ mod tests {
             scheduled_start_at: None,
             seed_source: "suggested".to_string(),
             registration_mode: "open".to_string(),
+            format: "single_elim".to_string(),
+            grand_final_reset: false,
+            swiss_rounds: None,
+            group_count: None,
+            group_advance: None,
+            playoff_format: "single_elim".to_string(),
+            schedule_reminders: "60,10".to_string(),
+            autopilot: false,
+            checkin_reminded_at: None,
             created_by: 1,
             created_at: Utc::now(),
             started_at: None,
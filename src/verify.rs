@@ -0,0 +1,57 @@
+//! Profile ownership for `/bind verify`: the bot hands out a short code, the
+//! player puts it in their in-game name for a moment, and `/bind confirm` looks
+//! for it on aoe4world. Only whoever controls the account can rename it, so
+//! finding the code there is proof enough.
+
+use chrono::{DateTime, Duration, Utc};
+use rand::RngExt;
+
+/// How long a code stays good. Long enough to start the game and rename.
+pub(crate) const CODE_LIFETIME: Duration = Duration::minutes(30);
+
+const CODE_LEN: usize = 5;
+/// No 0/O or 1/I, so a code copied by eye into a name is not copied wrong.
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+pub(crate) fn new_code() -> String {
+    let mut rng = rand::rng();
+    (0..CODE_LEN)
+        .map(|_| char::from(CODE_ALPHABET[rng.random_range(0..CODE_ALPHABET.len())]))
+        .collect()
+}
+
+pub(crate) fn is_expired(issued_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    now - issued_at > CODE_LIFETIME
+}
+
+/// Anywhere in the name, in either case — the game keeps whatever case was
+/// typed, and players will not all type it the same.
+pub(crate) fn name_carries(name: &str, code: &str) -> bool {
+    name.to_uppercase().contains(&code.to_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_short_and_unambiguous() {
+        let code = new_code();
+        assert_eq!(code.len(), CODE_LEN);
+        assert!(code.bytes().all(|byte| CODE_ALPHABET.contains(&byte)));
+    }
+
+    #[test]
+    fn the_code_may_sit_anywhere_in_the_name_in_any_case() {
+        assert!(name_carries("Jump__ k7q2m", "K7Q2M"));
+        assert!(name_carries("K7Q2M", "K7Q2M"));
+        assert!(!name_carries("Jump__", "K7Q2M"));
+    }
+
+    #[test]
+    fn a_code_expires_after_its_lifetime() {
+        let issued = DateTime::UNIX_EPOCH;
+        assert!(!is_expired(issued, issued + CODE_LIFETIME));
+        assert!(is_expired(issued, issued + CODE_LIFETIME + Duration::seconds(1)));
+    }
+}